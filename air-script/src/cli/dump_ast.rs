@@ -0,0 +1,49 @@
+use std::{path::PathBuf, sync::Arc};
+
+use clap::Args;
+use miden_diagnostics::CodeMap;
+
+use super::MessageFormat;
+
+#[derive(Args)]
+pub struct DumpAst {
+    /// Path to input file
+    input: PathBuf,
+
+    #[arg(
+        long,
+        value_enum,
+        default_value = "human",
+        help = "The format in which diagnostics are reported"
+    )]
+    message_format: MessageFormat,
+
+    #[arg(long, help = "Print the AST as JSON")]
+    json: bool,
+}
+
+impl DumpAst {
+    pub fn execute(&self) -> Result<(), String> {
+        let codemap = Arc::new(CodeMap::new());
+        let diagnostics = self.message_format.diagnostics(codemap.clone());
+
+        // Parse from file, the program is printed before constant propagation and inlining
+        match air_parser::parse_file(&diagnostics, codemap, &self.input) {
+            Ok(ast) => {
+                if self.json {
+                    let json =
+                        serde_json::to_string_pretty(&ast).map_err(|err| format!("{err:?}"))?;
+                    println!("{json}");
+                } else {
+                    println!("{ast}");
+                }
+
+                Ok(())
+            }
+            Err(err) => {
+                diagnostics.emit(err);
+                Err("parsing failed".into())
+            }
+        }
+    }
+}
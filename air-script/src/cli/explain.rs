@@ -0,0 +1,46 @@
+use std::{path::PathBuf, sync::Arc};
+
+use air_ir::CompileError;
+use air_script::CompileOptions;
+
+use clap::Args;
+use miden_diagnostics::CodeMap;
+
+use super::MessageFormat;
+
+#[derive(Args)]
+pub struct Explain {
+    /// Path to input file
+    input: PathBuf,
+
+    #[arg(
+        long,
+        value_enum,
+        default_value = "human",
+        help = "The format in which diagnostics are reported"
+    )]
+    message_format: MessageFormat,
+}
+
+impl Explain {
+    pub fn execute(&self) -> Result<(), String> {
+        let codemap = Arc::new(CodeMap::new());
+        let diagnostics = self.message_format.diagnostics(codemap.clone());
+
+        // Parse from file to internal representation
+        let air = air_parser::parse_file(&diagnostics, codemap, &self.input)
+            .map_err(CompileError::Parse)
+            .and_then(|ast| CompileOptions::default().compile(&diagnostics, ast));
+
+        match air {
+            Ok(air) => {
+                println!("{}", air.explain());
+                Ok(())
+            }
+            Err(err) => {
+                diagnostics.emit(err);
+                Err("compilation failed".into())
+            }
+        }
+    }
+}
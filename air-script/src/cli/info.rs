@@ -0,0 +1,62 @@
+use std::{path::PathBuf, sync::Arc};
+
+use air_ir::CompileError;
+use air_pass::Pass;
+
+use clap::Args;
+use miden_diagnostics::CodeMap;
+
+use super::MessageFormat;
+
+#[derive(Args)]
+pub struct Info {
+    /// Path to input file
+    input: PathBuf,
+
+    #[arg(
+        long,
+        value_enum,
+        default_value = "human",
+        help = "The format in which diagnostics are reported"
+    )]
+    message_format: MessageFormat,
+
+    #[arg(long, help = "Print the summary as JSON")]
+    json: bool,
+}
+
+impl Info {
+    pub fn execute(&self) -> Result<(), String> {
+        let codemap = Arc::new(CodeMap::new());
+        let diagnostics = self.message_format.diagnostics(codemap.clone());
+
+        // Parse from file to internal representation
+        let air = air_parser::parse_file(&diagnostics, codemap, &self.input)
+            .map_err(CompileError::Parse)
+            .and_then(|ast| {
+                let mut pipeline = air_parser::transforms::ConstantPropagation::new(&diagnostics)
+                    .chain(air_parser::transforms::Inlining::new(&diagnostics))
+                    .chain(air_ir::passes::AstToAir::new(&diagnostics));
+                pipeline.run(ast)
+            });
+
+        match air {
+            Ok(air) => {
+                let summary = air.summary();
+                if self.json {
+                    let json =
+                        serde_json::to_string_pretty(&summary).map_err(|err| format!("{err:?}"))?;
+                    println!("{json}");
+                } else {
+                    println!("{summary}");
+                }
+
+                Ok(())
+            }
+            Err(err) => {
+                diagnostics.emit(err);
+                Err("compilation failed".into())
+            }
+        }
+    }
+}
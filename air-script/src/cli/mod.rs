@@ -1,2 +1,41 @@
+mod dump_ast;
+mod explain;
+mod info;
 mod transpile;
+pub use dump_ast::DumpAst;
+pub use explain::Explain;
+pub use info::Info;
 pub use transpile::Transpile;
+
+use std::sync::Arc;
+
+use air_script::JsonEmitter;
+use clap::ValueEnum;
+use miden_diagnostics::{
+    term::termcolor::ColorChoice, CodeMap, DefaultEmitter, DiagnosticsHandler,
+};
+
+/// The format in which diagnostics are reported
+#[derive(Copy, Clone, Default, PartialEq, Eq, ValueEnum)]
+pub enum MessageFormat {
+    /// Render diagnostics for display in a terminal
+    #[default]
+    Human,
+    /// Write each diagnostic to stderr as a single line of JSON
+    Json,
+}
+impl MessageFormat {
+    /// Returns a [DiagnosticsHandler] which reports diagnostics in this format
+    pub fn diagnostics(&self, codemap: Arc<CodeMap>) -> DiagnosticsHandler {
+        match self {
+            Self::Human => {
+                let emitter = Arc::new(DefaultEmitter::new(ColorChoice::Auto));
+                DiagnosticsHandler::new(Default::default(), codemap, emitter)
+            }
+            Self::Json => {
+                let emitter = Arc::new(JsonEmitter::stderr());
+                DiagnosticsHandler::new(JsonEmitter::config(), codemap, emitter)
+            }
+        }
+    }
+}
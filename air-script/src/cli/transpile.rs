@@ -1,36 +1,56 @@
 use std::{fs, path::PathBuf, sync::Arc};
 
-use air_ir::{CodeGenerator, CompileError};
-use air_pass::Pass;
+use air_ir::{CodeGenerator, CompileError, PrimeField};
+use air_script::{CompileOptions, MetadataGenerator};
 
 use clap::{Args, ValueEnum};
-use miden_diagnostics::{
-    term::termcolor::ColorChoice, CodeMap, DefaultEmitter, DiagnosticsHandler,
-};
+use miden_diagnostics::CodeMap;
+
+use super::MessageFormat;
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
 pub enum Target {
     Winterfell,
     Masm,
+    Plonkish,
 }
 impl Target {
     pub fn extension(&self) -> &'static str {
         match self {
             Self::Winterfell => "rs",
             Self::Masm => "masm",
+            Self::Plonkish => "json",
         }
     }
 }
 
+/// The kind of artifact written by `airc transpile`
+#[derive(Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum Emit {
+    /// Code evaluating the constraints, in the language of the selected target
+    #[default]
+    Code,
+    /// A JSON summary of the trace, constraints, and inputs, i.e. the output of `airc info --json`
+    Metadata,
+}
+
 #[derive(Args)]
 pub struct Transpile {
     /// Path to input file
     input: PathBuf,
 
+    #[arg(
+        long,
+        value_enum,
+        default_value = "human",
+        help = "The format in which diagnostics are reported"
+    )]
+    message_format: MessageFormat,
+
     #[arg(
         short,
         long,
-        help = "Output filename, defaults to the input file with the .rs extension for Winterfell or .masm for MASM"
+        help = "Output filename, defaults to the input file with the .rs extension for Winterfell, .masm for MASM, .json for Plonkish, or .metadata.json when emitting metadata"
     )]
     output: Option<PathBuf>,
 
@@ -40,6 +60,130 @@ pub struct Transpile {
         help = "Defines the target language, defaults to Winterfell"
     )]
     target: Option<Target>,
+
+    #[arg(
+        long,
+        value_enum,
+        default_value = "code",
+        help = "The artifact to write, either code for the target or only the metadata of the constraints as JSON"
+    )]
+    emit: Emit,
+
+    #[arg(
+        long,
+        help = "Emit one Rust module per AirScript module when targeting Winterfell, rather than a single flattened file"
+    )]
+    modular: bool,
+
+    #[arg(
+        long,
+        help = "Emit a module declaring the index of each named trace column when targeting Winterfell"
+    )]
+    column_constants: bool,
+
+    #[arg(
+        long,
+        help = "Emit a test module asserting the trace widths and constraint counts of the generated Air when targeting Winterfell"
+    )]
+    constraint_count_tests: bool,
+
+    #[arg(
+        long,
+        help = "Evaluate selectors shared by several integrity constraints once, rather than once per constraint, when targeting Winterfell"
+    )]
+    factor_selectors: bool,
+
+    #[arg(
+        long,
+        help = "Emit the comments preceding each constraint in the source above the code generated for it when targeting Winterfell"
+    )]
+    doc_comments: bool,
+
+    #[arg(
+        long,
+        help = "End the code generated for each constraint with a comment giving the file and line of the constraint in the source when targeting Winterfell"
+    )]
+    provenance: bool,
+
+    #[arg(
+        long,
+        help = "Prefix the names of the generated Air struct and its PublicInputs struct when targeting Winterfell, e.g. to include several Airs in one crate"
+    )]
+    name_prefix: Option<String>,
+
+    #[arg(
+        long,
+        help = "The name of the generated Air struct when targeting Winterfell, defaults to the name of the AirScript program"
+    )]
+    air_name: Option<String>,
+
+    #[arg(
+        long,
+        help = "The name of the generated public inputs struct when targeting Winterfell, defaults to PublicInputs"
+    )]
+    public_inputs_name: Option<String>,
+
+    #[arg(
+        long,
+        help = "Also write a module declaring the memory addresses, exemption points, and other constants baked into the generated procedures, next to the output with the .constants.masm extension, when targeting MASM"
+    )]
+    masm_constants: bool,
+
+    #[arg(
+        long,
+        help = "Serialize constants as hexadecimal strings rather than decimal numbers when targeting Plonkish"
+    )]
+    hex_constants: bool,
+
+    #[arg(
+        long,
+        help = "The format version of the JSON output when targeting Plonkish (default 1), version 2 records the degree of each gate"
+    )]
+    plonkish_format_version: Option<u8>,
+
+    #[arg(
+        long,
+        help = "Remove periodic columns which are not referenced by any constraint from the output"
+    )]
+    prune_unused_periodic_columns: bool,
+
+    #[arg(
+        long,
+        help = "Remove integrity constraints which are identical to a previous constraint from the output"
+    )]
+    remove_duplicate_constraints: bool,
+
+    #[arg(
+        long,
+        help = "Warn about integrity constraints whose degree exceeds this value, unlimited by default"
+    )]
+    max_degree: Option<usize>,
+
+    #[arg(
+        long,
+        value_parser = clap::value_parser!(u8).range(0..=air_script::MAX_OPT_LEVEL as i64),
+        help = "The optimization level, from 0 to 3, trading compile time for the size of the constraint graph, defaults to 2"
+    )]
+    opt_level: Option<u8>,
+
+    #[arg(
+        long,
+        help = "Warn about comprehensions which unroll to more than this many iterations, defaults to 1024"
+    )]
+    unroll_warning_threshold: Option<usize>,
+
+    #[arg(
+        long,
+        help = "The modulus of the prime field over which constraints are expressed, defaults to the Goldilocks field (2^64 - 2^32 + 1)"
+    )]
+    field_modulus: Option<u64>,
+
+    #[arg(
+        long,
+        value_delimiter = ',',
+        help = "Enable the given comma-separated features, keeping the constraints and trace columns gated by them"
+    )]
+    features: Vec<String>,
 }
 
 impl Transpile {
@@ -48,19 +192,34 @@ impl Transpile {
         println!("Transpiling...");
 
         let input_path = &self.input;
+        let field = match self.field_modulus {
+            Some(modulus) if modulus < 2 => {
+                return Err(format!("invalid field modulus: {modulus}"));
+            }
+            Some(modulus) => PrimeField::new(modulus),
+            None => PrimeField::default(),
+        };
 
         let codemap = Arc::new(CodeMap::new());
-        let emitter = Arc::new(DefaultEmitter::new(ColorChoice::Auto));
-        let diagnostics = DiagnosticsHandler::new(Default::default(), codemap.clone(), emitter);
+        let diagnostics = self.message_format.diagnostics(codemap.clone());
 
         // Parse from file to internal representation
-        let air = air_parser::parse_file(&diagnostics, codemap, input_path)
+        let air = air_parser::parse_file(&diagnostics, codemap.clone(), input_path)
             .map_err(CompileError::Parse)
             .and_then(|ast| {
-                let mut pipeline = air_parser::transforms::ConstantPropagation::new(&diagnostics)
-                    .chain(air_parser::transforms::Inlining::new(&diagnostics))
-                    .chain(air_ir::passes::AstToAir::new(&diagnostics));
-                pipeline.run(ast)
+                CompileOptions::default()
+                    .with_opt_level(self.opt_level.unwrap_or(air_script::DEFAULT_OPT_LEVEL))
+                    .with_field(field)
+                    .with_unroll_warning_threshold(
+                        self.unroll_warning_threshold
+                            .unwrap_or(air_script::transforms::DEFAULT_UNROLL_WARNING_THRESHOLD),
+                    )
+                    .with_features(&self.features)
+                    .compile_with(&diagnostics, ast, |pass| {
+                        pass.with_unused_periodic_columns_pruned(self.prune_unused_periodic_columns)
+                            .with_duplicate_constraints_removed(self.remove_duplicate_constraints)
+                            .with_max_degree(self.max_degree)
+                    })
             });
 
         match air {
@@ -68,8 +227,36 @@ impl Transpile {
                 // generate Rust code targeting Winterfell
                 let target = self.target.unwrap_or(Target::Winterfell);
                 let backend: Box<dyn CodeGenerator<Output = String>> = match target {
-                    Target::Winterfell => Box::new(air_codegen_winter::CodeGenerator),
+                    _ if self.emit == Emit::Metadata => Box::new(MetadataGenerator),
+                    Target::Winterfell => {
+                        let mut backend = air_codegen_winter::CodeGenerator::default()
+                            .with_modular_output(self.modular)
+                            .with_column_constants(self.column_constants)
+                            .with_constraint_count_tests(self.constraint_count_tests)
+                            .with_factored_selectors(self.factor_selectors)
+                            .with_doc_comments(self.doc_comments)
+                            .with_provenance(self.provenance)
+                            .with_codemap(codemap);
+                        if let Some(prefix) = &self.name_prefix {
+                            backend = backend.with_name_prefix(prefix);
+                        }
+                        if let Some(name) = &self.air_name {
+                            backend = backend.with_air_name(name);
+                        }
+                        if let Some(name) = &self.public_inputs_name {
+                            backend = backend.with_public_inputs_name(name);
+                        }
+                        Box::new(backend)
+                    }
                     Target::Masm => Box::<air_codegen_masm::CodeGenerator>::default(),
+                    Target::Plonkish => Box::new(
+                        air_codegen_plonkish::CodeGenerator::default()
+                            .with_hex_constants(self.hex_constants)
+                            .with_format_version(
+                                self.plonkish_format_version
+                                    .unwrap_or(air_codegen_plonkish::DEFAULT_FORMAT_VERSION),
+                            ),
+                    ),
                 };
 
                 // write transpiled output to the output path
@@ -77,16 +264,33 @@ impl Transpile {
                     Some(path) => path.clone(),
                     None => {
                         let mut path = input_path.clone();
-                        path.set_extension(target.extension());
+                        match self.emit {
+                            Emit::Code => path.set_extension(target.extension()),
+                            Emit::Metadata => path.set_extension("metadata.json"),
+                        };
                         path
                     }
                 };
-                let code = backend.generate(&air).expect("code generation failed");
+                let code = backend
+                    .generate(&air)
+                    .map_err(|err| format!("code generation failed: {err}"))?;
                 if let Err(err) = fs::write(&output_path, code) {
                     return Err(format!("{err:?}"));
                 }
 
                 println!("Success! Transpiled to {}", output_path.display());
+
+                if target == Target::Masm && self.emit == Emit::Code && self.masm_constants {
+                    let constants_path = output_path.with_extension("constants.masm");
+                    let constants = air_codegen_masm::CodeGenerator::default()
+                        .generate_constants(&air)
+                        .map_err(|err| format!("code generation failed: {err}"))?;
+                    if let Err(err) = fs::write(&constants_path, constants) {
+                        return Err(format!("{err:?}"));
+                    }
+                    println!("Constants written to {}", constants_path.display());
+                }
+
                 println!("============================================================");
 
                 Ok(())
@@ -0,0 +1,582 @@
+use std::{collections::BTreeMap, io::Write, mem, sync::Mutex};
+
+use miden_diagnostics::{
+    term::{
+        termcolor::{Buffer, Color, ColorSpec},
+        Chars, DisplayStyle,
+    },
+    DiagnosticsConfig, Emitter,
+};
+use serde_json::{json, Value};
+use unicode_width::UnicodeWidthChar;
+
+/// [JsonEmitter] writes each diagnostic as a single line of JSON, for consumption by tools such as
+/// editors, rather than rendering it for display in a terminal, e.g.:
+///
+/// ```text
+/// {"labels":[{"column":9,"end_column":11,"end_line":12,"file":"example.air","line":12,"message":"use `=` for constraint equality, not `==`","primary":true}],"message":"invalid constraint","notes":[],"severity":"error","spans":[{"column":9,"end_column":11,"end_line":12,"file":"example.air","line":12}]}
+/// ```
+///
+/// The `spans` are those of the primary labels of the diagnostic, and `labels` lists every label,
+/// along with its message. Lines and columns start at 1, and each span ends just before its
+/// `end_line` and `end_column`, i.e. the end is exclusive.
+///
+/// Diagnostics reach an [Emitter] already rendered, so this must be used with a
+/// [DiagnosticsHandler](miden_diagnostics::DiagnosticsHandler) constructed using
+/// [JsonEmitter::config], which renders each part of a diagnostic in a color of its own, so that
+/// the labels can be recovered from the rendering. The extent of a label which overlaps another
+/// label on the same line is drawn merged with that label, in which case it is recovered on the
+/// assumption that labels are nested, e.g. that a secondary label encloses a primary label.
+pub struct JsonEmitter {
+    writer: Mutex<Box<dyn Write + Send>>,
+}
+impl JsonEmitter {
+    /// Constructs a [JsonEmitter] which writes diagnostics to `writer`
+    pub fn new(writer: impl Write + Send + 'static) -> Self {
+        Self {
+            writer: Mutex::new(Box::new(writer)),
+        }
+    }
+
+    /// Constructs a [JsonEmitter] which writes diagnostics to stderr
+    pub fn stderr() -> Self {
+        Self::new(std::io::stderr())
+    }
+
+    /// Returns the [DiagnosticsConfig] which must be used with this emitter
+    pub fn config() -> DiagnosticsConfig {
+        let mut config = DiagnosticsConfig::default();
+        let display = &mut config.display;
+        display.display_style = DisplayStyle::Rich;
+        display.chars = Chars::box_drawing();
+        // Tabs are rendered as a single space, so that each character of the source is drawn
+        // with its own width, and columns of the rendering can be mapped back to characters
+        display.tab_width = 1;
+
+        let styles = &mut display.styles;
+        for header in [
+            &mut styles.header_bug,
+            &mut styles.header_error,
+            &mut styles.header_warning,
+            &mut styles.header_note,
+            &mut styles.header_help,
+        ] {
+            *header = Role::Header.color();
+        }
+        styles.header_message = Role::Message.color();
+        for label in [
+            &mut styles.primary_label_bug,
+            &mut styles.primary_label_error,
+            &mut styles.primary_label_warning,
+            &mut styles.primary_label_note,
+            &mut styles.primary_label_help,
+        ] {
+            *label = Role::Primary.color();
+        }
+        styles.secondary_label = Role::Secondary.color();
+        styles.line_number = Role::LineNumber.color();
+        styles.source_border = Role::Border.color();
+        styles.note_bullet = Role::NoteBullet.color();
+        config
+    }
+}
+impl Emitter for JsonEmitter {
+    fn buffer(&self) -> Buffer {
+        Buffer::ansi()
+    }
+
+    fn print(&self, buffer: Buffer) -> std::io::Result<()> {
+        let rendered = String::from_utf8_lossy(buffer.as_slice());
+        let mut writer = self.writer.lock().unwrap();
+        writeln!(writer, "{}", to_json(&rendered))
+    }
+}
+
+/// The part of a diagnostic which a character of its rendering belongs to, as given by the color
+/// in which [JsonEmitter::config] renders it
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Role {
+    Plain,
+    Header,
+    Message,
+    LineNumber,
+    Border,
+    NoteBullet,
+    Primary,
+    Secondary,
+}
+impl Role {
+    const ROLES: [Self; 8] = [
+        Self::Plain,
+        Self::Header,
+        Self::Message,
+        Self::LineNumber,
+        Self::Border,
+        Self::NoteBullet,
+        Self::Primary,
+        Self::Secondary,
+    ];
+
+    fn color(self) -> ColorSpec {
+        let mut spec = ColorSpec::new();
+        spec.set_fg(Some(Color::Ansi256(self as u8)));
+        spec
+    }
+
+    /// Returns the role rendered using the escape sequence with the given parameters
+    fn from_escape(params: &str) -> Self {
+        params
+            .strip_prefix("38;5;")
+            .and_then(|color| color.parse::<usize>().ok())
+            .and_then(|color| Self::ROLES.get(color).copied())
+            .unwrap_or(Self::Plain)
+    }
+
+    fn is_label(self) -> bool {
+        matches!(self, Self::Primary | Self::Secondary)
+    }
+}
+
+/// A line of a rendered diagnostic, with the role of each of its characters
+type Line = Vec<(Role, char)>;
+
+fn text(line: &[(Role, char)]) -> String {
+    line.iter().map(|(_, ch)| ch).collect()
+}
+
+/// Converts a single diagnostic, rendered using [JsonEmitter::config], to JSON.
+///
+/// A diagnostic is rendered as a header, e.g. `error: invalid constraint`, followed by a snippet
+/// of source for each file its labels refer to, in which each label is drawn under the lines it
+/// spans, and finally by its notes, each of which is prefixed with `=`.
+fn to_json(rendered: &str) -> Value {
+    let mut lines = vec![Line::new()];
+    let mut role = Role::Plain;
+    let mut chars = rendered.chars();
+    while let Some(ch) = chars.next() {
+        match ch {
+            // Colors are set using escape sequences of the form `ESC [ <params> m`
+            '\x1b' => {
+                let params = chars
+                    .by_ref()
+                    .skip(1)
+                    .take_while(|ch| *ch != 'm')
+                    .collect::<String>();
+                role = Role::from_escape(&params);
+            }
+            '\n' => lines.push(Line::new()),
+            _ => lines.last_mut().unwrap().push((role, ch)),
+        }
+    }
+
+    let header = &lines[0];
+    let severity = header
+        .iter()
+        .filter(|(role, _)| *role == Role::Header)
+        .map(|(_, ch)| ch)
+        .collect::<String>();
+    let mut message = text(&header[header.len().min(severity.len())..]);
+    let mut body = lines[1..].iter();
+    // The message itself may span several lines
+    let mut rest = body.clone();
+    while let Some(line) = rest
+        .next()
+        .filter(|line| line.first().map(|(role, _)| *role) == Some(Role::Message))
+    {
+        message.push('\n');
+        message.push_str(&text(line));
+        body = rest.clone();
+    }
+
+    let mut parser = Parser::default();
+    for line in body {
+        parser.parse_line(line);
+    }
+    parser.finish_line();
+
+    let spans = parser
+        .labels
+        .iter()
+        .filter(|label| label.primary)
+        .map(Label::span)
+        .collect::<Vec<_>>();
+    let labels = parser
+        .labels
+        .iter()
+        .map(|label| {
+            let mut value = label.span();
+            value["primary"] = label.primary.into();
+            value["message"] = label.message.as_str().into();
+            value
+        })
+        .collect::<Vec<_>>();
+    json!({
+        "severity": if severity.is_empty() { "error" } else { severity.as_str() },
+        "message": message.strip_prefix(": ").unwrap_or(&message),
+        "spans": spans,
+        "labels": labels,
+        "notes": parser.notes,
+    })
+}
+
+/// A label of a diagnostic, recovered from its rendering
+struct Label {
+    file: String,
+    /// The line and column at which the label starts
+    start: (usize, usize),
+    /// The line and column just past the end of the label
+    end: (usize, usize),
+    primary: bool,
+    message: String,
+}
+impl Label {
+    fn span(&self) -> Value {
+        json!({
+            "file": self.file,
+            "line": self.start.0,
+            "column": self.start.1,
+            "end_line": self.end.0,
+            "end_column": self.end.1,
+        })
+    }
+}
+
+/// A line of source, as drawn in a snippet
+struct SourceLine {
+    number: usize,
+    /// The column of the rendering at which each character of the line is drawn, followed by the
+    /// column just past the end of the line
+    columns: Vec<usize>,
+}
+impl SourceLine {
+    /// Returns the index of the character drawn at `column`, relative to the start of the line
+    fn char_at(&self, column: usize) -> usize {
+        let end = self.columns.len() - 1;
+        self.columns
+            .iter()
+            .position(|start| *start >= column)
+            .unwrap_or_else(|| end + column - self.columns[end])
+    }
+}
+
+/// The labels drawn on a single line of source, which are only known once all of the lines
+/// under it have been parsed
+#[derive(Default)]
+struct SingleLabels {
+    /// Whether the caret under each column of the line, if any, is that of a primary label
+    carets: Option<Vec<Option<bool>>>,
+    /// The label whose message follows the carets, if any
+    trailing: Option<(bool, String)>,
+    /// The labels whose messages are drawn under the carets, with the column at which they start
+    hanging: Vec<(bool, usize, String)>,
+}
+
+/// The message which is continued by a line starting with the text of a label
+#[derive(Copy, Clone)]
+enum Continued {
+    Trailing,
+    Hanging(usize),
+    Label(usize),
+}
+
+/// Recovers the labels and notes of a diagnostic from the lines following its header
+#[derive(Default)]
+struct Parser {
+    labels: Vec<Label>,
+    notes: Vec<String>,
+    /// The file of the current snippet
+    file: String,
+    /// The offset of the left border of the current snippet
+    border: usize,
+    /// The number of multi-line labels of the current snippet, each of which is drawn in a column
+    /// of its own between the left border and the source
+    num_multi_labels: Option<usize>,
+    /// The line of source the rows under which are being parsed
+    source: Option<SourceLine>,
+    single_labels: SingleLabels,
+    /// The line and column at which each multi-line label which has not yet ended starts
+    multi_labels: BTreeMap<usize, (usize, usize)>,
+    continued: Option<Continued>,
+    /// The offset of the text of the notes, which are continued on lines indented to match
+    note_indent: Option<usize>,
+}
+impl Parser {
+    fn parse_line(&mut self, line: &Line) {
+        if line.is_empty() {
+            return;
+        }
+        if let Some(bullet) = line.iter().position(|(role, _)| *role == Role::NoteBullet) {
+            self.finish_line();
+            self.note_indent = Some(bullet + 2);
+            self.notes
+                .push(text(line.get(bullet + 2..).unwrap_or_default()));
+            return;
+        }
+        if let Some(indent) = self.note_indent {
+            let note = self.notes.last_mut().unwrap();
+            note.push('\n');
+            note.push_str(&text(line.get(indent..).unwrap_or_default()));
+            return;
+        }
+        // A message which spans several lines continues at the start of the next line
+        if line[0].0.is_label() {
+            self.continue_message(&text(line));
+            return;
+        }
+        self.continued = None;
+
+        let Some(border) = line.iter().position(|(role, _)| *role == Role::Border) else {
+            return;
+        };
+        match line[border].1 {
+            // The start of a snippet, e.g. `┌─ example.air:12:9`
+            '┌' => {
+                self.finish_line();
+                let locus = text(&line[border..]);
+                let locus = locus.split_once(' ').map_or("", |(_, locus)| locus);
+                self.file = locus.rsplitn(3, ':').nth(2).unwrap_or(locus).to_string();
+                self.border = border;
+                self.num_multi_labels = None;
+                self.multi_labels.clear();
+                return;
+            }
+            // A break between lines of source
+            '·' => {
+                self.finish_line();
+                return;
+            }
+            _ => (),
+        }
+
+        // The first line of a snippet is empty, other than the columns of any multi-line labels
+        let num_multi_labels = *self
+            .num_multi_labels
+            .get_or_insert((line.len() - border - 1) / 2);
+        let gutter = &line
+            [(border + 1).min(line.len())..(border + 1 + 2 * num_multi_labels).min(line.len())];
+        let source_start = border + 2 + 2 * num_multi_labels;
+        let source = line.get(source_start..).unwrap_or_default();
+
+        if line[..border]
+            .iter()
+            .any(|(role, _)| *role == Role::LineNumber)
+        {
+            self.parse_source_line(line, border, gutter, source);
+        } else if let Some(corner) = gutter
+            .iter()
+            .position(|(role, ch)| role.is_label() && matches!(ch, '╭' | '╰'))
+        {
+            self.parse_multi_line_label(line, border + 1 + corner, source_start);
+        } else if source.iter().all(|(_, ch)| ch.is_whitespace()) {
+            self.finish_line();
+        } else if self.single_labels.carets.is_none() {
+            self.parse_carets(source);
+        } else {
+            self.parse_hanging_label(source);
+        }
+    }
+
+    /// Parses a line of source, e.g. `12 │     enf a == b`
+    fn parse_source_line(
+        &mut self,
+        line: &Line,
+        border: usize,
+        gutter: &[(Role, char)],
+        source: &[(Role, char)],
+    ) {
+        self.finish_line();
+        let number = text(&line[..border]).trim().parse().unwrap_or_default();
+        let mut columns = vec![0];
+        for (_, ch) in source {
+            columns.push(columns.last().unwrap() + ch.width().unwrap_or(0));
+        }
+        // A multi-line label which starts after only whitespace is drawn from the gutter
+        let indent = source
+            .iter()
+            .take_while(|(_, ch)| ch.is_whitespace())
+            .count();
+        for (column, (role, ch)) in gutter.iter().enumerate() {
+            if role.is_label() && *ch == '╭' {
+                self.multi_labels.insert(column / 2, (number, indent + 1));
+            }
+        }
+        self.source = Some(SourceLine { number, columns });
+    }
+
+    /// Parses the top or bottom of a multi-line label, e.g. `│ ╭─────^` or `│ ╰──^ message`
+    fn parse_multi_line_label(&mut self, line: &Line, corner: usize, source_start: usize) {
+        self.finish_line();
+        let Some(source) = self.source.as_ref() else {
+            return;
+        };
+        let index = (corner - self.border - 1) / 2;
+        let Some(caret) = line[corner + 1..]
+            .iter()
+            .position(|(_, ch)| !matches!(ch, '─' | '│'))
+            .map(|offset| corner + 1 + offset)
+        else {
+            return;
+        };
+        // The caret is drawn under the first character of the label at the top, and under the
+        // last character of the label at the bottom
+        let column = source.char_at((caret + 1).saturating_sub(source_start));
+        if line[corner].1 == '╭' {
+            self.multi_labels
+                .insert(index, (source.number, column.saturating_sub(1) + 1));
+            return;
+        }
+
+        let start = self
+            .multi_labels
+            .remove(&index)
+            .unwrap_or((source.number, 1));
+        self.labels.push(Label {
+            file: self.file.clone(),
+            start,
+            end: (source.number, column + 1),
+            primary: line[caret].0 == Role::Primary,
+            message: text(line.get(caret + 2..).unwrap_or_default()),
+        });
+        self.continued = Some(Continued::Label(self.labels.len() - 1));
+    }
+
+    /// Parses the carets under a line of source, which are followed by the message of the last
+    /// label on the line, unless it overlaps another label, e.g. `│ ------^^^ message`
+    fn parse_carets(&mut self, source: &[(Role, char)]) {
+        let mut carets = vec![];
+        let mut column = 0;
+        while column < source.len() {
+            let role = source[column].0;
+            if !role.is_label() {
+                column += 1;
+                continue;
+            }
+            let end = source[column..]
+                .iter()
+                .position(|(r, _)| *r != role)
+                .map_or(source.len(), |len| column + len);
+            let caret = if role == Role::Primary { '^' } else { '-' };
+            if source[column..end].iter().any(|(_, ch)| *ch != caret) {
+                self.single_labels.trailing =
+                    Some((role == Role::Primary, text(&source[column..])));
+                self.continued = Some(Continued::Trailing);
+                break;
+            }
+            carets.resize(column, None);
+            carets.resize(end, Some(role == Role::Primary));
+            column = end;
+        }
+        self.single_labels.carets = Some(carets);
+    }
+
+    /// Parses the message of a label drawn under the carets, at the column at which the label
+    /// starts, e.g. `│ │  message`
+    fn parse_hanging_label(&mut self, source: &[(Role, char)]) {
+        // The message is preceded by pointers to the other labels to its left
+        if let Some(column) = source
+            .iter()
+            .position(|(role, ch)| role.is_label() && *ch != '│')
+        {
+            let primary = source[column].0 == Role::Primary;
+            self.single_labels
+                .hanging
+                .push((primary, column, text(&source[column..])));
+            self.continued = Some(Continued::Hanging(self.single_labels.hanging.len() - 1));
+        }
+    }
+
+    fn continue_message(&mut self, line: &str) {
+        let message = match self.continued {
+            Some(Continued::Trailing) => match self.single_labels.trailing.as_mut() {
+                Some((_, message)) => message,
+                None => return,
+            },
+            Some(Continued::Hanging(index)) => &mut self.single_labels.hanging[index].2,
+            Some(Continued::Label(index)) => &mut self.labels[index].message,
+            None => return,
+        };
+        message.push('\n');
+        message.push_str(line);
+    }
+
+    /// Records the labels drawn on the current line of source, now that they are all known
+    fn finish_line(&mut self) {
+        let single_labels = mem::take(&mut self.single_labels);
+        self.continued = None;
+        let (Some(source), Some(carets)) = (self.source.as_ref(), single_labels.carets) else {
+            return;
+        };
+
+        let mut runs = vec![];
+        let mut column = 0;
+        while column < carets.len() {
+            match carets[column] {
+                Some(primary) => {
+                    let end = carets[column..]
+                        .iter()
+                        .position(|caret| *caret != Some(primary))
+                        .map_or(carets.len(), |len| column + len);
+                    runs.push((column, end, primary));
+                    column = end;
+                }
+                None => column += 1,
+            }
+        }
+
+        let mut labels = vec![];
+        // The trailing label overlaps no other label, so it is drawn as the last run of carets
+        let mut limit = carets.len();
+        if let (Some((primary, message)), Some(&(start, end, _))) =
+            (single_labels.trailing, runs.last())
+        {
+            labels.push((start, end, primary, message));
+            limit = start;
+        }
+        // Primary labels are drawn over secondary labels, so a primary label ends with its run of
+        // carets, while a secondary label extends over those of any primary labels within it
+        for (primary, start, message) in single_labels.hanging {
+            let end = (start..limit)
+                .find(|column| match carets.get(*column) {
+                    Some(Some(caret)) => primary && !caret,
+                    _ => true,
+                })
+                .unwrap_or(limit);
+            labels.push((start, end.max(start + 1), primary, message));
+        }
+        // Any carets not covered by the labels above belong to labels without a message
+        let covered = |labels: &[(usize, usize, bool, String)], column: usize, primary: bool| {
+            labels
+                .iter()
+                .any(|(start, end, p, _)| (*start..*end).contains(&column) && (*p || !primary))
+        };
+        for (start, end, primary) in runs {
+            let mut column = start;
+            while column < end {
+                if covered(&labels, column, primary) {
+                    column += 1;
+                    continue;
+                }
+                let uncovered = column;
+                while column < end && !covered(&labels, column, primary) {
+                    column += 1;
+                }
+                labels.push((uncovered, column, primary, String::new()));
+            }
+        }
+
+        labels.sort_by_key(|(start, end, _, _)| (*start, *end));
+        // An empty label at the end of a line is drawn as a caret past its last character
+        let len = source.columns.len() - 1;
+        for (start, end, primary, message) in labels {
+            let start = source.char_at(start);
+            let end = source.char_at(end).min(len).max(start);
+            self.labels.push(Label {
+                file: self.file.clone(),
+                start: (source.number, start + 1),
+                end: (source.number, end + 1),
+                primary,
+                message,
+            });
+        }
+    }
+}
@@ -1,7 +1,295 @@
 pub use air_codegen_masm::{
     CodeGenerator as MasmCodeGenerator, CodegenConfig as MasmCodegenConfig,
 };
+pub use air_codegen_plonkish::CodeGenerator as PlonkishCodeGenerator;
 pub use air_codegen_winter::CodeGenerator as WinterfellCodeGenerator;
-pub use air_ir::{passes, Air, CompileError};
-pub use air_parser::{parse, parse_file, transforms};
+pub use air_ir::{passes, Air, CompileError, PrimeField};
+pub use air_parser::{ast, parse, parse_file, parse_with_cache, transforms};
 pub use air_pass::Pass;
+pub use diagnostics::JsonEmitter;
+pub use metadata::MetadataGenerator;
+
+mod diagnostics;
+mod metadata;
+
+use std::{
+    collections::{hash_map::DefaultHasher, BTreeSet, HashMap},
+    hash::{Hash, Hasher},
+    path::PathBuf,
+    sync::Arc,
+};
+
+use miden_diagnostics::{CodeMap, DiagnosticsHandler, FileName, Spanned};
+
+/// The optimization level used by [CompileOptions] by default
+pub const DEFAULT_OPT_LEVEL: u8 = 2;
+
+/// The highest supported optimization level, see [CompileOptions::with_opt_level]
+pub const MAX_OPT_LEVEL: u8 = 3;
+
+/// Options controlling the standard pass pipeline, which compiles a parsed [ast::Program] to
+/// [Air].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompileOptions {
+    /// The optimization level, see [CompileOptions::with_opt_level]
+    pub opt_level: u8,
+    /// The prime field over which constraints are expressed
+    pub field: PrimeField,
+    /// The number of iterations of a comprehension above which a warning is raised, see
+    /// [transforms::Inlining::with_unroll_warning_threshold]
+    pub unroll_warning_threshold: usize,
+    /// The features which are enabled, see [CompileOptions::with_features]
+    pub features: Vec<String>,
+}
+impl Default for CompileOptions {
+    fn default() -> Self {
+        Self {
+            opt_level: DEFAULT_OPT_LEVEL,
+            field: PrimeField::default(),
+            unroll_warning_threshold: transforms::DEFAULT_UNROLL_WARNING_THRESHOLD,
+            features: vec![],
+        }
+    }
+}
+impl CompileOptions {
+    /// Sets the optimization level, which trades compile time for the size of the constraint
+    /// graph:
+    ///
+    /// * `0` propagates constants once, before inlining, and inserts every occurrence of an
+    ///   expression into the graph as a node of its own.
+    /// * `1` is the same as `0`, except that identical expressions share a single node in the
+    ///   graph.
+    /// * `2` repeats constant folding and inlining until the program stops changing, and expands
+    ///   exponents up to [passes::DEFAULT_EXP_LOWERING_THRESHOLD] into multiplications.
+    /// * `3` is the same as `2`, except that all constant exponents are expanded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `opt_level` is larger than [MAX_OPT_LEVEL].
+    ///
+    /// NOTE: Constant propagation is never skipped entirely, as the translation to [Air] requires
+    /// references to constants to have been replaced by their values.
+    pub fn with_opt_level(mut self, opt_level: u8) -> Self {
+        assert!(
+            opt_level <= MAX_OPT_LEVEL,
+            "the optimization level must be at most {MAX_OPT_LEVEL}, but was {opt_level}"
+        );
+        self.opt_level = opt_level;
+        self
+    }
+
+    /// Sets the prime field over which programs are compiled, which defaults to Goldilocks
+    pub fn with_field(mut self, field: PrimeField) -> Self {
+        self.field = field;
+        self
+    }
+
+    /// Sets the number of iterations above which unrolling a single comprehension raises a
+    /// warning, which defaults to [transforms::DEFAULT_UNROLL_WARNING_THRESHOLD]
+    pub fn with_unroll_warning_threshold(mut self, threshold: usize) -> Self {
+        self.unroll_warning_threshold = threshold;
+        self
+    }
+
+    /// Enables the given features, in addition to any which are already enabled.
+    ///
+    /// Constraints and trace columns gated by a feature which is not enabled, e.g.
+    /// `@[feature="debug"] enf dbg = a`, are removed before any other pass is run, see
+    /// [transforms::FeatureGating].
+    pub fn with_features<I, S>(mut self, features: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.features.extend(features.into_iter().map(Into::into));
+        self
+    }
+
+    /// Compiles `program` to [Air] using the pass pipeline selected by these options
+    pub fn compile(
+        &self,
+        diagnostics: &DiagnosticsHandler,
+        program: ast::Program,
+    ) -> Result<Air, CompileError> {
+        self.compile_with(diagnostics, program, |pass| pass)
+    }
+
+    /// Same as [CompileOptions::compile], but uses `configure` to set any further options of the
+    /// translation to [Air], after those implied by the optimization level have been applied.
+    pub fn compile_with<F>(
+        &self,
+        diagnostics: &DiagnosticsHandler,
+        program: ast::Program,
+        configure: F,
+    ) -> Result<Air, CompileError>
+    where
+        F: for<'a> FnOnce(passes::AstToAir<'a>) -> passes::AstToAir<'a>,
+    {
+        let exp_lowering_threshold = match self.opt_level {
+            0 | 1 => 0,
+            2 => passes::DEFAULT_EXP_LOWERING_THRESHOLD,
+            _ => usize::MAX,
+        };
+        let feature_gating =
+            transforms::FeatureGating::new(diagnostics).with_features(&self.features);
+        let constant_propagation =
+            transforms::ConstantPropagation::new(diagnostics).with_field(self.field);
+        let inlining = transforms::Inlining::new(diagnostics)
+            .with_field(self.field)
+            .with_unroll_warning_threshold(self.unroll_warning_threshold);
+        let translate = configure(
+            passes::AstToAir::new(diagnostics)
+                .with_field(self.field)
+                .with_exp_lowering_threshold(exp_lowering_threshold)
+                .with_value_numbering(self.opt_level > 0),
+        );
+
+        match self.opt_level {
+            0 | 1 => feature_gating
+                .chain(constant_propagation)
+                .chain(inlining)
+                .chain(translate)
+                .run(program),
+            _ => feature_gating
+                .chain(constant_propagation.chain(inlining).fixpoint())
+                .chain(translate)
+                .run(program),
+        }
+    }
+}
+
+/// A cache of compiled [Air], keyed on a hash of the source code it was compiled from, and of the
+/// [CompileOptions] it was compiled with.
+///
+/// This is intended for tooling which recompiles the same program frequently, e.g. on every
+/// keystroke in an editor, and wraps [parse_with_cache] and the standard pass pipeline. The hash covers
+/// both the root source and the contents of every module it imports, so a change to any of
+/// those modules causes the program to be recompiled.
+///
+/// Only successful compilations are cached, so that diagnostics are reported every time a
+/// program which fails to compile is requested. Imported modules are cached separately, in an
+/// [ast::ModuleCache], so that a module shared by several programs is only parsed once.
+#[derive(Default)]
+pub struct CompilationCache {
+    /// Compiled programs, keyed on the hash of their sources and compile options
+    entries: HashMap<u64, Arc<Air>>,
+    /// The paths of the modules imported by a root source the last time it was compiled,
+    /// keyed on the hash of the root source alone
+    imports: HashMap<u64, Vec<PathBuf>>,
+    /// Parsed imported modules, shared by all programs compiled using this cache
+    modules: ast::ModuleCache,
+    /// The number of requests which were served from the cache
+    hits: usize,
+}
+impl CompilationCache {
+    /// Creates a new, empty [CompilationCache]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the [Air] for `source` compiled with `options`, compiling it only if there is no
+    /// cached result for the current contents of `source` and the modules it imports, compiled
+    /// with equivalent options.
+    ///
+    /// Imported modules are hashed using their current contents on disk, as that is what the
+    /// parser would use, even if `codemap` still holds the contents from a previous compilation.
+    /// Modules which are not on disk are hashed using their contents in `codemap`.
+    pub fn compile(
+        &mut self,
+        diagnostics: &DiagnosticsHandler,
+        codemap: Arc<CodeMap>,
+        source: &str,
+        options: &CompileOptions,
+    ) -> Result<Arc<Air>, CompileError> {
+        let source_hash = hash_source(source);
+        if let Some(imports) = self.imports.get(&source_hash) {
+            let key = hash_sources(&codemap, source, imports, options);
+            if let Some(air) = self.entries.get(&key) {
+                self.hits += 1;
+                return Ok(air.clone());
+            }
+        }
+
+        let ast = parse_with_cache(diagnostics, codemap.clone(), source, &self.modules)
+            .map_err(CompileError::Parse)?;
+        let imports = ast
+            .modules
+            .iter()
+            .filter_map(|module| match codemap.name(module.span().source_id()) {
+                Ok(FileName::Real(path)) => Some(path),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let air = Arc::new(options.compile(diagnostics, ast)?);
+
+        let key = hash_sources(&codemap, source, &imports, options);
+        self.imports.insert(source_hash, imports);
+        self.entries.insert(key, air.clone());
+        Ok(air)
+    }
+
+    /// Returns the number of compilations which were served from the cache
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    /// Returns the number of programs in the cache
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if the cache is empty
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the cache of parsed modules imported by the programs compiled using this cache
+    pub fn modules(&self) -> &ast::ModuleCache {
+        &self.modules
+    }
+
+    /// Removes all programs from the cache
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.imports.clear();
+        self.modules.clear();
+    }
+}
+
+fn hash_source(source: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    source.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Hashes `source` along with the current contents of each of the given imported modules, and
+/// every option which affects the result of compiling them.
+///
+/// A module which cannot be read is hashed as such, so that the program is recompiled and
+/// the failure is reported by the parser.
+fn hash_sources(
+    codemap: &CodeMap,
+    source: &str,
+    imports: &[PathBuf],
+    options: &CompileOptions,
+) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    options.opt_level.hash(&mut hasher);
+    options.field.hash(&mut hasher);
+    options.unroll_warning_threshold.hash(&mut hasher);
+    // The order in which features are enabled, or enabling one twice, makes no difference
+    let features = options.features.iter().collect::<BTreeSet<_>>();
+    features.hash(&mut hasher);
+    source.hash(&mut hasher);
+    for path in imports {
+        path.hash(&mut hasher);
+        match std::fs::read_to_string(path) {
+            Ok(source) => Some(source).hash(&mut hasher),
+            Err(_) => codemap
+                .get_by_name(&FileName::Real(path.clone()))
+                .map(|file| file.source().to_string())
+                .hash(&mut hasher),
+        }
+    }
+    hasher.finish()
+}
@@ -15,6 +15,12 @@ pub struct Cli {
 pub enum Command {
     /// Transpile AirScript source code to Rust targeting Winterfell
     Transpile(cli::Transpile),
+    /// Print a summary of the trace, constraints, and inputs of an AirScript program
+    Info(cli::Info),
+    /// Print each constraint of an AirScript program in infix form, as it is after compilation
+    Explain(cli::Explain),
+    /// Print the AST of an AirScript program, before constant propagation and inlining
+    DumpAst(cli::DumpAst),
 }
 
 pub fn main() {
@@ -27,6 +33,9 @@ pub fn main() {
 
     let res = match cli.command {
         Command::Transpile(transpile) => transpile.execute(),
+        Command::Info(info) => info.execute(),
+        Command::Explain(explain) => explain.execute(),
+        Command::DumpAst(dump_ast) => dump_ast.execute(),
     };
 
     if let Err(error) = res {
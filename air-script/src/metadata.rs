@@ -0,0 +1,16 @@
+use air_ir::{Air, CodeGenerator};
+
+/// A [CodeGenerator] which emits the [summary](Air::summary) of an [Air] as JSON, rather than code
+/// evaluating its constraints.
+///
+/// This is intended for build pipelines which only need to track the shape of an AIR, e.g. its
+/// constraint counts and degrees, and is the same summary printed by `airc info --json`.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct MetadataGenerator;
+impl CodeGenerator for MetadataGenerator {
+    type Output = String;
+
+    fn generate(&self, ir: &Air) -> anyhow::Result<Self::Output> {
+        Ok(serde_json::to_string_pretty(&ir.summary())?)
+    }
+}
@@ -26,6 +26,7 @@ pub struct AuxiliaryAir {
 }
 
 impl AuxiliaryAir {
+    pub const MAX_CONSTRAINT_DEGREE: usize = 3;
     pub fn last_step(&self) -> usize {
         self.trace_length() - self.context().num_transition_exemptions()
     }
@@ -69,10 +70,11 @@ impl Air for AuxiliaryAir {
     }
 
     fn get_aux_assertions<E: FieldElement<BaseField = Felt>>(&self, aux_rand_elements: &AuxTraceRandElements<E>) -> Vec<Assertion<E>> {
+        let rand_elements: &[E; 2] = aux_rand_elements.get_segment_elements(0).get(..2).and_then(|elements| elements.try_into().ok()).expect("expected at least 2 random values");
         let mut result = Vec::new();
         result.push(Assertion::single(0, 0, E::ONE));
         result.push(Assertion::single(0, self.last_step(), E::ONE));
-        result.push(Assertion::single(1, 0, aux_rand_elements.get_segment_elements(0)[0]));
+        result.push(Assertion::single(1, 0, rand_elements[0]));
         result.push(Assertion::single(1, self.last_step(), E::ONE));
         result
     }
@@ -93,7 +95,8 @@ impl Air for AuxiliaryAir {
         let main_next = main_frame.next();
         let aux_current = aux_frame.current();
         let aux_next = aux_frame.next();
-        result[0] = aux_next[0] - aux_current[0] * (E::from(main_current[0]) + aux_rand_elements.get_segment_elements(0)[0] + E::from(main_current[1]) + aux_rand_elements.get_segment_elements(0)[1]);
-        result[1] = aux_current[1] - aux_next[1] * (E::from(main_current[2]) + aux_rand_elements.get_segment_elements(0)[0]);
+        let rand_elements: &[E; 2] = aux_rand_elements.get_segment_elements(0).get(..2).and_then(|elements| elements.try_into().ok()).expect("expected at least 2 random values");
+        result[0] = aux_next[0] - aux_current[0] * (E::from(main_current[0]) + rand_elements[0] + E::from(main_current[1]) + rand_elements[1]);
+        result[1] = aux_current[1] - aux_next[1] * (E::from(main_current[2]) + rand_elements[0]);
     }
 }
\ No newline at end of file
@@ -0,0 +1,132 @@
+use winter_air::{Air, AirContext, Assertion, AuxTraceRandElements, EvaluationFrame, ProofOptions as WinterProofOptions, TransitionConstraintDegree, TraceInfo};
+use winter_math::fields::f64::BaseElement as Felt;
+use winter_math::{ExtensionOf, FieldElement};
+use winter_utils::collections::Vec;
+use winter_utils::{ByteWriter, Serializable};
+
+pub struct PublicInputs {
+    stack_inputs: [Felt; 16],
+}
+
+impl PublicInputs {
+    pub fn new(stack_inputs: [Felt; 16]) -> Self {
+        Self { stack_inputs }
+    }
+}
+
+impl Serializable for PublicInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write(self.stack_inputs.as_slice());
+    }
+}
+
+pub struct AuxiliaryAir {
+    context: AirContext<Felt>,
+    stack_inputs: [Felt; 16],
+}
+
+impl AuxiliaryAir {
+    pub const MAX_CONSTRAINT_DEGREE: usize = 3;
+    pub fn last_step(&self) -> usize {
+        self.trace_length() - self.context().num_transition_exemptions()
+    }
+}
+
+impl Air for AuxiliaryAir {
+    type BaseField = Felt;
+    type PublicInputs = PublicInputs;
+
+    fn context(&self) -> &AirContext<Felt> {
+        &self.context
+    }
+
+    fn new(trace_info: TraceInfo, public_inputs: PublicInputs, options: WinterProofOptions) -> Self {
+        let main_degrees = vec![TransitionConstraintDegree::new(3), TransitionConstraintDegree::new(1), TransitionConstraintDegree::new(1)];
+        let aux_degrees = vec![TransitionConstraintDegree::new(2), TransitionConstraintDegree::new(2)];
+        let num_main_assertions = 2;
+        let num_aux_assertions = 4;
+
+        let context = AirContext::new_multi_segment(
+            trace_info,
+            main_degrees,
+            aux_degrees,
+            num_main_assertions,
+            num_aux_assertions,
+            options,
+        )
+        .set_num_transition_exemptions(2);
+        Self { context, stack_inputs: public_inputs.stack_inputs }
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<Felt>> {
+        vec![]
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Felt>> {
+        let mut result = Vec::new();
+        result.push(Assertion::single(0, 0, Felt::ONE));
+        result.push(Assertion::single(1, 0, Felt::ONE));
+        result
+    }
+
+    fn get_aux_assertions<E: FieldElement<BaseField = Felt>>(&self, aux_rand_elements: &AuxTraceRandElements<E>) -> Vec<Assertion<E>> {
+        let rand_elements: &[E; 2] = aux_rand_elements.get_segment_elements(0).get(..2).and_then(|elements| elements.try_into().ok()).expect("expected at least 2 random values");
+        let mut result = Vec::new();
+        result.push(Assertion::single(0, 0, E::ONE));
+        result.push(Assertion::single(0, self.last_step(), E::ONE));
+        result.push(Assertion::single(1, 0, rand_elements[0]));
+        result.push(Assertion::single(1, self.last_step(), E::ONE));
+        result
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = Felt>>(&self, frame: &EvaluationFrame<E>, periodic_values: &[E], result: &mut [E]) {
+        let main_current = frame.current();
+        let main_next = frame.next();
+        result[0] = main_next[0] - (main_current[1] + main_current[0] * main_current[1] * main_current[2]);
+        result[1] = main_next[1] - (main_current[2] + main_next[0]);
+        result[2] = main_current[2] - (main_current[0] + main_current[1]);
+    }
+
+    fn evaluate_aux_transition<F, E>(&self, main_frame: &EvaluationFrame<F>, aux_frame: &EvaluationFrame<E>, _periodic_values: &[F], aux_rand_elements: &AuxTraceRandElements<E>, result: &mut [E])
+    where F: FieldElement<BaseField = Felt>,
+          E: FieldElement<BaseField = Felt> + ExtensionOf<F>,
+    {
+        let main_current = main_frame.current();
+        let main_next = main_frame.next();
+        let aux_current = aux_frame.current();
+        let aux_next = aux_frame.next();
+        let rand_elements: &[E; 2] = aux_rand_elements.get_segment_elements(0).get(..2).and_then(|elements| elements.try_into().ok()).expect("expected at least 2 random values");
+        result[0] = aux_next[0] - aux_current[0] * (E::from(main_current[0]) + rand_elements[0] + E::from(main_current[1]) + rand_elements[1]);
+        result[1] = aux_current[1] - aux_next[1] * (E::from(main_current[2]) + rand_elements[0]);
+    }
+}
+
+#[cfg(test)] 
+mod constraint_counts {
+    use super::*;
+    use winter_air::{FieldExtension, TraceLayout};
+
+    const MAIN_TRACE_WIDTH: usize = 3;
+    const AUX_TRACE_WIDTH: usize = 2;
+    const NUM_RANDOM_VALUES: usize = 2;
+    const NUM_MAIN_TRANSITION_CONSTRAINTS: usize = 3;
+    const NUM_AUX_TRANSITION_CONSTRAINTS: usize = 2;
+    const NUM_MAIN_ASSERTIONS: usize = 2;
+    const NUM_AUX_ASSERTIONS: usize = 4;
+
+    #[test]
+    fn constraint_counts() {
+        let layout = TraceLayout::new(MAIN_TRACE_WIDTH, [AUX_TRACE_WIDTH], [NUM_RANDOM_VALUES]);
+        let trace_info = TraceInfo::new_multi_segment(layout, 1024, vec![]);
+        let options = WinterProofOptions::new(27, 128, 0, FieldExtension::None, 8, 31);
+        let air = AuxiliaryAir::new(trace_info, PublicInputs::new([Felt::ZERO; 16]), options);
+        assert_eq!(air.trace_layout().main_trace_width(), MAIN_TRACE_WIDTH);
+        assert_eq!(air.trace_layout().aux_trace_width(), AUX_TRACE_WIDTH);
+        assert_eq!(air.context().num_main_transition_constraints(), NUM_MAIN_TRANSITION_CONSTRAINTS);
+        assert_eq!(air.context().num_aux_transition_constraints(), NUM_AUX_TRANSITION_CONSTRAINTS);
+        assert_eq!(air.get_assertions().len(), NUM_MAIN_ASSERTIONS);
+        let mut aux_rand_elements = AuxTraceRandElements::<Felt>::new();
+        aux_rand_elements.add_segment_elements(vec![Felt::ZERO; NUM_RANDOM_VALUES]);
+        assert_eq!(air.get_aux_assertions(&aux_rand_elements).len(), NUM_AUX_ASSERTIONS);
+    }
+}
\ No newline at end of file
@@ -26,6 +26,7 @@ pub struct BitwiseAir {
 }
 
 impl BitwiseAir {
+    pub const MAX_CONSTRAINT_DEGREE: usize = 3;
     pub fn last_step(&self) -> usize {
         self.trace_length() - self.context().num_transition_exemptions()
     }
@@ -75,16 +76,16 @@ impl Air for BitwiseAir {
     fn evaluate_transition<E: FieldElement<BaseField = Felt>>(&self, frame: &EvaluationFrame<E>, periodic_values: &[E], result: &mut [E]) {
         let main_current = frame.current();
         let main_next = frame.next();
-        result[0] = main_current[0].exp(E::PositiveInteger::from(2_u64)) - main_current[0] - E::ZERO;
+        result[0] = main_current[0].square() - main_current[0] - E::ZERO;
         result[1] = periodic_values[1] * (main_next[0] - main_current[0]) - E::ZERO;
-        result[2] = main_current[3].exp(E::PositiveInteger::from(2_u64)) - main_current[3] - E::ZERO;
-        result[3] = main_current[4].exp(E::PositiveInteger::from(2_u64)) - main_current[4] - E::ZERO;
-        result[4] = main_current[5].exp(E::PositiveInteger::from(2_u64)) - main_current[5] - E::ZERO;
-        result[5] = main_current[6].exp(E::PositiveInteger::from(2_u64)) - main_current[6] - E::ZERO;
-        result[6] = main_current[7].exp(E::PositiveInteger::from(2_u64)) - main_current[7] - E::ZERO;
-        result[7] = main_current[8].exp(E::PositiveInteger::from(2_u64)) - main_current[8] - E::ZERO;
-        result[8] = main_current[9].exp(E::PositiveInteger::from(2_u64)) - main_current[9] - E::ZERO;
-        result[9] = main_current[10].exp(E::PositiveInteger::from(2_u64)) - main_current[10] - E::ZERO;
+        result[2] = main_current[3].square() - main_current[3] - E::ZERO;
+        result[3] = main_current[4].square() - main_current[4] - E::ZERO;
+        result[4] = main_current[5].square() - main_current[5] - E::ZERO;
+        result[5] = main_current[6].square() - main_current[6] - E::ZERO;
+        result[6] = main_current[7].square() - main_current[7] - E::ZERO;
+        result[7] = main_current[8].square() - main_current[8] - E::ZERO;
+        result[8] = main_current[9].square() - main_current[9] - E::ZERO;
+        result[9] = main_current[10].square() - main_current[10] - E::ZERO;
         result[10] = periodic_values[0] * (main_current[1] - (E::ONE * main_current[3] + E::from(2_u64) * main_current[4] + E::from(4_u64) * main_current[5] + E::from(8_u64) * main_current[6])) - E::ZERO;
         result[11] = periodic_values[0] * (main_current[2] - (E::ONE * main_current[7] + E::from(2_u64) * main_current[8] + E::from(4_u64) * main_current[9] + E::from(8_u64) * main_current[10])) - E::ZERO;
         result[12] = periodic_values[1] * (main_next[1] - (main_current[1] * E::from(16_u64) + E::ONE * main_current[3] + E::from(2_u64) * main_current[4] + E::from(4_u64) * main_current[5] + E::from(8_u64) * main_current[6])) - E::ZERO;
@@ -0,0 +1,203 @@
+use std::sync::Arc;
+
+use air_script::{CompilationCache, CompileOptions, PrimeField};
+use miden_diagnostics::{
+    term::termcolor::ColorChoice, CodeMap, DefaultEmitter, DiagnosticsHandler, FileName,
+};
+
+const ROOT: &str = "
+def test
+
+use cache_lib::*
+
+trace_columns:
+    main: [a, b]
+
+public_inputs:
+    stack_inputs: [16]
+
+boundary_constraints:
+    enf a.first = 0
+
+integrity_constraints:
+    enf lib_constraint([a, b])";
+
+const LIB: &str = "
+mod cache_lib
+
+ev lib_constraint([a, b]):
+    enf a' = a + b";
+
+const LIB_MODIFIED: &str = "
+mod cache_lib
+
+ev lib_constraint([a, b]):
+    enf a' = a * b";
+
+/// Creates a new [CodeMap] and [DiagnosticsHandler], with the `cache_lib` module registered
+/// in the codemap using the given source, so that it is resolved without touching the disk.
+fn session(lib: &str) -> (Arc<CodeMap>, DiagnosticsHandler) {
+    let codemap = Arc::new(CodeMap::new());
+    let cwd = std::env::current_dir().unwrap();
+    codemap.add(FileName::Real(cwd.join("cache_lib.air")), lib.to_string());
+    let emitter = Arc::new(DefaultEmitter::new(ColorChoice::Auto));
+    let diagnostics = DiagnosticsHandler::new(Default::default(), codemap.clone(), emitter);
+    (codemap, diagnostics)
+}
+
+#[test]
+fn cache_hit_on_identical_source() {
+    let mut cache = CompilationCache::new();
+    let (codemap, diagnostics) = session(LIB);
+
+    let first = cache
+        .compile(
+            &diagnostics,
+            codemap.clone(),
+            ROOT,
+            &CompileOptions::default(),
+        )
+        .unwrap();
+    assert_eq!(cache.hits(), 0);
+
+    let second = cache
+        .compile(&diagnostics, codemap, ROOT, &CompileOptions::default())
+        .unwrap();
+    assert_eq!(cache.hits(), 1);
+    assert!(Arc::ptr_eq(&first, &second));
+    assert_eq!(cache.len(), 1);
+}
+
+#[test]
+fn cache_miss_on_modified_source() {
+    let mut cache = CompilationCache::new();
+    let (codemap, diagnostics) = session(LIB);
+
+    cache
+        .compile(
+            &diagnostics,
+            codemap.clone(),
+            ROOT,
+            &CompileOptions::default(),
+        )
+        .unwrap();
+    let modified = ROOT.replace("enf a.first = 0", "enf a.first = 1");
+    cache
+        .compile(&diagnostics, codemap, &modified, &CompileOptions::default())
+        .unwrap();
+    assert_eq!(cache.hits(), 0);
+    assert_eq!(cache.len(), 2);
+}
+
+#[test]
+fn cache_miss_on_modified_import() {
+    let mut cache = CompilationCache::new();
+
+    let (codemap, diagnostics) = session(LIB);
+    let first = cache
+        .compile(&diagnostics, codemap, ROOT, &CompileOptions::default())
+        .unwrap();
+
+    let (codemap, diagnostics) = session(LIB_MODIFIED);
+    let second = cache
+        .compile(&diagnostics, codemap, ROOT, &CompileOptions::default())
+        .unwrap();
+    assert_eq!(cache.hits(), 0);
+    assert!(!Arc::ptr_eq(&first, &second));
+
+    // The cached result for the original import is still valid
+    let (codemap, diagnostics) = session(LIB);
+    let third = cache
+        .compile(&diagnostics, codemap, ROOT, &CompileOptions::default())
+        .unwrap();
+    assert_eq!(cache.hits(), 1);
+    assert!(Arc::ptr_eq(&first, &third));
+}
+
+#[test]
+fn cache_miss_on_import_edited_on_disk() {
+    let mut cache = CompilationCache::new();
+    let codemap = Arc::new(CodeMap::new());
+    let emitter = Arc::new(DefaultEmitter::new(ColorChoice::Auto));
+    let diagnostics = DiagnosticsHandler::new(Default::default(), codemap.clone(), emitter);
+
+    // The import is resolved relative to the current directory, as the root source is not a file
+    let path = std::env::current_dir()
+        .unwrap()
+        .join("cache_edited_lib.air");
+    let root = ROOT.replace("cache_lib", "cache_edited_lib");
+    let constraints = [LIB, LIB_MODIFIED].map(|lib| {
+        std::fs::write(&path, lib.replace("cache_lib", "cache_edited_lib")).unwrap();
+        // The same codemap is used for both compilations, so it still holds the original
+        // contents of the import when it is edited
+        let air = cache.compile(
+            &diagnostics,
+            codemap.clone(),
+            &root,
+            &CompileOptions::default(),
+        );
+        air.map(|air| air.explain().to_string())
+    });
+    std::fs::remove_file(&path).unwrap();
+
+    let [first, second] = constraints.map(Result::unwrap);
+    assert_eq!(cache.hits(), 0);
+    assert!(first.contains("a' - (a + b) = 0"), "{first}");
+    assert!(second.contains("a' - a * b = 0"), "{second}");
+}
+
+#[test]
+fn shared_import_parsed_once() {
+    let mut cache = CompilationCache::new();
+    let (codemap, diagnostics) = session(LIB);
+
+    cache
+        .compile(
+            &diagnostics,
+            codemap.clone(),
+            ROOT,
+            &CompileOptions::default(),
+        )
+        .unwrap();
+    let other = ROOT.replace("def test", "def other");
+    cache
+        .compile(&diagnostics, codemap, &other, &CompileOptions::default())
+        .unwrap();
+    assert_eq!(cache.len(), 2);
+    assert_eq!(cache.modules().misses(), 1);
+    assert_eq!(cache.modules().hits(), 1);
+}
+
+#[test]
+fn cache_miss_on_different_options() {
+    let mut cache = CompilationCache::new();
+    let (codemap, diagnostics) = session(LIB);
+
+    let options = CompileOptions::default();
+    let first = cache
+        .compile(&diagnostics, codemap.clone(), ROOT, &options)
+        .unwrap();
+    for other in [
+        options.clone().with_opt_level(0),
+        options.clone().with_field(PrimeField::new(2013265921)),
+        options.clone().with_unroll_warning_threshold(1),
+        options.clone().with_features(["debug"]),
+    ] {
+        let second = cache
+            .compile(&diagnostics, codemap.clone(), ROOT, &other)
+            .unwrap();
+        assert!(!Arc::ptr_eq(&first, &second));
+    }
+    assert_eq!(cache.hits(), 0);
+    assert_eq!(cache.len(), 5);
+
+    // Features are a set, so the order in which they are enabled makes no difference
+    let ab = options.clone().with_features(["a", "b"]);
+    let ba = options.with_features(["b", "a", "a"]);
+    let first = cache
+        .compile(&diagnostics, codemap.clone(), ROOT, &ab)
+        .unwrap();
+    let second = cache.compile(&diagnostics, codemap, ROOT, &ba).unwrap();
+    assert!(Arc::ptr_eq(&first, &second));
+    assert_eq!(cache.hits(), 1);
+}
@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
-use air_ir::{CodeGenerator, CompileError};
-use air_pass::Pass;
+use air_ir::{Air, CodeGenerator, CompileError, PrimeField};
+use air_script::CompileOptions;
 use miden_diagnostics::{
     term::termcolor::ColorChoice, CodeMap, DefaultEmitter, DiagnosticsHandler,
 };
@@ -9,37 +9,64 @@ use miden_diagnostics::{
 pub enum Target {
     Winterfell,
     Masm,
+    Plonkish,
 }
 
 pub struct Test {
     input_path: String,
+    field: PrimeField,
+    codemap: Arc<CodeMap>,
 }
 impl Test {
     pub fn new(input_path: String) -> Self {
-        Test { input_path }
+        Test {
+            input_path,
+            field: PrimeField::default(),
+            codemap: Arc::new(CodeMap::new()),
+        }
     }
 
-    pub fn transpile(&self, target: Target) -> Result<String, CompileError> {
-        let codemap = Arc::new(CodeMap::new());
-        let emitter = Arc::new(DefaultEmitter::new(ColorChoice::Auto));
-        let diagnostics = DiagnosticsHandler::new(Default::default(), codemap.clone(), emitter);
+    pub fn with_field(mut self, field: PrimeField) -> Self {
+        self.field = field;
+        self
+    }
 
-        // Parse from file to internal representation
-        let air = air_parser::parse_file(&diagnostics, codemap, &self.input_path)
-            .map_err(CompileError::Parse)
-            .and_then(|ast| {
-                let mut pipeline = air_parser::transforms::ConstantPropagation::new(&diagnostics)
-                    .chain(air_parser::transforms::Inlining::new(&diagnostics))
-                    .chain(air_ir::passes::AstToAir::new(&diagnostics));
-                pipeline.run(ast)
-            })?;
+    /// Returns the [CodeMap] into which the sources of this test are loaded
+    pub fn codemap(&self) -> Arc<CodeMap> {
+        self.codemap.clone()
+    }
 
+    pub fn transpile(&self, target: Target) -> Result<String, CompileError> {
         let backend: Box<dyn CodeGenerator<Output = String>> = match target {
-            Target::Winterfell => Box::new(air_codegen_winter::CodeGenerator),
+            Target::Winterfell => Box::<air_codegen_winter::CodeGenerator>::default(),
             Target::Masm => Box::<air_codegen_masm::CodeGenerator>::default(),
+            Target::Plonkish => Box::<air_codegen_plonkish::CodeGenerator>::default(),
         };
+        self.transpile_with(backend.as_ref())
+    }
 
-        // generate Rust code targeting Winterfell
+    pub fn transpile_with(
+        &self,
+        backend: &dyn CodeGenerator<Output = String>,
+    ) -> Result<String, CompileError> {
+        let air = self.compile()?;
+
+        // generate code for the provided backend
         Ok(backend.generate(&air).expect("code generation failed"))
     }
+
+    pub fn compile(&self) -> Result<Air, CompileError> {
+        let codemap = self.codemap.clone();
+        let emitter = Arc::new(DefaultEmitter::new(ColorChoice::Auto));
+        let diagnostics = DiagnosticsHandler::new(Default::default(), codemap.clone(), emitter);
+
+        // Parse from file to internal representation
+        air_parser::parse_file(&diagnostics, codemap, &self.input_path)
+            .map_err(CompileError::Parse)
+            .and_then(|ast| {
+                CompileOptions::default()
+                    .with_field(self.field)
+                    .compile(&diagnostics, ast)
+            })
+    }
 }
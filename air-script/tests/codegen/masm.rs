@@ -1,3 +1,5 @@
+use air_ir::{CodeGenerator, PrimeField};
+
 use super::helpers::{Target, Test};
 use expect_test::expect_file;
 
@@ -44,6 +46,16 @@ fn pub_inputs() {
     expected.assert_eq(&generated_masm);
 }
 
+#[test]
+fn pub_inputs_matrix() {
+    let generated_masm = Test::new("tests/pub_inputs/pub_inputs_matrix.air".to_string())
+        .transpile(Target::Masm)
+        .unwrap();
+
+    let expected = expect_file!["../pub_inputs/pub_inputs_matrix.masm"];
+    expected.assert_eq(&generated_masm);
+}
+
 #[test]
 fn system() {
     let generated_masm = Test::new("tests/system/system.air".to_string())
@@ -167,6 +179,16 @@ fn selectors() {
     expected.assert_eq(&generated_masm);
 }
 
+#[test]
+fn trace_len() {
+    let generated_masm = Test::new("tests/trace_len/trace_len.air".to_string())
+        .transpile(Target::Masm)
+        .unwrap();
+
+    let expected = expect_file!["../trace_len/trace_len.masm"];
+    expected.assert_eq(&generated_masm);
+}
+
 #[test]
 fn constraint_comprehension() {
     let generated_masm =
@@ -185,3 +207,116 @@ fn constraint_comprehension() {
     let expected = expect_file!["../constraint_comprehension/constraint_comprehension.masm"];
     expected.assert_eq(&generated_masm);
 }
+
+#[test]
+fn output_is_deterministic() {
+    let test = Test::new("tests/modules/modules.air".to_string());
+    let expected = test.transpile(Target::Masm).unwrap();
+    for _ in 0..4 {
+        assert_eq!(test.transpile(Target::Masm).unwrap(), expected);
+    }
+}
+
+#[test]
+fn err_unsupported_field() {
+    let air = Test::new("tests/binary/binary.air".to_string())
+        .with_field(PrimeField::F62)
+        .compile()
+        .unwrap();
+
+    let err = air_codegen_masm::CodeGenerator::default()
+        .generate(&air)
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "the Miden VM only supports the Goldilocks field, but constraints are expressed over GF(4611624995532046337)"
+    );
+}
+
+#[test]
+fn err_unsupported_boundary() {
+    let air = Test::new("tests/boundary_offsets/boundary_offsets.air".to_string())
+        .compile()
+        .unwrap();
+
+    let err = air_codegen_masm::CodeGenerator::default()
+        .generate(&air)
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "the Miden VM only supports boundary constraints on the first and last rows, but a constraint is applied to the row `last - 1`"
+    );
+}
+
+#[test]
+fn err_unsupported_integrity() {
+    let air = Test::new("tests/cyclic/cyclic.air".to_string())
+        .compile()
+        .unwrap();
+
+    let err = air_codegen_masm::CodeGenerator::default()
+        .generate(&air)
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "the Miden VM exempts the last two rows of the trace from all integrity constraints, but a constraint is applied to every frame of 2 consecutive rows, wrapping around the trace"
+    );
+}
+
+#[test]
+fn err_row_offsets_exceed_frame() {
+    let air = Test::new("tests/row_offsets/row_offsets.air".to_string())
+        .compile()
+        .unwrap();
+
+    let err = air_codegen_masm::CodeGenerator::default()
+        .generate(&air)
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "row offsets greater than 1 are not supported by the Miden assembly backend: the Miden VM only evaluates integrity constraints over the current and next rows, but a constraint is applied to every frame of 3 consecutive rows"
+    );
+}
+
+#[test]
+fn err_pub_inputs_overflow() {
+    let air = Test::new("tests/pub_inputs/pub_inputs_overflow.air".to_string())
+        .compile()
+        .unwrap();
+
+    let err = air_codegen_masm::CodeGenerator::default()
+        .generate(&air)
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "the public inputs have too many elements in total to be laid out in memory starting at address 4294800000"
+    );
+}
+
+#[test]
+fn pub_inputs_large() {
+    let air = Test::new("tests/pub_inputs/pub_inputs_large.air".to_string())
+        .compile()
+        .unwrap();
+
+    // The inputs span 8912896 words, which do not fit after the default address
+    let err = air_codegen_masm::CodeGenerator::default()
+        .generate(&air)
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "the public inputs have too many elements in total to be laid out in memory starting at address 4294800000"
+    );
+
+    // but do when laid out from a lower address
+    let config = air_codegen_masm::CodegenConfig {
+        public_inputs_address: 0,
+        ..Default::default()
+    };
+    let generated_masm = air_codegen_masm::CodeGenerator::new(config)
+        .generate(&air)
+        .unwrap();
+    // `roots` is laid out first, followed by `stack_inputs`
+    assert!(generated_masm.contains("padw mem_loadw.8388607 drop drop"));
+    assert!(generated_masm.contains("padw mem_loadw.8912895 drop drop"));
+}
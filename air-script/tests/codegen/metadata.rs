@@ -0,0 +1,17 @@
+use air_script::MetadataGenerator;
+use expect_test::expect_file;
+
+use super::helpers::Test;
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn aux_trace() {
+    let generated = Test::new("tests/aux_trace/aux_trace.air".to_string())
+        .transpile_with(&MetadataGenerator)
+        .unwrap();
+
+    let expected = expect_file!["../aux_trace/aux_trace_metadata.json"];
+    expected.assert_eq(&generated);
+}
@@ -1,3 +1,5 @@
 mod helpers;
 mod masm;
+mod metadata;
+mod plonkish;
 mod winterfell;
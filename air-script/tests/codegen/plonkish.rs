@@ -0,0 +1,192 @@
+use air_ir::CodeGenerator;
+
+use super::helpers::{Target, Test};
+use expect_test::expect_file;
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn periodic_columns() {
+    let generated = Test::new("tests/periodic_columns/periodic_columns.air".to_string())
+        .transpile(Target::Plonkish)
+        .unwrap();
+
+    let expected = expect_file!["../periodic_columns/periodic_columns.json"];
+    expected.assert_eq(&generated);
+}
+
+#[test]
+fn aux_trace() {
+    let generated = Test::new("tests/aux_trace/aux_trace.air".to_string())
+        .transpile(Target::Plonkish)
+        .unwrap();
+
+    let expected = expect_file!["../aux_trace/aux_trace.json"];
+    expected.assert_eq(&generated);
+}
+
+#[test]
+fn periodic_columns_hex_constants() {
+    let generated = Test::new("tests/periodic_columns/periodic_columns.air".to_string())
+        .transpile_with(&air_codegen_plonkish::CodeGenerator::default().with_hex_constants(true))
+        .unwrap();
+
+    let expected = expect_file!["../periodic_columns/periodic_columns_hex.json"];
+    expected.assert_eq(&generated);
+}
+
+#[test]
+fn pub_inputs_large() {
+    let generated = Test::new("tests/pub_inputs/pub_inputs_large.air".to_string())
+        .transpile(Target::Plonkish)
+        .unwrap();
+
+    let expected = expect_file!["../pub_inputs/pub_inputs_large.json"];
+    expected.assert_eq(&generated);
+}
+
+#[test]
+fn pub_inputs_overflow() {
+    let air = Test::new("tests/pub_inputs/pub_inputs_overflow.air".to_string())
+        .compile()
+        .unwrap();
+
+    let err = air_codegen_plonkish::CodeGenerator::default()
+        .generate(&air)
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "the public inputs have too many elements in total to be exported"
+    );
+}
+
+#[test]
+fn max_exponent() {
+    let generated = Test::new("tests/exponents/max_exponent.air".to_string())
+        .transpile(Target::Plonkish)
+        .unwrap();
+
+    // the power is written out as a product with an occurrence of `a` for each unit of exponent
+    let queries = generated.matches(r#""type": "advice""#).count();
+    assert_eq!(queries, air_codegen_plonkish::MAX_EXPONENT + 1);
+}
+
+#[test]
+fn err_large_exponent() {
+    let air = Test::new("tests/exponents/large_exponent.air".to_string())
+        .compile()
+        .unwrap();
+
+    let err = air_codegen_plonkish::CodeGenerator::default()
+        .generate(&air)
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "the constraint `integrity_0_0` raises a value to the power of 3000000, but powers can only be exported with exponents up to 4096"
+    );
+}
+
+#[test]
+fn aux_trace_format_version_2() {
+    let generated = Test::new("tests/aux_trace/aux_trace.air".to_string())
+        .transpile_with(&air_codegen_plonkish::CodeGenerator::default().with_format_version(2))
+        .unwrap();
+
+    let expected = expect_file!["../aux_trace/aux_trace_v2.json"];
+    expected.assert_eq(&generated);
+}
+
+#[test]
+fn gate_degrees_match_constraint_degrees() {
+    for path in [
+        "tests/aux_trace/aux_trace.air",
+        "tests/periodic_columns/periodic_columns.air",
+        "tests/selectors/selectors.air",
+    ] {
+        let air = Test::new(path.to_string()).compile().unwrap();
+        let generated = air_codegen_plonkish::CodeGenerator::default()
+            .with_format_version(2)
+            .generate(&air)
+            .unwrap();
+        let system: serde_json::Value = serde_json::from_str(&generated).unwrap();
+
+        let expected = (0..air.trace_segment_widths.len())
+            .flat_map(|segment| air.integrity_constraint_degrees(segment))
+            .map(|degree| degree.degree() as u64)
+            .collect::<Vec<_>>();
+        let degrees = system["gates"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|gate| gate["degree"].as_u64().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(degrees, expected, "mismatched gate degrees for {path}");
+    }
+}
+
+#[test]
+fn unsupported_format_version() {
+    let air = Test::new("tests/aux_trace/aux_trace.air".to_string())
+        .compile()
+        .unwrap();
+
+    let err = air_codegen_plonkish::CodeGenerator::default()
+        .with_format_version(3)
+        .generate(&air)
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "unsupported format version 3, expected a version from 1 to 2"
+    );
+}
+
+#[test]
+fn cyclic_format_version_2() {
+    let generated = Test::new("tests/cyclic/cyclic.air".to_string())
+        .transpile_with(&air_codegen_plonkish::CodeGenerator::default().with_format_version(2))
+        .unwrap();
+
+    let expected = expect_file!["../cyclic/cyclic_v2.json"];
+    expected.assert_eq(&generated);
+}
+
+#[test]
+fn err_cyclic_format_version_1() {
+    let air = Test::new("tests/cyclic/cyclic.air".to_string())
+        .compile()
+        .unwrap();
+
+    let err = air_codegen_plonkish::CodeGenerator::default()
+        .generate(&air)
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "the constraint `integrity_0_1` is marked `cyclic`, which can only be exported from format version 2"
+    );
+}
+
+#[test]
+fn row_offsets_format_version_2() {
+    let generated = Test::new("tests/row_offsets/row_offsets.air".to_string())
+        .transpile_with(&air_codegen_plonkish::CodeGenerator::default().with_format_version(2))
+        .unwrap();
+
+    let expected = expect_file!["../row_offsets/row_offsets_v2.json"];
+    expected.assert_eq(&generated);
+}
+
+#[test]
+fn err_row_offsets_format_version_1() {
+    let air = Test::new("tests/row_offsets/row_offsets.air".to_string())
+        .compile()
+        .unwrap();
+
+    let err = air_codegen_plonkish::CodeGenerator::default()
+        .generate(&air)
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "the constraint `integrity_0_0` accesses the row 2 rows ahead, but rows more than one row ahead can only be exported from format version 2"
+    );
+}
@@ -1,3 +1,5 @@
+use air_ir::{CodeGenerator, PrimeField};
+
 use super::helpers::{Target, Test};
 use expect_test::expect_file;
 
@@ -14,6 +16,27 @@ fn aux_trace() {
     expected.assert_eq(&generated_air);
 }
 
+#[test]
+fn aux_segments() {
+    let generated_air = Test::new("tests/aux_segments/aux_segments.air".to_string())
+        .transpile(Target::Winterfell)
+        .unwrap();
+
+    let expected = expect_file!["../aux_segments/aux_segments.rs"];
+    expected.assert_eq(&generated_air);
+}
+
+#[test]
+fn aux_trace_constraint_count_tests() {
+    let backend = air_codegen_winter::CodeGenerator::default().with_constraint_count_tests(true);
+    let generated_air = Test::new("tests/aux_trace/aux_trace.air".to_string())
+        .transpile_with(&backend)
+        .unwrap();
+
+    let expected = expect_file!["../aux_trace/aux_trace_constraint_counts.rs"];
+    expected.assert_eq(&generated_air);
+}
+
 #[test]
 fn binary() {
     let generated_air = Test::new("tests/binary/binary.air".to_string())
@@ -44,6 +67,26 @@ fn pub_inputs() {
     expected.assert_eq(&generated_air);
 }
 
+#[test]
+fn pub_inputs_matrix() {
+    let generated_air = Test::new("tests/pub_inputs/pub_inputs_matrix.air".to_string())
+        .transpile(Target::Winterfell)
+        .unwrap();
+
+    let expected = expect_file!["../pub_inputs/pub_inputs_matrix.rs"];
+    expected.assert_eq(&generated_air);
+}
+
+#[test]
+fn pub_inputs_slices() {
+    let generated_air = Test::new("tests/pub_inputs/pub_inputs_slices.air".to_string())
+        .transpile(Target::Winterfell)
+        .unwrap();
+
+    let expected = expect_file!["../pub_inputs/pub_inputs_slices.rs"];
+    expected.assert_eq(&generated_air);
+}
+
 #[test]
 fn system() {
     let generated_air = Test::new("tests/system/system.air".to_string())
@@ -104,6 +147,17 @@ fn trace_col_groups() {
     expected.assert_eq(&generated_air);
 }
 
+#[test]
+fn trace_col_groups_column_constants() {
+    let backend = air_codegen_winter::CodeGenerator::default().with_column_constants(true);
+    let generated_air = Test::new("tests/trace_col_groups/trace_col_groups.air".to_string())
+        .transpile_with(&backend)
+        .unwrap();
+
+    let expected = expect_file!["../trace_col_groups/trace_col_groups_columns.rs"];
+    expected.assert_eq(&generated_air);
+}
+
 #[test]
 fn indexed_trace_access() {
     let generated_air =
@@ -169,6 +223,61 @@ fn selectors() {
     expected.assert_eq(&generated_air);
 }
 
+#[test]
+fn selectors_factored() {
+    let backend = air_codegen_winter::CodeGenerator::default().with_factored_selectors(true);
+    let generated_air = Test::new("tests/selectors/selectors_factored.air".to_string())
+        .transpile_with(&backend)
+        .unwrap();
+
+    let expected = expect_file!["../selectors/selectors_factored.rs"];
+    expected.assert_eq(&generated_air);
+}
+
+#[test]
+fn doc_comments() {
+    let test = Test::new("tests/doc_comments/doc_comments.air".to_string());
+    let backend = air_codegen_winter::CodeGenerator::default()
+        .with_doc_comments(true)
+        .with_codemap(test.codemap());
+    let generated_air = test.transpile_with(&backend).unwrap();
+
+    let expected = expect_file!["../doc_comments/doc_comments.rs"];
+    expected.assert_eq(&generated_air);
+}
+
+#[test]
+fn provenance() {
+    let test = Test::new("tests/provenance/provenance.air".to_string());
+    let backend = air_codegen_winter::CodeGenerator::default()
+        .with_provenance(true)
+        .with_codemap(test.codemap());
+    let generated_air = test.transpile_with(&backend).unwrap();
+
+    let expected = expect_file!["../provenance/provenance.rs"];
+    expected.assert_eq(&generated_air);
+}
+
+#[test]
+fn trace_len() {
+    let generated_air = Test::new("tests/trace_len/trace_len.air".to_string())
+        .transpile(Target::Winterfell)
+        .unwrap();
+
+    let expected = expect_file!["../trace_len/trace_len.rs"];
+    expected.assert_eq(&generated_air);
+}
+
+#[test]
+fn includes() {
+    let generated_air = Test::new("tests/includes/includes.air".to_string())
+        .transpile(Target::Winterfell)
+        .unwrap();
+
+    let expected = expect_file!["../includes/includes.rs"];
+    expected.assert_eq(&generated_air);
+}
+
 #[test]
 fn constraint_comprehension() {
     let generated_air =
@@ -187,3 +296,235 @@ fn constraint_comprehension() {
     let expected = expect_file!["../constraint_comprehension/constraint_comprehension.rs"];
     expected.assert_eq(&generated_air);
 }
+
+#[test]
+fn modules() {
+    let generated_air = Test::new("tests/modules/modules.air".to_string())
+        .transpile(Target::Winterfell)
+        .unwrap();
+
+    let expected = expect_file!["../modules/modules.rs"];
+    expected.assert_eq(&generated_air);
+}
+
+#[test]
+fn modules_modular_output() {
+    let backend = air_codegen_winter::CodeGenerator::default().with_modular_output(true);
+    let generated_air = Test::new("tests/modules/modules.air".to_string())
+        .transpile_with(&backend)
+        .unwrap();
+
+    let expected = expect_file!["../modules/modules_modular.rs"];
+    expected.assert_eq(&generated_air);
+}
+
+#[test]
+fn modules_modular_output_name_clash() {
+    // the root module is named as the Air struct, and its columns as a keyword and each other
+    let backend = air_codegen_winter::CodeGenerator::default().with_modular_output(true);
+    let generated_air = Test::new("tests/modules/modules_name_clash.air".to_string())
+        .transpile_with(&backend)
+        .unwrap();
+
+    let expected = expect_file!["../modules/modules_name_clash.rs"];
+    expected.assert_eq(&generated_air);
+}
+
+#[test]
+fn boundary_offsets() {
+    let generated_air = Test::new("tests/boundary_offsets/boundary_offsets.air".to_string())
+        .transpile(Target::Winterfell)
+        .unwrap();
+
+    let expected = expect_file!["../boundary_offsets/boundary_offsets.rs"];
+    expected.assert_eq(&generated_air);
+}
+
+#[test]
+fn name_prefixes_avoid_collisions() {
+    let sources = ["tests/binary/binary.air", "tests/pub_inputs/pub_inputs.air"];
+
+    // without prefixes, both Airs declare a `PublicInputs` struct
+    let structs = sources
+        .iter()
+        .flat_map(|source| {
+            let generated_air = Test::new(source.to_string())
+                .transpile(Target::Winterfell)
+                .unwrap();
+            struct_names(&generated_air)
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        structs,
+        ["PublicInputs", "BinaryAir", "PublicInputs", "PubInputsAir"]
+    );
+
+    let structs = sources
+        .iter()
+        .zip(["First", "Second"])
+        .flat_map(|(source, prefix)| {
+            let backend = air_codegen_winter::CodeGenerator::default().with_name_prefix(prefix);
+            let generated_air = Test::new(source.to_string())
+                .transpile_with(&backend)
+                .unwrap();
+            assert!(generated_air.contains(&format!("type PublicInputs = {prefix}PublicInputs;")));
+            struct_names(&generated_air)
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        structs,
+        [
+            "FirstPublicInputs",
+            "FirstBinaryAir",
+            "SecondPublicInputs",
+            "SecondPubInputsAir"
+        ]
+    );
+}
+
+#[test]
+fn explicit_names() {
+    let backend = air_codegen_winter::CodeGenerator::default()
+        .with_name_prefix("Ignored")
+        .with_air_name("Binary")
+        .with_public_inputs_name("BinaryInputs")
+        .with_constraint_count_tests(true);
+    let generated_air = Test::new("tests/binary/binary.air".to_string())
+        .transpile_with(&backend)
+        .unwrap();
+
+    assert_eq!(struct_names(&generated_air), ["BinaryInputs", "Binary"]);
+    assert!(generated_air.contains("impl Serializable for BinaryInputs {"));
+    assert!(generated_air.contains("impl Air for Binary {"));
+    assert!(generated_air.contains("fn new(trace_info: TraceInfo, public_inputs: BinaryInputs, "));
+    assert!(generated_air.contains("let air = Binary::new(trace_info, BinaryInputs::new("));
+}
+
+#[test]
+fn err_invalid_names() {
+    let air = Test::new("tests/binary/binary.air".to_string())
+        .compile()
+        .unwrap();
+
+    let err = air_codegen_winter::CodeGenerator::default()
+        .with_name_prefix("2")
+        .generate(&air)
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`2BinaryAir` is not a valid name for a Rust struct"
+    );
+
+    let err = air_codegen_winter::CodeGenerator::default()
+        .with_air_name("Inputs")
+        .with_public_inputs_name("Inputs")
+        .generate(&air)
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "the Air struct and its public inputs struct are both named `Inputs`"
+    );
+}
+
+#[test]
+fn err_row_offsets_exceed_frame() {
+    let air = Test::new("tests/row_offsets/row_offsets.air".to_string())
+        .compile()
+        .unwrap();
+
+    let err = air_codegen_winter::CodeGenerator::default()
+        .generate(&air)
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "row offsets greater than 1 are not supported by the Winterfell backend: Winterfell only evaluates transition constraints over frames of 2 rows, but constraints are evaluated over frames of 3 rows"
+    );
+}
+
+#[test]
+fn exponents() {
+    let generated_air = Test::new("tests/exponents/exponents.air".to_string())
+        .transpile(Target::Winterfell)
+        .unwrap();
+
+    let expected = expect_file!["../exponents/exponents.rs"];
+    expected.assert_eq(&generated_air);
+}
+
+#[test]
+fn err_cyclic_constraints() {
+    let air = Test::new("tests/cyclic/cyclic.air".to_string())
+        .compile()
+        .unwrap();
+
+    let err = air_codegen_winter::CodeGenerator::default()
+        .generate(&air)
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "constraints marked `cyclic` are not supported by the Winterfell backend: Winterfell exempts at least the last row of the trace from all transition constraints, but a constraint is applied to every frame of 2 consecutive rows, wrapping around the trace"
+    );
+}
+
+#[test]
+fn err_random_value_out_of_bounds() {
+    let mut air = Test::new("tests/random_values/random_values_simple.air".to_string())
+        .compile()
+        .unwrap();
+    // the constraints access the random value at index 15, which is out of bounds if fewer than
+    // 16 random values are declared
+    air.num_random_values = 15;
+
+    let err = air_codegen_winter::CodeGenerator::default()
+        .generate(&air)
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "a constraint references the random value at index 15, but only 15 random values are declared"
+    );
+}
+
+#[test]
+fn f62_field() {
+    let generated_air = Test::new("tests/binary/binary.air".to_string())
+        .with_field(PrimeField::F62)
+        .transpile(Target::Winterfell)
+        .unwrap();
+
+    assert!(generated_air.contains("use winter_math::fields::f62::BaseElement as Felt;"));
+    assert!(!generated_air.contains("fields::f64"));
+}
+
+#[test]
+fn err_unsupported_field() {
+    let air = Test::new("tests/binary/binary.air".to_string())
+        .with_field(PrimeField::new(17))
+        .compile()
+        .unwrap();
+
+    let err = air_codegen_winter::CodeGenerator::default()
+        .generate(&air)
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "constraints are expressed over GF(17), but Winterfell only supports the f64 (Goldilocks) and f62 fields"
+    );
+}
+
+#[test]
+fn output_is_deterministic() {
+    let test = Test::new("tests/modules/modules.air".to_string());
+    let expected = test.transpile(Target::Winterfell).unwrap();
+    for _ in 0..4 {
+        assert_eq!(test.transpile(Target::Winterfell).unwrap(), expected);
+    }
+}
+
+/// Returns the names of the structs declared at the top level of the generated code
+fn struct_names(generated_air: &str) -> Vec<String> {
+    generated_air
+        .lines()
+        .filter_map(|line| line.strip_prefix("pub struct "))
+        .map(|line| line.trim_end_matches(" {").to_string())
+        .collect()
+}
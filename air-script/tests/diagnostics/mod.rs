@@ -0,0 +1,182 @@
+use std::{
+    io::Write,
+    sync::{Arc, Mutex},
+};
+
+use air_script::JsonEmitter;
+use miden_diagnostics::{
+    ByteIndex, CodeMap, DiagnosticsHandler, Severity, SourceIndex, SourceSpan,
+};
+
+/// A writer which appends to a buffer shared with the test, so that the emitted diagnostics can
+/// be examined once they have been written.
+#[derive(Default, Clone)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+impl SharedBuffer {
+    fn lines(&self) -> Vec<serde_json::Value> {
+        let buffer = self.0.lock().unwrap();
+        std::str::from_utf8(&buffer)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("expected a well-formed JSON object"))
+            .collect()
+    }
+}
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn parse_error_as_json() {
+    let source = "
+def test
+
+trace_columns:
+    main: [a]
+
+public_inputs:
+    stack_inputs: [16]
+
+boundary_constraints:
+    enf a.first = 0
+
+integrity_constraints:
+    enf a' = a +";
+
+    let buffer = SharedBuffer::default();
+    let codemap = Arc::new(CodeMap::new());
+    let emitter = Arc::new(JsonEmitter::new(buffer.clone()));
+    let diagnostics = DiagnosticsHandler::new(JsonEmitter::config(), codemap.clone(), emitter);
+
+    let err = air_script::parse(&diagnostics, codemap, source).unwrap_err();
+    diagnostics.emit(err);
+
+    let diagnostics = buffer.lines();
+    assert_eq!(diagnostics.len(), 1);
+    let diagnostic = &diagnostics[0];
+    assert_eq!(diagnostic["severity"], "error");
+    assert_eq!(diagnostic["message"], "unexpected eof");
+    assert_eq!(diagnostic["notes"], serde_json::json!([]));
+    assert!(diagnostic["spans"][0]["file"].is_string());
+    assert_eq!(diagnostic_spans(diagnostic), vec![((14, 17), (14, 17))]);
+    let label = &diagnostic["labels"][0];
+    assert_eq!(label["primary"], true);
+    assert!(label["message"]
+        .as_str()
+        .unwrap()
+        .starts_with("expected one of: "));
+}
+
+#[test]
+fn notes_as_json() {
+    let source = "
+def test
+
+trace_columns:
+    main: [a, b]
+
+public_inputs:
+    stack_inputs: [16]
+
+boundary_constraints:
+    enf a.first = 0
+
+integrity_constraints:
+    enf a != b";
+
+    let buffer = SharedBuffer::default();
+    let codemap = Arc::new(CodeMap::new());
+    let emitter = Arc::new(JsonEmitter::new(buffer.clone()));
+    let diagnostics = DiagnosticsHandler::new(JsonEmitter::config(), codemap.clone(), emitter);
+
+    let err = air_script::parse(&diagnostics, codemap, source).unwrap_err();
+    diagnostics.emit(err);
+
+    let diagnostics = buffer.lines();
+    assert_eq!(diagnostics[0]["message"], "invalid constraint");
+    assert_eq!(
+        diagnostics[0]["notes"],
+        serde_json::json!([
+            "The inverse of the difference must be witnessed by a trace column, which is given with `with`, e.g. `enf a != b with inv`"
+        ])
+    );
+}
+
+#[test]
+fn labels_as_json() {
+    let source = "def test\n\tenf a' == b + c\n  let x = foo(\n    a,\n  b)\n";
+
+    let buffer = SharedBuffer::default();
+    let codemap = Arc::new(CodeMap::new());
+    let id = codemap.add("labels.air", source.to_string());
+    let emitter = Arc::new(JsonEmitter::new(buffer.clone()));
+    let diagnostics = DiagnosticsHandler::new(JsonEmitter::config(), codemap, emitter);
+
+    let span = |start, end| {
+        SourceSpan::new(
+            SourceIndex::new(id, ByteIndex(start)),
+            SourceIndex::new(id, ByteIndex(end)),
+        )
+    };
+    diagnostics
+        .diagnostic(Severity::Error)
+        .with_message("invalid constraint")
+        .with_primary_label(span(14, 19), "use `=`\nfor equality")
+        .with_secondary_label(span(10, 25), "in this constraint")
+        .with_secondary_label(span(4, 8), "")
+        .with_secondary_label(span(28, 47), "in this binding")
+        .with_primary_label(span(36, 52), "this call")
+        .with_note("a note")
+        .emit();
+
+    let diagnostics = buffer.lines();
+    let label = |(line, column), (end_line, end_column), primary, message| {
+        serde_json::json!({
+            "file": "<labels.air>",
+            "line": line,
+            "column": column,
+            "end_line": end_line,
+            "end_column": end_column,
+            "primary": primary,
+            "message": message,
+        })
+    };
+    assert_eq!(
+        diagnostics[0]["labels"],
+        serde_json::json!([
+            label((1, 5), (1, 9), false, ""),
+            label((2, 2), (2, 17), false, "in this constraint"),
+            label((2, 6), (2, 11), true, "use `=`\nfor equality"),
+            label((3, 3), (4, 7), false, "in this binding"),
+            label((3, 11), (5, 5), true, "this call"),
+        ])
+    );
+    let spans = diagnostic_spans(&diagnostics[0]);
+    assert_eq!(spans, vec![((2, 6), (2, 11)), ((3, 11), (5, 5))]);
+    assert_eq!(diagnostics[0]["notes"], serde_json::json!(["a note"]));
+}
+
+/// Returns the start and end of each of the spans of `diagnostic`
+fn diagnostic_spans(diagnostic: &serde_json::Value) -> Vec<((u64, u64), (u64, u64))> {
+    let position = |span: &serde_json::Value, line: &str, column: &str| {
+        (span[line].as_u64().unwrap(), span[column].as_u64().unwrap())
+    };
+    diagnostic["spans"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|span| {
+            (
+                position(span, "line", "column"),
+                position(span, "end_line", "end_column"),
+            )
+        })
+        .collect()
+}
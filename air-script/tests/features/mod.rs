@@ -0,0 +1,50 @@
+use std::sync::Arc;
+
+use air_ir::Air;
+use air_script::{parse, CompileOptions};
+use miden_diagnostics::{
+    term::termcolor::ColorChoice, CodeMap, DefaultEmitter, DiagnosticsHandler,
+};
+
+const SOURCE: &str = r#"
+def test
+
+trace_columns:
+    main: [a, @[feature="debug"] dbg, b]
+
+public_inputs:
+    stack_inputs: [16]
+
+boundary_constraints:
+    enf a.first = 0
+
+integrity_constraints:
+    enf a' = a + b
+    @[feature="debug"]
+    enf dbg' = dbg + a"#;
+
+fn compile(options: CompileOptions) -> Air {
+    let codemap = Arc::new(CodeMap::new());
+    let emitter = Arc::new(DefaultEmitter::new(ColorChoice::Auto));
+    let diagnostics = DiagnosticsHandler::new(Default::default(), codemap.clone(), emitter);
+
+    let program = parse(&diagnostics, codemap, SOURCE).unwrap();
+    options.compile(&diagnostics, program).unwrap()
+}
+
+#[test]
+fn features_select_constraints_and_columns() {
+    let disabled = compile(CompileOptions::default());
+    assert_eq!(disabled.trace_segment_widths, vec![2]);
+    assert_eq!(disabled.integrity_constraints(0).len(), 1);
+
+    for opt_level in 0..=air_script::MAX_OPT_LEVEL {
+        let enabled = compile(
+            CompileOptions::default()
+                .with_opt_level(opt_level)
+                .with_features(["debug"]),
+        );
+        assert_eq!(enabled.trace_segment_widths, vec![3]);
+        assert_eq!(enabled.integrity_constraints(0).len(), 2);
+    }
+}
@@ -1 +1,5 @@
+mod cache;
 mod codegen;
+mod diagnostics;
+mod features;
+mod opt_level;
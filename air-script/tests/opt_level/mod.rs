@@ -0,0 +1,107 @@
+use std::sync::Arc;
+
+use air_ir::{Air, Operation, Value};
+use air_script::{parse, CompileOptions};
+use miden_diagnostics::{
+    term::termcolor::ColorChoice, CodeMap, DefaultEmitter, DiagnosticsHandler,
+};
+
+const SOURCE: &str = "
+def test
+
+trace_columns:
+    main: [a, b]
+
+public_inputs:
+    stack_inputs: [16]
+
+boundary_constraints:
+    enf a.first = 0
+
+integrity_constraints:
+    enf a' = a + 2 * 3
+    enf b' = a * b + a * b";
+
+fn compile(opt_level: u8) -> Air {
+    let codemap = Arc::new(CodeMap::new());
+    let emitter = Arc::new(DefaultEmitter::new(ColorChoice::Auto));
+    let diagnostics = DiagnosticsHandler::new(Default::default(), codemap.clone(), emitter);
+
+    let program = parse(&diagnostics, codemap, SOURCE).unwrap();
+    CompileOptions::default()
+        .with_opt_level(opt_level)
+        .compile(&diagnostics, program)
+        .unwrap()
+}
+
+/// Returns the number of nodes in the constraint graph of `air` for which `predicate` holds
+fn count_nodes<F>(air: &Air, predicate: F) -> usize
+where
+    F: Fn(&Operation) -> bool,
+{
+    let graph = air.constraint_graph();
+    graph
+        .nodes()
+        .filter(|(_, node)| predicate(node.op()))
+        .count()
+}
+
+#[test]
+fn opt_level_0_preserves_redundant_nodes() {
+    let unoptimized = compile(0);
+    let optimized = compile(2);
+    // The two occurrences of `a * b` only share a node when value numbering is enabled, while
+    // `2 * 3` is folded at every level
+    let products = |air: &Air| count_nodes(air, |op| matches!(op, Operation::Mul(_, _)));
+    assert_eq!(products(&unoptimized), 2);
+    assert_eq!(products(&optimized), 1);
+
+    let folded = |air: &Air| count_nodes(air, |op| *op == Operation::Value(Value::Constant(6)));
+    assert_eq!(folded(&unoptimized), 1);
+    assert_eq!(folded(&optimized), 1);
+}
+
+#[test]
+fn opt_levels_compile_all_test_programs() {
+    for entry in std::fs::read_dir("tests").unwrap() {
+        let dir = entry.unwrap().path();
+        let Some(name) = dir.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        let path = dir.join(format!("{name}.air"));
+        if !path.exists() {
+            continue;
+        }
+        let mut num_constraints = None;
+        for opt_level in 0..=air_script::MAX_OPT_LEVEL {
+            let codemap = Arc::new(CodeMap::new());
+            let emitter = Arc::new(DefaultEmitter::new(ColorChoice::Auto));
+            let diagnostics = DiagnosticsHandler::new(Default::default(), codemap.clone(), emitter);
+            let program = air_script::parse_file(&diagnostics, codemap, &path).unwrap();
+            let result = CompileOptions::default()
+                .with_opt_level(opt_level)
+                .compile(&diagnostics, program);
+            let air = result.unwrap_or_else(|_| {
+                panic!(
+                    "{} failed to compile at optimization level {opt_level}",
+                    path.display()
+                )
+            });
+            // Optimizations may simplify constraints, but never add or remove any
+            let counts = (0..air.trace_segment_widths.len())
+                .map(|segment| {
+                    (
+                        air.num_boundary_constraints(segment),
+                        air.integrity_constraints(segment).len(),
+                    )
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(
+                num_constraints.get_or_insert_with(|| counts.clone()),
+                &counts,
+                "{} has a different number of constraints at optimization level {opt_level}",
+                path.display()
+            );
+        }
+    }
+}
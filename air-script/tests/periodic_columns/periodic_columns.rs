@@ -26,6 +26,7 @@ pub struct PeriodicColumnsAir {
 }
 
 impl PeriodicColumnsAir {
+    pub const MAX_CONSTRAINT_DEGREE: usize = 2;
     pub fn last_step(&self) -> usize {
         self.trace_length() - self.context().num_transition_exemptions()
     }
@@ -35,6 +35,7 @@ pub struct PubInputsAir {
 }
 
 impl PubInputsAir {
+    pub const MAX_CONSTRAINT_DEGREE: usize = 1;
     pub fn last_step(&self) -> usize {
         self.trace_length() - self.context().num_transition_exemptions()
     }
@@ -26,6 +26,7 @@ pub struct RandomValuesAir {
 }
 
 impl RandomValuesAir {
+    pub const MAX_CONSTRAINT_DEGREE: usize = 1;
     pub fn last_step(&self) -> usize {
         self.trace_length() - self.context().num_transition_exemptions()
     }
@@ -67,9 +68,10 @@ impl Air for RandomValuesAir {
     }
 
     fn get_aux_assertions<E: FieldElement<BaseField = Felt>>(&self, aux_rand_elements: &AuxTraceRandElements<E>) -> Vec<Assertion<E>> {
+        let rand_elements: &[E; 16] = aux_rand_elements.get_segment_elements(0).get(..16).and_then(|elements| elements.try_into().ok()).expect("expected at least 16 random values");
         let mut result = Vec::new();
-        result.push(Assertion::single(0, 0, aux_rand_elements.get_segment_elements(0)[5] + aux_rand_elements.get_segment_elements(0)[3] + aux_rand_elements.get_segment_elements(0)[15]));
-        result.push(Assertion::single(0, self.last_step(), aux_rand_elements.get_segment_elements(0)[0] + aux_rand_elements.get_segment_elements(0)[15] + aux_rand_elements.get_segment_elements(0)[11]));
+        result.push(Assertion::single(0, 0, rand_elements[5] + rand_elements[3] + rand_elements[15]));
+        result.push(Assertion::single(0, self.last_step(), rand_elements[0] + rand_elements[15] + rand_elements[11]));
         result
     }
 
@@ -86,6 +88,7 @@ impl Air for RandomValuesAir {
         let main_next = main_frame.next();
         let aux_current = aux_frame.current();
         let aux_next = aux_frame.next();
-        result[0] = aux_next[0] - (aux_rand_elements.get_segment_elements(0)[15] - aux_rand_elements.get_segment_elements(0)[0] + aux_rand_elements.get_segment_elements(0)[3]);
+        let rand_elements: &[E; 16] = aux_rand_elements.get_segment_elements(0).get(..16).and_then(|elements| elements.try_into().ok()).expect("expected at least 16 random values");
+        result[0] = aux_next[0] - (rand_elements[15] - rand_elements[0] + rand_elements[3]);
     }
 }
\ No newline at end of file
@@ -26,6 +26,7 @@ pub struct SelectorsAir {
 }
 
 impl SelectorsAir {
+    pub const MAX_CONSTRAINT_DEGREE: usize = 4;
     pub fn last_step(&self) -> usize {
         self.trace_length() - self.context().num_transition_exemptions()
     }
@@ -40,7 +41,7 @@ impl Air for SelectorsAir {
     }
 
     fn new(trace_info: TraceInfo, public_inputs: PublicInputs, options: WinterProofOptions) -> Self {
-        let main_degrees = vec![TransitionConstraintDegree::new(3), TransitionConstraintDegree::new(4), TransitionConstraintDegree::new(3)];
+        let main_degrees = vec![TransitionConstraintDegree::new(3), TransitionConstraintDegree::new(4), TransitionConstraintDegree::new(3), TransitionConstraintDegree::new(2), TransitionConstraintDegree::new(2), TransitionConstraintDegree::new(2)];
         let aux_degrees = vec![];
         let num_main_assertions = 1;
         let num_aux_assertions = 0;
@@ -78,6 +79,9 @@ impl Air for SelectorsAir {
         result[0] = (main_next[3] - E::ZERO) * main_current[0] * (E::ONE - main_current[1]);
         result[1] = (main_next[3] - main_current[3]) * main_current[0] * main_current[1] * main_current[2];
         result[2] = (main_next[3] - E::ONE) * (E::ONE - main_current[1]) * (E::ONE - main_current[2]);
+        result[3] = main_current[0].square() - main_current[0];
+        result[4] = main_current[1].square() - main_current[1];
+        result[5] = main_current[2].square() - main_current[2];
     }
 
     fn evaluate_aux_transition<F, E>(&self, main_frame: &EvaluationFrame<F>, aux_frame: &EvaluationFrame<E>, _periodic_values: &[F], aux_rand_elements: &AuxTraceRandElements<E>, result: &mut [E])
@@ -26,6 +26,7 @@ pub struct TraceColGroupAir {
 }
 
 impl TraceColGroupAir {
+    pub const MAX_CONSTRAINT_DEGREE: usize = 1;
     pub fn last_step(&self) -> usize {
         self.trace_length() - self.context().num_transition_exemptions()
     }
@@ -29,6 +29,7 @@ pub struct VariablesAir {
 }
 
 impl VariablesAir {
+    pub const MAX_CONSTRAINT_DEGREE: usize = 3;
     pub fn last_step(&self) -> usize {
         self.trace_length() - self.context().num_transition_exemptions()
     }
@@ -79,7 +80,7 @@ impl Air for VariablesAir {
     fn evaluate_transition<E: FieldElement<BaseField = Felt>>(&self, frame: &EvaluationFrame<E>, periodic_values: &[E], result: &mut [E]) {
         let main_current = frame.current();
         let main_next = frame.next();
-        result[0] = main_current[0].exp(E::PositiveInteger::from(2_u64)) - main_current[0];
+        result[0] = main_current[0].square() - main_current[0];
         result[1] = periodic_values[0] * (main_next[0] - main_current[0]) - E::ZERO;
         result[2] = (E::ONE - main_current[0]) * (main_current[3] - main_current[1] - main_current[2]) - (E::from(6_u64) - main_current[0]);
         result[3] = main_current[0] * (main_current[3] - main_current[1] * main_current[2]) - (main_next[0] - E::from(3_u64) - E::from(2_u64));
@@ -93,6 +94,7 @@ impl Air for VariablesAir {
         let main_next = main_frame.next();
         let aux_current = aux_frame.current();
         let aux_next = aux_frame.next();
-        result[0] = aux_next[0] - aux_current[0] * (E::from(main_current[3]) + aux_rand_elements.get_segment_elements(0)[0]);
+        let rand_elements: &[E; 1] = aux_rand_elements.get_segment_elements(0).get(..1).and_then(|elements| elements.try_into().ok()).expect("expected at least 1 random values");
+        result[0] = aux_next[0] - aux_current[0] * (E::from(main_current[3]) + rand_elements[0]);
     }
 }
\ No newline at end of file
@@ -1,5 +1,5 @@
 use crate::config::CodegenConfig;
-use crate::constants::{AUX_TRACE, MAIN_TRACE};
+use crate::constants::{AUX_TRACE, EXEMPTION_POINT_OFFSETS, MAIN_TRACE};
 use crate::error::CodegenError;
 use crate::utils::{
     boundary_group_to_procedure_name, load_quadratic_element, periodic_group_to_memory_offset,
@@ -11,7 +11,7 @@ use crate::visitor::{
 use crate::writer::Writer;
 use air_ir::{
     Air, ConstraintDomain, ConstraintRoot, Identifier, NodeIndex, Operation, PeriodicColumn,
-    TraceSegmentId, Value,
+    PrimeField, TraceSegmentId, Value, MIN_TRACE_LENGTH,
 };
 use miden_core::{Felt, StarkField};
 use std::collections::btree_map::BTreeMap;
@@ -26,16 +26,88 @@ impl CodeGenerator {
     pub fn new(config: CodegenConfig) -> Self {
         Self { config }
     }
+
+    /// Generates a companion module to the code emitted by [air_ir::CodeGenerator::generate],
+    /// declaring the constants which are baked into its procedures, i.e. the memory addresses
+    /// they use, the exemption points of the integrity constraint divisor, the layout of the
+    /// cached powers of `z`, and the smallest trace length they support, so that integrators can
+    /// audit them against the verifier.
+    ///
+    /// The trace domain generator is not among them, as it is loaded from memory at runtime.
+    pub fn generate_constants(&self, ir: &Air) -> anyhow::Result<String> {
+        validate(ir, &self.config)?;
+        Ok(Backend::new(ir, self.config).gen_constants())
+    }
 }
 impl air_ir::CodeGenerator for CodeGenerator {
     type Output = String;
 
     fn generate(&self, ir: &Air) -> anyhow::Result<Self::Output> {
+        validate(ir, &self.config)?;
         let generator = Backend::new(ir, self.config);
         generator.generate()
     }
 }
 
+/// Checks that the constraints of `ir` can be evaluated by the Miden verifier
+fn validate(ir: &Air, config: &CodegenConfig) -> Result<(), CodegenError> {
+    // the verifier only knows about the main and auxiliary trace segments
+    if ir.trace_segment_widths.len() > 2 {
+        return Err(CodegenError::TooManyTraceSegments(
+            ir.trace_segment_widths.len(),
+        ));
+    }
+    // the verifier performs arithmetic in the base field of the VM
+    if ir.field != PrimeField::GOLDILOCKS {
+        return Err(CodegenError::UnsupportedField(ir.field));
+    }
+    // the verifier only evaluates boundary constraints on the first and last rows
+    if let Some(domain) = (0..ir.trace_segment_widths.len())
+        .flat_map(|segment| ir.boundary_constraints(segment))
+        .map(|constraint| constraint.domain())
+        .find(|domain| matches!(domain, ConstraintDomain::BeforeLastRow(_)))
+    {
+        return Err(CodegenError::UnsupportedBoundary(domain));
+    }
+    // the verifier divides all integrity constraints by the same divisor, which has exemptions
+    if let Some(domain) = (0..ir.trace_segment_widths.len())
+        .flat_map(|segment| ir.integrity_constraints(segment))
+        .map(|constraint| constraint.domain())
+        .find(|domain| domain.is_cyclic())
+    {
+        return Err(CodegenError::UnsupportedIntegrity(domain));
+    }
+    // the out-of-domain frame only consists of the current and next rows
+    if let Some(domain) = (0..ir.trace_segment_widths.len())
+        .flat_map(|segment| ir.integrity_constraints(segment))
+        .map(|constraint| constraint.domain())
+        .find(|domain| domain.frame_size() > 2)
+    {
+        return Err(CodegenError::UnsupportedFrame(domain));
+    }
+    // the public inputs are laid out in memory two to a word, and must all be addressable
+    let last_address = ir
+        .public_inputs()
+        .try_fold(0u32, |num_elements, input| {
+            input
+                .num_elements()
+                .and_then(|n| u32::try_from(n).ok())
+                .and_then(|n| num_elements.checked_add(n))
+        })
+        .and_then(|num_elements| {
+            config
+                .public_inputs_address
+                .checked_add(num_elements.saturating_sub(1) / 2)
+        });
+    if last_address.is_none() {
+        return Err(CodegenError::TooManyPublicInputs(
+            config.public_inputs_address,
+        ));
+    }
+
+    Ok(())
+}
+
 struct Backend<'ast> {
     /// Miden Assembly writer.
     ///
@@ -97,12 +169,13 @@ impl<'ast> Backend<'ast> {
         // v      v                   Input "c" starts at offset 20, after the values of "a" and "b"
         // [ .... | ................ | ....]
         //
-        // The offset is used by the codegen to load public input values.
+        // The offset is used by the codegen to load public input values. The total number of
+        // inputs has been checked to fit in memory by [validate], so this cannot overflow.
         let public_input_to_offset = ir
             .public_inputs()
             .scan(0, |public_input_count, input| {
                 let start_offset = *public_input_count;
-                *public_input_count += input.size;
+                *public_input_count += input.num_elements().unwrap();
                 Some((input.name, start_offset))
             })
             .collect();
@@ -138,6 +211,103 @@ impl<'ast> Backend<'ast> {
         Ok(self.writer.into_code())
     }
 
+    /// Emits the declarations of the constants baked into the generated procedures, see
+    /// [CodeGenerator::generate_constants].
+    fn gen_constants(mut self) -> String {
+        self.writer.header(format!(
+            "Constants baked into the procedures generated for `{}`",
+            self.ir.name()
+        ));
+        self.writer.new_line();
+
+        self.writer
+            .header("Memory addresses read or written by the procedures");
+        let addresses = [
+            ("TRACE_LEN_ADDRESS", self.config.trace_len_address),
+            ("LOG2_TRACE_LEN_ADDRESS", self.config.log2_trace_len_address),
+            ("Z_ADDRESS", self.config.z_address),
+            ("OOD_FRAME_ADDRESS", self.config.ood_frame_address),
+            ("OOD_AUX_FRAME_ADDRESS", self.config.ood_aux_frame_address),
+            (
+                "COMPOSITION_COEF_ADDRESS",
+                self.config.composition_coef_address,
+            ),
+            ("PUBLIC_INPUTS_ADDRESS", self.config.public_inputs_address),
+            ("AUX_RAND_ADDRESS", self.config.aux_rand_address),
+            (
+                "TRACE_DOMAIN_GENERATOR_ADDRESS",
+                self.config.trace_domain_generator_address,
+            ),
+            ("EXEMPTION_TWO_ADDRESS", self.config.exemption_two_address),
+        ];
+        for (name, address) in addresses {
+            self.writer.constant(name, address.into());
+        }
+        self.writer.new_line();
+        self.writer.new_line();
+
+        self.writer.header("Exemption points of the integrity constraint divisor, as offsets `k` of the points `g^{trace_len-k}`, where `g` is the trace domain generator");
+        for (i, offset) in EXEMPTION_POINT_OFFSETS.iter().enumerate() {
+            self.writer
+                .constant(format!("EXEMPTION_POINT_{i}_OFFSET"), *offset);
+        }
+        self.writer.new_line();
+        self.writer.new_line();
+
+        // `cache_z_exp` stores `z^{trace_len/period}` for each period in order, followed by `z^trace_len`
+        self.writer
+            .header("Addresses of the powers of `z` cached by `cache_z_exp`");
+        for (group, period) in self.periods.iter().enumerate() {
+            let offset: u32 = group.try_into().expect("periods are u32");
+            self.writer.constant(
+                format!("Z_EXP_PERIOD_{period}_ADDRESS"),
+                (self.config.z_exp_address + offset).into(),
+            );
+            self.writer.comment(format!("z^{{trace_len/{period}}}"));
+        }
+        let offset: u32 = self.periods.len().try_into().expect("periods are u32");
+        self.writer.constant(
+            "Z_EXP_TRACE_LEN_ADDRESS",
+            (self.config.z_exp_address + offset).into(),
+        );
+        self.writer.comment("z^trace_len");
+        self.writer.new_line();
+        self.writer.new_line();
+
+        if !self.ir.periodic_columns.is_empty() {
+            self.writer.header(
+                "Addresses of the evaluations of the periodic columns cached by `cache_periodic_polys`",
+            );
+            for (column, name) in self.ir.periodic_columns.keys().enumerate() {
+                let offset: u32 = column.try_into().expect("periodic columns are u32");
+                self.writer.constant(
+                    format!("PERIODIC_COLUMN_{column}_ADDRESS"),
+                    (self.config.periodic_values_address + offset).into(),
+                );
+                self.writer.comment(name.to_string());
+            }
+            self.writer.new_line();
+            self.writer.new_line();
+        }
+
+        // the number of squarings of `z` in `cache_z_exp` is only non-negative when the trace is
+        // at least as long as the longest periodic column
+        self.writer.header(
+            "The smallest trace length supported by the procedures, which must be a power of two",
+        );
+        let min_trace_len = self
+            .periods
+            .first()
+            .map_or(MIN_TRACE_LENGTH, |&period| period.max(MIN_TRACE_LENGTH));
+        self.writer.constant(
+            "MIN_TRACE_LEN",
+            min_trace_len.try_into().expect("trace length is u64"),
+        );
+        self.writer.new_line();
+
+        self.writer.into_code()
+    }
+
     /// Emits code for the procedure `cache_z_exp`.
     ///
     /// The procedure computes and caches the necessary exponentiation of `z`. These values are
@@ -743,6 +913,11 @@ impl<'ast> Backend<'ast> {
         self.writer.end();
     }
 
+    /// Emits code to load the `trace_len` onto the top of the stack.
+    fn load_trace_len(&mut self) {
+        self.writer.mem_load(self.config.trace_len_address);
+    }
+
     /// Emits code to load the `log_2(trace_len)` onto the top of the stack.
     fn load_log2_trace_len(&mut self) {
         self.writer.mem_load(self.config.log2_trace_len_address);
@@ -1108,6 +1283,12 @@ impl<'ast> AirVisitor<'ast> for Backend<'ast> {
                     (*element).try_into().or(Err(CodegenError::InvalidIndex))?,
                 )?;
             }
+            Value::TraceLen => {
+                // The trace length is cached by the verifier alongside its logarithm, and is lifted
+                // to the quadratic extension like constants
+                self.load_trace_len();
+                self.writer.push(0);
+            }
         };
 
         Ok(())
@@ -32,3 +32,8 @@ pub const TRACE_DOMAIN_GENERATOR_ADDRESS: u32 = 4294799999;
 pub const PERIODIC_VALUES_ADDRESS: u32 = 500000000;
 pub const Z_EXP_ADDRESS: u32 = 500000100;
 pub const EXEMPTION_TWO_ADDRESS: u32 = 500000101;
+
+// The exemption points of the integrity constraint divisor are `g^{trace_len-k}` for each offset
+// `k` below, where `g` is the trace domain generator, in the order in which the procedure
+// `get_exemptions_points` leaves them on the stack.
+pub const EXEMPTION_POINT_OFFSETS: [u64; 2] = [2, 1];
@@ -1,3 +1,5 @@
+use air_ir::{ConstraintDomain, PrimeField};
+
 #[derive(Debug, thiserror::Error)]
 pub enum CodegenError {
     #[error("invalid access type")]
@@ -12,4 +14,28 @@ pub enum CodegenError {
     InvalidBoundaryConstraint,
     #[error("invalid integrity constraint")]
     InvalidIntegrityConstraint,
+    #[error("expected at most 2 trace segments, got {0}")]
+    TooManyTraceSegments(usize),
+    #[error(
+        "the Miden VM only supports the Goldilocks field, but constraints are expressed over {0}"
+    )]
+    UnsupportedField(PrimeField),
+    #[error(
+        "the Miden VM only supports boundary constraints on the first and last rows, but a constraint is applied to {0}"
+    )]
+    UnsupportedBoundary(ConstraintDomain),
+    #[error(
+        "the Miden VM exempts the last two rows of the trace from all integrity constraints, but a constraint is applied to {0}"
+    )]
+    UnsupportedIntegrity(ConstraintDomain),
+    #[error(
+        "row offsets greater than 1 are not supported by the Miden assembly backend: the Miden VM only evaluates integrity constraints over the current and next rows, but a constraint is applied to {0}"
+    )]
+    UnsupportedFrame(ConstraintDomain),
+    #[error(
+        "the public inputs have too many elements in total to be laid out in memory starting at address {0}"
+    )]
+    TooManyPublicInputs(u32),
+    #[error("the memory address {0} + {1} is out of range")]
+    AddressOutOfRange(u32, u32),
 }
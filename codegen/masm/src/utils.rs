@@ -9,8 +9,10 @@ pub fn contraint_root_domain(boundary: &&ConstraintRoot) -> u8 {
     match boundary.domain() {
         ConstraintDomain::FirstRow => 0,
         ConstraintDomain::LastRow => 1,
+        ConstraintDomain::BeforeLastRow(_) => panic!("BeforeLastRow is not supported"),
         ConstraintDomain::EveryRow => panic!("EveryRow is not supported"),
         ConstraintDomain::EveryFrame(_) => panic!("EveryFrame is not supported"),
+        ConstraintDomain::Cyclic(_) => panic!("Cyclic is not supported"),
     }
 }
 
@@ -38,7 +40,9 @@ pub fn load_quadratic_element(
     element: u32,
 ) -> Result<(), CodegenError> {
     let target_word: u32 = element / 2;
-    let address = base_addr + target_word;
+    let address = base_addr
+        .checked_add(target_word)
+        .ok_or(CodegenError::AddressOutOfRange(base_addr, target_word))?;
 
     // Load data from memory
     writer.padw();
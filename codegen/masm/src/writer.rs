@@ -114,6 +114,19 @@ impl Writer {
         self.state = LineState::Comment;
     }
 
+    /// Declares a named constant, which must be outside of any procedure.
+    pub fn constant(&mut self, name: impl Borrow<str>, value: u64) {
+        assert!(
+            self.procedure.is_none(),
+            "Can not declare constants inside of a procedure, currently writing {:?}",
+            self.procedure
+        );
+        self.maybe_new_line_and_indent();
+        self.code
+            .push_str(&format!("const.{}={}", name.borrow(), value));
+        self.state = LineState::Instructions;
+    }
+
     // CONTROL FLOW
     // -------------------------------------------------------------------------------------------
 
@@ -47,7 +47,7 @@ fn test_integrity_divisor() {
                     descriptor: "main_trace",
                 },
                 Data {
-                    data: to_stack_order(&vec![one; 2]),
+                    data: to_stack_order(&[one; 2]),
                     address: constants::COMPOSITION_COEF_ADDRESS,
                     descriptor: "composition_coefficients",
                 },
@@ -100,6 +100,9 @@ trace_columns:
 public_inputs:
     stack_inputs: [1]
 
+random_values:
+    rand: [1]
+
 boundary_constraints:
     enf a.first = 0
     enf a.last = 0
@@ -143,7 +146,7 @@ fn test_boundary_divisor() {
                     descriptor: "aux_trace",
                 },
                 Data {
-                    data: to_stack_order(&vec![one; 5]),
+                    data: to_stack_order(&[one; 5]),
                     address: constants::COMPOSITION_COEF_ADDRESS,
                     descriptor: "composition_coefficients",
                 },
@@ -218,6 +221,9 @@ trace_columns:
 public_inputs:
     stack_inputs: [1]
 
+random_values:
+    rand: [1]
+
 boundary_constraints:
     enf a.first = 3
     enf b.last = 5
@@ -259,7 +265,7 @@ fn test_mixed_boundary_divisor() {
                     descriptor: "aux_trace",
                 },
                 Data {
-                    data: to_stack_order(&vec![one; 5]),
+                    data: to_stack_order(&[one; 5]),
                     address: constants::COMPOSITION_COEF_ADDRESS,
                     descriptor: "composition_coefficients",
                 },
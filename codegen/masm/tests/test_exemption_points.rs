@@ -6,7 +6,7 @@ use miden_processor::{
 };
 
 mod utils;
-use utils::{codegen, test_code, to_stack_order, Data};
+use utils::{codegen, lower, test_code, to_stack_order, Data};
 
 static SIMPLE_AIR: &str = "
 def Simple
@@ -73,3 +73,57 @@ fn test_exemption_points() {
         );
     }
 }
+
+#[test]
+fn test_exemption_points_match_constants() {
+    let constants = air_codegen_masm::CodeGenerator::default()
+        .generate_constants(&lower(SIMPLE_AIR))
+        .expect("codegen failed");
+    let offsets: Vec<u64> = (0..)
+        .map_while(|i| {
+            let prefix = format!("const.EXEMPTION_POINT_{i}_OFFSET=");
+            constants.lines().find_map(|line| {
+                line.trim()
+                    .strip_prefix(&prefix)
+                    .map(|value| value.parse().expect("invalid offset"))
+            })
+        })
+        .collect();
+    assert_eq!(offsets.len(), 2, "{constants}");
+
+    let one = QuadExtension::new(Felt::new(1), Felt::ZERO);
+    let a = QuadExtension::new(Felt::new(3), Felt::ZERO);
+
+    for power in [3, 10, 20] {
+        let trace_len = 2u64.pow(power);
+
+        let code = test_code(
+            codegen(SIMPLE_AIR),
+            vec![Data {
+                data: to_stack_order(&[a, a]),
+                address: constants::OOD_FRAME_ADDRESS,
+                descriptor: "main_trace",
+            }],
+            trace_len,
+            one,
+            &["get_exemptions_points"],
+        );
+        let program = Assembler::default().compile(code).unwrap();
+
+        let mut process: Process<MemAdviceProvider> = Process::new(
+            Kernel::new(&[]),
+            StackInputs::new(vec![]),
+            AdviceInputs::default().into(),
+        );
+        let program_outputs = process.execute(&program).expect("execution failed");
+        let result_stack = program_outputs.stack();
+
+        // the declared exemption points are in stack-order
+        let g = Felt::get_root_of_unity(power);
+        let expected: Vec<u64> = offsets
+            .iter()
+            .map(|offset| g.exp(trace_len - offset).as_int())
+            .collect();
+        assert_eq!(&result_stack[..expected.len()], &expected[..]);
+    }
+}
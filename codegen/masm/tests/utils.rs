@@ -17,15 +17,15 @@ where
     pub descriptor: &'a str,
 }
 
-pub fn codegen(source: &str) -> String {
-    use air_ir::CodeGenerator;
+/// Lowers `source` to the IR consumed by the code generator.
+pub fn lower(source: &str) -> air_ir::Air {
     use air_pass::Pass;
 
     let codemap = Arc::new(CodeMap::new());
     let emitter = Arc::new(DefaultEmitter::new(ColorChoice::Auto));
     let diagnostics = DiagnosticsHandler::new(Default::default(), codemap.clone(), emitter);
 
-    let air = air_parser::parse(&diagnostics, codemap, source)
+    air_parser::parse(&diagnostics, codemap, source)
         .map_err(air_ir::CompileError::Parse)
         .and_then(|ast| {
             let mut pipeline = air_parser::transforms::ConstantPropagation::new(&diagnostics)
@@ -33,8 +33,13 @@ pub fn codegen(source: &str) -> String {
                 .chain(air_ir::passes::AstToAir::new(&diagnostics));
             pipeline.run(ast)
         })
-        .expect("lowering failed");
+        .expect("lowering failed")
+}
+
+pub fn codegen(source: &str) -> String {
+    use air_ir::CodeGenerator;
 
+    let air = lower(source);
     let codegen = air_codegen_masm::CodeGenerator::default();
     let code = codegen.generate(&air).expect("codegen failed");
 
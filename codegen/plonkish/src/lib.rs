@@ -0,0 +1,395 @@
+use air_ir::{Air, NodeIndex, Operation, TraceSegmentId, Value};
+use serde::Serialize;
+
+// EXPORT CONSTRAINTS AS A PLONKISH CONSTRAINT SYSTEM
+// ================================================================================================
+
+/// CodeGenerator is used to export the constraints of an AirIR as a generic Plonkish constraint
+/// system, serialized as JSON, for import into Plonkish (e.g. Halo2-style) proving systems.
+///
+/// The columns of all trace segments are exported as advice columns, in segment order, periodic
+/// columns as fixed columns, and public inputs as instance columns, flattened in row-major order in
+/// the case of matrices. Each integrity constraint is
+/// exported as a gate, whose polynomial must evaluate to zero on every row, and in which the row
+/// offset of each trace column access becomes the rotation at which the column is queried.
+///
+/// Boundary constraints are not exported.
+///
+/// By default, constants, i.e. the values of constant expressions and of fixed columns, are
+/// serialized as decimal numbers. When hex constants are enabled, they are instead serialized as
+/// hexadecimal strings, e.g. `"0x1f"`, for tools which canonicalize field elements in that form.
+///
+/// The layout of the output is determined by its format version. Version 1, the default, is the
+/// original layout. Version 2 additionally records the degree of each gate, as a multiple of the
+/// trace length, for provers which size their composition polynomials from the output, and the
+/// number of last rows on which each gate is exempt from evaluating to zero, i.e. its largest
+/// rotation. It also marks the gates derived from constraints marked `cyclic`, which must also
+/// evaluate to zero on the last rows, with rotations wrapping around to the first rows. Version 1
+/// implicitly exempts the last row only, so constraints which are cyclic, or which access rows
+/// more than one row ahead, can only be exported from version 2.
+///
+/// Plonkish gates have no exponentiation, so powers are expanded into products by repeated
+/// squaring. Gate polynomials are trees, in which each use of a subexpression is written out in
+/// full, so `x^k` is written with `k` occurrences of `x`, and exponents larger than [MAX_EXPONENT]
+/// are rejected rather than exported.
+#[derive(Debug, Clone, Copy)]
+pub struct CodeGenerator {
+    hex_constants: bool,
+    format_version: u8,
+}
+impl Default for CodeGenerator {
+    fn default() -> Self {
+        Self {
+            hex_constants: false,
+            format_version: DEFAULT_FORMAT_VERSION,
+        }
+    }
+}
+
+/// The format version of the output when none is specified
+pub const DEFAULT_FORMAT_VERSION: u8 = 1;
+/// The most recent format version of the output
+pub const LATEST_FORMAT_VERSION: u8 = 2;
+/// The largest exponent of a power which can be exported, see [CodeGenerator]
+pub const MAX_EXPONENT: usize = 1 << 12;
+
+impl CodeGenerator {
+    /// Toggles between serializing constants as decimal numbers (the default), and as hexadecimal
+    /// strings.
+    pub fn with_hex_constants(mut self, enabled: bool) -> Self {
+        self.hex_constants = enabled;
+        self
+    }
+
+    /// Sets the format version of the output, see [CodeGenerator] for the supported versions.
+    pub fn with_format_version(mut self, version: u8) -> Self {
+        self.format_version = version;
+        self
+    }
+}
+impl air_ir::CodeGenerator for CodeGenerator {
+    type Output = String;
+
+    fn generate(&self, ir: &Air) -> anyhow::Result<Self::Output> {
+        if !(DEFAULT_FORMAT_VERSION..=LATEST_FORMAT_VERSION).contains(&self.format_version) {
+            anyhow::bail!(
+                "unsupported format version {}, expected a version from {} to {}",
+                self.format_version,
+                DEFAULT_FORMAT_VERSION,
+                LATEST_FORMAT_VERSION
+            );
+        }
+        let system = ConstraintSystem::new(ir, self.hex_constants, self.format_version)?;
+        Ok(serde_json::to_string_pretty(&system)?)
+    }
+}
+
+/// The root of the exported constraint system
+#[derive(Debug, Serialize)]
+struct ConstraintSystem {
+    /// The format version of the output, which is omitted in version 1
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<u8>,
+    /// The name of the program the [Air] was derived from
+    name: String,
+    /// The modulus of the prime field over which the gates are expressed
+    modulus: u64,
+    /// The columns of the trace, in segment order
+    advice_columns: Vec<AdviceColumn>,
+    /// The periodic columns, whose values repeat with the given period
+    fixed_columns: Vec<FixedColumn>,
+    /// The public inputs
+    instance_columns: Vec<InstanceColumn>,
+    /// The total number of elements of the public inputs
+    num_instance_values: usize,
+    /// The number of random values which are drawn by the verifier
+    num_challenges: u16,
+    /// The integrity constraints, in segment order
+    gates: Vec<Gate>,
+}
+
+#[derive(Debug, Serialize)]
+struct AdviceColumn {
+    /// The trace segment to which this column belongs
+    segment: TraceSegmentId,
+    /// The index of this column in its trace segment
+    index: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct FixedColumn {
+    /// The fully-qualified name of the periodic column, i.e. `module::name`
+    name: String,
+    /// The values of a single period of the column
+    values: Vec<Constant>,
+}
+
+#[derive(Debug, Serialize)]
+struct InstanceColumn {
+    name: String,
+    /// The number of elements of the public input, i.e. the product of its dimensions
+    size: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct Gate {
+    /// The name given by the `name` attribute of the constraint, if present, otherwise one
+    /// derived from the segment and index of the constraint
+    name: String,
+    /// The trace segment against which the constraint is applied
+    segment: TraceSegmentId,
+    /// The degree of the polynomial as a multiple of the trace length, from format version 2
+    #[serde(skip_serializing_if = "Option::is_none")]
+    degree: Option<usize>,
+    /// The number of last rows on which the polynomial need not evaluate to zero, i.e. its largest
+    /// rotation, or zero if it is cyclic, from format version 2
+    #[serde(skip_serializing_if = "Option::is_none")]
+    exemptions: Option<usize>,
+    /// Whether the polynomial must also evaluate to zero on the last rows, with rotations wrapping
+    /// around to the first rows, which is only recorded when set, from format version 2
+    #[serde(skip_serializing_if = "core::ops::Not::not")]
+    cyclic: bool,
+    /// The polynomial which must evaluate to zero
+    polynomial: Expression,
+}
+
+/// A gate polynomial, in terms of queries of columns at a rotation relative to the current row
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Expression {
+    Constant {
+        value: Constant,
+    },
+    Advice {
+        column: usize,
+        rotation: usize,
+    },
+    Fixed {
+        column: usize,
+        rotation: usize,
+    },
+    Challenge {
+        index: usize,
+    },
+    /// The number of rows of the trace, i.e. the size of the evaluation domain
+    TraceLength,
+    Negated {
+        expr: Box<Expression>,
+    },
+    Sum {
+        lhs: Box<Expression>,
+        rhs: Box<Expression>,
+    },
+    Product {
+        lhs: Box<Expression>,
+        rhs: Box<Expression>,
+    },
+}
+
+/// A field element, serialized as a decimal number, or as a hexadecimal string
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+enum Constant {
+    Decimal(u64),
+    Hex(String),
+}
+impl Constant {
+    fn new(value: u64, hex: bool) -> Self {
+        if hex {
+            Self::Hex(format!("{value:#x}"))
+        } else {
+            Self::Decimal(value)
+        }
+    }
+}
+
+impl ConstraintSystem {
+    fn new(ir: &Air, hex_constants: bool, format_version: u8) -> anyhow::Result<Self> {
+        let advice_columns = ir
+            .trace_segment_widths
+            .iter()
+            .enumerate()
+            .flat_map(|(segment, &width)| {
+                (0..width as usize).map(move |index| AdviceColumn { segment, index })
+            })
+            .collect();
+        let fixed_columns = ir
+            .periodic_columns
+            .iter()
+            .map(|(name, column)| FixedColumn {
+                name: name.to_string(),
+                values: column
+                    .values
+                    .iter()
+                    .map(|&value| Constant::new(value, hex_constants))
+                    .collect(),
+            })
+            .collect();
+        // The sizes of the public inputs are totalled using checked arithmetic, since the
+        // dimensions of a matrix may be large enough for the number of its elements to overflow
+        let mut num_instance_values = 0usize;
+        let mut instance_columns = vec![];
+        for input in ir.public_inputs() {
+            let Some(size) = input.size.checked_mul(input.columns.unwrap_or(1)) else {
+                anyhow::bail!(
+                    "the public input `{}` has too many elements to be exported",
+                    input.name
+                );
+            };
+            let Some(total) = num_instance_values.checked_add(size) else {
+                anyhow::bail!("the public inputs have too many elements in total to be exported");
+            };
+            num_instance_values = total;
+            instance_columns.push(InstanceColumn {
+                name: input.name.to_string(),
+                size,
+            });
+        }
+
+        let builder = ExpressionBuilder { ir, hex_constants };
+        let mut gates = vec![];
+        for segment in 0..ir.trace_segment_widths.len() {
+            let degrees = ir.integrity_constraint_degrees(segment);
+            for (index, (constraint, degree)) in ir
+                .integrity_constraints(segment)
+                .iter()
+                .zip(degrees)
+                .enumerate()
+            {
+                let name = match constraint.metadata().get("name") {
+                    Some(name) => name.clone(),
+                    None => format!("integrity_{segment}_{index}"),
+                };
+                let cyclic = constraint.domain().is_cyclic();
+                if cyclic && format_version < 2 {
+                    anyhow::bail!(
+                        "the constraint `{name}` is marked `cyclic`, which can only be exported from format version 2"
+                    );
+                }
+                let frame_size = constraint.domain().frame_size();
+                if frame_size > 2 && format_version < 2 {
+                    anyhow::bail!(
+                        "the constraint `{name}` accesses the row {} rows ahead, but rows more than one row ahead can only be exported from format version 2",
+                        frame_size - 1
+                    );
+                }
+                let exemptions = if cyclic { 0 } else { frame_size - 1 };
+                let polynomial = builder.build(constraint.node_index()).map_err(|exp| {
+                    anyhow::anyhow!(
+                        "the constraint `{name}` raises a value to the power of {exp}, but powers can only be exported with exponents up to {MAX_EXPONENT}"
+                    )
+                })?;
+                gates.push(Gate {
+                    name,
+                    segment,
+                    degree: (format_version >= 2).then(|| degree.degree()),
+                    exemptions: (format_version >= 2).then_some(exemptions),
+                    cyclic,
+                    polynomial,
+                });
+            }
+        }
+
+        Ok(Self {
+            version: (format_version > DEFAULT_FORMAT_VERSION).then_some(format_version),
+            name: ir.name().to_string(),
+            modulus: ir.field.modulus(),
+            advice_columns,
+            fixed_columns,
+            instance_columns,
+            num_instance_values,
+            num_challenges: ir.num_random_values,
+            gates,
+        })
+    }
+}
+
+/// Converts subgraphs of the constraint graph of an [Air] into gate polynomials
+struct ExpressionBuilder<'a> {
+    ir: &'a Air,
+    hex_constants: bool,
+}
+impl<'a> ExpressionBuilder<'a> {
+    /// Returns the polynomial of the subgraph with the specified node as its tip, or the exponent
+    /// of a power in it which is larger than [MAX_EXPONENT]
+    fn build(&self, index: &NodeIndex) -> Result<Expression, usize> {
+        Ok(match self.ir.constraint_graph().node(index).op() {
+            Operation::Value(value) => self.build_value(value),
+            Operation::Add(lhs, rhs) => Expression::Sum {
+                lhs: Box::new(self.build(lhs)?),
+                rhs: Box::new(self.build(rhs)?),
+            },
+            Operation::Sub(lhs, rhs) => Expression::Sum {
+                lhs: Box::new(self.build(lhs)?),
+                rhs: Box::new(Expression::Negated {
+                    expr: Box::new(self.build(rhs)?),
+                }),
+            },
+            Operation::Mul(lhs, rhs) => Expression::Product {
+                lhs: Box::new(self.build(lhs)?),
+                rhs: Box::new(self.build(rhs)?),
+            },
+            Operation::Exp(_, exp) if *exp > MAX_EXPONENT => return Err(*exp),
+            Operation::Exp(base, exp) => match *exp {
+                0 => Expression::Constant {
+                    value: Constant::new(1, self.hex_constants),
+                },
+                exp => Self::power(self.build(base)?, exp),
+            },
+        })
+    }
+
+    /// Expands `base^exp` into products by repeated squaring, so that the depth of the resulting
+    /// polynomial is logarithmic in the exponent, e.g. `x^6` becomes `(x * x * x) * (x * x * x)`
+    fn power(base: Expression, exp: usize) -> Expression {
+        match exp {
+            1 => base,
+            exp if exp % 2 == 0 => {
+                let half = Self::power(base, exp / 2);
+                Expression::Product {
+                    lhs: Box::new(half.clone()),
+                    rhs: Box::new(half),
+                }
+            }
+            exp => Expression::Product {
+                lhs: Box::new(Self::power(base.clone(), exp - 1)),
+                rhs: Box::new(base),
+            },
+        }
+    }
+
+    fn build_value(&self, value: &Value) -> Expression {
+        match value {
+            Value::Constant(value) => Expression::Constant {
+                value: Constant::new(*value, self.hex_constants),
+            },
+            Value::TraceAccess(access) => {
+                let offset = self.ir.trace_segment_widths[..access.segment]
+                    .iter()
+                    .map(|&width| width as usize)
+                    .sum::<usize>();
+                Expression::Advice {
+                    column: offset + access.column,
+                    rotation: access.row_offset,
+                }
+            }
+            Value::PeriodicColumn(access) => {
+                let column = self
+                    .ir
+                    .periodic_columns
+                    .keys()
+                    .position(|name| *name == access.name)
+                    .expect("reference to undefined periodic column");
+                Expression::Fixed {
+                    column,
+                    rotation: 0,
+                }
+            }
+            Value::RandomValue(index) => Expression::Challenge { index: *index },
+            Value::TraceLen => Expression::TraceLength,
+            Value::PublicInput(_) => {
+                unreachable!("unexpected access to public input in integrity constraint")
+            }
+        }
+    }
+}
@@ -4,7 +4,10 @@ use air_ir::{
     Air, AlgebraicGraph, ConstraintDomain, NodeIndex, Operation, TraceAccess, TraceSegmentId, Value,
 };
 
-use super::{Codegen, ElemType, Impl};
+use super::{
+    add_constraint_line, column_offset, declare_rand_elements, Codegen, ElemType, Impl,
+    SourceComments,
+};
 
 // HELPERS TO GENERATE THE WINTERFELL BOUNDARY CONSTRAINT METHODS
 // ================================================================================================
@@ -12,15 +15,22 @@ use super::{Codegen, ElemType, Impl};
 /// Adds an implementation of the "get_assertions" method to the referenced Air implementation
 /// based on the data in the provided IR.
 /// TODO: add result types to these functions.
-pub(super) fn add_fn_get_assertions(impl_ref: &mut Impl, ir: &Air) {
+pub(super) fn add_fn_get_assertions(
+    impl_ref: &mut Impl,
+    ir: &Air,
+    comments: Option<&SourceComments>,
+) {
     // define the function
     let get_assertions = impl_ref
         .new_fn("get_assertions")
         .arg_ref_self()
         .ret("Vec<Assertion<Felt>>");
 
+    // declare the result vector to be returned.
+    get_assertions.line("let mut result = Vec::new();");
+
     // add the boundary constraints
-    add_assertions(get_assertions, ir, 0);
+    add_assertions(get_assertions, ir, 0, comments);
 
     // return the result
     get_assertions.line("result");
@@ -28,7 +38,11 @@ pub(super) fn add_fn_get_assertions(impl_ref: &mut Impl, ir: &Air) {
 
 /// Adds an implementation of the "get_aux_assertions" method to the referenced Air implementation
 /// based on the data in the provided IR.
-pub(super) fn add_fn_get_aux_assertions(impl_ref: &mut Impl, ir: &Air) {
+pub(super) fn add_fn_get_aux_assertions(
+    impl_ref: &mut Impl,
+    ir: &Air,
+    comments: Option<&SourceComments>,
+) {
     // define the function
     let get_aux_assertions = impl_ref
         .new_fn("get_aux_assertions")
@@ -37,25 +51,34 @@ pub(super) fn add_fn_get_aux_assertions(impl_ref: &mut Impl, ir: &Air) {
         .arg("aux_rand_elements", "&AuxTraceRandElements<E>")
         .ret("Vec<Assertion<E>>");
 
-    // add the boundary constraints
-    add_assertions(get_aux_assertions, ir, 1);
+    // declare the random values, and the result vector to be returned.
+    let constraints = (1..ir.trace_segment_widths.len())
+        .flat_map(|trace_segment| ir.boundary_constraints(trace_segment));
+    declare_rand_elements(get_aux_assertions, ir, constraints);
+    get_aux_assertions.line("let mut result = Vec::new();");
+
+    // add the boundary constraints of each auxiliary segment
+    for trace_segment in 1..ir.trace_segment_widths.len() {
+        add_assertions(get_aux_assertions, ir, trace_segment, comments);
+    }
 
     // return the result
     get_aux_assertions.line("result");
 }
 
-/// Declares a result vector and adds assertions for boundary constraints to it for the specified
-/// trace segment
-fn add_assertions(func_body: &mut codegen::Function, ir: &Air, trace_segment: TraceSegmentId) {
+/// Adds assertions for the boundary constraints of the specified trace segment to the result vector
+fn add_assertions(
+    func_body: &mut codegen::Function,
+    ir: &Air,
+    trace_segment: TraceSegmentId,
+    comments: Option<&SourceComments>,
+) {
     let elem_type = if trace_segment == 0 {
         ElemType::Base
     } else {
         ElemType::Ext
     };
 
-    // declare the result vector to be returned.
-    func_body.line("let mut result = Vec::new();");
-
     // add the boundary constraints
     for constraint in ir.boundary_constraints(trace_segment) {
         let (trace_access, expr_root) =
@@ -64,11 +87,11 @@ fn add_assertions(func_body: &mut codegen::Function, ir: &Air, trace_segment: Tr
 
         let assertion = format!(
             "result.push(Assertion::single({}, {}, {}));",
-            trace_access.column,
+            column_offset(ir, trace_segment) + trace_access.column,
             domain_to_str(constraint.domain()),
             expr_root.to_string(ir, elem_type, trace_segment)
         );
-        func_body.line(assertion);
+        add_constraint_line(func_body, comments, constraint, assertion);
     }
 }
 
@@ -77,6 +100,7 @@ fn domain_to_str(domain: ConstraintDomain) -> String {
     match domain {
         ConstraintDomain::FirstRow => "0".to_string(),
         ConstraintDomain::LastRow => "self.last_step()".to_string(),
+        ConstraintDomain::BeforeLastRow(offset) => format!("self.last_step() - {offset}"),
         // TODO: replace this with an Error once we have a Result return type.
         _ => panic!("invalid constraint domain"),
     }
@@ -0,0 +1,41 @@
+use air_ir::Air;
+
+use super::{column_offset, Scope};
+
+/// Updates the provided scope with a `columns` module declaring a constant for each named trace
+/// column binding, e.g. `pub const CLK: usize = 0;`, so that code building the execution trace
+/// can refer to columns by the names used in the AirScript source.
+///
+/// Single columns are declared as indices, and groups of columns as ranges, e.g.
+/// `pub const FMP: Range<usize> = 1..3;`. Indices refer to the main trace for bindings in the
+/// `main` segment, and otherwise to the auxiliary trace, in which all auxiliary segments are laid
+/// out consecutively.
+pub(super) fn add_column_constants(scope: &mut Scope, ir: &Air) {
+    let mut constants = vec![];
+    let mut uses_range = false;
+    for segment in ir.trace_columns.iter() {
+        let offset = column_offset(ir, segment.id);
+        for binding in segment.bindings.iter() {
+            let Some(name) = binding.name else {
+                continue;
+            };
+            let name = name.as_str().to_uppercase();
+            let start = offset + binding.offset;
+            if binding.size == 1 {
+                constants.push(format!("pub const {name}: usize = {start};"));
+            } else {
+                uses_range = true;
+                constants.push(format!(
+                    "pub const {name}: Range<usize> = {start}..{};",
+                    start + binding.size
+                ));
+            }
+        }
+    }
+
+    let module = scope.new_module("columns").vis("pub");
+    if uses_range {
+        module.import("core::ops", "Range");
+    }
+    module.scope().raw(constants.join("\n"));
+}
@@ -0,0 +1,80 @@
+use air_ir::ConstraintRoot;
+use miden_diagnostics::CodeMap;
+
+// HELPERS TO RECOVER SOURCE COMMENTS OF CONSTRAINTS
+// ================================================================================================
+
+/// The comments derived from the source of each constraint which are emitted into the generated
+/// code, read from the [CodeMap] containing the sources.
+pub(crate) struct SourceComments<'a> {
+    codemap: &'a CodeMap,
+    /// Whether the `#` comments preceding each constraint are emitted above its code
+    doc_comments: bool,
+    /// Whether the file and line of each constraint are emitted after its code
+    provenance: bool,
+}
+impl<'a> SourceComments<'a> {
+    pub fn new(codemap: &'a CodeMap, doc_comments: bool, provenance: bool) -> Self {
+        Self {
+            codemap,
+            doc_comments,
+            provenance,
+        }
+    }
+
+    /// Returns the text of the `#` comments on the lines immediately preceding the source of
+    /// `constraint`, if any, one item per line, with the leading `#` and a single space removed.
+    ///
+    /// Attributes given between the comments and the constraint, e.g. `@[name="a"]`, are skipped,
+    /// but a blank line ends the comment block, so that e.g. a section header is not attributed to
+    /// the first constraint of the section.
+    pub fn doc_comments(&self, constraint: &ConstraintRoot) -> Vec<String> {
+        if !self.doc_comments {
+            return vec![];
+        }
+        doc_comments(self.codemap, constraint)
+    }
+
+    /// Returns the location of the source of `constraint`, as `<file>:<line>`, if known.
+    ///
+    /// For constraints produced by inlining an evaluator or a comprehension, this is the location of
+    /// the constraint in the body of the evaluator or comprehension.
+    pub fn provenance(&self, constraint: &ConstraintRoot) -> Option<String> {
+        let span = constraint.span();
+        if !self.provenance || span.is_unknown() {
+            return None;
+        }
+        let file = self.codemap.get_with_span(span).ok()?;
+        let location = file.location(span.start().index()).ok()?;
+        Some(format!("{}:{}", file.name(), location.line.number()))
+    }
+}
+
+fn doc_comments(codemap: &CodeMap, constraint: &ConstraintRoot) -> Vec<String> {
+    let span = constraint.span();
+    if span.is_unknown() {
+        return vec![];
+    }
+    let Ok(file) = codemap.get_with_span(span) else {
+        return vec![];
+    };
+    let source = file.source();
+    let start = span.start().index().to_usize().min(source.len());
+    // the text of the lines before the one on which the constraint starts
+    let Some(end) = source[..start].rfind('\n') else {
+        return vec![];
+    };
+    let preceding = &source[..end];
+
+    let mut comments = vec![];
+    for line in preceding.split('\n').rev() {
+        let line = line.trim();
+        if let Some(comment) = line.strip_prefix('#') {
+            comments.push(comment.strip_prefix(' ').unwrap_or(comment).to_string());
+        } else if !line.starts_with('@') {
+            break;
+        }
+    }
+    comments.reverse();
+    comments
+}
@@ -0,0 +1,86 @@
+use air_ir::Air;
+
+use super::{array_of, AirNames, Scope};
+
+/// The length of the execution trace against which the generated Air is instantiated in tests.
+const TEST_TRACE_LENGTH: usize = 1024;
+
+/// Updates the provided scope with a test module asserting that the generated Air has the trace
+/// widths and the numbers of constraints derived from the provided IR at generation time.
+///
+/// Since these counts are baked into the generated code, regenerating it after an edit to the
+/// AirScript source which changes any of them will break the emitted test, prompting a review.
+pub(super) fn add_constraint_count_tests(scope: &mut Scope, ir: &Air, names: &AirNames) {
+    let module = scope
+        .new_module("constraint_counts")
+        .attr("cfg(test)")
+        .import("super", "*")
+        .import("winter_air", "FieldExtension")
+        .import("winter_air", "TraceLayout");
+
+    // declare the counts derived from the IR.
+    let num_segments = ir.trace_segment_widths.len();
+    let main_trace_width = ir.trace_segment_widths.first().copied().unwrap_or_default() as usize;
+    let aux_trace_width = ir
+        .trace_segment_widths
+        .iter()
+        .skip(1)
+        .map(|&width| width as usize)
+        .sum::<usize>();
+    let num_aux_transition_constraints = (1..num_segments)
+        .map(|trace_segment| ir.integrity_constraints(trace_segment).len())
+        .sum::<usize>();
+    let num_aux_assertions = (1..num_segments)
+        .map(|trace_segment| ir.num_boundary_constraints(trace_segment))
+        .sum::<usize>();
+    let constants = [
+        ("MAIN_TRACE_WIDTH", main_trace_width),
+        ("AUX_TRACE_WIDTH", aux_trace_width),
+        ("NUM_RANDOM_VALUES", ir.num_random_values as usize),
+        (
+            "NUM_MAIN_TRANSITION_CONSTRAINTS",
+            ir.integrity_constraints(0).len(),
+        ),
+        (
+            "NUM_AUX_TRANSITION_CONSTRAINTS",
+            num_aux_transition_constraints,
+        ),
+        ("NUM_MAIN_ASSERTIONS", ir.num_boundary_constraints(0)),
+        ("NUM_AUX_ASSERTIONS", num_aux_assertions),
+    ];
+    let constants = constants
+        .iter()
+        .map(|(constant, value)| format!("const {constant}: usize = {value};"))
+        .collect::<Vec<_>>();
+    module.scope().raw(constants.join("\n"));
+
+    // instantiate the Air, with all public inputs set to zero.
+    let public_inputs = ir
+        .public_inputs()
+        .map(|public_input| array_of(public_input, "Felt::ZERO"))
+        .collect::<Vec<_>>();
+    let test = module.new_fn("constraint_counts").attr("test");
+    test.line(
+        "let layout = TraceLayout::new(MAIN_TRACE_WIDTH, [AUX_TRACE_WIDTH], [NUM_RANDOM_VALUES]);",
+    );
+    test.line(format!(
+        "let trace_info = TraceInfo::new_multi_segment(layout, {TEST_TRACE_LENGTH}, vec![]);"
+    ));
+    test.line("let options = WinterProofOptions::new(27, 128, 0, FieldExtension::None, 8, 31);");
+    test.line(format!(
+        "let air = {}::new(trace_info, {}::new({}), options);",
+        names.air,
+        names.public_inputs,
+        public_inputs.join(", ")
+    ));
+
+    // assert the trace widths and constraint counts.
+    test.line("assert_eq!(air.trace_layout().main_trace_width(), MAIN_TRACE_WIDTH);");
+    test.line("assert_eq!(air.trace_layout().aux_trace_width(), AUX_TRACE_WIDTH);");
+    test.line("assert_eq!(air.context().num_main_transition_constraints(), NUM_MAIN_TRANSITION_CONSTRAINTS);");
+    test.line("assert_eq!(air.context().num_aux_transition_constraints(), NUM_AUX_TRANSITION_CONSTRAINTS);");
+    test.line("assert_eq!(air.get_assertions().len(), NUM_MAIN_ASSERTIONS);");
+    test.line("let mut aux_rand_elements = AuxTraceRandElements::<Felt>::new();");
+    test.line("aux_rand_elements.add_segment_elements(vec![Felt::ZERO; NUM_RANDOM_VALUES]);");
+    test.line("assert_eq!(air.get_aux_assertions(&aux_rand_elements).len(), NUM_AUX_ASSERTIONS);");
+}
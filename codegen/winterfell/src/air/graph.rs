@@ -2,7 +2,7 @@ use air_ir::{
     Air, IntegrityConstraintDegree, NodeIndex, Operation, TraceAccess, TraceSegmentId, Value,
 };
 
-use super::ElemType;
+use super::{column_offset, ElemType};
 
 // RUST STRING GENERATION FOR THE CONSTRAINT GRAPH
 // ================================================================================================
@@ -35,14 +35,15 @@ impl Codegen for IntegrityConstraintDegree {
 }
 
 impl Codegen for TraceAccess {
-    fn to_string(&self, _ir: &Air, _elem_type: ElemType, trace_segment: TraceSegmentId) -> String {
+    fn to_string(&self, ir: &Air, _elem_type: ElemType, trace_segment: TraceSegmentId) -> String {
         let frame = if self.segment == 0 { "main" } else { "aux" };
+        let column = column_offset(ir, self.segment) + self.column;
         let row_offset = match self.row_offset {
             0 => {
-                format!("current[{}]", self.column)
+                format!("current[{column}]")
             }
             1 => {
-                format!("next[{}]", self.column)
+                format!("next[{column}]")
             }
             _ => panic!("Winterfell doesn't support row offsets greater than 1."),
         };
@@ -123,11 +124,20 @@ impl Codegen for Value {
                 format!("periodic_values[{index}]")
             }
             Value::PublicInput(air_ir::PublicInputAccess { name, index }) => {
-                format!("self.{name}[{index}]")
+                match ir.public_inputs[name].columns {
+                    None => format!("self.{name}[{index}]"),
+                    Some(columns) => {
+                        format!("self.{name}[{}][{}]", index / columns, index % columns)
+                    }
+                }
             }
             Value::RandomValue(idx) => {
-                format!("aux_rand_elements.get_segment_elements(0)[{idx}]")
+                format!("rand_elements[{idx}]")
             }
+            Value::TraceLen => match elem_type {
+                ElemType::Base => "Felt::new(self.trace_length() as u64)".to_string(),
+                ElemType::Ext => "E::from(self.trace_length() as u64)".to_string(),
+            },
         }
     }
 }
@@ -162,6 +172,18 @@ fn binary_op_to_string(
             };
             format!("{lhs} - {rhs}")
         }
+        // Squares are emitted as such, rather than by repeating the operand, so that the powers
+        // into which small exponents are expanded are not evaluated again for each of their uses,
+        // e.g. `x^8` is emitted as `x.square().square().square()` rather than 7 multiplications
+        Operation::Mul(l_idx, r_idx) if l_idx == r_idx => {
+            let base = l_idx.to_string(ir, elem_type, trace_segment);
+            let is_square = matches!(ir.constraint_graph().node(l_idx).op(), Operation::Mul(lhs, rhs) if lhs == rhs);
+            if is_leaf(l_idx, ir) || is_square {
+                format!("{base}.square()")
+            } else {
+                format!("({base}).square()")
+            }
+        }
         Operation::Mul(l_idx, r_idx) => {
             let lhs = if ir.constraint_graph().node(l_idx).op().precedence() < op.precedence() {
                 format!("({})", l_idx.to_string(ir, elem_type, trace_segment))
@@ -1,8 +1,10 @@
 mod public_inputs;
-use public_inputs::add_public_inputs_struct;
+use public_inputs::{add_public_inputs_struct, array_of};
 
 mod periodic_columns;
-use periodic_columns::add_fn_get_periodic_column_values;
+use periodic_columns::{
+    add_fn_get_periodic_column_values, add_periodic_column_modules, PeriodicColumnNames,
+};
 
 mod graph;
 use graph::Codegen;
@@ -10,10 +12,24 @@ use graph::Codegen;
 mod boundary_constraints;
 use boundary_constraints::{add_fn_get_assertions, add_fn_get_aux_assertions};
 
+mod columns;
+use columns::add_column_constants;
+
+mod comments;
+pub(super) use comments::SourceComments;
+
+mod constraint_counts;
+use constraint_counts::add_constraint_count_tests;
+
 mod transition_constraints;
-use transition_constraints::{add_fn_evaluate_aux_transition, add_fn_evaluate_transition};
+use transition_constraints::{
+    add_fn_evaluate_aux_transition, add_fn_evaluate_segment_transitions, add_fn_evaluate_transition,
+};
 
-use air_ir::{Air, TraceSegmentId};
+use core::ops::RangeBounds;
+use std::collections::BTreeSet;
+
+use air_ir::{Air, ConstraintRoot, Operation, TraceSegmentId, Value};
 
 use super::{Impl, Scope};
 
@@ -26,26 +42,88 @@ pub enum ElemType {
     Ext,
 }
 
+/// The names of the structs emitted for an Air
+#[derive(Debug, Clone)]
+pub(super) struct AirNames {
+    /// The name of the Air struct, which implements the Winterfell Air trait
+    pub air: String,
+    /// The name of the struct holding the public inputs of the Air
+    pub public_inputs: String,
+}
+
 // HELPERS TO GENERATE AN IMPLEMENTATION OF THE WINTERFELL AIR TRAIT
 // ================================================================================================
 
 /// Updates the provided scope with a new Air struct and Winterfell Air trait implementation
-/// which are equivalent the provided AirIR.
-pub(super) fn add_air(scope: &mut Scope, ir: &Air) {
+/// which are equivalent the provided AirIR, named as given by `names`.
+///
+/// If `modular` is set, the periodic columns are emitted into one Rust module per AirScript module
+/// in which they were declared, rather than inline in the Air trait implementation, see
+/// [PeriodicColumnNames].
+///
+/// If `column_constants` is set, a `columns` module declaring the index of each named trace column
+/// is emitted as well.
+///
+/// If `constraint_count_tests` is set, a test module asserting the trace widths and constraint
+/// counts of the generated Air is emitted as well.
+///
+/// If `factor_selectors` is set, selectors shared by several integrity constraints are evaluated
+/// once per transition, rather than once per constraint.
+///
+/// If `comments` is given, comments derived from the source of each constraint are emitted along
+/// with the code generated for the constraint, see [SourceComments].
+#[allow(clippy::too_many_arguments)]
+pub(super) fn add_air(
+    scope: &mut Scope,
+    ir: &Air,
+    names: &AirNames,
+    modular: bool,
+    column_constants: bool,
+    constraint_count_tests: bool,
+    factor_selectors: bool,
+    comments: Option<&SourceComments>,
+) {
     // add the Public Inputs struct and its base implementation.
-    add_public_inputs_struct(scope, ir);
+    add_public_inputs_struct(scope, ir, &names.public_inputs);
 
-    let name = ir.name();
+    // add a module for each AirScript module declaring periodic columns.
+    let periodic_column_names = modular.then(|| PeriodicColumnNames::new(ir, names));
+    if let Some(modules) = periodic_column_names.as_ref() {
+        add_periodic_column_modules(scope, ir, modules);
+    }
 
     // add the Air struct and its base implementation.
-    add_air_struct(scope, ir, name);
+    add_air_struct(scope, ir, &names.air, factor_selectors, comments);
 
     // add Winterfell Air trait implementation for the provided AirIR.
-    add_air_trait(scope, ir, name);
+    add_air_trait(
+        scope,
+        ir,
+        names,
+        periodic_column_names.as_ref(),
+        factor_selectors,
+        comments,
+    );
+
+    // add a module declaring the indices of the named trace columns.
+    if column_constants {
+        add_column_constants(scope, ir);
+    }
+
+    // add a test module asserting the constraint counts of the generated Air.
+    if constraint_count_tests {
+        add_constraint_count_tests(scope, ir, names);
+    }
 }
 
 /// Updates the provided scope with a custom Air struct.
-fn add_air_struct(scope: &mut Scope, ir: &Air, name: &str) {
+fn add_air_struct(
+    scope: &mut Scope,
+    ir: &Air,
+    name: &str,
+    factor_selectors: bool,
+    comments: Option<&SourceComments>,
+) {
     // define the custom Air struct.
     let air_struct = scope
         .new_struct(name)
@@ -54,14 +132,23 @@ fn add_air_struct(scope: &mut Scope, ir: &Air, name: &str) {
 
     // add public inputs
     for public_input in ir.public_inputs() {
-        air_struct.field(
-            public_input.name.as_str(),
-            format!("[Felt; {}]", public_input.size),
-        );
+        air_struct.field(public_input.name.as_str(), array_of(public_input, "Felt"));
     }
 
     // add the custom Air implementation block
     let base_impl = scope.new_impl(name);
+    // add the maximum degree of the integrity constraints, to help choose a blowup factor.
+    let max_constraint_degree = (0..ir.trace_segment_widths.len())
+        .flat_map(|trace_segment| ir.integrity_constraint_degrees(trace_segment))
+        .map(|degree| degree.degree())
+        .max()
+        .unwrap_or(1);
+    base_impl.associate_const(
+        "MAX_CONSTRAINT_DEGREE",
+        "usize",
+        max_constraint_degree.to_string(),
+        "pub",
+    );
     // add a simple method to get the last step.
     base_impl
         .new_fn("last_step")
@@ -69,17 +156,27 @@ fn add_air_struct(scope: &mut Scope, ir: &Air, name: &str) {
         .vis("pub")
         .ret("usize")
         .line("self.trace_length() - self.context().num_transition_exemptions()");
+
+    // add the evaluation methods of each auxiliary trace segment, if there are several.
+    add_fn_evaluate_segment_transitions(base_impl, ir, factor_selectors, comments);
 }
 
 /// Updates the provided scope with the custom Air struct and an Air trait implementation based on
 /// the provided AirIR.
-fn add_air_trait(scope: &mut Scope, ir: &Air, name: &str) {
+fn add_air_trait(
+    scope: &mut Scope,
+    ir: &Air,
+    names: &AirNames,
+    periodic_column_names: Option<&PeriodicColumnNames>,
+    factor_selectors: bool,
+    comments: Option<&SourceComments>,
+) {
     // add the implementation block for the Air trait.
     let air_impl = scope
-        .new_impl(name)
+        .new_impl(&names.air)
         .impl_trait("Air")
         .associate_type("BaseField", "Felt")
-        .associate_type("PublicInputs", "PublicInputs");
+        .associate_type("PublicInputs", &names.public_inputs);
 
     // add default function "context".
     let fn_context = air_impl
@@ -89,35 +186,35 @@ fn add_air_trait(scope: &mut Scope, ir: &Air, name: &str) {
     fn_context.line("&self.context");
 
     // add the method implementations required by the AIR trait.
-    add_fn_new(air_impl, ir);
+    add_fn_new(air_impl, ir, &names.public_inputs);
 
-    add_fn_get_periodic_column_values(air_impl, ir);
+    add_fn_get_periodic_column_values(air_impl, ir, periodic_column_names);
 
-    add_fn_get_assertions(air_impl, ir);
+    add_fn_get_assertions(air_impl, ir, comments);
 
-    add_fn_get_aux_assertions(air_impl, ir);
+    add_fn_get_aux_assertions(air_impl, ir, comments);
 
-    add_fn_evaluate_transition(air_impl, ir);
+    add_fn_evaluate_transition(air_impl, ir, factor_selectors, comments);
 
-    add_fn_evaluate_aux_transition(air_impl, ir);
+    add_fn_evaluate_aux_transition(air_impl, ir, factor_selectors, comments);
 }
 
 /// Adds an implementation of the "new" method to the referenced Air implementation based on the
 /// data in the provided AirIR.
-fn add_fn_new(impl_ref: &mut Impl, ir: &Air) {
+fn add_fn_new(impl_ref: &mut Impl, ir: &Air, public_inputs_name: &str) {
     // define the function.
     let new = impl_ref
         .new_fn("new")
         .arg("trace_info", "TraceInfo")
-        .arg("public_inputs", "PublicInputs")
+        .arg("public_inputs", public_inputs_name)
         .arg("options", "WinterProofOptions")
         .ret("Self");
 
     // define the integrity constraint degrees of the main trace `main_degrees`.
-    add_constraint_degrees(new, ir, 0, "main_degrees");
+    add_constraint_degrees(new, ir, 0..1, "main_degrees");
 
     // define the integrity constraint degrees of the aux trace `aux_degrees`.
-    add_constraint_degrees(new, ir, 1, "aux_degrees");
+    add_constraint_degrees(new, ir, 1.., "aux_degrees");
 
     // define the number of main trace boundary constraints `num_main_assertions`.
     new.line(format!(
@@ -126,10 +223,10 @@ fn add_fn_new(impl_ref: &mut Impl, ir: &Air) {
     ));
 
     // define the number of aux trace boundary constraints `num_aux_assertions`.
-    new.line(format!(
-        "let num_aux_assertions = {};",
-        ir.num_boundary_constraints(1)
-    ));
+    let num_aux_assertions = (1..ir.trace_segment_widths.len())
+        .map(|trace_segment| ir.num_boundary_constraints(trace_segment))
+        .sum::<usize>();
+    new.line(format!("let num_aux_assertions = {num_aux_assertions};"));
 
     // define the context.
     let context = "
@@ -156,16 +253,102 @@ let context = AirContext::new_multi_segment(
 
 /// Iterates through the degrees of the integrity constraints in the IR, and appends a line of
 /// generated code to the function body that declares all of the constraint degrees.
+///
+/// The degrees of all of the trace segments in `trace_segments` are declared in segment order,
+/// which is how the auxiliary segments are laid out in the single Winterfell auxiliary trace.
 fn add_constraint_degrees(
     func_body: &mut codegen::Function,
     ir: &Air,
-    trace_segment: TraceSegmentId,
+    trace_segments: impl RangeBounds<TraceSegmentId>,
     decl_name: &str,
 ) {
-    let degrees = ir
-        .integrity_constraint_degrees(trace_segment)
-        .iter()
-        .map(|degree| degree.to_string(ir, ElemType::Ext, trace_segment))
+    let degrees = (0..ir.trace_segment_widths.len())
+        .filter(|trace_segment| trace_segments.contains(trace_segment))
+        .flat_map(|trace_segment| {
+            ir.integrity_constraint_degrees(trace_segment)
+                .iter()
+                .map(|degree| degree.to_string(ir, ElemType::Ext, trace_segment))
+                .collect::<Vec<_>>()
+        })
         .collect::<Vec<_>>();
     func_body.line(format!("let {decl_name} = vec![{}];", degrees.join(", ")));
 }
+
+/// Returns the index of the first column of `trace_segment` in the Winterfell trace segment it
+/// is laid out in, i.e. the main trace for segment 0, and the auxiliary trace for all others.
+pub(super) fn column_offset(ir: &Air, trace_segment: TraceSegmentId) -> usize {
+    if trace_segment == 0 {
+        return 0;
+    }
+    ir.trace_segment_widths[1..trace_segment]
+        .iter()
+        .map(|&width| width as usize)
+        .sum()
+}
+
+/// Declares the random values provided by the verifier as `rand_elements`, an array of the size
+/// declared by the program, if any of the given constraints refer to a random value.
+///
+/// Every access to a random value in the generated code is then checked against the declared
+/// number of random values when the code is compiled, while a verifier which provides fewer random
+/// values is reported as soon as the constraints are evaluated.
+fn declare_rand_elements<'a, I>(func_body: &mut codegen::Function, ir: &Air, constraints: I)
+where
+    I: IntoIterator<Item = &'a ConstraintRoot>,
+{
+    let num_random_values = ir.num_random_values;
+    if num_random_values == 0 || !uses_random_values(ir, constraints) {
+        return;
+    }
+    func_body.line(format!(
+        "let rand_elements: &[E; {num_random_values}] = aux_rand_elements.get_segment_elements(0).get(..{num_random_values}).and_then(|elements| elements.try_into().ok()).expect(\"expected at least {num_random_values} random values\");"
+    ));
+}
+
+/// Returns true if any of the given constraints refer to a random value.
+fn uses_random_values<'a, I>(ir: &Air, constraints: I) -> bool
+where
+    I: IntoIterator<Item = &'a ConstraintRoot>,
+{
+    let graph = ir.constraint_graph();
+    let mut visited = BTreeSet::new();
+    let mut stack = constraints
+        .into_iter()
+        .map(|constraint| *constraint.node_index())
+        .collect::<Vec<_>>();
+    while let Some(index) = stack.pop() {
+        if !visited.insert(index) {
+            continue;
+        }
+        match graph.node(&index).op() {
+            Operation::Value(Value::RandomValue(_)) => return true,
+            op => stack.extend(op.children()),
+        }
+    }
+    false
+}
+
+/// Appends `line`, the code generated for `constraint`, to the provided codegen function body,
+/// along with the comments derived from the source of the constraint, if `comments` is given.
+fn add_constraint_line(
+    func_body: &mut codegen::Function,
+    comments: Option<&SourceComments>,
+    constraint: &air_ir::ConstraintRoot,
+    line: String,
+) {
+    let Some(comments) = comments else {
+        func_body.line(line);
+        return;
+    };
+    for comment in comments.doc_comments(constraint) {
+        if comment.is_empty() {
+            func_body.line("//");
+        } else {
+            func_body.line(format!("// {comment}"));
+        }
+    }
+    match comments.provenance(constraint) {
+        Some(provenance) => func_body.line(format!("{line} // from {provenance}")),
+        None => func_body.line(line),
+    };
+}
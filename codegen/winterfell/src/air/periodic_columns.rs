@@ -1,19 +1,150 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
-use air_ir::{Air, PeriodicColumn, QualifiedIdentifier};
+use air_ir::{Air, Identifier, PeriodicColumn, QualifiedIdentifier};
 
-use super::Impl;
+use super::{AirNames, Impl, Scope};
 
-pub(super) fn add_fn_get_periodic_column_values(impl_ref: &mut Impl, ir: &Air) {
+/// The names of the items emitted into the generated scope besides the Air and public inputs
+/// structs, i.e. the imported crates and the other modules, with which the modules of periodic
+/// columns must not clash.
+const RESERVED_NAMES: [&str; 5] = [
+    "columns",
+    "constraint_counts",
+    "winter_air",
+    "winter_math",
+    "winter_utils",
+];
+
+/// The keywords of Rust, including those reserved for future use, none of which can name a module
+/// or function.
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in",
+    "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
+    "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// The names of the Rust modules and functions into which the periodic columns are emitted when
+/// output is modular.
+///
+/// These are the names of the AirScript modules and columns in snake case, each suffixed with `_`
+/// as many times as needed so that it is not a Rust keyword, and does not clash with any other
+/// item in the same scope, e.g. the module `test` is emitted as `test_` if the Air struct is also
+/// named `test`, and the modules `FooBar` and `foo_bar` as `foo_bar` and `foo_bar_`.
+pub(super) struct PeriodicColumnNames(BTreeMap<QualifiedIdentifier, (String, String)>);
+impl PeriodicColumnNames {
+    pub(super) fn new(ir: &Air, names: &AirNames) -> Self {
+        let mut module_names = RESERVED_NAMES
+            .iter()
+            .map(|name| name.to_string())
+            .chain([names.air.clone(), names.public_inputs.clone()])
+            .collect::<BTreeSet<_>>();
+        let mut modules = BTreeMap::<Identifier, (String, BTreeSet<String>)>::new();
+        let columns = ir
+            .periodic_columns
+            .keys()
+            .map(|qid| {
+                let (module, function_names) = modules.entry(qid.module).or_insert_with(|| {
+                    let module = to_snake_case(qid.module.as_str());
+                    (unique_name(module, &mut module_names), BTreeSet::new())
+                });
+                let function = unique_name(to_snake_case(qid.name().as_str()), function_names);
+                (*qid, (module.clone(), function))
+            })
+            .collect();
+        Self(columns)
+    }
+
+    /// Returns the name of the Rust module corresponding to the AirScript module in which the
+    /// periodic column was declared.
+    fn module(&self, qid: &QualifiedIdentifier) -> &str {
+        &self.0[qid].0
+    }
+
+    /// Returns the name of the Rust function corresponding to the periodic column.
+    fn function(&self, qid: &QualifiedIdentifier) -> &str {
+        &self.0[qid].1
+    }
+}
+
+/// Returns `name`, suffixed with `_` as many times as needed for it to be neither a Rust keyword
+/// nor among the `taken` names, to which it is then added.
+fn unique_name(mut name: String, taken: &mut BTreeSet<String>) -> String {
+    while KEYWORDS.contains(&name.as_str()) || taken.contains(&name) {
+        name.push('_');
+    }
+    taken.insert(name.clone());
+    name
+}
+
+/// Adds the `get_periodic_column_values` function, which references the periodic columns in the
+/// modules named by `modules` if given, and otherwise defines them inline.
+pub(super) fn add_fn_get_periodic_column_values(
+    impl_ref: &mut Impl,
+    ir: &Air,
+    modules: Option<&PeriodicColumnNames>,
+) {
     // define the function.
     let get_periodic_column_values = impl_ref
         .new_fn("get_periodic_column_values")
         .arg_ref_self()
         .ret("Vec<Vec<Felt>>");
 
-    // output the periodic columns.
-    let periodic_columns = &ir.periodic_columns;
-    get_periodic_column_values.line(periodic_columns.codegen());
+    // output the periodic columns, or references to them if they are defined in their own modules.
+    let columns = match modules {
+        Some(modules) => ir
+            .periodic_columns
+            .keys()
+            .map(|qid| format!("{}::{}()", modules.module(qid), modules.function(qid)))
+            .collect::<Vec<_>>(),
+        None => ir
+            .periodic_columns
+            .values()
+            .map(|column| column.codegen())
+            .collect::<Vec<_>>(),
+    };
+    get_periodic_column_values.line(format!("vec![{}]", columns.join(", ")));
+}
+
+/// Updates the provided scope with a module for each AirScript module in which periodic columns
+/// are declared. Each periodic column is emitted as a function returning its values, named after
+/// the column, see [PeriodicColumnNames].
+pub(super) fn add_periodic_column_modules(
+    scope: &mut Scope,
+    ir: &Air,
+    modules: &PeriodicColumnNames,
+) {
+    for (qid, column) in ir.periodic_columns.iter() {
+        scope
+            .get_or_new_module(modules.module(qid))
+            .vis("pub")
+            .import("super", "*")
+            .new_fn(modules.function(qid))
+            .vis("pub")
+            .ret("Vec<Felt>")
+            .line(column.codegen());
+    }
+}
+
+/// Converts an AirScript identifier, which may be in camel case (e.g. `ProcessorAir`), to the
+/// snake case naming convention used for Rust modules and functions (e.g. `processor_air`).
+fn to_snake_case(name: &str) -> String {
+    let mut result = String::with_capacity(name.len());
+    let mut prev_is_lower = false;
+    for c in name.chars() {
+        if c.is_ascii_uppercase() {
+            if prev_is_lower {
+                result.push('_');
+            }
+            result.push(c.to_ascii_lowercase());
+            prev_is_lower = false;
+        } else {
+            result.push(c);
+            prev_is_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+        }
+    }
+    result
 }
 
 /// Code generation trait for generating Rust code strings from Periodic Columns.
@@ -21,26 +152,22 @@ trait Codegen {
     fn codegen(&self) -> String;
 }
 
-impl Codegen for &BTreeMap<QualifiedIdentifier, PeriodicColumn> {
+impl Codegen for PeriodicColumn {
     fn codegen(&self) -> String {
-        let mut columns = vec![];
-        for column in self.values() {
-            let mut rows = vec![];
-            for row in column.values.iter().copied() {
-                match row {
-                    0 => {
-                        rows.push("Felt::ZERO".to_string());
-                    }
-                    1 => {
-                        rows.push("Felt::ONE".to_string());
-                    }
-                    row => {
-                        rows.push(format!("Felt::new({row})"));
-                    }
+        let mut rows = vec![];
+        for row in self.values.iter().copied() {
+            match row {
+                0 => {
+                    rows.push("Felt::ZERO".to_string());
+                }
+                1 => {
+                    rows.push("Felt::ONE".to_string());
+                }
+                row => {
+                    rows.push(format!("Felt::new({row})"));
                 }
             }
-            columns.push(format!("vec![{}]", rows.join(", ")));
         }
-        format!("vec![{}]", columns.join(", "))
+        format!("vec![{}]", rows.join(", "))
     }
 }
@@ -1,18 +1,14 @@
-use air_ir::Air;
+use air_ir::{Air, PublicInput};
 
 use super::Scope;
 
-/// Updates the provided scope with a public inputs.
-pub(super) fn add_public_inputs_struct(scope: &mut Scope, ir: &Air) {
-    let name = "PublicInputs";
+/// Updates the provided scope with a public inputs struct of the given name.
+pub(super) fn add_public_inputs_struct(scope: &mut Scope, ir: &Air, name: &str) {
     // define the PublicInputs struct.
     let pub_inputs_struct = scope.new_struct(name).vis("pub");
 
     for public_input in ir.public_inputs() {
-        pub_inputs_struct.field(
-            public_input.name.as_str(),
-            format!("[Felt; {}]", public_input.size),
-        );
+        pub_inputs_struct.field(public_input.name.as_str(), array_of(public_input, "Felt"));
     }
 
     // add the public inputs implementation block
@@ -30,24 +26,40 @@ pub(super) fn add_public_inputs_struct(scope: &mut Scope, ir: &Air) {
         .ret("Self")
         .line(format!("Self {{ {} }}", pub_inputs_values.join(", ")));
     for public_input in ir.public_inputs() {
-        new_fn.arg(
-            public_input.name.as_str(),
-            format!("[Felt; {}]", public_input.size),
-        );
+        new_fn.arg(public_input.name.as_str(), array_of(public_input, "Felt"));
     }
 
-    add_serializable_impl(scope, pub_inputs_values)
+    add_serializable_impl(scope, ir, name)
 }
 
-/// Adds Serialization implementation for PublicInputs to the scope
-fn add_serializable_impl(scope: &mut Scope, pub_input_values: Vec<String>) {
-    let serializable_impl = scope.new_impl("PublicInputs").impl_trait("Serializable");
+/// Returns an array with the shape of the provided public input, i.e. `[elem; size]` for vectors
+/// and `[[elem; columns]; rows]` for matrices.
+///
+/// When `elem` is a type, this is the type of the public input, and when it is a value, this is an
+/// array expression with all elements set to that value.
+pub(super) fn array_of(public_input: &PublicInput, elem: &str) -> String {
+    match public_input.columns {
+        None => format!("[{elem}; {}]", public_input.size),
+        Some(columns) => format!("[[{elem}; {columns}]; {}]", public_input.size),
+    }
+}
+
+/// Adds Serialization implementation for the public inputs struct of the given name to the scope
+fn add_serializable_impl(scope: &mut Scope, ir: &Air, name: &str) {
+    let serializable_impl = scope.new_impl(name).impl_trait("Serializable");
     let write_into_fn = serializable_impl
         .new_fn("write_into")
         .generic("W: ByteWriter")
         .arg_ref_self()
         .arg("target", "&mut W");
-    for pub_input_value in pub_input_values {
-        write_into_fn.line(format!("target.write(self.{pub_input_value}.as_slice());"));
+    for public_input in ir.public_inputs() {
+        let name = public_input.name;
+        match public_input.columns {
+            None => write_into_fn.line(format!("target.write(self.{name}.as_slice());")),
+            // matrices are written in row-major order
+            Some(_) => write_into_fn.line(format!(
+                "self.{name}.iter().for_each(|row| target.write(row.as_slice()));"
+            )),
+        };
     }
 }
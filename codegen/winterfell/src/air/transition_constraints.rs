@@ -1,13 +1,23 @@
-use air_ir::{Air, TraceSegmentId};
+use std::collections::BTreeMap;
 
-use super::{Codegen, ElemType, Impl};
+use air_ir::{Air, NodeIndex, Operation, TraceSegmentId};
+
+use super::{add_constraint_line, declare_rand_elements, Codegen, ElemType, Impl, SourceComments};
 
 // HELPERS TO GENERATE THE WINTERFELL TRANSITION CONSTRAINT METHODS
 // ================================================================================================
 
 /// Adds an implementation of the "evaluate_transition" method to the referenced Air implementation
 /// based on the data in the provided IR.
-pub(super) fn add_fn_evaluate_transition(impl_ref: &mut Impl, ir: &Air) {
+///
+/// If `factor_selectors` is set, selectors shared by several constraints are evaluated once, see
+/// [add_constraints].
+pub(super) fn add_fn_evaluate_transition(
+    impl_ref: &mut Impl,
+    ir: &Air,
+    factor_selectors: bool,
+    comments: Option<&SourceComments>,
+) {
     // define the function.
     let evaluate_transition = impl_ref
         .new_fn("evaluate_transition")
@@ -22,15 +32,93 @@ pub(super) fn add_fn_evaluate_transition(impl_ref: &mut Impl, ir: &Air) {
     evaluate_transition.line("let main_next = frame.next();");
 
     // output the constraints.
-    add_constraints(evaluate_transition, ir, 0);
+    add_constraints(evaluate_transition, ir, 0, factor_selectors, comments);
 }
 
 /// Adds an implementation of the "evaluate_aux_transition" method to the referenced Air implementation
 /// based on the data in the provided IR.
-pub(super) fn add_fn_evaluate_aux_transition(impl_ref: &mut Impl, ir: &Air) {
+///
+/// Winterfell only supports a single auxiliary trace segment, so when the program declares more
+/// than one, the constraints of each are evaluated by a dedicated method (see
+/// [add_fn_evaluate_segment_transitions]) against its own slice of the result.
+pub(super) fn add_fn_evaluate_aux_transition(
+    impl_ref: &mut Impl,
+    ir: &Air,
+    factor_selectors: bool,
+    comments: Option<&SourceComments>,
+) {
     // define the function.
-    let evaluate_aux_transition = impl_ref
-        .new_fn("evaluate_aux_transition")
+    let evaluate_aux_transition = new_aux_transition_fn(impl_ref, "evaluate_aux_transition");
+
+    if ir.trace_segment_widths.len() <= 2 {
+        // declare current and next trace row arrays, and the random values.
+        declare_aux_frames(evaluate_aux_transition);
+        declare_rand_elements(evaluate_aux_transition, ir, ir.integrity_constraints(1));
+
+        // output the constraints.
+        add_constraints(evaluate_aux_transition, ir, 1, factor_selectors, comments);
+        return;
+    }
+
+    // delegate to the evaluation method of each auxiliary segment.
+    let mut offset = 0;
+    for trace_segment in 1..ir.trace_segment_widths.len() {
+        let num_constraints = ir.integrity_constraints(trace_segment).len();
+        evaluate_aux_transition.line(format!(
+            "self.{}(main_frame, aux_frame, _periodic_values, aux_rand_elements, &mut result[{}..{}]);",
+            segment_fn_name(trace_segment),
+            offset,
+            offset + num_constraints
+        ));
+        offset += num_constraints;
+    }
+}
+
+/// Adds a method evaluating the integrity constraints of each auxiliary trace segment to the
+/// referenced implementation, when the program declares more than one auxiliary segment.
+pub(super) fn add_fn_evaluate_segment_transitions(
+    impl_ref: &mut Impl,
+    ir: &Air,
+    factor_selectors: bool,
+    comments: Option<&SourceComments>,
+) {
+    if ir.trace_segment_widths.len() <= 2 {
+        return;
+    }
+
+    for trace_segment in 1..ir.trace_segment_widths.len() {
+        let evaluate_segment_transition =
+            new_aux_transition_fn(impl_ref, &segment_fn_name(trace_segment));
+
+        // declare current and next trace row arrays, and the random values.
+        declare_aux_frames(evaluate_segment_transition);
+        declare_rand_elements(
+            evaluate_segment_transition,
+            ir,
+            ir.integrity_constraints(trace_segment),
+        );
+
+        // output the constraints.
+        add_constraints(
+            evaluate_segment_transition,
+            ir,
+            trace_segment,
+            factor_selectors,
+            comments,
+        );
+    }
+}
+
+/// Returns the name of the method evaluating the integrity constraints of `trace_segment`.
+fn segment_fn_name(trace_segment: TraceSegmentId) -> String {
+    format!("evaluate_segment_{trace_segment}_transition")
+}
+
+/// Defines a new function with the signature of "evaluate_aux_transition" in the referenced
+/// implementation.
+fn new_aux_transition_fn<'a>(impl_ref: &'a mut Impl, name: &str) -> &'a mut codegen::Function {
+    impl_ref
+        .new_fn(name)
         .generic("F, E")
         .arg_ref_self()
         .arg("main_frame", "&EvaluationFrame<F>")
@@ -39,28 +127,104 @@ pub(super) fn add_fn_evaluate_aux_transition(impl_ref: &mut Impl, ir: &Air) {
         .arg("aux_rand_elements", "&AuxTraceRandElements<E>")
         .arg("result", "&mut [E]")
         .bound("F", "FieldElement<BaseField = Felt>")
-        .bound("E", "FieldElement<BaseField = Felt> + ExtensionOf<F>");
-
-    // declare current and next trace row arrays.
-    evaluate_aux_transition.line("let main_current = main_frame.current();");
-    evaluate_aux_transition.line("let main_next = main_frame.next();");
-    evaluate_aux_transition.line("let aux_current = aux_frame.current();");
-    evaluate_aux_transition.line("let aux_next = aux_frame.next();");
+        .bound("E", "FieldElement<BaseField = Felt> + ExtensionOf<F>")
+}
 
-    // output the constraints.
-    add_constraints(evaluate_aux_transition, ir, 1);
+/// Declares the current and next rows of the main and auxiliary trace frames.
+fn declare_aux_frames(func_body: &mut codegen::Function) {
+    func_body.line("let main_current = main_frame.current();");
+    func_body.line("let main_next = main_frame.next();");
+    func_body.line("let aux_current = aux_frame.current();");
+    func_body.line("let aux_next = aux_frame.next();");
 }
 
 /// Iterates through the integrity constraints in the IR, and appends a line of generated code to
 /// the provided codegen function body for each constraint.
-fn add_constraints(func_body: &mut codegen::Function, ir: &Air, trace_segment: TraceSegmentId) {
+///
+/// If `factor_selectors` is set, the selectors shared by several constraints, e.g. those of the
+/// constraints of an evaluator called with `when`, are bound to a variable which is evaluated once,
+/// and by which each of those constraints is multiplied, rather than being evaluated again for
+/// every constraint.
+fn add_constraints(
+    func_body: &mut codegen::Function,
+    ir: &Air,
+    trace_segment: TraceSegmentId,
+    factor_selectors: bool,
+    comments: Option<&SourceComments>,
+) {
+    let selectors = if factor_selectors {
+        shared_selectors(ir, trace_segment)
+    } else {
+        BTreeMap::new()
+    };
+    let mut declared = vec![false; selectors.len()];
+
     for (idx, constraint) in ir.integrity_constraints(trace_segment).iter().enumerate() {
-        func_body.line(format!(
-            "result[{}] = {};",
-            idx,
-            constraint
-                .node_index()
-                .to_string(ir, ElemType::Ext, trace_segment)
-        ));
+        let root = constraint.node_index();
+        let op = ir.constraint_graph().node(root).op();
+        let factored = match op {
+            Operation::Mul(body, selector) => selectors
+                .get(selector)
+                .map(|&selector_idx| (body, selector, selector_idx)),
+            _ => None,
+        };
+        let Some((body, selector, selector_idx)) = factored else {
+            let line = format!(
+                "result[{}] = {};",
+                idx,
+                root.to_string(ir, ElemType::Ext, trace_segment)
+            );
+            add_constraint_line(func_body, comments, constraint, line);
+            continue;
+        };
+
+        // declare the selector before the first constraint which uses it.
+        if !declared[selector_idx] {
+            declared[selector_idx] = true;
+            func_body.line(format!(
+                "let selector_{} = {};",
+                selector_idx,
+                selector.to_string(ir, ElemType::Ext, trace_segment)
+            ));
+        }
+        let body_str = body.to_string(ir, ElemType::Ext, trace_segment);
+        let body_str = if ir.constraint_graph().node(body).op().precedence() < op.precedence() {
+            format!("({body_str})")
+        } else {
+            body_str
+        };
+        let line = format!("result[{idx}] = {body_str} * selector_{selector_idx};");
+        add_constraint_line(func_body, comments, constraint, line);
     }
 }
+
+/// Returns the selectors which are shared by more than one of the integrity constraints of
+/// `trace_segment`, each mapped to the index of the variable to which it is bound, in order of
+/// first use.
+///
+/// A constraint enforced under a selector `s` is rooted at `(lhs - rhs) * s`, and identical
+/// subexpressions share a node of the graph, so the selectors of constraints are found by
+/// comparing the right-hand operands of such roots. Selectors which are leaves, e.g. a single
+/// trace column, are not worth binding, and are ignored.
+fn shared_selectors(ir: &Air, trace_segment: TraceSegmentId) -> BTreeMap<NodeIndex, usize> {
+    let graph = ir.constraint_graph();
+    let mut uses = Vec::<(NodeIndex, usize)>::new();
+    for constraint in ir.integrity_constraints(trace_segment) {
+        let Operation::Mul(_, selector) = graph.node(constraint.node_index()).op() else {
+            continue;
+        };
+        if matches!(graph.node(selector).op(), Operation::Value(_)) {
+            continue;
+        }
+        match uses.iter_mut().find(|(node, _)| node == selector) {
+            Some((_, count)) => *count += 1,
+            None => uses.push((*selector, 1)),
+        }
+    }
+
+    uses.into_iter()
+        .filter(|(_, count)| *count > 1)
+        .enumerate()
+        .map(|(idx, (selector, _))| (selector, idx))
+        .collect()
+}
@@ -1,7 +1,20 @@
+use air_ir::PrimeField;
+
 use super::Scope;
 
-/// Adds the required imports to the provided scope.
-pub(super) fn add_imports(scope: &mut Scope) {
+/// Returns the module of `winter_math` providing the base field element type for `field`, if
+/// Winterfell supports that field.
+pub(super) fn base_field_module(field: &PrimeField) -> Option<&'static str> {
+    match *field {
+        PrimeField::GOLDILOCKS => Some("winter_math::fields::f64"),
+        PrimeField::F62 => Some("winter_math::fields::f62"),
+        _ => None,
+    }
+}
+
+/// Adds the required imports to the provided scope, using `base_field` as the module from which
+/// the base field element type is imported.
+pub(super) fn add_imports(scope: &mut Scope, base_field: &str) {
     // add winterfell imports
     scope.import("winter_air", "Air");
     scope.import("winter_air", "AirContext");
@@ -11,7 +24,7 @@ pub(super) fn add_imports(scope: &mut Scope) {
     scope.import("winter_air", "ProofOptions as WinterProofOptions");
     scope.import("winter_air", "TransitionConstraintDegree");
     scope.import("winter_air", "TraceInfo");
-    scope.import("winter_math::fields::f64", "BaseElement as Felt");
+    scope.import(base_field, "BaseElement as Felt");
     scope.import("winter_math", "ExtensionOf");
     scope.import("winter_math", "FieldElement");
     scope.import("winter_utils::collections", "Vec");
@@ -1,27 +1,256 @@
-use air_ir::Air;
+use std::sync::Arc;
+
+use air_ir::{Air, Operation, Value};
 use codegen::{Impl, Scope};
+use miden_diagnostics::CodeMap;
 
 mod air;
 mod imports;
 
+/// The number of rows in a Winterfell evaluation frame, i.e. the current and next rows.
+const MAX_FRAME_SIZE: usize = 2;
+
 // GENERATE RUST CODE FOR WINTERFELL AIR
 // ================================================================================================
 
 /// CodeGenerator is used to generate a Rust implementation of the Winterfell STARK prover library's
 /// Air trait. The generated Air expresses the constraints specified by the AirIR used to build the
 /// CodeGenerator.
-pub struct CodeGenerator;
+///
+/// By default, all items are emitted into a single flattened scope. When modular output is
+/// enabled, items which originate from a specific AirScript module (such as periodic columns) are
+/// emitted into a Rust module of the same name, and referenced from the Air implementation using
+/// their module path. The names of these modules and items are converted to snake case, and
+/// suffixed with `_` where they would otherwise be Rust keywords, or clash with one another or
+/// with other items in the generated code.
+///
+/// When column constants are enabled, a `columns` module is emitted alongside the Air, declaring
+/// the index (or range of indices) of each named trace column binding, for use when building the
+/// execution trace.
+///
+/// When constraint count tests are enabled, a `#[cfg(test)]` module is emitted alongside the Air,
+/// asserting that its trace widths and numbers of constraints match those of the AirIR at the time
+/// the code was generated.
+///
+/// When selector factoring is enabled, selectors shared by several integrity constraints, e.g. the
+/// selector of an evaluator called with `when`, are evaluated once in the generated
+/// `evaluate_transition` methods and bound to a variable, by which each of those constraints is
+/// multiplied.
+///
+/// When doc comments are enabled, the `#` comments immediately preceding each constraint in the
+/// source are emitted as `//` comments above the code generated for the constraint. Similarly, when
+/// provenance comments are enabled, each line of code generated for a constraint ends with a
+/// `// from <file>:<line>` comment giving the location of the constraint in the source. Both
+/// require the [CodeMap] containing the sources to be provided using [CodeGenerator::with_codemap].
+///
+/// The Air struct is named after the AirScript program, and its public inputs struct is named
+/// `PublicInputs`. To include several generated Airs in the same crate without their names
+/// clashing, both names can be prefixed using [CodeGenerator::with_name_prefix], or replaced
+/// altogether using [CodeGenerator::with_air_name] and [CodeGenerator::with_public_inputs_name].
+#[derive(Debug, Default, Clone)]
+pub struct CodeGenerator {
+    modular: bool,
+    column_constants: bool,
+    constraint_count_tests: bool,
+    factor_selectors: bool,
+    doc_comments: bool,
+    provenance: bool,
+    codemap: Option<Arc<CodeMap>>,
+    name_prefix: Option<String>,
+    air_name: Option<String>,
+    public_inputs_name: Option<String>,
+}
+impl CodeGenerator {
+    /// Toggles between flattened (the default) and modular output.
+    pub fn with_modular_output(mut self, modular: bool) -> Self {
+        self.modular = modular;
+        self
+    }
+
+    /// Toggles the emission of a module declaring the indices of the named trace columns, which is
+    /// disabled by default.
+    pub fn with_column_constants(mut self, enabled: bool) -> Self {
+        self.column_constants = enabled;
+        self
+    }
+
+    /// Toggles the emission of a test module asserting the constraint counts of the generated Air,
+    /// which is disabled by default.
+    pub fn with_constraint_count_tests(mut self, enabled: bool) -> Self {
+        self.constraint_count_tests = enabled;
+        self
+    }
+
+    /// Toggles the factoring of selectors shared by several integrity constraints out of those
+    /// constraints, which is disabled by default.
+    pub fn with_factored_selectors(mut self, enabled: bool) -> Self {
+        self.factor_selectors = enabled;
+        self
+    }
+
+    /// Toggles the emission of the comments preceding each constraint in the source, which is
+    /// disabled by default.
+    pub fn with_doc_comments(mut self, enabled: bool) -> Self {
+        self.doc_comments = enabled;
+        self
+    }
+
+    /// Toggles the emission of the location of each constraint in the source after the code
+    /// generated for it, which is disabled by default.
+    pub fn with_provenance(mut self, enabled: bool) -> Self {
+        self.provenance = enabled;
+        self
+    }
+
+    /// Sets the [CodeMap] from which the sources of the constraints are read, when emitting
+    /// comments derived from them.
+    pub fn with_codemap(mut self, codemap: Arc<CodeMap>) -> Self {
+        self.codemap = Some(codemap);
+        self
+    }
+
+    /// Sets a prefix of the names of the Air struct and its public inputs struct, e.g. the prefix
+    /// `Foo` names them `FooExampleAir` and `FooPublicInputs`. Names set explicitly using
+    /// [CodeGenerator::with_air_name] or [CodeGenerator::with_public_inputs_name] are not prefixed.
+    pub fn with_name_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.name_prefix = Some(prefix.into());
+        self
+    }
+
+    /// Sets the name of the Air struct, which defaults to the name of the AirScript program.
+    pub fn with_air_name(mut self, name: impl Into<String>) -> Self {
+        self.air_name = Some(name.into());
+        self
+    }
+
+    /// Sets the name of the public inputs struct, which defaults to `PublicInputs`.
+    pub fn with_public_inputs_name(mut self, name: impl Into<String>) -> Self {
+        self.public_inputs_name = Some(name.into());
+        self
+    }
+
+    /// Returns the names of the structs emitted for `ir`, applying any overrides.
+    fn names(&self, ir: &Air) -> anyhow::Result<air::AirNames> {
+        let prefix = self.name_prefix.as_deref().unwrap_or_default();
+        let names = air::AirNames {
+            air: self
+                .air_name
+                .clone()
+                .unwrap_or_else(|| format!("{prefix}{}", ir.name())),
+            public_inputs: self
+                .public_inputs_name
+                .clone()
+                .unwrap_or_else(|| format!("{prefix}PublicInputs")),
+        };
+        for name in [&names.air, &names.public_inputs] {
+            if !is_identifier(name) {
+                anyhow::bail!("`{name}` is not a valid name for a Rust struct");
+            }
+        }
+        if names.air == names.public_inputs {
+            anyhow::bail!(
+                "the Air struct and its public inputs struct are both named `{}`",
+                names.air
+            );
+        }
+        Ok(names)
+    }
+}
+
+/// Returns true if `name` is a valid Rust identifier, ignoring keywords, which are lowercase and so
+/// never clash with the names of structs in practice.
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && name != "_"
+}
 impl air_ir::CodeGenerator for CodeGenerator {
     type Output = String;
 
     fn generate(&self, ir: &Air) -> anyhow::Result<Self::Output> {
+        // Winterfell evaluation frames only consist of the current and next rows
+        let frame_size = (0..ir.trace_segment_widths.len())
+            .flat_map(|trace_segment| ir.integrity_constraints(trace_segment))
+            .map(|constraint| constraint.domain().frame_size())
+            .max()
+            .unwrap_or(1);
+        if frame_size > MAX_FRAME_SIZE {
+            anyhow::bail!(
+                "row offsets greater than 1 are not supported by the Winterfell backend: Winterfell only evaluates transition constraints over frames of {MAX_FRAME_SIZE} rows, but constraints are evaluated over frames of {frame_size} rows"
+            );
+        }
+
+        // Winterfell exempts the last rows of the trace from every transition constraint, using a
+        // single divisor for all of them, so constraints cannot be applied to every row
+        if let Some(domain) = (0..ir.trace_segment_widths.len())
+            .flat_map(|trace_segment| ir.integrity_constraints(trace_segment))
+            .map(|constraint| constraint.domain())
+            .find(|domain| domain.is_cyclic())
+        {
+            anyhow::bail!(
+                "constraints marked `cyclic` are not supported by the Winterfell backend: Winterfell exempts at least the last row of the trace from all transition constraints, but a constraint is applied to {domain}"
+            );
+        }
+
+        // Random values are read from an array of the size declared by the program, so an access
+        // beyond its end would only be caught when the generated code is compiled
+        let num_random_values = ir.num_random_values as usize;
+        if let Some(index) = ir
+            .constraint_graph()
+            .nodes()
+            .find_map(|(_, node)| match node.op() {
+                Operation::Value(Value::RandomValue(index)) if *index >= num_random_values => {
+                    Some(*index)
+                }
+                _ => None,
+            })
+        {
+            anyhow::bail!(
+                "a constraint references the random value at index {index}, but only {num_random_values} random values are declared"
+            );
+        }
+
+        let Some(base_field) = imports::base_field_module(&ir.field) else {
+            anyhow::bail!(
+                "constraints are expressed over {}, but Winterfell only supports the f64 (Goldilocks) and f62 fields",
+                ir.field
+            );
+        };
+
+        let comments = if self.doc_comments || self.provenance {
+            let Some(codemap) = self.codemap.as_deref() else {
+                anyhow::bail!(
+                    "emitting comments derived from the sources requires the codemap containing them"
+                );
+            };
+            Some(air::SourceComments::new(
+                codemap,
+                self.doc_comments,
+                self.provenance,
+            ))
+        } else {
+            None
+        };
+
+        let names = self.names(ir)?;
+
         let mut scope = Scope::new();
 
         // add winterfell imports.
-        imports::add_imports(&mut scope);
+        imports::add_imports(&mut scope, base_field);
 
         // add an Air struct and Winterfell Air trait implementation for the provided AirIR.
-        air::add_air(&mut scope, ir);
+        air::add_air(
+            &mut scope,
+            ir,
+            &names,
+            self.modular,
+            self.column_constants,
+            self.constraint_count_tests,
+            self.factor_selectors,
+            comments.as_ref(),
+        );
 
         Ok(scope.to_string())
     }
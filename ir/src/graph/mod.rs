@@ -6,7 +6,7 @@ use crate::ir::*;
 ///
 /// The raw value of this identifier is an index in the `nodes` vector
 /// of the [AlgebraicGraph] struct.
-#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct NodeIndex(usize);
 impl core::ops::Add<usize> for NodeIndex {
     type Output = NodeIndex;
@@ -23,6 +23,9 @@ impl core::ops::Add<usize> for &NodeIndex {
     }
 }
 
+/// The base degree of a subgraph, and the cycle lengths of the periodic columns it references
+type DegreeInfo = (usize, BTreeMap<QualifiedIdentifier, usize>);
+
 /// A node in the [AlgebraicGraph]
 #[derive(Debug, Clone)]
 pub struct Node {
@@ -70,15 +73,112 @@ impl AlgebraicGraph {
         self.nodes.len()
     }
 
+    /// Returns an iterator over all of the nodes in the graph, along with their indices, in the
+    /// order in which they were inserted.
+    ///
+    /// Since nodes may only refer to nodes inserted before them, the children of each node are
+    /// visited before the node itself.
+    pub fn nodes(&self) -> impl ExactSizeIterator<Item = (NodeIndex, &Node)> + '_ {
+        self.nodes
+            .iter()
+            .enumerate()
+            .map(|(index, node)| (NodeIndex(index), node))
+    }
+
+    /// Returns an iterator over the tip nodes of the graph, i.e. the nodes which are not the child
+    /// of any other node, along with their indices, in the order in which they were inserted.
+    ///
+    /// Every tip node represents a constraint, but not every constraint is a tip node, since a
+    /// constraint may also be a subgraph of another constraint. Use [crate::Air::boundary_constraints]
+    /// and [crate::Air::integrity_constraints] to visit the roots of all of the constraints.
+    pub fn roots(&self) -> impl Iterator<Item = (NodeIndex, &Node)> + '_ {
+        let mut has_parent = vec![false; self.nodes.len()];
+        for node in self.nodes.iter() {
+            for child in node.op().children() {
+                has_parent[child.0] = true;
+            }
+        }
+        self.nodes().filter(move |(index, _)| !has_parent[index.0])
+    }
+
     /// Returns the degree of the subgraph which has the specified node as its tip.
     pub fn degree(&self, index: &NodeIndex) -> IntegrityConstraintDegree {
-        let mut cycles = BTreeMap::default();
-        let base = self.accumulate_degree(&mut cycles, index);
+        self.degrees([index]).pop().unwrap()
+    }
+
+    /// Returns the degrees of the subgraphs which have the specified nodes as their tips.
+    ///
+    /// This produces the same results as calling [AlgebraicGraph::degree] for each node, but the
+    /// degree of each node is computed only once, and reused by all of the subgraphs sharing it.
+    pub fn degrees<'a, I>(&self, indices: I) -> Vec<IntegrityConstraintDegree>
+    where
+        I: IntoIterator<Item = &'a NodeIndex>,
+    {
+        let mut memo = BTreeMap::default();
+        indices
+            .into_iter()
+            .map(|index| {
+                let (base, cycles) = self.memoized_degree(&mut memo, index);
+                if cycles.is_empty() {
+                    IntegrityConstraintDegree::new(base)
+                } else {
+                    IntegrityConstraintDegree::with_cycles(base, cycles.into_values().collect())
+                }
+            })
+            .collect()
+    }
+
+    /// Asserts that this graph is acyclic, as is required by all of the recursive traversals of it.
+    ///
+    /// Every node is visited depth-first, and if an edge back to a node which is still being
+    /// visited is found, this panics with the path of node indices which forms the cycle.
+    pub fn assert_acyclic(&self) {
+        #[derive(Copy, Clone, PartialEq, Eq)]
+        enum Visit {
+            Pending,
+            InProgress,
+            Done,
+        }
+
+        let mut visits = vec![Visit::Pending; self.nodes.len()];
+        for root in 0..self.nodes.len() {
+            if visits[root] != Visit::Pending {
+                continue;
+            }
 
-        if cycles.is_empty() {
-            IntegrityConstraintDegree::new(base)
-        } else {
-            IntegrityConstraintDegree::with_cycles(base, cycles.values().copied().collect())
+            // the path from the root to the node being visited, along with the number of
+            // children of each node on the path which have been visited so far
+            let mut path = vec![(NodeIndex(root), 0)];
+            visits[root] = Visit::InProgress;
+            while let Some((index, visited)) = path.last_mut() {
+                let children = self.node(index).op().children();
+                match children.get(*visited).copied() {
+                    Some(child) => {
+                        *visited += 1;
+                        match visits[child.0] {
+                            Visit::Pending => {
+                                visits[child.0] = Visit::InProgress;
+                                path.push((child, 0));
+                            }
+                            Visit::InProgress => {
+                                let start = path.iter().position(|(i, _)| *i == child).unwrap();
+                                let cycle = path[start..]
+                                    .iter()
+                                    .map(|(i, _)| i.0)
+                                    .chain(core::iter::once(child.0))
+                                    .map(|i| i.to_string())
+                                    .collect::<Vec<_>>();
+                                panic!("cycle detected in algebraic graph: {}", cycle.join(" -> "));
+                            }
+                            Visit::Done => (),
+                        }
+                    }
+                    None => {
+                        visits[index.0] = Visit::Done;
+                        path.pop();
+                    }
+                }
+            }
         }
     }
 
@@ -91,11 +191,11 @@ impl AlgebraicGraph {
         // recursively walk the subgraph and infer the trace segment and domain
         match self.node(index).op() {
             Operation::Value(value) => match value {
-                Value::Constant(_) => Ok((DEFAULT_SEGMENT, default_domain)),
+                Value::Constant(_) | Value::TraceLen => Ok((DEFAULT_SEGMENT, default_domain)),
                 Value::PeriodicColumn(_) => {
                     assert!(
                         !default_domain.is_boundary(),
-                        "unexpected access to periodic column in boundary constraint"
+                        "periodic columns in boundary constraints should have been substituted"
                     );
                     // the default domain for [IntegrityConstraints] is `EveryRow`
                     Ok((DEFAULT_SEGMENT, ConstraintDomain::EveryRow))
@@ -152,41 +252,63 @@ impl AlgebraicGraph {
         )
     }
 
-    /// Recursively accumulates the base degree and the cycle lengths of the periodic columns.
-    fn accumulate_degree(
+    /// Removes all of the nodes which were inserted after the graph had `len` nodes.
+    ///
+    /// This is used to discard subgraphs which are only built for inspection, and so must not be
+    /// referenced by any of the nodes which are retained.
+    pub(crate) fn truncate(&mut self, len: usize) {
+        self.nodes.truncate(len);
+    }
+
+    /// Insert the operation as a new node and return its node index, even if an identical node
+    /// already exists.
+    pub(crate) fn push_node(&mut self, op: Operation) -> NodeIndex {
+        let index = self.nodes.len();
+        self.nodes.push(Node { op });
+        NodeIndex(index)
+    }
+
+    /// Computes the base degree and the cycle lengths of the periodic columns of the subgraph
+    /// with the specified node as its tip, reusing the results recorded in `memo` for any nodes
+    /// which have already been visited.
+    fn memoized_degree(
         &self,
-        cycles: &mut BTreeMap<QualifiedIdentifier, usize>,
+        memo: &mut BTreeMap<NodeIndex, DegreeInfo>,
         index: &NodeIndex,
-    ) -> usize {
-        // recursively walk the subgraph and compute the degree from the operation and child nodes
-        match self.node(index).op() {
+    ) -> DegreeInfo {
+        if let Some(info) = memo.get(index) {
+            return info.clone();
+        }
+
+        let info = match self.node(index).op() {
             Operation::Value(value) => match value {
-                Value::Constant(_) | Value::RandomValue(_) | Value::PublicInput(_) => 0,
-                Value::TraceAccess(_) => 1,
-                Value::PeriodicColumn(pc) => {
-                    cycles.insert(pc.name, pc.cycle);
-                    0
-                }
+                Value::Constant(_)
+                | Value::RandomValue(_)
+                | Value::PublicInput(_)
+                | Value::TraceLen => (0, BTreeMap::default()),
+                Value::TraceAccess(_) => (1, BTreeMap::default()),
+                Value::PeriodicColumn(pc) => (0, BTreeMap::from([(pc.name, pc.cycle)])),
             },
-            Operation::Add(lhs, rhs) => {
-                let lhs_base = self.accumulate_degree(cycles, lhs);
-                let rhs_base = self.accumulate_degree(cycles, rhs);
-                lhs_base.max(rhs_base)
-            }
-            Operation::Sub(lhs, rhs) => {
-                let lhs_base = self.accumulate_degree(cycles, lhs);
-                let rhs_base = self.accumulate_degree(cycles, rhs);
-                lhs_base.max(rhs_base)
+            Operation::Add(lhs, rhs) | Operation::Sub(lhs, rhs) => {
+                let (lhs_base, mut cycles) = self.memoized_degree(memo, lhs);
+                let (rhs_base, rhs_cycles) = self.memoized_degree(memo, rhs);
+                cycles.extend(rhs_cycles);
+                (lhs_base.max(rhs_base), cycles)
             }
             Operation::Mul(lhs, rhs) => {
-                let lhs_base = self.accumulate_degree(cycles, lhs);
-                let rhs_base = self.accumulate_degree(cycles, rhs);
-                lhs_base + rhs_base
+                let (lhs_base, mut cycles) = self.memoized_degree(memo, lhs);
+                let (rhs_base, rhs_cycles) = self.memoized_degree(memo, rhs);
+                cycles.extend(rhs_cycles);
+                (lhs_base + rhs_base, cycles)
             }
             Operation::Exp(lhs, rhs) => {
-                let lhs_base = self.accumulate_degree(cycles, lhs);
-                lhs_base * rhs
+                debug_assert!(*rhs > 1, "expected trivial exponents to have been folded");
+                let (lhs_base, cycles) = self.memoized_degree(memo, lhs);
+                (lhs_base * rhs, cycles)
             }
-        }
+        };
+
+        memo.insert(*index, info.clone());
+        info
     }
 }
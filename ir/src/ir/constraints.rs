@@ -24,6 +24,18 @@ pub enum ConstraintError {
 /// For example, integrity constraints for the main execution trace, which has a trace segment
 /// id of 0, will be specified by the vector of constraint roots found at index 0 of the
 /// `integrity_constraints` matrix.
+///
+/// # Ordering
+///
+/// The order of the constraints is deterministic, and backends emit constraints in this order,
+/// so that compiling the same source always produces identical output. Constraints are ordered by
+/// trace segment, and then by declaration order within the `boundary_constraints` or
+/// `integrity_constraints` section of the source, where:
+///
+/// * The constraints produced by a call to an evaluator take the place of the call, in the order
+///   in which they are declared in the body of the evaluator.
+/// * The constraints produced by a constraint comprehension or a `match` take the place of the
+///   comprehension, in iteration order, or in the order of the match arms, respectively.
 #[derive(Default, Debug)]
 pub struct Constraints {
     /// Constraint roots for all boundary constraints against the execution trace, by trace segment,
@@ -80,10 +92,11 @@ impl Constraints {
             return vec![];
         }
 
-        self.integrity_constraints[trace_segment]
-            .iter()
-            .map(|entry_index| self.graph.degree(entry_index.node_index()))
-            .collect()
+        self.graph.degrees(
+            self.integrity_constraints[trace_segment]
+                .iter()
+                .map(|entry_index| entry_index.node_index()),
+        )
     }
 
     /// Returns the set of integrity constraints for the given trace segment.
@@ -98,14 +111,23 @@ impl Constraints {
         &self.integrity_constraints[trace_segment]
     }
 
-    /// Inserts a new constraint against `trace_segment`, using the provided `root` and `domain`
+    /// Inserts a new constraint against `trace_segment`, using the provided `root` and `domain`,
+    /// and carrying the `metadata` given by the attributes of the constraint, if any, as well as
+    /// the `span` of the constraint in the source, which may be [SourceSpan::UNKNOWN].
+    ///
+    /// The constraint is appended to the constraints of its kind for that segment, so constraints
+    /// must be inserted in declaration order, see [Constraints].
     pub fn insert_constraint(
         &mut self,
         trace_segment: TraceSegmentId,
         root: NodeIndex,
         domain: ConstraintDomain,
+        metadata: BTreeMap<String, String>,
+        span: SourceSpan,
     ) {
-        let root = ConstraintRoot::new(root, domain);
+        let root = ConstraintRoot::new(root, domain)
+            .with_metadata(metadata)
+            .with_span(span);
         if domain.is_boundary() {
             if self.boundary_constraints.len() <= trace_segment {
                 self.boundary_constraints.resize(trace_segment + 1, vec![]);
@@ -134,16 +156,40 @@ impl Constraints {
 
 /// A [ConstraintRoot] represents the entry node of a subgraph within the [AlgebraicGraph]
 /// representing a constraint. It also contains the [ConstraintDomain] for the constraint, which is
-/// the domain against which the constraint should be applied.
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// the domain against which the constraint should be applied, and the metadata given by the
+/// attributes of the constraint in the source, e.g. `@[name="range_check"]`.
+///
+/// The span of the constraint in the source is also recorded, for use in diagnostics, but it is
+/// [SourceSpan::UNKNOWN] for constraints which were not derived from source code, and it is
+/// ignored when comparing constraints.
+#[derive(Debug, Clone)]
 pub struct ConstraintRoot {
     index: NodeIndex,
     domain: ConstraintDomain,
+    metadata: BTreeMap<String, String>,
+    span: SourceSpan,
 }
 impl ConstraintRoot {
     /// Creates a new [ConstraintRoot] with the specified entry index and row offset.
     pub const fn new(index: NodeIndex, domain: ConstraintDomain) -> Self {
-        Self { index, domain }
+        Self {
+            index,
+            domain,
+            metadata: BTreeMap::new(),
+            span: SourceSpan::UNKNOWN,
+        }
+    }
+
+    /// Sets the span of the constraint in the source
+    pub fn with_span(mut self, span: SourceSpan) -> Self {
+        self.span = span;
+        self
+    }
+
+    /// Sets the metadata of this constraint
+    pub fn with_metadata(mut self, metadata: BTreeMap<String, String>) -> Self {
+        self.metadata = metadata;
+        self
     }
 
     /// Returns the index of the entry node of the subgraph representing the constraint.
@@ -156,6 +202,23 @@ impl ConstraintRoot {
     pub const fn domain(&self) -> ConstraintDomain {
         self.domain
     }
+
+    /// Returns the metadata of this constraint, i.e. its attributes, keyed by name.
+    pub const fn metadata(&self) -> &BTreeMap<String, String> {
+        &self.metadata
+    }
+
+    /// Returns the span of the constraint in the source, or [SourceSpan::UNKNOWN] if it was not
+    /// derived from source code.
+    pub const fn span(&self) -> SourceSpan {
+        self.span
+    }
+}
+impl Eq for ConstraintRoot {}
+impl PartialEq for ConstraintRoot {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index && self.domain == other.domain && self.metadata == other.metadata
+    }
 }
 
 /// [ConstraintDomain] corresponds to the domain over which a constraint is applied.
@@ -167,6 +230,8 @@ pub enum ConstraintDomain {
     FirstRow,
     /// For boundary constraints which apply to the last row
     LastRow,
+    /// For boundary constraints which apply to the row the given number of rows before the last row
+    BeforeLastRow(usize),
     /// For constraints which apply to every row of the trace
     ///
     /// This is used for validity constraints
@@ -181,16 +246,42 @@ pub enum ConstraintDomain {
     ///
     /// This is used for transition constraints.
     EveryFrame(usize),
+    /// For integrity constraints, observing frames of the given size, which are marked `cyclic`.
+    ///
+    /// Backends usually exempt the last rows of the trace from integrity constraints, so that
+    /// frames never extend past the end of the trace. Constraints in this domain are instead
+    /// applied to every row without exemption, i.e. frames starting at the last rows of the trace
+    /// wrap around to its first rows, and are divided by the vanishing polynomial of the whole
+    /// trace domain.
+    Cyclic(usize),
 }
 impl ConstraintDomain {
     /// Returns true if this domain is a boundary domain (e.g. first or last)
     pub fn is_boundary(&self) -> bool {
-        matches!(self, Self::FirstRow | Self::LastRow)
+        matches!(
+            self,
+            Self::FirstRow | Self::LastRow | Self::BeforeLastRow(_)
+        )
     }
 
     /// Returns true if this domain is an integrity constraint domain.
     pub fn is_integrity(&self) -> bool {
-        matches!(self, Self::EveryRow | Self::EveryFrame(_))
+        matches!(self, Self::EveryRow | Self::EveryFrame(_) | Self::Cyclic(_))
+    }
+
+    /// Returns true if this domain is an integrity constraint domain which is applied to every
+    /// row of the trace, without exempting its last rows.
+    pub fn is_cyclic(&self) -> bool {
+        matches!(self, Self::Cyclic(_))
+    }
+
+    /// Returns the number of consecutive rows against which constraints in this domain are
+    /// evaluated, i.e. the largest row offset accessed by those constraints, plus one.
+    pub fn frame_size(&self) -> usize {
+        match self {
+            Self::EveryFrame(size) | Self::Cyclic(size) => *size,
+            Self::FirstRow | Self::LastRow | Self::BeforeLastRow(_) | Self::EveryRow => 1,
+        }
     }
 
     /// Returns a [ConstraintDomain] corresponding to the given row offset.
@@ -230,6 +321,7 @@ impl From<Boundary> for ConstraintDomain {
         match boundary {
             Boundary::First => Self::FirstRow,
             Boundary::Last => Self::LastRow,
+            Boundary::BeforeLast(offset) => Self::BeforeLastRow(offset),
         }
     }
 }
@@ -238,10 +330,17 @@ impl fmt::Display for ConstraintDomain {
         match self {
             Self::FirstRow => write!(f, "the first row"),
             Self::LastRow => write!(f, "the last row"),
+            Self::BeforeLastRow(offset) => write!(f, "the row `last - {offset}`"),
             Self::EveryRow => write!(f, "every row"),
             Self::EveryFrame(size) => {
                 write!(f, "every frame of {size} consecutive rows")
             }
+            Self::Cyclic(size) => {
+                write!(
+                    f,
+                    "every frame of {size} consecutive rows, wrapping around the trace"
+                )
+            }
         }
     }
 }
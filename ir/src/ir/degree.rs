@@ -30,6 +30,14 @@ impl IntegrityConstraintDegree {
         &self.cycles
     }
 
+    /// Returns the degree of this constraint, as a multiple of the trace length, rounded up.
+    ///
+    /// Each periodic column involved in the constraint contributes less than a trace column to
+    /// the degree, so it is counted as one additional multiplication of trace columns.
+    pub fn degree(&self) -> usize {
+        self.base + self.cycles.len()
+    }
+
     /// Creates a new integrity constraint degree descriptor for constraints which involve
     /// multiplications of trace columns only.
     ///
@@ -0,0 +1,187 @@
+use core::fmt;
+
+use air_parser::ast::TraceBinding;
+
+use crate::graph::NodeIndex;
+
+use super::*;
+
+/// A human-readable listing of the constraints of an [Air], with each constraint printed in
+/// infix form as reconstructed from the constraint graph, e.g.
+/// `integrity[0] (segment main, degree 2): a' - a * b = 0`.
+///
+/// Since the listing is derived from the graph, rather than the source, it reflects the effect
+/// of constant propagation, inlining, and the expansion of comprehensions.
+///
+/// See [Air::explain].
+pub struct AirExplanation<'a> {
+    air: &'a Air,
+}
+
+impl<'a> AirExplanation<'a> {
+    pub(super) fn new(air: &'a Air) -> Self {
+        Self { air }
+    }
+
+    /// Writes the subgraph rooted at `index` in infix form, parenthesized if its precedence is
+    /// lower than `min_precedence`
+    fn fmt_node(
+        &self,
+        f: &mut fmt::Formatter,
+        index: &NodeIndex,
+        min_precedence: u8,
+    ) -> fmt::Result {
+        let op = self.air.constraint_graph().node(index).op();
+        let precedence = match op {
+            Operation::Value(_) => 4,
+            Operation::Exp(_, _) => 3,
+            Operation::Mul(_, _) => 2,
+            Operation::Add(_, _) | Operation::Sub(_, _) => 1,
+        };
+        if precedence < min_precedence {
+            f.write_str("(")?;
+        }
+        match op {
+            Operation::Value(value) => self.fmt_value(f, value)?,
+            Operation::Add(lhs, rhs) => {
+                self.fmt_node(f, lhs, 1)?;
+                f.write_str(" + ")?;
+                self.fmt_node(f, rhs, 1)?;
+            }
+            // Subtraction is not associative, so the right-hand side is parenthesized if it is
+            // itself a sum or difference
+            Operation::Sub(lhs, rhs) => {
+                self.fmt_node(f, lhs, 1)?;
+                f.write_str(" - ")?;
+                self.fmt_node(f, rhs, 2)?;
+            }
+            Operation::Mul(lhs, rhs) => {
+                self.fmt_node(f, lhs, 2)?;
+                f.write_str(" * ")?;
+                self.fmt_node(f, rhs, 2)?;
+            }
+            Operation::Exp(base, exp) => {
+                self.fmt_node(f, base, 4)?;
+                write!(f, "^{exp}")?;
+            }
+        }
+        if precedence < min_precedence {
+            f.write_str(")")?;
+        }
+        Ok(())
+    }
+
+    /// Writes `value` using the name it was bound to in the program, where possible
+    fn fmt_value(&self, f: &mut fmt::Formatter, value: &Value) -> fmt::Result {
+        match value {
+            Value::Constant(value) => write!(f, "{value}"),
+            Value::TraceAccess(access) => {
+                let segment = &self.air.trace_columns[access.segment];
+                let binding = segment.bindings.iter().find(|binding| {
+                    (binding.offset..binding.offset + binding.size).contains(&access.column)
+                });
+                match binding {
+                    Some(
+                        binding @ TraceBinding {
+                            name: Some(name), ..
+                        },
+                    ) => {
+                        write!(f, "{name}")?;
+                        if binding.size > 1 {
+                            write!(f, "[{}]", access.column - binding.offset)?;
+                        }
+                    }
+                    _ => write!(f, "{}[{}]", segment.name, access.column)?,
+                }
+                for _ in 0..access.row_offset {
+                    f.write_str("'")?;
+                }
+                Ok(())
+            }
+            Value::PeriodicColumn(access) => write!(f, "{}", access.name.item),
+            Value::PublicInput(access) => write!(f, "{}[{}]", access.name, access.index),
+            Value::TraceLen => f.write_str("$trace_len"),
+            Value::RandomValue(index) => {
+                let random_values = self
+                    .air
+                    .random_values
+                    .as_ref()
+                    .expect("reference to random value in program without random values");
+                let binding = random_values.bindings.iter().find(|binding| {
+                    (binding.offset..binding.offset + binding.size).contains(index)
+                });
+                match binding {
+                    Some(binding) if binding.size > 1 => {
+                        write!(f, "{}[{}]", binding.name, index - binding.offset)
+                    }
+                    Some(binding) => write!(f, "{}", binding.name),
+                    None => write!(f, "{}[{}]", random_values.name, index),
+                }
+            }
+        }
+    }
+}
+
+impl<'a> fmt::Display for AirExplanation<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let air = self.air;
+        let mut first = true;
+        for segment in 0..air.trace_segment_widths.len() {
+            let name = air.trace_segment_name(segment);
+            for (index, constraint) in air.boundary_constraints(segment).iter().enumerate() {
+                if !first {
+                    writeln!(f)?;
+                }
+                first = false;
+                let row = match constraint.domain() {
+                    ConstraintDomain::FirstRow => "first row".to_string(),
+                    ConstraintDomain::LastRow => "last row".to_string(),
+                    ConstraintDomain::BeforeLastRow(offset) => format!("row last - {offset}"),
+                    domain => unreachable!("invalid boundary constraint domain: {domain}"),
+                };
+                write!(f, "boundary[{index}] (segment {name}, {row}): ")?;
+                self.fmt_node(f, constraint.node_index(), 0)?;
+                f.write_str(" = 0")?;
+            }
+            let degrees = air.integrity_constraint_degrees(segment);
+            for (index, constraint) in air.integrity_constraints(segment).iter().enumerate() {
+                if !first {
+                    writeln!(f)?;
+                }
+                first = false;
+                write!(
+                    f,
+                    "integrity[{index}] (segment {name}, degree {}",
+                    degrees[index].base()
+                )?;
+                if constraint.domain().is_cyclic() {
+                    f.write_str(", cyclic")?;
+                }
+                f.write_str("): ")?;
+                self.fmt_node(f, constraint.node_index(), 0)?;
+                f.write_str(" = 0")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Displays the subgraph rooted at a single node of the constraint graph of an [Air], in the same
+/// infix form as [AirExplanation]
+pub(crate) struct DisplayNode<'a> {
+    explanation: AirExplanation<'a>,
+    index: NodeIndex,
+}
+impl<'a> DisplayNode<'a> {
+    pub(crate) fn new(air: &'a Air, index: NodeIndex) -> Self {
+        Self {
+            explanation: AirExplanation::new(air),
+            index,
+        }
+    }
+}
+impl<'a> fmt::Display for DisplayNode<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.explanation.fmt_node(f, &self.index, 0)
+    }
+}
@@ -0,0 +1,189 @@
+use std::collections::HashMap;
+
+use super::*;
+
+/// The FNV-1a offset basis, for 64-bit hashes
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+/// The FNV-1a prime, for 64-bit hashes
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Computes the fingerprint of an [Air], see [Air::fingerprint].
+///
+/// The fingerprint is computed using FNV-1a, rather than [std::hash::Hasher], as the latter makes
+/// no guarantee that its output is stable across releases of Rust. For the same reason, names are
+/// hashed as strings, rather than by their interned [Symbol], whose value depends on the order in
+/// which symbols are interned.
+pub(super) fn fingerprint(air: &Air) -> u64 {
+    let mut hasher = Fingerprinter::default();
+    hasher.write_str(air.name());
+    hasher.write_u64(air.field.modulus());
+
+    hasher.write_usize(air.trace_segment_widths.len());
+    for width in air.trace_segment_widths.iter() {
+        hasher.write_u64(*width as u64);
+    }
+
+    let mut periodic_columns = air
+        .periodic_columns
+        .iter()
+        .map(|(name, column)| (name.to_string(), column.values.as_slice()))
+        .collect::<Vec<_>>();
+    periodic_columns.sort();
+    hasher.write_usize(periodic_columns.len());
+    for (name, values) in periodic_columns {
+        hasher.write_str(&name);
+        hasher.write_usize(values.len());
+        for value in values {
+            hasher.write_u64(*value);
+        }
+    }
+
+    let mut public_inputs = air
+        .public_inputs()
+        .map(|input| (input.name.as_str(), input.size, input.columns))
+        .collect::<Vec<_>>();
+    public_inputs.sort();
+    hasher.write_usize(public_inputs.len());
+    for (name, size, columns) in public_inputs {
+        hasher.write_str(name);
+        hasher.write_usize(size);
+        hasher.write_usize(columns.map_or(0, |columns| columns + 1));
+    }
+
+    hasher.write_u64(air.num_random_values as u64);
+
+    // The children of a node always precede it in the graph, so the hash of each node can be
+    // computed from those of its children in a single pass
+    let graph = air.constraint_graph();
+    let mut node_hashes = HashMap::with_capacity(graph.num_nodes());
+    for (index, node) in graph.nodes() {
+        let mut node_hasher = Fingerprinter::default();
+        match node.op() {
+            Operation::Value(value) => {
+                node_hasher.write_u64(0);
+                hash_value(&mut node_hasher, value);
+            }
+            Operation::Add(lhs, rhs) => {
+                node_hasher.write_u64(1);
+                node_hasher.write_u64(node_hashes[lhs]);
+                node_hasher.write_u64(node_hashes[rhs]);
+            }
+            Operation::Sub(lhs, rhs) => {
+                node_hasher.write_u64(2);
+                node_hasher.write_u64(node_hashes[lhs]);
+                node_hasher.write_u64(node_hashes[rhs]);
+            }
+            Operation::Mul(lhs, rhs) => {
+                node_hasher.write_u64(3);
+                node_hasher.write_u64(node_hashes[lhs]);
+                node_hasher.write_u64(node_hashes[rhs]);
+            }
+            Operation::Exp(base, exp) => {
+                node_hasher.write_u64(4);
+                node_hasher.write_u64(node_hashes[base]);
+                node_hasher.write_usize(*exp);
+            }
+        }
+        node_hashes.insert(index, node_hasher.finish());
+    }
+
+    for segment in 0..air.trace_segment_widths.len() {
+        for constraints in [
+            air.boundary_constraints(segment),
+            air.integrity_constraints(segment),
+        ] {
+            hasher.write_usize(constraints.len());
+            for constraint in constraints {
+                hash_domain(&mut hasher, constraint.domain());
+                hasher.write_u64(node_hashes[constraint.node_index()]);
+                hasher.write_usize(constraint.metadata().len());
+                for (key, value) in constraint.metadata() {
+                    hasher.write_str(key);
+                    hasher.write_str(value);
+                }
+            }
+        }
+    }
+
+    hasher.finish()
+}
+
+fn hash_value(hasher: &mut Fingerprinter, value: &Value) {
+    match value {
+        Value::Constant(value) => {
+            hasher.write_u64(0);
+            hasher.write_u64(*value);
+        }
+        Value::TraceAccess(access) => {
+            hasher.write_u64(1);
+            hasher.write_usize(access.segment);
+            hasher.write_usize(access.column);
+            hasher.write_usize(access.row_offset);
+        }
+        Value::PeriodicColumn(access) => {
+            hasher.write_u64(2);
+            hasher.write_str(&access.name.to_string());
+            hasher.write_usize(access.cycle);
+        }
+        Value::PublicInput(access) => {
+            hasher.write_u64(3);
+            hasher.write_str(access.name.as_str());
+            hasher.write_usize(access.index);
+        }
+        Value::RandomValue(index) => {
+            hasher.write_u64(4);
+            hasher.write_usize(*index);
+        }
+        Value::TraceLen => {
+            hasher.write_u64(5);
+        }
+    }
+}
+
+fn hash_domain(hasher: &mut Fingerprinter, domain: ConstraintDomain) {
+    let (tag, size) = match domain {
+        ConstraintDomain::FirstRow => (0, 0),
+        ConstraintDomain::LastRow => (1, 0),
+        ConstraintDomain::BeforeLastRow(offset) => (2, offset),
+        ConstraintDomain::EveryRow => (3, 0),
+        ConstraintDomain::EveryFrame(size) => (4, size),
+        ConstraintDomain::Cyclic(size) => (5, size),
+    };
+    hasher.write_u64(tag);
+    hasher.write_usize(size);
+}
+
+/// A 64-bit FNV-1a hasher, which is stable across platforms and releases of Rust
+struct Fingerprinter(u64);
+impl Default for Fingerprinter {
+    fn default() -> Self {
+        Self(FNV_OFFSET_BASIS)
+    }
+}
+impl Fingerprinter {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    fn write_u64(&mut self, value: u64) {
+        self.write(&value.to_le_bytes());
+    }
+
+    /// Sizes are hashed as 64-bit values, so that the hash does not depend on the platform
+    fn write_usize(&mut self, value: usize) {
+        self.write_u64(value as u64);
+    }
+
+    /// Strings are prefixed with their length, so that consecutive strings cannot be confused
+    fn write_str(&mut self, value: &str) {
+        self.write_usize(value.len());
+        self.write(value.as_bytes());
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
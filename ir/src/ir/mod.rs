@@ -1,21 +1,30 @@
 mod constraints;
 mod degree;
+mod explain;
+mod fingerprint;
 mod operation;
+mod summary;
 mod trace;
 mod value;
 
 pub use self::constraints::{ConstraintDomain, ConstraintError, ConstraintRoot, Constraints};
 pub use self::degree::IntegrityConstraintDegree;
+pub use self::explain::AirExplanation;
+pub(crate) use self::explain::DisplayNode;
 pub use self::operation::Operation;
+pub use self::summary::{
+    AirSummary, AnnotatedConstraintSummary, ConstraintKind, PeriodicColumnSummary,
+    PublicInputSummary, TraceSegmentSummary,
+};
 pub use self::trace::TraceAccess;
 pub use self::value::{PeriodicColumnAccess, PublicInputAccess, Value};
 
 pub use air_parser::{
     ast::{
         AccessType, Boundary, Identifier, PeriodicColumn, PublicInput, QualifiedIdentifier,
-        TraceSegmentId,
+        RandomValues, TraceSegment, TraceSegmentId,
     },
-    Symbol,
+    PrimeField, Symbol,
 };
 
 /// The default segment against which a constraint is applied is the main trace segment.
@@ -26,6 +35,9 @@ pub const AUX_SEGMENT: TraceSegmentId = 1;
 pub const CURRENT_ROW: usize = 0;
 /// The minimum cycle length of a periodic column
 pub const MIN_CYCLE_LENGTH: usize = 2;
+/// The minimum length of an execution trace, which bounds how far before the last row a boundary
+/// constraint may be applied
+pub const MIN_TRACE_LENGTH: usize = 8;
 
 use std::collections::BTreeMap;
 
@@ -48,6 +60,10 @@ pub struct Air {
     /// The widths (number of columns) of each segment of the trace, in segment order (i.e. the
     /// index in this vector matches the index of the segment in the program).
     pub trace_segment_widths: Vec<u16>,
+    /// The trace segments declared by this program, including the names bound to their columns.
+    ///
+    /// These are taken straight from the [air_parser::ast::Program] without modification.
+    pub trace_columns: Vec<TraceSegment>,
     /// The periodic columns referenced by this program.
     ///
     /// These are taken straight from the [air_parser::ast::Program] without modification.
@@ -58,8 +74,16 @@ pub struct Air {
     pub public_inputs: BTreeMap<Identifier, PublicInput>,
     /// The total number of elements in the random values array
     pub num_random_values: u16,
+    /// The random values declared by this program, if any, including the names bound to them.
+    ///
+    /// These are taken straight from the [air_parser::ast::Program] without modification.
+    pub random_values: Option<RandomValues>,
     /// The constraints enforced by this program, in their algebraic graph representation.
     pub constraints: Constraints,
+    /// The prime field over which the constraints are expressed, which defaults to Goldilocks.
+    ///
+    /// All constants in the constraint graph are canonical elements of this field.
+    pub field: PrimeField,
 }
 impl Default for Air {
     fn default() -> Self {
@@ -80,10 +104,13 @@ impl Air {
         Self {
             name,
             trace_segment_widths: vec![],
+            trace_columns: vec![],
             periodic_columns: Default::default(),
             public_inputs: Default::default(),
             num_random_values: 0,
+            random_values: None,
             constraints: Default::default(),
+            field: PrimeField::default(),
         }
     }
 
@@ -93,6 +120,17 @@ impl Air {
         self.name.as_str()
     }
 
+    /// Returns the name of the given trace segment, as declared in the program, without the `$`
+    /// prefix, e.g. `main` or `aux`
+    ///
+    /// # Panics
+    ///
+    /// Panics if the program has no such segment.
+    pub fn trace_segment_name(&self, trace_segment: TraceSegmentId) -> &str {
+        let name = self.trace_columns[trace_segment].name.as_str();
+        name.strip_prefix('$').unwrap_or(name)
+    }
+
     pub fn public_inputs(&self) -> impl Iterator<Item = &PublicInput> + '_ {
         self.public_inputs.values()
     }
@@ -106,12 +144,14 @@ impl Air {
         self.constraints.num_boundary_constraints(trace_segment)
     }
 
-    /// Return the set of [ConstraintRoot] corresponding to the boundary constraints
+    /// Return the set of [ConstraintRoot] corresponding to the boundary constraints, in the order
+    /// described by [Constraints]
     pub fn boundary_constraints(&self, trace_segment: TraceSegmentId) -> &[ConstraintRoot] {
         self.constraints.boundary_constraints(trace_segment)
     }
 
-    /// Return the set of [ConstraintRoot] corresponding to the integrity constraints
+    /// Return the set of [ConstraintRoot] corresponding to the integrity constraints, in the order
+    /// described by [Constraints]
     pub fn integrity_constraints(&self, trace_segment: TraceSegmentId) -> &[ConstraintRoot] {
         self.constraints.integrity_constraints(trace_segment)
     }
@@ -146,6 +186,27 @@ impl Air {
             .filter(|constraint| matches!(constraint.domain(), ConstraintDomain::EveryFrame(_)))
     }
 
+    /// Returns an [AirSummary] describing the trace segments, constraints, periodic columns,
+    /// public inputs, and random values of this [Air]
+    pub fn summary(&self) -> AirSummary {
+        AirSummary::new(self)
+    }
+
+    /// Returns a hash of the structure of this [Air], i.e. its name, field, trace segment widths,
+    /// periodic columns, public inputs, number of random values, and constraints.
+    ///
+    /// The fingerprint does not depend on the source from which this [Air] was derived, beyond its
+    /// semantics, e.g. formatting and comments, and is stable across runs, so it can be used to
+    /// determine whether code generated from a previous version of a program is out of date.
+    pub fn fingerprint(&self) -> u64 {
+        fingerprint::fingerprint(self)
+    }
+
+    /// Returns an [AirExplanation], which lists each constraint of this [Air] in infix form
+    pub fn explain(&self) -> AirExplanation<'_> {
+        AirExplanation::new(self)
+    }
+
     /// Return a reference to the raw [AlgebraicGraph] corresponding to the constraints
     #[inline]
     pub fn constraint_graph(&self) -> &AlgebraicGraph {
@@ -38,4 +38,13 @@ impl Operation {
             _ => 4,
         }
     }
+
+    /// Returns the nodes which are the operands of this [Operation]
+    pub fn children(&self) -> Vec<NodeIndex> {
+        match self {
+            Self::Value(_) => vec![],
+            Self::Add(lhs, rhs) | Self::Sub(lhs, rhs) | Self::Mul(lhs, rhs) => vec![*lhs, *rhs],
+            Self::Exp(lhs, _) => vec![*lhs],
+        }
+    }
 }
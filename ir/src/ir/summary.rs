@@ -0,0 +1,191 @@
+use core::fmt;
+
+use serde::Serialize;
+
+use super::*;
+
+/// A machine-readable summary of an [Air], intended for tooling which needs to know the shape of a
+/// compiled AIR without inspecting generated code.
+///
+/// See [Air::summary].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AirSummary {
+    /// The name of the program the [Air] was derived from
+    pub name: String,
+    /// A summary of each trace segment, in segment order
+    pub trace_segments: Vec<TraceSegmentSummary>,
+    /// The periodic columns referenced by the program
+    pub periodic_columns: Vec<PeriodicColumnSummary>,
+    /// The public inputs declared by the program
+    pub public_inputs: Vec<PublicInputSummary>,
+    /// The total number of elements in the random values array
+    pub num_random_values: u16,
+}
+
+/// Summarizes the columns and constraints of a single trace segment.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TraceSegmentSummary {
+    /// The number of columns in this segment
+    pub width: u16,
+    /// The number of boundary constraints applied against this segment
+    pub num_boundary_constraints: usize,
+    /// The number of integrity constraints applied against this segment
+    pub num_integrity_constraints: usize,
+    /// The largest base degree of the integrity constraints applied against this segment.
+    ///
+    /// This does not account for periodic columns, whose contribution depends on the trace length.
+    pub max_constraint_degree: usize,
+    /// The constraints applied against this segment which have attributes, e.g. `@[name="x"]`
+    pub annotated_constraints: Vec<AnnotatedConstraintSummary>,
+}
+
+/// Summarizes the attributes of a constraint.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AnnotatedConstraintSummary {
+    /// Whether this is a boundary or integrity constraint
+    pub kind: ConstraintKind,
+    /// The index of the constraint among the constraints of the same kind in its segment
+    pub index: usize,
+    /// The attributes of the constraint, keyed by name
+    pub metadata: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConstraintKind {
+    Boundary,
+    Integrity,
+}
+impl fmt::Display for ConstraintKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Boundary => f.write_str("boundary"),
+            Self::Integrity => f.write_str("integrity"),
+        }
+    }
+}
+
+/// Summarizes a periodic column.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PeriodicColumnSummary {
+    /// The fully-qualified name of the column, i.e. `module::name`
+    pub name: String,
+    /// The length of the cycle of the column
+    pub cycle: usize,
+}
+
+/// Summarizes a public input.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PublicInputSummary {
+    pub name: String,
+    pub size: usize,
+}
+
+impl AirSummary {
+    pub(super) fn new(air: &Air) -> Self {
+        let trace_segments = air
+            .trace_segment_widths
+            .iter()
+            .enumerate()
+            .map(|(segment, &width)| TraceSegmentSummary {
+                width,
+                num_boundary_constraints: air.num_boundary_constraints(segment),
+                num_integrity_constraints: air.integrity_constraints(segment).len(),
+                max_constraint_degree: air
+                    .integrity_constraint_degrees(segment)
+                    .iter()
+                    .map(|degree| degree.base())
+                    .max()
+                    .unwrap_or_default(),
+                annotated_constraints: annotated_constraints(air, segment),
+            })
+            .collect();
+        let periodic_columns = air
+            .periodic_columns
+            .iter()
+            .map(|(name, column)| PeriodicColumnSummary {
+                name: name.to_string(),
+                cycle: column.period(),
+            })
+            .collect();
+        let public_inputs = air
+            .public_inputs()
+            .map(|input| PublicInputSummary {
+                name: input.name.to_string(),
+                // The number of elements is checked not to overflow during semantic analysis
+                size: input.num_elements().unwrap(),
+            })
+            .collect();
+
+        Self {
+            name: air.name().to_string(),
+            trace_segments,
+            periodic_columns,
+            public_inputs,
+            num_random_values: air.num_random_values,
+        }
+    }
+}
+
+/// Summarizes the constraints applied against `segment` which have attributes
+fn annotated_constraints(air: &Air, segment: TraceSegmentId) -> Vec<AnnotatedConstraintSummary> {
+    let boundary = air
+        .boundary_constraints(segment)
+        .iter()
+        .enumerate()
+        .map(|(index, root)| (ConstraintKind::Boundary, index, root));
+    let integrity = air
+        .integrity_constraints(segment)
+        .iter()
+        .enumerate()
+        .map(|(index, root)| (ConstraintKind::Integrity, index, root));
+    boundary
+        .chain(integrity)
+        .filter(|(_, _, root)| !root.metadata().is_empty())
+        .map(|(kind, index, root)| AnnotatedConstraintSummary {
+            kind,
+            index,
+            metadata: root.metadata().clone(),
+        })
+        .collect()
+}
+
+impl fmt::Display for AirSummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "name: {}", &self.name)?;
+        writeln!(f, "trace segments:")?;
+        for (id, segment) in self.trace_segments.iter().enumerate() {
+            writeln!(
+                f,
+                "  {id}: width = {}, boundary constraints = {}, integrity constraints = {}, max degree = {}",
+                segment.width,
+                segment.num_boundary_constraints,
+                segment.num_integrity_constraints,
+                segment.max_constraint_degree
+            )?;
+            for constraint in segment.annotated_constraints.iter() {
+                let metadata = constraint
+                    .metadata
+                    .iter()
+                    .map(|(key, value)| format!("{key}=\"{value}\""))
+                    .collect::<Vec<_>>();
+                writeln!(
+                    f,
+                    "    {} constraint {}: {}",
+                    constraint.kind,
+                    constraint.index,
+                    metadata.join(", ")
+                )?;
+            }
+        }
+        writeln!(f, "periodic columns:")?;
+        for column in self.periodic_columns.iter() {
+            writeln!(f, "  {}: cycle = {}", &column.name, column.cycle)?;
+        }
+        writeln!(f, "public inputs:")?;
+        for input in self.public_inputs.iter() {
+            writeln!(f, "  {}: size = {}", &input.name, input.size)?;
+        }
+        write!(f, "random values: {}", self.num_random_values)
+    }
+}
@@ -18,6 +18,8 @@ pub enum Value {
     PublicInput(PublicInputAccess),
     /// A reference to the `random_values` array, specifically the element at the given index
     RandomValue(usize),
+    /// The length of the execution trace, which is only known at runtime
+    TraceLen,
 }
 
 /// Represents an access of a [PeriodicColumn], similar in nature to [TraceAccess]
@@ -37,7 +39,7 @@ impl PeriodicColumnAccess {
 pub struct PublicInputAccess {
     /// The name of the public input to access
     pub name: Identifier,
-    /// The index of the element in the public input to access
+    /// The index of the element in the public input to access, in row-major order for matrices
     pub index: usize,
 }
 impl PublicInputAccess {
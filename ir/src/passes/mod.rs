@@ -1,6 +1,6 @@
 mod translate;
 
-pub use self::translate::AstToAir;
+pub use self::translate::{AstToAir, DEFAULT_EXP_LOWERING_THRESHOLD};
 
 use air_pass::Pass;
 
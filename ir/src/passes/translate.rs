@@ -1,20 +1,196 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 
 use air_parser::ast;
 use air_pass::Pass;
 
-use miden_diagnostics::{DiagnosticsHandler, Severity, Span, Spanned};
+use miden_diagnostics::{DiagnosticsHandler, Severity, SourceSpan, Span, Spanned};
 
 use crate::{graph::NodeIndex, ir::*, CompileError};
 
+/// The default largest exponent which is expanded into multiplications, see
+/// [AstToAir::with_exp_lowering_threshold].
+pub const DEFAULT_EXP_LOWERING_THRESHOLD: usize = 8;
+
 pub struct AstToAir<'a> {
     diagnostics: &'a DiagnosticsHandler,
+    exp_lowering_threshold: usize,
+    prune_unused_periodic_columns: bool,
+    remove_duplicate_constraints: bool,
+    max_degree: Option<usize>,
+    value_numbering: bool,
+    field: PrimeField,
 }
 impl<'a> AstToAir<'a> {
     /// Create a new instance of this pass
     #[inline]
     pub fn new(diagnostics: &'a DiagnosticsHandler) -> Self {
-        Self { diagnostics }
+        Self {
+            diagnostics,
+            exp_lowering_threshold: DEFAULT_EXP_LOWERING_THRESHOLD,
+            prune_unused_periodic_columns: false,
+            remove_duplicate_constraints: false,
+            max_degree: None,
+            value_numbering: true,
+            field: PrimeField::default(),
+        }
+    }
+
+    /// Sets the prime field over which the resulting [Air] is expressed, so that backends can
+    /// target it. This should be the same field used to fold constants during
+    /// [air_parser::transforms::ConstantPropagation].
+    pub fn with_field(mut self, field: PrimeField) -> Self {
+        self.field = field;
+        self
+    }
+
+    /// Sets the largest constant exponent which is expanded into a tree of multiplications,
+    /// rather than emitted as an exponentiation, e.g. `x^4` becomes `(x * x) * (x * x)`.
+    ///
+    /// Exponents above the threshold are left as is, and a threshold below `2` disables this.
+    pub fn with_exp_lowering_threshold(mut self, threshold: usize) -> Self {
+        self.exp_lowering_threshold = threshold;
+        self
+    }
+
+    /// When enabled, periodic columns which are not referenced by any constraint are removed from
+    /// the [Air], rather than only being warned about, so that they are not emitted by backends.
+    pub fn with_unused_periodic_columns_pruned(mut self, prune: bool) -> Self {
+        self.prune_unused_periodic_columns = prune;
+        self
+    }
+
+    /// When enabled, an integrity constraint which is identical to one already enforced against the
+    /// same trace segment is removed from the [Air], rather than only being warned about.
+    ///
+    /// Identical constraints are only detected when value numbering is enabled, as they are then
+    /// rooted at the same node of the constraint graph.
+    pub fn with_duplicate_constraints_removed(mut self, remove: bool) -> Self {
+        self.remove_duplicate_constraints = remove;
+        self
+    }
+
+    /// Sets the largest degree an integrity constraint may have before a warning is raised, which
+    /// is unlimited by default.
+    ///
+    /// The degree is that of [IntegrityConstraintDegree::degree], i.e. it accounts for periodic
+    /// columns as well as trace columns.
+    pub fn with_max_degree(mut self, max_degree: Option<usize>) -> Self {
+        self.max_degree = max_degree;
+        self
+    }
+
+    /// When enabled, which is the default, identical expressions share a single node in the
+    /// constraint graph, rather than each occurrence being inserted as a node of its own.
+    pub fn with_value_numbering(mut self, value_numbering: bool) -> Self {
+        self.value_numbering = value_numbering;
+        self
+    }
+
+    /// Warns about any integrity constraint whose degree is larger than the maximum degree, if
+    /// one was given.
+    ///
+    /// High-degree constraints, e.g. the result of deeply nested multiplications, increase the
+    /// cost of proving, so these are usually accidental.
+    fn check_constraint_degrees(&self, air: &Air) {
+        let Some(max_degree) = self.max_degree else {
+            return;
+        };
+        for segment in 0..air.trace_segment_widths.len() {
+            let degrees = air.integrity_constraint_degrees(segment);
+            for (index, (root, degree)) in air
+                .integrity_constraints(segment)
+                .iter()
+                .zip(degrees)
+                .enumerate()
+            {
+                let degree = degree.degree();
+                if degree <= max_degree {
+                    continue;
+                }
+                let constraint = match root.metadata().get("name") {
+                    Some(name) => format!("integrity constraint {index} (`{name}`)"),
+                    None => format!("integrity constraint {index}"),
+                };
+                let mut diagnostic = self
+                    .diagnostics
+                    .diagnostic(Severity::Warning)
+                    .with_message("constraint degree exceeds the maximum");
+                if !root.span().is_unknown() {
+                    diagnostic = diagnostic.with_primary_label(
+                        root.span(),
+                        format!("this constraint has degree {degree}"),
+                    );
+                }
+                diagnostic
+                    .with_note(format!(
+                        "{constraint} of the `{}` trace segment has degree {degree}, but the maximum degree is {max_degree}",
+                        air.trace_segment_name(segment)
+                    ))
+                    .emit();
+            }
+        }
+    }
+
+    /// Warns about any periodic column which is not referenced by a constraint, removing it from
+    /// `air` if pruning is enabled.
+    ///
+    /// Periodic columns can be declared and referenced, e.g. by a `let` which is never used, and
+    /// still not contribute to any constraint, in which case the prover would compute their
+    /// values for nothing. Columns referenced by boundary constraints are no longer referenced by
+    /// the graph once their values are substituted, so they are given by `boundary_references`.
+    fn check_unused_periodic_columns(
+        &self,
+        air: &mut Air,
+        boundary_references: &BTreeSet<QualifiedIdentifier>,
+    ) {
+        let graph = air.constraint_graph();
+        let mut visited = BTreeSet::new();
+        let mut worklist = (0..air.trace_segment_widths.len())
+            .flat_map(|segment| {
+                air.boundary_constraints(segment)
+                    .iter()
+                    .chain(air.integrity_constraints(segment))
+            })
+            .map(|root| *root.node_index())
+            .collect::<Vec<_>>();
+        let mut used = boundary_references.clone();
+        while let Some(index) = worklist.pop() {
+            if !visited.insert(index) {
+                continue;
+            }
+            let op = graph.node(&index).op();
+            if let Operation::Value(Value::PeriodicColumn(access)) = op {
+                used.insert(access.name);
+            }
+            worklist.extend(op.children());
+        }
+
+        let unused = air
+            .periodic_columns
+            .keys()
+            .filter(|name| !used.contains(*name))
+            .copied()
+            .collect::<Vec<_>>();
+        for name in unused {
+            let column = &air.periodic_columns[&name];
+            let mut diagnostic = self
+                .diagnostics
+                .diagnostic(Severity::Warning)
+                .with_message("unused periodic column")
+                .with_primary_label(
+                    column.name.span(),
+                    format!("`{}` is never referenced by a constraint", column.name),
+                );
+            if self.prune_unused_periodic_columns {
+                diagnostic = diagnostic.with_note("This column has been removed from the output.");
+                air.periodic_columns.remove(&name);
+            } else {
+                diagnostic = diagnostic.with_note(
+                    "Its values are still computed by the prover, consider removing it.",
+                );
+            }
+            diagnostic.emit();
+        }
     }
 }
 impl<'p> Pass for AstToAir<'p> {
@@ -24,6 +200,7 @@ impl<'p> Pass for AstToAir<'p> {
 
     fn run<'a>(&mut self, program: Self::Input<'a>) -> Result<Self::Output<'a>, Self::Error> {
         let mut air = Air::new(program.name);
+        air.field = self.field;
 
         let random_values = program.random_values;
         let trace_columns = program.trace_columns;
@@ -31,7 +208,9 @@ impl<'p> Pass for AstToAir<'p> {
         let integrity_constraints = program.integrity_constraints;
 
         air.trace_segment_widths = trace_columns.iter().map(|ts| ts.size as u16).collect();
+        air.trace_columns = trace_columns.clone();
         air.num_random_values = random_values.as_ref().map(|rv| rv.size as u16).unwrap_or(0);
+        air.random_values = random_values.clone();
         air.periodic_columns = program.periodic_columns;
         air.public_inputs = program.public_inputs;
 
@@ -41,6 +220,12 @@ impl<'p> Pass for AstToAir<'p> {
             random_values,
             trace_columns,
             bindings: Default::default(),
+            metadata: Default::default(),
+            integrity_roots: Default::default(),
+            boundary_periodic_columns: Default::default(),
+            remove_duplicate_constraints: self.remove_duplicate_constraints,
+            exp_lowering_threshold: self.exp_lowering_threshold,
+            value_numbering: self.value_numbering,
         };
 
         for bc in boundary_constraints.iter() {
@@ -50,6 +235,18 @@ impl<'p> Pass for AstToAir<'p> {
         for bc in integrity_constraints.iter() {
             builder.build_integrity_constraint(bc)?;
         }
+        let boundary_periodic_columns = builder.boundary_periodic_columns;
+
+        // The graph is acyclic by construction, but as the degree and domain computations would
+        // never terminate on a cycle, this is verified in debug builds
+        #[cfg(debug_assertions)]
+        air.constraint_graph().assert_acyclic();
+
+        self.check_unused_periodic_columns(&mut air, &boundary_periodic_columns);
+        self.check_constraint_degrees(&air);
+        if self.diagnostics.has_errors() {
+            return Err(CompileError::Failed);
+        }
 
         Ok(air)
     }
@@ -71,19 +268,47 @@ struct AirBuilder<'a> {
     random_values: Option<ast::RandomValues>,
     trace_columns: Vec<ast::TraceSegment>,
     bindings: HashMap<Identifier, MemoizedBinding>,
+    /// The metadata given by the attributes of the constraint being built
+    metadata: BTreeMap<String, String>,
+    /// The span of the first integrity constraint rooted at each node, per trace segment
+    integrity_roots: HashMap<(TraceSegmentId, NodeIndex), SourceSpan>,
+    /// The periodic columns referenced by boundary constraints, whose values are substituted, and
+    /// so which are not referenced by the graph
+    boundary_periodic_columns: BTreeSet<QualifiedIdentifier>,
+    remove_duplicate_constraints: bool,
+    exp_lowering_threshold: usize,
+    value_numbering: bool,
 }
 impl<'a> AirBuilder<'a> {
     fn build_boundary_constraint(&mut self, bc: &ast::Statement) -> Result<(), CompileError> {
         match bc {
             ast::Statement::Enforce(ast::ScalarExpr::Binary(ast::BinaryExpr {
+                span,
                 op: ast::BinaryOp::Eq,
                 ref lhs,
                 ref rhs,
-                ..
-            })) => self.build_boundary_equality(lhs, rhs),
+            })) => self.build_boundary_equality(*span, lhs, rhs),
             ast::Statement::Let(expr) => {
                 self.build_let(expr, |bldr, stmt| bldr.build_boundary_constraint(stmt))
             }
+            ast::Statement::Debug(expr) => {
+                self.build_debug(expr);
+                Ok(())
+            }
+            ast::Statement::WithAttributes(attrs, stmt) => {
+                if attrs.get("cyclic").is_some() {
+                    self.diagnostics
+                        .diagnostic(Severity::Error)
+                        .with_message("invalid boundary constraint")
+                        .with_primary_label(attrs.span(), "boundary constraints cannot be cyclic")
+                        .with_note("Only integrity constraints may be marked `cyclic`, as boundary constraints only apply to a single row.")
+                        .emit();
+                    return Err(CompileError::Failed);
+                }
+                self.build_with_attributes(attrs, stmt, |bldr, stmt| {
+                    bldr.build_boundary_constraint(stmt)
+                })
+            }
             invalid => {
                 self.diagnostics
                     .diagnostic(Severity::Bug)
@@ -101,23 +326,32 @@ impl<'a> AirBuilder<'a> {
     fn build_integrity_constraint(&mut self, bc: &ast::Statement) -> Result<(), CompileError> {
         match bc {
             ast::Statement::Enforce(ast::ScalarExpr::Binary(ast::BinaryExpr {
+                span,
                 op: ast::BinaryOp::Eq,
                 ref lhs,
                 ref rhs,
-                ..
-            })) => self.build_integrity_equality(lhs, rhs, None),
+            })) => self.build_integrity_equality(*span, lhs, rhs, None),
             ast::Statement::EnforceIf(
                 ast::ScalarExpr::Binary(ast::BinaryExpr {
+                    span,
                     op: ast::BinaryOp::Eq,
                     ref lhs,
                     ref rhs,
-                    ..
                 }),
                 ref condition,
-            ) => self.build_integrity_equality(lhs, rhs, Some(condition)),
+            ) => self.build_integrity_equality(*span, lhs, rhs, Some(condition)),
             ast::Statement::Let(expr) => {
                 self.build_let(expr, |bldr, stmt| bldr.build_integrity_constraint(stmt))
             }
+            ast::Statement::Debug(expr) => {
+                self.build_debug(expr);
+                Ok(())
+            }
+            ast::Statement::WithAttributes(attrs, stmt) => {
+                self.build_with_attributes(attrs, stmt, |bldr, stmt| {
+                    bldr.build_integrity_constraint(stmt)
+                })
+            }
             invalid => {
                 self.diagnostics
                     .diagnostic(Severity::Bug)
@@ -132,6 +366,50 @@ impl<'a> AirBuilder<'a> {
         }
     }
 
+    /// Reports the degree and lowered form of the expression of a `debug` statement.
+    ///
+    /// The subgraph built for the expression is discarded afterwards, so the statement has no
+    /// effect on the constraints of the program, or on the code generated from them.
+    fn build_debug(&mut self, expr: &ast::ScalarExpr) {
+        let num_nodes = self.air.constraint_graph().num_nodes();
+        let node = self.insert_scalar_expr(expr);
+        let degree = self.air.constraint_graph().degree(&node);
+        let label = match degree.cycles() {
+            [] => format!("this expression has degree {}", degree.base()),
+            cycles => format!(
+                "this expression has degree {}, with periodic columns of cycle lengths {:?}",
+                degree.base(),
+                cycles
+            ),
+        };
+        self.diagnostics
+            .diagnostic(Severity::Note)
+            .with_message("debug")
+            .with_primary_label(expr.span(), label)
+            .with_note(format!("Lowered to `{}`", DisplayNode::new(self.air, node)))
+            .emit();
+        self.air.constraint_graph_mut().truncate(num_nodes);
+    }
+
+    fn build_with_attributes<F>(
+        &mut self,
+        attrs: &ast::Attributes,
+        stmt: &ast::Statement,
+        statement_builder: F,
+    ) -> Result<(), CompileError>
+    where
+        F: FnOnce(&mut AirBuilder, &ast::Statement) -> Result<(), CompileError>,
+    {
+        let metadata = attrs
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+        let prev = core::mem::replace(&mut self.metadata, metadata);
+        let result = statement_builder(self, stmt);
+        self.metadata = prev;
+        result
+    }
+
     fn build_let<F>(
         &mut self,
         expr: &ast::Let,
@@ -165,14 +443,14 @@ impl<'a> AirBuilder<'a> {
             ast::Expr::Range(ref values) => {
                 let values = values
                     .item
-                    .clone()
+                    .iter()
                     .map(|v| self.insert_constant(v as u64))
                     .collect();
                 self.bindings
                     .insert(expr.name, MemoizedBinding::Vector(values));
             }
             ast::Expr::Vector(ref values) => match values[0].ty().unwrap() {
-                ast::Type::Felt => {
+                ast::Type::Felt | ast::Type::Binary | ast::Type::U32 => {
                     let mut nodes = vec![];
                     for value in values.iter().cloned() {
                         let value = value.try_into().unwrap();
@@ -232,6 +510,11 @@ impl<'a> AirBuilder<'a> {
                 self.bindings
                     .insert(expr.name, MemoizedBinding::Scalar(value));
             }
+            ast::Expr::TraceLen(_) => {
+                let value = self.insert_op(Operation::Value(Value::TraceLen));
+                self.bindings
+                    .insert(expr.name, MemoizedBinding::Scalar(value));
+            }
             ast::Expr::SymbolAccess(ref access) => {
                 match self.bindings.get(access.name.as_ref()) {
                     None => {
@@ -284,6 +567,7 @@ impl<'a> AirBuilder<'a> {
 
     fn build_boundary_equality(
         &mut self,
+        span: SourceSpan,
         lhs: &ast::ScalarExpr,
         rhs: &ast::ScalarExpr,
     ) -> Result<(), CompileError> {
@@ -303,12 +587,31 @@ impl<'a> AirBuilder<'a> {
                 .emit();
             return Err(CompileError::Failed);
         };
+        // Raise a validation error if the constrained row may lie before the start of the trace
+        if let ast::Boundary::BeforeLast(offset) = access.boundary {
+            if offset >= MIN_TRACE_LENGTH - 1 {
+                self.diagnostics
+                    .diagnostic(Severity::Error)
+                    .with_message("invalid boundary constraint")
+                    .with_primary_label(
+                        access.span(),
+                        format!("this constrains the row {offset} rows before the last row"),
+                    )
+                    .with_note(format!(
+                        "Boundary constraints may only be applied to rows less than {} rows before the last row, as the trace may have as few as {MIN_TRACE_LENGTH} rows.",
+                        MIN_TRACE_LENGTH - 1
+                    ))
+                    .emit();
+                return Err(CompileError::Failed);
+            }
+        }
+
         // Insert the trace access into the graph
         let trace_access = self.trace_access(&access.column).unwrap();
 
         // Raise a validation error if this column boundary has already been constrained
         if let Some(prev) = self.trace_columns[trace_access.segment].mark_constrained(
-            lhs_span,
+            span,
             trace_access.column,
             access.boundary,
         ) {
@@ -316,17 +619,23 @@ impl<'a> AirBuilder<'a> {
                 .diagnostic(Severity::Error)
                 .with_message("overlapping boundary constraints")
                 .with_primary_label(
-                    lhs_span,
-                    "this constrains a column and boundary that has already been constrained",
+                    span,
+                    format!(
+                        "this constrains `{}` at the {} row, but it has already been constrained there",
+                        access.column, access.boundary
+                    ),
                 )
-                .with_secondary_label(prev, "previous constraint occurs here")
+                .with_secondary_label(prev, "originally constrained here")
+                .with_note("Each column may only be constrained once on each boundary.")
                 .emit();
             return Err(CompileError::Failed);
         }
 
         let lhs = self.insert_op(Operation::Value(Value::TraceAccess(trace_access)));
-        // Insert the right-hand expression into the graph
+        // Insert the right-hand expression into the graph, with any periodic columns replaced by
+        // their values at the constrained row
         let rhs = self.insert_scalar_expr(rhs);
+        let rhs = self.substitute_periodic_columns(rhs, access.boundary);
         // Compare the inferred trace segment and domain of the operands
         let domain = access.boundary.into();
         {
@@ -359,15 +668,20 @@ impl<'a> AirBuilder<'a> {
         // Merge the expressions into a single constraint
         let root = self.merge_equal_exprs(lhs, rhs, None);
         // Store the generated constraint
-        self.air
-            .constraints
-            .insert_constraint(trace_access.segment, root, domain);
+        self.air.constraints.insert_constraint(
+            trace_access.segment,
+            root,
+            domain,
+            self.metadata.clone(),
+            span,
+        );
 
         Ok(())
     }
 
     fn build_integrity_equality(
         &mut self,
+        span: SourceSpan,
         lhs: &ast::ScalarExpr,
         rhs: &ast::ScalarExpr,
         condition: Option<&ast::ScalarExpr>,
@@ -379,18 +693,54 @@ impl<'a> AirBuilder<'a> {
         // Get the trace segment and domain of the constraint.
         //
         // The default domain for integrity constraints is `EveryRow`
-        let (trace_segment, domain) = self
+        let (trace_segment, mut domain) = self
             .air
             .constraint_graph()
             .node_details(&root, ConstraintDomain::EveryRow)?;
+        // Constraints marked `cyclic` are applied to every row, without exemptions
+        if self.metadata.get("cyclic").map(String::as_str) == Some("true") {
+            domain = ConstraintDomain::Cyclic(domain.frame_size());
+        }
+        // Identical constraints share a root, in which case this one is redundant
+        if let Some(prev) = self.integrity_roots.get(&(trace_segment, root)) {
+            let note = if self.remove_duplicate_constraints {
+                "This constraint has been removed from the output."
+            } else {
+                "It is still evaluated by the prover, consider removing it."
+            };
+            self.diagnostics
+                .diagnostic(Severity::Warning)
+                .with_message("duplicate constraint")
+                .with_primary_label(
+                    span,
+                    "this constraint is identical to a previous constraint",
+                )
+                .with_secondary_label(*prev, "which is enforced here")
+                .with_note(note)
+                .emit();
+            if self.remove_duplicate_constraints {
+                return Ok(());
+            }
+        } else {
+            self.integrity_roots.insert((trace_segment, root), span);
+        }
         // Save the constraint information
-        self.air
-            .constraints
-            .insert_constraint(trace_segment, root, domain);
+        self.air.constraints.insert_constraint(
+            trace_segment,
+            root,
+            domain,
+            self.metadata.clone(),
+            span,
+        );
 
         Ok(())
     }
 
+    /// Builds the root of the constraint `lhs = rhs`, multiplied by `selector`, if present.
+    ///
+    /// Selectors are inserted like any other expression, so constraints sharing a selector, e.g.
+    /// the arms of a `match`, or the constraints of an evaluator called with `when`, reuse the
+    /// same selector subgraph, and only differ in the final multiplication.
     fn merge_equal_exprs(
         &mut self,
         lhs: NodeIndex,
@@ -410,6 +760,7 @@ impl<'a> AirBuilder<'a> {
             ast::ScalarExpr::Const(value) => {
                 self.insert_op(Operation::Value(Value::Constant(value.item)))
             }
+            ast::ScalarExpr::TraceLen(_) => self.insert_op(Operation::Value(Value::TraceLen)),
             ast::ScalarExpr::SymbolAccess(access) => self.insert_symbol_access(access),
             ast::ScalarExpr::Binary(expr) => self.insert_binary_expr(expr),
             ast::ScalarExpr::Call(_) | ast::ScalarExpr::BoundedSymbolAccess(_) => unreachable!(),
@@ -418,11 +769,22 @@ impl<'a> AirBuilder<'a> {
 
     fn insert_binary_expr(&mut self, expr: &ast::BinaryExpr) -> NodeIndex {
         if expr.op == ast::BinaryOp::Exp {
-            let lhs = self.insert_scalar_expr(expr.lhs.as_ref());
             let ast::ScalarExpr::Const(rhs) = expr.rhs.as_ref() else {
                 unreachable!();
             };
-            return self.insert_op(Operation::Exp(lhs, rhs.item as usize));
+            // Fold trivial exponents, as powers may only become constant during inlining
+            return match rhs.item as usize {
+                0 => self.insert_constant(1),
+                1 => self.insert_scalar_expr(expr.lhs.as_ref()),
+                exp if exp <= self.exp_lowering_threshold => {
+                    let lhs = self.insert_scalar_expr(expr.lhs.as_ref());
+                    self.insert_mul_tree(lhs, exp)
+                }
+                exp => {
+                    let lhs = self.insert_scalar_expr(expr.lhs.as_ref());
+                    self.insert_op(Operation::Exp(lhs, exp))
+                }
+            };
         }
 
         let lhs = self.insert_scalar_expr(expr.lhs.as_ref());
@@ -435,6 +797,23 @@ impl<'a> AirBuilder<'a> {
         }
     }
 
+    /// Expands `base^exp` into a balanced tree of multiplications by repeated squaring, so that
+    /// each intermediate power is a single node shared by its uses, e.g. `x^6` becomes
+    /// `(x^2 * x) * (x^2 * x)` where `x^2` is `x * x`.
+    fn insert_mul_tree(&mut self, base: NodeIndex, exp: usize) -> NodeIndex {
+        match exp {
+            1 => base,
+            exp if exp % 2 == 0 => {
+                let half = self.insert_mul_tree(base, exp / 2);
+                self.insert_op(Operation::Mul(half, half))
+            }
+            exp => {
+                let rest = self.insert_mul_tree(base, exp - 1);
+                self.insert_op(Operation::Mul(rest, base))
+            }
+        }
+    }
+
     fn insert_symbol_access(&mut self, access: &ast::SymbolAccess) -> NodeIndex {
         use air_parser::ast::ResolvableIdentifier;
         match access.name {
@@ -476,6 +855,28 @@ impl<'a> AirBuilder<'a> {
                     );
                 }
 
+                // Let-bound variables take precedence, as they may shadow a root declaration
+                if let Some(binding) = self.bindings.get(access.name.as_ref()) {
+                    return match binding {
+                        MemoizedBinding::Scalar(node) => {
+                            assert_eq!(access.access_type, AccessType::Default);
+                            *node
+                        }
+                        MemoizedBinding::Vector(nodes) => {
+                            if let AccessType::Index(idx) = &access.access_type {
+                                return nodes[*idx];
+                            }
+                            unreachable!("impossible vector access: {:?}", access)
+                        }
+                        MemoizedBinding::Matrix(nodes) => {
+                            if let AccessType::Matrix(row, col) = &access.access_type {
+                                return nodes[*row][*col];
+                            }
+                            unreachable!("impossible matrix access: {:?}", access)
+                        }
+                    };
+                }
+
                 // Otherwise, we check the trace bindings, random value bindings, and public inputs, in that order
                 if let Some(trace_access) = self.trace_access(access) {
                     return self.insert_op(Operation::Value(Value::TraceAccess(trace_access)));
@@ -489,29 +890,9 @@ impl<'a> AirBuilder<'a> {
                     return self.insert_op(Operation::Value(Value::PublicInput(public_input)));
                 }
 
-                // If we reach here, this must be a let-bound variable
-                match self
-                    .bindings
-                    .get(access.name.as_ref())
-                    .expect("undefined variable")
-                {
-                    MemoizedBinding::Scalar(node) => {
-                        assert_eq!(access.access_type, AccessType::Default);
-                        *node
-                    }
-                    MemoizedBinding::Vector(nodes) => {
-                        if let AccessType::Index(idx) = &access.access_type {
-                            return nodes[*idx];
-                        }
-                        unreachable!("impossible vector access: {:?}", access)
-                    }
-                    MemoizedBinding::Matrix(nodes) => {
-                        if let AccessType::Matrix(row, col) = &access.access_type {
-                            return nodes[*row][*col];
-                        }
-                        unreachable!("impossible matrix access: {:?}", access)
-                    }
-                }
+                // It should never be possible to reach this point - semantic analysis
+                // would have caught that this identifier is undefined.
+                unreachable!("undefined variable: {:?}", access)
             }
             // These should have been eliminated by previous compiler passes
             ResolvableIdentifier::Unresolved(_) => {
@@ -552,14 +933,20 @@ impl<'a> AirBuilder<'a> {
 
     fn public_input_access(&self, access: &ast::SymbolAccess) -> Option<PublicInputAccess> {
         let public_input = self.air.public_inputs.get(access.name.as_ref())?;
-        if let AccessType::Index(index) = access.access_type {
-            Some(PublicInputAccess::new(public_input.name, index))
-        } else {
+        // Elements of matrix public inputs are addressed in row-major order
+        match (access.access_type.clone(), public_input.columns) {
+            (AccessType::Index(index), None) => {
+                Some(PublicInputAccess::new(public_input.name, index))
+            }
+            (AccessType::Matrix(row, col), Some(columns)) => Some(PublicInputAccess::new(
+                public_input.name,
+                row * columns + col,
+            )),
             // This should have been caught earlier during compilation
-            unreachable!(
+            _ => unreachable!(
                 "unexpected public input access type encountered during lowering: {:#?}",
                 access
-            )
+            ),
         }
     }
 
@@ -606,10 +993,101 @@ impl<'a> AirBuilder<'a> {
         None
     }
 
+    /// Replaces each access of a periodic column in the subgraph rooted at `node` with the value
+    /// of that column at the row given by `boundary`, returning the root of the resulting subgraph.
+    ///
+    /// The length of the trace is a multiple of the cycle length of every periodic column, so the
+    /// first and last rows of the trace are the first and last rows of a cycle.
+    ///
+    /// As the substituted values are constants, operations over them are folded again, e.g. `k + 1`
+    /// becomes `2` rather than `1 + 1` if the value of `k` is `1`.
+    fn substitute_periodic_columns(
+        &mut self,
+        node: NodeIndex,
+        boundary: ast::Boundary,
+    ) -> NodeIndex {
+        let op = *self.air.constraint_graph().node(&node).op();
+        let substituted = match op {
+            Operation::Value(Value::PeriodicColumn(access)) => {
+                self.boundary_periodic_columns.insert(access.name);
+                let values = &self.air.periodic_columns[&access.name].values;
+                let cycle = values.len();
+                let row = match boundary {
+                    ast::Boundary::First => 0,
+                    ast::Boundary::Last => cycle - 1,
+                    ast::Boundary::BeforeLast(offset) => cycle - 1 - offset % cycle,
+                };
+                return self.insert_constant(values[row]);
+            }
+            Operation::Value(_) => return node,
+            Operation::Add(lhs, rhs) => Operation::Add(
+                self.substitute_periodic_columns(lhs, boundary),
+                self.substitute_periodic_columns(rhs, boundary),
+            ),
+            Operation::Sub(lhs, rhs) => Operation::Sub(
+                self.substitute_periodic_columns(lhs, boundary),
+                self.substitute_periodic_columns(rhs, boundary),
+            ),
+            Operation::Mul(lhs, rhs) => Operation::Mul(
+                self.substitute_periodic_columns(lhs, boundary),
+                self.substitute_periodic_columns(rhs, boundary),
+            ),
+            Operation::Exp(base, exp) => {
+                Operation::Exp(self.substitute_periodic_columns(base, boundary), exp)
+            }
+        };
+        // Subgraphs without periodic columns are left as they are
+        if substituted == op {
+            node
+        } else {
+            self.fold_op(substituted)
+        }
+    }
+
+    /// Adds the specified operation to the graph like [Self::insert_op], unless it can be folded,
+    /// i.e. all of its operands are constants, or it is an identity such as `x + 0` or `x * 1`, in
+    /// which case the node of the result is returned instead.
+    fn fold_op(&mut self, op: Operation) -> NodeIndex {
+        let field = self.air.field;
+        let constant = |node: NodeIndex| match self.air.constraint_graph().node(&node).op() {
+            Operation::Value(Value::Constant(value)) => Some(*value),
+            _ => None,
+        };
+        match op {
+            Operation::Add(lhs, rhs) => match (constant(lhs), constant(rhs)) {
+                (Some(lhs), Some(rhs)) => self.insert_constant(field.add(lhs, rhs)),
+                (Some(0), _) => rhs,
+                (_, Some(0)) => lhs,
+                _ => self.insert_op(op),
+            },
+            Operation::Sub(lhs, rhs) => match (constant(lhs), constant(rhs)) {
+                (Some(lhs), Some(rhs)) => self.insert_constant(field.sub(lhs, rhs)),
+                (_, Some(0)) => lhs,
+                _ => self.insert_op(op),
+            },
+            Operation::Mul(lhs, rhs) => match (constant(lhs), constant(rhs)) {
+                (Some(lhs), Some(rhs)) => self.insert_constant(field.mul(lhs, rhs)),
+                (Some(0), _) | (_, Some(0)) => self.insert_constant(0),
+                (Some(1), _) => rhs,
+                (_, Some(1)) => lhs,
+                _ => self.insert_op(op),
+            },
+            Operation::Exp(base, exp) => match constant(base) {
+                Some(base) => self.insert_constant(field.pow(base, exp as u64)),
+                None => self.insert_op(op),
+            },
+            Operation::Value(_) => self.insert_op(op),
+        }
+    }
+
     /// Adds the specified operation to the graph and returns the index of its node.
     #[inline]
     fn insert_op(&mut self, op: Operation) -> NodeIndex {
-        self.air.constraint_graph_mut().insert_node(op)
+        if self.value_numbering {
+            self.air.constraint_graph_mut().insert_node(op)
+        } else {
+            self.air.constraint_graph_mut().push_node(op)
+        }
     }
 
     fn insert_constant(&mut self, value: u64) -> NodeIndex {
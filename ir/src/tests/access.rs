@@ -19,7 +19,7 @@ fn invalid_vector_access_in_boundary_constraint() {
 
     expect_diagnostic(
         source,
-        "attempted to access an index which is out of bounds",
+        "constant `B` has 3 elements, but index 3 was accessed",
     );
 }
 
@@ -42,7 +42,7 @@ fn invalid_matrix_row_access_in_boundary_constraint() {
 
     expect_diagnostic(
         source,
-        "attempted to access an index which is out of bounds",
+        "constant `C` is a 2x3 matrix, but index [3][2] was accessed",
     );
 }
 
@@ -65,7 +65,7 @@ fn invalid_matrix_column_access_in_boundary_constraint() {
 
     expect_diagnostic(
         source,
-        "attempted to access an index which is out of bounds",
+        "constant `C` is a 2x3 matrix, but index [1][3] was accessed",
     );
 }
 
@@ -88,7 +88,7 @@ fn invalid_vector_access_in_integrity_constraint() {
 
     expect_diagnostic(
         source,
-        "attempted to access an index which is out of bounds",
+        "constant `B` has 3 elements, but index 3 was accessed",
     );
 }
 
@@ -111,7 +111,7 @@ fn invalid_matrix_row_access_in_integrity_constraint() {
 
     expect_diagnostic(
         source,
-        "attempted to access an index which is out of bounds",
+        "constant `C` is a 2x3 matrix, but index [3][2] was accessed",
     );
 }
 
@@ -134,6 +134,6 @@ fn invalid_matrix_column_access_in_integrity_constraint() {
 
     expect_diagnostic(
         source,
-        "attempted to access an index which is out of bounds",
+        "constant `C` is a 2x3 matrix, but index [1][3] was accessed",
     );
 }
@@ -0,0 +1,149 @@
+use std::collections::BTreeMap;
+
+use crate::ConstraintDomain;
+
+use super::{compile, expect_diagnostic};
+
+fn metadata(attrs: &[(&str, &str)]) -> BTreeMap<String, String> {
+    attrs
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
+}
+
+#[test]
+fn attributes_on_constraints() {
+    let source = r#"
+    def test
+    trace_columns:
+        main: [a, b]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        @[name="a_init"]
+        enf a.first = 0
+    integrity_constraints:
+        @[name="a_binary"] @[owner="alice"]
+        enf a^2 = a
+        enf b' = b + a"#;
+
+    let air = compile(source).expect("compilation failed");
+    let boundary = air.boundary_constraints(0);
+    assert_eq!(boundary[0].metadata(), &metadata(&[("name", "a_init")]));
+    let integrity = air.integrity_constraints(0);
+    assert_eq!(
+        integrity[0].metadata(),
+        &metadata(&[("name", "a_binary"), ("owner", "alice")])
+    );
+    assert!(integrity[1].metadata().is_empty());
+}
+
+#[test]
+fn attributes_are_applied_to_expanded_constraints() {
+    let source = r#"
+    def test
+    ev is_binary_pair([x, y]):
+        enf x^2 = x
+        @[name="y_binary"]
+        enf y^2 = y
+
+    trace_columns:
+        main: [a, b, c[2], s]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        @[group="binary"]
+        enf is_binary_pair([a, b])
+        @[group="comprehension"]
+        enf x' = x for x in c
+        @[group="selectors"]
+        enf match:
+            case s: a' = a
+            case !s: b' = b
+        enf is_binary(s)"#;
+
+    let air = compile(source).expect("compilation failed");
+    let integrity = air.integrity_constraints(0);
+    assert_eq!(integrity.len(), 7);
+    assert_eq!(integrity[0].metadata(), &metadata(&[("group", "binary")]));
+    assert_eq!(
+        integrity[1].metadata(),
+        &metadata(&[("group", "binary"), ("name", "y_binary")])
+    );
+    for root in &integrity[2..4] {
+        assert_eq!(root.metadata(), &metadata(&[("group", "comprehension")]));
+    }
+    for root in &integrity[4..6] {
+        assert_eq!(root.metadata(), &metadata(&[("group", "selectors")]));
+    }
+    assert_eq!(integrity[6].metadata(), &metadata(&[]));
+}
+
+#[test]
+fn err_duplicate_attribute() {
+    let source = r#"
+    def test
+    trace_columns:
+        main: [a]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        @[name="a"] @[name="b"]
+        enf a' = a"#;
+
+    expect_diagnostic(source, "duplicate attribute");
+}
+
+#[test]
+fn cyclic_constraints() {
+    let source = r#"
+    def test
+    trace_columns:
+        main: [a, b]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        enf a' = a + 1
+        @cyclic
+        enf b' = a
+        @[cyclic="true", name="b_binary"]
+        enf b^2 = b"#;
+
+    let air = compile(source).expect("compilation failed");
+    let integrity = air.integrity_constraints(0);
+    assert_eq!(integrity[0].domain(), ConstraintDomain::EveryFrame(2));
+    assert_eq!(integrity[1].domain(), ConstraintDomain::Cyclic(2));
+    assert_eq!(integrity[1].metadata(), &metadata(&[("cyclic", "true")]));
+    assert_eq!(integrity[2].domain(), ConstraintDomain::Cyclic(1));
+    assert!(integrity[2].domain().is_integrity());
+
+    let expected = "\
+boundary[0] (segment main, first row): a - 0 = 0
+integrity[0] (segment main, degree 1): a' - (a + 1) = 0
+integrity[1] (segment main, degree 1, cyclic): b' - a = 0
+integrity[2] (segment main, degree 2, cyclic): b * b - b = 0";
+    assert_eq!(air.explain().to_string(), expected);
+}
+
+#[test]
+fn err_cyclic_boundary_constraint() {
+    let source = r#"
+    def test
+    trace_columns:
+        main: [a]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        @cyclic
+        enf a.first = 0
+    integrity_constraints:
+        enf a' = a"#;
+
+    expect_diagnostic(source, "boundary constraints cannot be cyclic");
+}
@@ -1,3 +1,5 @@
+use crate::ConstraintDomain;
+
 use super::{compile, expect_diagnostic};
 
 #[test]
@@ -50,3 +52,196 @@ fn err_bc_duplicate_last() {
 
     expect_diagnostic(source, "overlapping boundary constraints");
 }
+
+#[test]
+fn err_bc_duplicate_reports_original_constraint() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+        enf clk.last = 1
+        enf clk.first = 2
+    integrity_constraints:
+        enf clk' = clk + 1";
+
+    expect_diagnostic(source, "originally constrained here");
+}
+
+#[test]
+fn boundary_constraint_at_penultimate_row() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.last(1) = 0
+        enf clk.last = 1
+    integrity_constraints:
+        enf clk' = clk + 1";
+
+    let air = compile(source).expect("compilation failed");
+    let domains = air
+        .boundary_constraints(0)
+        .iter()
+        .map(|constraint| constraint.domain())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        domains,
+        vec![
+            ConstraintDomain::BeforeLastRow(1),
+            ConstraintDomain::LastRow
+        ]
+    );
+}
+
+#[test]
+fn err_bc_duplicate_penultimate() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.last(1) = 0
+        enf clk.last(1) = 1
+    integrity_constraints:
+        enf clk' = clk + 1";
+
+    expect_diagnostic(source, "overlapping boundary constraints");
+}
+
+#[test]
+fn err_bc_offset_exceeds_min_trace_length() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.last(7) = 0
+    integrity_constraints:
+        enf clk' = clk + 1";
+
+    expect_diagnostic(source, "as the trace may have as few as 8 rows");
+}
+
+#[test]
+fn boundary_constraint_comprehension() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, c[4]]
+    public_inputs:
+        stack_inputs: [4]
+    boundary_constraints:
+        enf x.first = y for (x, y) in (c, stack_inputs)
+        enf x.last = 0 for x in c
+    integrity_constraints:
+        enf clk' = clk + 1";
+
+    let air = compile(source).expect("compilation failed");
+    let domains = air
+        .boundary_constraints(0)
+        .iter()
+        .map(|constraint| constraint.domain())
+        .collect::<Vec<_>>();
+    assert_eq!(domains.len(), 8);
+    assert!(domains[..4]
+        .iter()
+        .all(|domain| *domain == ConstraintDomain::FirstRow));
+    assert!(domains[4..]
+        .iter()
+        .all(|domain| *domain == ConstraintDomain::LastRow));
+}
+
+#[test]
+fn err_bc_comprehension_duplicate() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, c[4]]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf x.first = 0 for x in c
+        enf c[2].first = 1
+    integrity_constraints:
+        enf clk' = clk + 1";
+
+    expect_diagnostic(source, "overlapping boundary constraints");
+}
+
+#[test]
+fn boundary_constraint_constant_vector() {
+    let source = "
+    def test
+    const C = [1, 2, 3, 4]
+    trace_columns:
+        main: [clk, c[4]]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf c.first = C
+        enf c[1..3].last = stack_inputs[0..2]
+    integrity_constraints:
+        enf clk' = clk + 1";
+
+    let air = compile(source).expect("compilation failed");
+    assert_eq!(
+        air.explain().to_string(),
+        "\
+boundary[0] (segment main, first row): c[0] - 1 = 0
+boundary[1] (segment main, first row): c[1] - 2 = 0
+boundary[2] (segment main, first row): c[2] - 3 = 0
+boundary[3] (segment main, first row): c[3] - 4 = 0
+boundary[4] (segment main, last row): c[1] - stack_inputs[0] = 0
+boundary[5] (segment main, last row): c[2] - stack_inputs[1] = 0
+integrity[0] (segment main, degree 1): clk' - (clk + 1) = 0"
+    );
+}
+
+#[test]
+fn err_bc_constant_vector_length_mismatch() {
+    let source = "
+    def test
+    const C = [1, 2, 3]
+    trace_columns:
+        main: [clk, c[4]]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf c.first = C
+    integrity_constraints:
+        enf clk' = clk + 1";
+
+    expect_diagnostic(source, "this vector has 3 elements");
+}
+
+#[test]
+fn err_bc_subtraction_from_last_row() {
+    // `a.last - 1` subtracts from the value of `a` in the last row, it does not refer to the
+    // penultimate row, which is written `a.last(1)`
+    let source = "
+    def test
+    trace_columns:
+        main: [clk]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.last - 1 = 0
+    integrity_constraints:
+        enf clk' = clk + 1";
+
+    expect_diagnostic(
+        source,
+        "expected this to be a reference to a trace column boundary",
+    );
+}
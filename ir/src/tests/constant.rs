@@ -1,4 +1,6 @@
-use super::{compile, expect_diagnostic};
+use crate::{Operation, Value};
+
+use super::{compile, expect_diagnostic, Compiler};
 
 #[test]
 fn boundary_constraint_with_constants() {
@@ -39,6 +41,58 @@ fn integrity_constraint_with_constants() {
     assert!(compile(source).is_ok());
 }
 
+#[test]
+fn indexed_constants_are_substituted() {
+    let source = "
+    def test
+    const B = [1, 2, 3]
+    const C = [[1, 2, 3], [4, 5, 6]]
+    trace_columns:
+        main: [clk]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = B[2]
+    integrity_constraints:
+        enf clk' = C[1][0]";
+
+    let air = compile(source).expect("compilation failed");
+    let graph = air.constraint_graph();
+    for (root, expected) in [
+        (air.boundary_constraints(0)[0].node_index(), 3),
+        (air.integrity_constraints(0)[0].node_index(), 4),
+    ] {
+        let Operation::Sub(_, rhs) = graph.node(root).op() else {
+            panic!("expected subtraction, got {:?}", graph.node(root).op());
+        };
+        assert_eq!(
+            graph.node(rhs).op(),
+            &Operation::Value(Value::Constant(expected))
+        );
+    }
+}
+
+#[test]
+fn err_vector_constant_slice_out_of_bounds() {
+    let source = "
+    def test
+    const B = [1, 2, 3]
+    trace_columns:
+        main: [clk]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        let x = B[1..4]
+        enf clk' = clk + x[0]";
+
+    expect_diagnostic(
+        source,
+        "constant `B` has 3 elements, but the range 1..4 was accessed",
+    );
+}
+
 #[test]
 fn invalid_matrix_constant() {
     let source = "
@@ -56,3 +110,131 @@ fn invalid_matrix_constant() {
 
     expect_diagnostic(source, "invalid matrix literal: mismatched dimensions");
 }
+
+#[test]
+fn err_literal_not_in_field() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf clk' = clk + 18446744069414584321";
+
+    expect_diagnostic(source, "this value is not an element of the field");
+}
+
+#[test]
+fn literal_below_modulus() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf clk' = clk + 18446744069414584320";
+
+    // the largest element of the field is kept as is, rather than being reduced
+    let air = compile(source).expect("compilation failed");
+    assert_eq!(
+        air.explain().to_string(),
+        "\
+boundary[0] (segment main, first row): clk - 0 = 0
+integrity[0] (segment main, degree 1): clk' - (clk + 18446744069414584320) = 0"
+    );
+}
+
+#[test]
+fn negated_constants() {
+    // negation is performed in the field, so `-1` is the largest element of the field
+    let source = "
+    def test
+    const A = 2
+    trace_columns:
+        main: [a, b]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = -1
+    integrity_constraints:
+        enf a' = -(a + b)
+        enf b' = b * -A";
+
+    let air = compile(source).expect("compilation failed");
+    assert_eq!(
+        air.explain().to_string(),
+        "\
+boundary[0] (segment main, first row): a - 18446744069414584320 = 0
+integrity[0] (segment main, degree 1): a' - (0 - (a + b)) = 0
+integrity[1] (segment main, degree 1): b' - b * 18446744069414584319 = 0"
+    );
+}
+
+#[test]
+fn err_periodic_column_value_not_in_field() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk]
+    public_inputs:
+        stack_inputs: [16]
+    periodic_columns:
+        k: [18446744069414584321, 0]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf clk' = clk + k";
+
+    expect_diagnostic(source, "this value is not an element of the field");
+}
+
+#[test]
+fn err_constant_not_in_field() {
+    let source = "
+    def test
+    const A = [1, 18446744073709551615]
+    trace_columns:
+        main: [clk]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf clk' = clk + A[0]";
+
+    expect_diagnostic(source, "this value is not an element of the field");
+}
+
+#[test]
+fn imported_constant() {
+    let root = "
+    def test
+    use lib::EXP
+    trace_columns:
+        main: [a]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = EXP
+    integrity_constraints:
+        enf a' = a * EXP + EXP";
+    let lib = "
+    mod lib
+    const EXP = 2";
+
+    let compiler = Compiler::default();
+    let path = std::env::current_dir().unwrap().join("lib.air");
+    compiler.codemap.add(path, lib.to_string());
+    let air = compiler.compile(root).expect("compilation failed");
+
+    let expected = "\
+boundary[0] (segment main, first row): a - 2 = 0
+integrity[0] (segment main, degree 1): a' - (a * 2 + 2) = 0";
+    assert_eq!(air.explain().to_string(), expected);
+}
@@ -0,0 +1,100 @@
+use miden_diagnostics::{DiagnosticsConfig, Verbosity};
+
+use super::{compile, expect_diagnostic, Compiler};
+
+#[test]
+fn debug_produces_no_constraints() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        let x = a * b
+        debug(x * (b' - 1))
+        enf a' = x";
+
+    let compiler = Compiler::new(DiagnosticsConfig {
+        verbosity: Verbosity::Info,
+        warnings_as_errors: true,
+        no_warn: false,
+        display: Default::default(),
+    });
+    let air = compiler.compile(source).expect("compilation failed");
+    assert_eq!(air.integrity_constraints(0).len(), 1);
+    assert_eq!(air.boundary_constraints(0).len(), 1);
+
+    let captured = compiler.emitter.captured();
+    assert!(captured.contains("this expression has degree 3"));
+    assert!(captured.contains("Lowered to `a * b * (b' - 1)`"));
+
+    // The subgraph of the debugged expression is discarded, so the graph is identical to that
+    // of the same program without the debug statement
+    let without_debug =
+        compile(&source.replace("debug(x * (b' - 1))", "")).expect("compilation failed");
+    assert_eq!(
+        air.constraint_graph().num_nodes(),
+        without_debug.constraint_graph().num_nodes()
+    );
+}
+
+#[test]
+fn debug_in_evaluator() {
+    let source = "
+    def test
+    ev foo([a, b]):
+        debug(a + b)
+        enf a' = a + b
+
+    trace_columns:
+        main: [a, b]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        enf foo([a, b])";
+
+    let air = compile(source).expect("compilation failed");
+    assert_eq!(air.integrity_constraints(0).len(), 1);
+}
+
+#[test]
+fn err_debug_in_boundary_constraints() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        debug(a.first)
+        enf a.first = 0
+    integrity_constraints:
+        enf a' = a";
+
+    expect_diagnostic(
+        source,
+        "debug statements are only permitted in integrity constraints",
+    );
+}
+
+#[test]
+fn err_debug_vector() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a[2]]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a[0].first = 0
+    integrity_constraints:
+        debug(a)
+        enf a[0]' = a[1]";
+
+    expect_diagnostic(source, "expected a scalar expression");
+}
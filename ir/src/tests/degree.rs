@@ -0,0 +1,139 @@
+use miden_diagnostics::{DiagnosticsConfig, Verbosity};
+
+use crate::{AlgebraicGraph, IntegrityConstraintDegree, Operation, TraceAccess, Value};
+
+use super::{compile, Compiler};
+
+#[test]
+fn memoized_degrees_match_recursive_degrees() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b, c]
+    public_inputs:
+        stack_inputs: [16]
+    periodic_columns:
+        k0: [1, 0, 0, 0]
+        k1: [1, 1, 0, 0, 0, 0, 0, 0]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        let x = a * b + k0 * c
+        let y = x * x + k1
+        enf a' = x
+        enf b' = y * k0
+        enf c' = y^9 + x * k1";
+
+    let air = compile(source).expect("compilation failed");
+    let graph = air.constraint_graph();
+    let expected = air
+        .integrity_constraints(0)
+        .iter()
+        .map(|constraint| graph.degree(constraint.node_index()))
+        .collect::<Vec<_>>();
+    assert_eq!(air.integrity_constraint_degrees(0), expected);
+    assert_eq!(
+        expected,
+        vec![
+            IntegrityConstraintDegree::with_cycles(2, vec![4]),
+            IntegrityConstraintDegree::with_cycles(4, vec![4, 8]),
+            IntegrityConstraintDegree::with_cycles(36, vec![4, 8]),
+        ]
+    );
+    assert_eq!(
+        expected
+            .iter()
+            .map(|degree| degree.degree())
+            .collect::<Vec<_>>(),
+        vec![3, 6, 38]
+    );
+}
+
+#[test]
+fn memoized_degrees_of_deeply_shared_graph() {
+    // Each node refers to the previous node twice, so a naive traversal of the tip of this
+    // graph visits an exponential number of nodes, while the graph itself is linear in size.
+    const DEPTH: usize = 1000;
+
+    let mut graph = AlgebraicGraph::default();
+    let a = graph.insert_node(Operation::Value(Value::TraceAccess(TraceAccess::new(
+        0, 0, 0,
+    ))));
+    let b = graph.insert_node(Operation::Value(Value::TraceAccess(TraceAccess::new(
+        0, 1, 0,
+    ))));
+    let mut tips = vec![a];
+    for _ in 0..DEPTH {
+        let prev = *tips.last().unwrap();
+        let mul = graph.insert_node(Operation::Mul(prev, b));
+        tips.push(graph.insert_node(Operation::Add(prev, mul)));
+    }
+
+    let degrees = graph.degrees(tips.iter());
+    let expected = (0..=DEPTH)
+        .map(|depth| IntegrityConstraintDegree::new(depth + 1))
+        .collect::<Vec<_>>();
+    assert_eq!(degrees, expected);
+
+    // The memoized results are identical to the recursive ones where the latter are tractable
+    for tip in tips.iter().take(16) {
+        assert_eq!(graph.degrees([tip]), vec![graph.degree(tip)]);
+    }
+}
+
+#[test]
+fn constraint_degree_exceeding_max_degree() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b, c]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        enf a' = a * b
+        enf b' = a * b * c * a * b * c";
+
+    let compiler = Compiler::new(DiagnosticsConfig {
+        verbosity: Verbosity::Warning,
+        warnings_as_errors: false,
+        no_warn: false,
+        display: Default::default(),
+    });
+    let air = compiler
+        .compile_with(source, |pass| pass.with_max_degree(Some(4)))
+        .expect("compilation failed");
+    assert_eq!(air.integrity_constraints(0).len(), 2);
+    let captured = compiler.emitter.captured();
+    assert!(captured.contains("constraint degree exceeds the maximum"));
+    assert!(captured.contains(
+        "integrity constraint 1 of the `main` trace segment has degree 6, but the maximum degree is 4"
+    ));
+    assert!(!captured.contains("integrity constraint 0 of"));
+}
+
+#[test]
+fn err_constraint_degree_exceeding_max_degree_with_warnings_as_errors() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b, c]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        enf a' = a * b
+        enf b' = a * b * c * a * b * c";
+
+    let compiler = Compiler::default();
+    assert!(compiler.compile(source).is_ok());
+    assert!(compiler
+        .compile_with(source, |pass| pass.with_max_degree(Some(4)))
+        .is_err());
+    assert!(compiler
+        .emitter
+        .captured()
+        .contains("integrity constraint 1 of the `main` trace segment has degree 6"));
+}
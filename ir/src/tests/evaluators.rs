@@ -61,6 +61,9 @@ fn evaluator_with_main_and_aux_cols() {
     public_inputs:
         stack_inputs: [16]
     
+    random_values:
+        rand: [1]
+
     boundary_constraints:
         enf clk.first = 0
     
@@ -84,6 +87,9 @@ fn ev_call_with_aux_only() {
     public_inputs:
         stack_inputs: [16]
     
+    random_values:
+        rand: [1]
+
     boundary_constraints:
         enf clk.first = 0
     
@@ -139,6 +145,9 @@ fn ev_call_inside_evaluator_with_aux() {
     public_inputs:
         stack_inputs: [16]
     
+    random_values:
+        rand: [1]
+
     boundary_constraints:
         enf clk.first = 0
     
@@ -170,6 +179,54 @@ fn ev_fn_call_with_column_group() {
     assert!(compile(source).is_ok());
 }
 
+#[test]
+fn ev_fn_call_with_column_group_comprehension() {
+    let source = "
+    def test
+    ev are_binary([bits[4]]):
+        enf x^2 = x for x in bits
+
+    trace_columns:
+        main: [clk, b[4]]
+
+    public_inputs:
+        stack_inputs: [16]
+
+    boundary_constraints:
+        enf clk.first = 0
+
+    integrity_constraints:
+        enf are_binary([b])";
+
+    let air = compile(source).expect("compilation failed");
+    assert_eq!(air.integrity_constraints(0).len(), 4);
+}
+
+#[test]
+fn err_ev_fn_call_with_column_group_size_mismatch() {
+    let source = "
+    def test
+    ev are_binary([bits[4]]):
+        enf x^2 = x for x in bits
+
+    trace_columns:
+        main: [clk, b[3]]
+
+    public_inputs:
+        stack_inputs: [16]
+
+    boundary_constraints:
+        enf clk.first = 0
+
+    integrity_constraints:
+        enf are_binary([b])";
+
+    expect_diagnostic(
+        source,
+        "callee expects 4 trace columns here, but this argument only provides 3",
+    );
+}
+
 #[test]
 fn err_ev_fn_call_wrong_segment_columns() {
     let source = "
@@ -184,6 +241,9 @@ fn err_ev_fn_call_wrong_segment_columns() {
     public_inputs:
         stack_inputs: [16]
     
+    random_values:
+        rand: [1]
+
     boundary_constraints:
         enf b.first = 0
     
@@ -192,3 +252,509 @@ fn err_ev_fn_call_wrong_segment_columns() {
 
     expect_diagnostic(source, "callee expects columns from the $main trace");
 }
+
+#[test]
+fn ev_call_in_boundary_constraints() {
+    let source = "
+    def test
+    ev reset([clk, a]):
+        enf clk = 0
+        enf a = 1
+
+    ev advance_clock([clk]):
+        enf clk' = clk + 1
+
+    trace_columns:
+        main: [clk, a]
+
+    public_inputs:
+        stack_inputs: [16]
+
+    boundary_constraints:
+        enf reset([clk, a]).first
+
+    integrity_constraints:
+        enf advance_clock([clk])";
+
+    let air = compile(source).expect("compilation failed");
+    assert_eq!(air.num_boundary_constraints(0), 2);
+}
+
+#[test]
+fn err_ev_call_in_boundary_constraints_with_offset() {
+    let source = "
+    def test
+    ev advance_clock([clk]):
+        enf clk' = clk + 1
+
+    trace_columns:
+        main: [clk]
+
+    public_inputs:
+        stack_inputs: [16]
+
+    boundary_constraints:
+        enf advance_clock([clk]).first
+
+    integrity_constraints:
+        enf advance_clock([clk])";
+
+    expect_diagnostic(source, "invalid access of a trace column with offset");
+}
+
+#[test]
+fn err_ev_call_in_boundary_constraints_without_boundary() {
+    let source = "
+    def test
+    ev reset([clk]):
+        enf clk = 0
+
+    trace_columns:
+        main: [clk]
+
+    public_inputs:
+        stack_inputs: [16]
+
+    boundary_constraints:
+        enf reset([clk])
+
+    integrity_constraints:
+        enf clk' = clk + 1";
+
+    expect_diagnostic(source, "must specify the boundary they apply to");
+}
+
+#[test]
+fn err_ev_call_at_boundary_in_integrity_constraints() {
+    let source = "
+    def test
+    ev reset([clk]):
+        enf clk = 0
+
+    trace_columns:
+        main: [clk]
+
+    public_inputs:
+        stack_inputs: [16]
+
+    boundary_constraints:
+        enf clk.first = 0
+
+    integrity_constraints:
+        enf reset([clk]).last";
+
+    expect_diagnostic(source, "evaluators cannot be applied to the last row here");
+}
+
+#[test]
+fn boundary_evaluator_applied_to_multiple_columns() {
+    let source = "
+    def test
+    ev init([x, y[2]]):
+        enf x.first = 0
+        enf c.last = 1 for c in y
+
+    trace_columns:
+        main: [a, b[2], c, d[2]]
+
+    public_inputs:
+        stack_inputs: [16]
+
+    boundary_constraints:
+        enf init([a, b])
+        enf init([c, d])
+
+    integrity_constraints:
+        enf a' = a + c";
+
+    let air = compile(source).expect("compilation failed");
+    assert_eq!(
+        air.explain().to_string(),
+        "\
+boundary[0] (segment main, first row): a - 0 = 0
+boundary[1] (segment main, last row): b[0] - 1 = 0
+boundary[2] (segment main, last row): b[1] - 1 = 0
+boundary[3] (segment main, first row): c - 0 = 0
+boundary[4] (segment main, last row): d[0] - 1 = 0
+boundary[5] (segment main, last row): d[1] - 1 = 0
+integrity[0] (segment main, degree 1): a' - (a + c) = 0"
+    );
+}
+
+#[test]
+fn boundary_evaluator_calling_evaluators() {
+    let source = "
+    def test
+    ev init([x, y]):
+        enf reset([x]).first
+        enf finish([y])
+
+    ev finish([y]):
+        enf y.last = 1
+
+    ev reset([x]):
+        enf x = 0
+
+    trace_columns:
+        main: [a, b]
+
+    public_inputs:
+        stack_inputs: [16]
+
+    boundary_constraints:
+        enf init([a, b])
+
+    integrity_constraints:
+        enf a' = a + b";
+
+    let air = compile(source).expect("compilation failed");
+    assert_eq!(
+        air.explain().to_string(),
+        "\
+boundary[0] (segment main, first row): a - 0 = 0
+boundary[1] (segment main, last row): b - 1 = 0
+integrity[0] (segment main, degree 1): a' - (a + b) = 0"
+    );
+}
+
+#[test]
+fn err_boundary_evaluator_in_integrity_constraints() {
+    let source = "
+    def test
+    ev init([x]):
+        enf x.first = 0
+
+    trace_columns:
+        main: [a]
+
+    public_inputs:
+        stack_inputs: [16]
+
+    boundary_constraints:
+        enf a.last = 1
+
+    integrity_constraints:
+        enf init([a])";
+
+    expect_diagnostic(
+        source,
+        "this evaluator enforces boundary constraints, which are not permitted here",
+    );
+}
+
+#[test]
+fn err_boundary_evaluator_applied_at_boundary() {
+    let source = "
+    def test
+    ev init([x]):
+        enf x.first = 0
+
+    trace_columns:
+        main: [a]
+
+    public_inputs:
+        stack_inputs: [16]
+
+    boundary_constraints:
+        enf init([a]).last
+
+    integrity_constraints:
+        enf a' = a + 1";
+
+    expect_diagnostic(source, "this evaluator cannot be applied to the last row");
+}
+
+#[test]
+fn err_boundary_evaluator_with_integrity_constraints() {
+    let source = "
+    def test
+    ev init([x]):
+        enf x.first = 0
+        enf x' = x + 1
+
+    trace_columns:
+        main: [a]
+
+    public_inputs:
+        stack_inputs: [16]
+
+    boundary_constraints:
+        enf init([a])
+
+    integrity_constraints:
+        enf a' = a + 1";
+
+    expect_diagnostic(
+        source,
+        "expected this to be a reference to a trace column boundary",
+    );
+}
+
+#[test]
+fn builtin_is_binary() {
+    let source = "
+    def test
+    ev are_binary([a, b[2]]):
+        enf is_binary(a)
+        enf is_binary(b) for b in b
+
+    trace_columns:
+        main: [s, a, b[2]]
+
+    public_inputs:
+        stack_inputs: [16]
+
+    boundary_constraints:
+        enf a.first = 0
+
+    integrity_constraints:
+        enf are_binary([a, b])
+        enf is_binary(a + b[0])
+        enf is_binary(s') when s";
+
+    let air = compile(source).expect("compilation failed");
+    let constraints = air.integrity_constraints(0);
+    assert_eq!(constraints.len(), 5);
+    let degrees = constraints
+        .iter()
+        .map(|c| air.constraint_graph().degree(c.node_index()).base())
+        .collect::<Vec<_>>();
+    assert_eq!(degrees, [2, 2, 2, 2, 3]);
+}
+
+#[test]
+fn user_defined_is_binary_takes_precedence() {
+    let source = "
+    def test
+    ev is_binary([x]):
+        enf x^3 = x
+
+    trace_columns:
+        main: [a]
+
+    public_inputs:
+        stack_inputs: [16]
+
+    boundary_constraints:
+        enf a.first = 0
+
+    integrity_constraints:
+        enf is_binary([a])";
+
+    let air = compile(source).expect("compilation failed");
+    let root = air.integrity_constraints(0)[0].node_index();
+    assert_eq!(air.constraint_graph().degree(root).base(), 3);
+}
+
+#[test]
+fn err_builtin_is_binary_with_vector() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b[2]]
+
+    public_inputs:
+        stack_inputs: [16]
+
+    boundary_constraints:
+        enf a.first = 0
+
+    integrity_constraints:
+        enf is_binary(b)";
+
+    expect_diagnostic(source, "but this argument is of aggregate type");
+}
+
+#[test]
+fn err_builtin_is_binary_at_boundary() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b[2]]
+
+    public_inputs:
+        stack_inputs: [16]
+
+    boundary_constraints:
+        enf is_binary(a).first
+
+    integrity_constraints:
+        enf a' = a";
+
+    expect_diagnostic(source, "is_binary cannot be applied to the first row");
+}
+
+#[test]
+fn inlined_constraints_are_ordered_by_declaration() {
+    let source = r#"
+    def test
+    ev inner([x]):
+        @[name="inner_0"]
+        enf x' = x
+        @[name="inner_1"]
+        enf x^2 = x
+
+    ev outer([x, y]):
+        @[name="outer_0"]
+        enf y' = y + x
+        enf inner([x])
+        @[name="outer_1"]
+        enf y^3 = y
+
+    trace_columns:
+        main: [a, b, c]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        @[name="first"]
+        enf c' = c + 1
+        enf outer([a, b])
+        @[name="last"]
+        enf c^2 = c"#;
+
+    let air = compile(source).expect("compilation failed");
+    let names = air
+        .integrity_constraints(0)
+        .iter()
+        .map(|root| root.metadata()["name"].as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        ["first", "outer_0", "inner_0", "inner_1", "outer_1", "last"]
+    );
+}
+
+#[test]
+fn evaluator_with_positional_params() {
+    let named = "
+    def test
+    ev enforce_constraints([clk], [a, b]):
+        enf clk' = clk + 1
+        enf a' = a + b
+
+    trace_columns:
+        main: [clk]
+        aux: [a, b]
+
+    public_inputs:
+        stack_inputs: [16]
+
+    random_values:
+        rand: [1]
+
+    boundary_constraints:
+        enf clk.first = 0
+
+    integrity_constraints:
+        enf enforce_constraints([clk], [a, b])";
+    let positional = "
+    def test
+    ev enforce_constraints([clk], [a, b]):
+        enf $0' = $0 + 1
+        enf $1' = $1 + $2
+
+    trace_columns:
+        main: [clk]
+        aux: [a, b]
+
+    public_inputs:
+        stack_inputs: [16]
+
+    random_values:
+        rand: [1]
+
+    boundary_constraints:
+        enf clk.first = 0
+
+    integrity_constraints:
+        enf enforce_constraints([clk], [a, b])";
+
+    let named = compile(named).expect("compilation failed");
+    let positional = compile(positional).expect("compilation failed");
+    assert_eq!(
+        named.integrity_constraints(0),
+        positional.integrity_constraints(0)
+    );
+    assert_eq!(
+        named.integrity_constraints(1),
+        positional.integrity_constraints(1)
+    );
+}
+
+#[test]
+fn err_evaluator_positional_param_out_of_range() {
+    let source = "
+    def test
+    ev enforce_constraints([clk], [a, b]):
+        enf $0' = $0 + $3
+
+    trace_columns:
+        main: [clk]
+        aux: [a, b]
+
+    public_inputs:
+        stack_inputs: [16]
+
+    random_values:
+        rand: [1]
+
+    boundary_constraints:
+        enf clk.first = 0
+
+    integrity_constraints:
+        enf enforce_constraints([clk], [a, b])";
+
+    expect_diagnostic(
+        source,
+        "there are only 3 parameters in scope, but this refers to parameter 3",
+    );
+}
+
+#[test]
+fn err_positional_param_outside_evaluator() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk]
+
+    public_inputs:
+        stack_inputs: [16]
+
+    boundary_constraints:
+        enf clk.first = 0
+
+    integrity_constraints:
+        enf clk' = $0 + 1";
+
+    expect_diagnostic(
+        source,
+        "positional parameter references are only valid in evaluator and function bodies",
+    );
+}
+
+#[test]
+fn err_ev_call_in_boundary_constraints_with_periodic_column() {
+    let source = "
+    def test
+    ev reset([clk]):
+        enf clk = k
+
+    trace_columns:
+        main: [clk]
+
+    public_inputs:
+        stack_inputs: [16]
+
+    periodic_columns:
+        k: [1, 0]
+
+    boundary_constraints:
+        enf reset([clk]).first
+
+    integrity_constraints:
+        enf clk' = clk + 1";
+
+    expect_diagnostic(source, "cannot access periodic column");
+}
@@ -0,0 +1,37 @@
+use super::compile;
+
+#[test]
+fn explain_constraints() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, a, b[2]]
+        aux: [p]
+    public_inputs:
+        stack_inputs: [16]
+    periodic_columns:
+        k0: [1, 0, 0, 0]
+    random_values:
+        rand: [alpha, beta[2]]
+    boundary_constraints:
+        enf clk.first = 0
+        enf a.last = stack_inputs[1]
+        enf p.first = 1
+    integrity_constraints:
+        enf clk' = clk + 1
+        enf a' = a * (b[0] - b[1] - 1)
+        enf k0 * (a - b[1]^2) = 0
+        enf p' = p * (a + $rand[0] + beta[1])";
+
+    let air = compile(source).expect("compilation failed");
+    let expected = "\
+boundary[0] (segment main, first row): clk - 0 = 0
+boundary[1] (segment main, last row): a - stack_inputs[1] = 0
+integrity[0] (segment main, degree 1): clk' - (clk + 1) = 0
+integrity[1] (segment main, degree 2): a' - a * (b[0] - b[1] - 1) = 0
+integrity[2] (segment main, degree 2): k0 * (a - b[1] * b[1]) - 0 = 0
+boundary[0] (segment aux, first row): p - 1 = 0
+integrity[0] (segment aux, degree 2): p' - p * (a + alpha + beta[1]) = 0";
+
+    assert_eq!(air.explain().to_string(), expected);
+}
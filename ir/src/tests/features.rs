@@ -0,0 +1,163 @@
+use crate::{Operation, TraceAccess, Value};
+
+use super::{expect_diagnostic, Compiler};
+
+const SOURCE: &str = r#"
+    def test
+    trace_columns:
+        main: [a, @[feature="debug"] dbg[2], b]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+        @[feature="debug"]
+        enf dbg[0].first = 0
+    integrity_constraints:
+        enf a' = a + b
+        @[feature="debug"]
+        enf dbg[0]' = dbg[0] + a
+        enf $main[3]' = b
+        let x = a * b
+        @[feature="debug"]
+        enf dbg[1] = x"#;
+
+fn compile(source: &str, features: &[&'static str]) -> crate::Air {
+    let compiler = Compiler::default().with_features(features);
+    match compiler.compile(source) {
+        Ok(air) => air,
+        Err(err) => {
+            compiler.diagnostics.emit(err);
+            compiler.emitter.print_captured_to_stderr();
+            panic!("compilation failed");
+        }
+    }
+}
+
+/// Returns the columns of the main segment which are accessed in the integrity constraints
+fn integrity_columns(air: &crate::Air) -> Vec<usize> {
+    let graph = air.constraint_graph();
+    let mut columns = graph
+        .nodes()
+        .filter_map(|(_, node)| match node.op() {
+            Operation::Value(Value::TraceAccess(TraceAccess {
+                segment: 0, column, ..
+            })) => Some(*column),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    columns.sort();
+    columns.dedup();
+    columns
+}
+
+#[test]
+fn features_gate_constraints_and_columns() {
+    let disabled = compile(SOURCE, &[]);
+    assert_eq!(disabled.trace_segment_widths, vec![2]);
+    assert_eq!(disabled.num_boundary_constraints(0), 1);
+    assert_eq!(disabled.integrity_constraints(0).len(), 2);
+    // `b` takes the place of the disabled `dbg` columns
+    assert_eq!(integrity_columns(&disabled), vec![0, 1]);
+
+    let enabled = compile(SOURCE, &["debug"]);
+    assert_eq!(enabled.trace_segment_widths, vec![4]);
+    assert_eq!(enabled.num_boundary_constraints(0), 2);
+    assert_eq!(enabled.integrity_constraints(0).len(), 4);
+    assert_eq!(integrity_columns(&enabled), vec![0, 1, 2, 3]);
+}
+
+#[test]
+fn features_gate_constraints_in_evaluators() {
+    let source = r#"
+    def test
+    ev check([x, y]):
+        enf x' = x + y
+        @[feature="strict"]
+        enf y^2 = y
+
+    trace_columns:
+        main: [a, b]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        enf check([a, b])"#;
+
+    assert_eq!(compile(source, &[]).integrity_constraints(0).len(), 1);
+    assert_eq!(
+        compile(source, &["strict"]).integrity_constraints(0).len(),
+        2
+    );
+    // Enabling a feature which gates nothing has no effect
+    assert_eq!(
+        compile(source, &["debug"]).integrity_constraints(0).len(),
+        1
+    );
+}
+
+#[test]
+fn err_enabled_constraint_references_disabled_column() {
+    let source = r#"
+    def test
+    trace_columns:
+        main: [a, @[feature="debug"] dbg]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        enf a' = a + dbg"#;
+
+    expect_diagnostic(source, "reference to a disabled trace column");
+    assert_eq!(compile(source, &["debug"]).trace_segment_widths, vec![2]);
+}
+
+#[test]
+fn err_segment_access_to_disabled_column() {
+    let source = r#"
+    def test
+    trace_columns:
+        main: [a, @[feature="debug"] dbg, b]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        enf a' = $main[1]"#;
+
+    expect_diagnostic(source, "reference to a disabled trace column");
+}
+
+#[test]
+fn err_all_constraints_disabled() {
+    let source = r#"
+    def test
+    trace_columns:
+        main: [a]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        @[feature="debug"]
+        enf a' = a"#;
+
+    expect_diagnostic(source, "no integrity constraints are enabled");
+}
+
+#[test]
+fn err_invalid_trace_column_attribute() {
+    let source = r#"
+    def test
+    trace_columns:
+        main: [a, @[name="debug"] dbg]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        enf a' = a"#;
+
+    expect_diagnostic(source, "invalid trace column attribute");
+}
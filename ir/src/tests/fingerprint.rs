@@ -0,0 +1,73 @@
+use super::compile;
+
+const SOURCE: &str = "
+def test
+trace_columns:
+    main: [a, b]
+public_inputs:
+    stack_inputs: [16]
+periodic_columns:
+    k0: [1, 0]
+boundary_constraints:
+    enf a.first = stack_inputs[0]
+integrity_constraints:
+    enf a' = a + b * k0
+    enf b' = b^2";
+
+#[test]
+fn fingerprint_ignores_formatting() {
+    let reformatted = "
+# A comment, which should not affect the fingerprint
+def test
+
+trace_columns:
+    main: [a,b]
+
+public_inputs:
+    stack_inputs: [16]
+
+periodic_columns:
+    k0: [1,0]
+
+boundary_constraints:
+    enf a.first=stack_inputs[0]
+
+integrity_constraints:
+    # Another comment
+    enf a'   =   a + (b * k0)
+    enf b' = b^2";
+
+    let air = compile(SOURCE).expect("compilation failed");
+    let reformatted = compile(reformatted).expect("compilation failed");
+    assert_eq!(air.fingerprint(), reformatted.fingerprint());
+}
+
+#[test]
+fn fingerprint_is_deterministic() {
+    let air = compile(SOURCE).expect("compilation failed");
+    let fingerprint = air.fingerprint();
+    assert_eq!(fingerprint, air.fingerprint());
+    assert_eq!(
+        fingerprint,
+        compile(SOURCE).expect("compilation failed").fingerprint()
+    );
+}
+
+#[test]
+fn fingerprint_reflects_changes() {
+    let air = compile(SOURCE).expect("compilation failed");
+    let changes = [
+        // a constraint is changed
+        SOURCE.replace("b^2", "b^3"),
+        // a periodic column has different values
+        SOURCE.replace("k0: [1, 0]", "k0: [0, 1]"),
+        // a public input has a different size
+        SOURCE.replace("stack_inputs: [16]", "stack_inputs: [8]"),
+        // a trace column is added
+        SOURCE.replace("main: [a, b]", "main: [a, b, c]"),
+    ];
+    for changed in changes.iter() {
+        let changed = compile(changed).expect("compilation failed");
+        assert_ne!(air.fingerprint(), changed.fingerprint());
+    }
+}
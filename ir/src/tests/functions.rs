@@ -0,0 +1,226 @@
+use crate::IntegrityConstraintDegree;
+
+use super::{compile, expect_diagnostic};
+
+#[test]
+fn function_in_integrity_constraint() {
+    let source = "
+    def test
+    fn madd(x: felt, y: felt, z: felt) -> felt:
+        let product = x * y
+        return product + z
+
+    trace_columns:
+        main: [a, b, c]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        enf a' = madd(a, b, c)
+        enf b' = madd(a, b, 1) * madd(b, c, a')";
+
+    let air = compile(source).expect("compilation failed");
+    assert_eq!(
+        air.integrity_constraint_degrees(0),
+        vec![
+            IntegrityConstraintDegree::new(2),
+            IntegrityConstraintDegree::new(4),
+        ]
+    );
+}
+
+#[test]
+fn function_with_vector_param() {
+    let source = "
+    def test
+    fn aggregate(limb: felt[4]) -> felt:
+        return sum([2^i * x for (i, x) in (0..4, limb)])
+
+    trace_columns:
+        main: [a, b[4]]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        enf a = aggregate(b)
+        enf a' = aggregate([b[0] * b[1], b[1], b[2], b[3]])";
+
+    let air = compile(source).expect("compilation failed");
+    assert_eq!(
+        air.integrity_constraint_degrees(0),
+        vec![
+            IntegrityConstraintDegree::new(1),
+            IntegrityConstraintDegree::new(2),
+        ]
+    );
+}
+
+#[test]
+fn function_calling_function() {
+    let source = "
+    def test
+    fn flag(s0: binary, s1: binary) -> binary:
+        return s0 & !s1
+
+    fn flagged(s0: binary, s1: binary, value: felt) -> felt:
+        return flag(s0, s1) * value
+
+    trace_columns:
+        main: [a, s0, s1]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        enf is_binary(s0)
+        enf is_binary(s1)
+        enf a' = a + flagged(s0, s1, a)
+        enf a' = 0 when flag(s0, s1)";
+
+    let air = compile(source).expect("compilation failed");
+    assert_eq!(
+        air.integrity_constraint_degrees(0),
+        vec![
+            IntegrityConstraintDegree::new(2),
+            IntegrityConstraintDegree::new(2),
+            IntegrityConstraintDegree::new(3),
+            IntegrityConstraintDegree::new(3),
+        ]
+    );
+}
+
+#[test]
+fn function_in_constraint_comprehension() {
+    let source = "
+    def test
+    fn square(x: felt) -> felt:
+        return x * x
+
+    trace_columns:
+        main: [a[3], b[3]]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a[0].first = 0
+    integrity_constraints:
+        enf y' = square(x) for (x, y) in (a, b)";
+
+    let air = compile(source).expect("compilation failed");
+    assert_eq!(
+        air.integrity_constraint_degrees(0),
+        vec![IntegrityConstraintDegree::new(2); 3]
+    );
+}
+
+#[test]
+fn err_recursive_function() {
+    let source = "
+    def test
+    fn even(x: felt) -> felt:
+        return odd(x - 1)
+
+    fn odd(x: felt) -> felt:
+        return even(x - 1)
+
+    trace_columns:
+        main: [a]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        enf a' = even(a)";
+
+    expect_diagnostic(source, "recursive function");
+}
+
+#[test]
+fn err_function_accesses_trace_column() {
+    let source = "
+    def test
+    fn next(x: felt) -> felt:
+        return x + a
+
+    trace_columns:
+        main: [a]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        enf a' = next(a)";
+
+    expect_diagnostic(source, "Functions may only reference their parameters");
+}
+
+#[test]
+fn err_function_called_with_wrong_number_of_arguments() {
+    let source = "
+    def test
+    fn double(x: felt) -> felt:
+        return 2 * x
+
+    trace_columns:
+        main: [a]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        enf a' = double(a, a)";
+
+    expect_diagnostic(
+        source,
+        "this function expects 1 arguments, but 2 were given",
+    );
+}
+
+#[test]
+fn err_function_enforced_as_constraint() {
+    let source = "
+    def test
+    fn double(x: felt) -> felt:
+        return 2 * x
+
+    trace_columns:
+        main: [a]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        enf double(a)";
+
+    expect_diagnostic(
+        source,
+        "calls in constraints must be to evaluator functions",
+    );
+}
+
+#[test]
+fn function_with_positional_params() {
+    let source = "
+    def test
+    fn madd(x: felt, y: felt, z: felt) -> felt:
+        return $0 * $1 + $2
+
+    trace_columns:
+        main: [a, b, c]
+
+    public_inputs:
+        stack_inputs: [16]
+
+    boundary_constraints:
+        enf a.first = 0
+
+    integrity_constraints:
+        enf c' = madd(a, b, c)";
+
+    let air = compile(source).expect("compilation failed");
+    let expected = "\
+boundary[0] (segment main, first row): a - 0 = 0
+integrity[0] (segment main, degree 2): c' - (a * b + c) = 0";
+    assert_eq!(air.explain().to_string(), expected);
+}
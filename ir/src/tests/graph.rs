@@ -0,0 +1,90 @@
+use std::collections::BTreeMap;
+
+use crate::{AlgebraicGraph, Operation, TraceAccess, Value};
+
+use super::compile;
+
+#[test]
+fn compiled_graph_is_acyclic() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b, c]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        let x = a * b + c
+        enf a' = x * x
+        enf b' = x^3 + a";
+
+    let air = compile(source).expect("compilation failed");
+    air.constraint_graph().assert_acyclic();
+}
+
+#[test]
+#[should_panic(expected = "cycle detected in algebraic graph: 1 -> 2 -> 1")]
+fn graph_with_cycle() {
+    let mut graph = AlgebraicGraph::default();
+    let a = graph.insert_node(Operation::Value(Value::TraceAccess(TraceAccess::new(
+        0, 0, 0,
+    ))));
+    // the multiplication refers to the node inserted after it, which refers back to it
+    graph.insert_node(Operation::Mul(a + 2, a));
+    graph.insert_node(Operation::Add(a, a + 1));
+
+    graph.assert_acyclic();
+}
+
+#[test]
+fn iterate_nodes() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b, c]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        let x = a * b
+        enf a' = x + c
+        enf b' = x^9 - c";
+
+    let air = compile(source).expect("compilation failed");
+    let graph = air.constraint_graph();
+
+    let mut counts = BTreeMap::<&str, usize>::new();
+    for (_, node) in graph.nodes() {
+        let variant = match node.op() {
+            Operation::Value(_) => "value",
+            Operation::Add(_, _) => "add",
+            Operation::Sub(_, _) => "sub",
+            Operation::Mul(_, _) => "mul",
+            Operation::Exp(_, _) => "exp",
+        };
+        *counts.entry(variant).or_default() += 1;
+    }
+    assert_eq!(graph.nodes().len(), graph.num_nodes());
+    assert_eq!(
+        counts.into_iter().collect::<Vec<_>>(),
+        vec![("add", 1), ("exp", 1), ("mul", 1), ("sub", 4), ("value", 6)]
+    );
+
+    // the indices yielded refer to the nodes they are yielded with
+    for (index, node) in graph.nodes() {
+        assert_eq!(graph.node(&index).op(), node.op());
+    }
+
+    // each constraint is a tip of the graph, as none of them is a subgraph of another
+    let roots = graph.roots().map(|(index, _)| index).collect::<Vec<_>>();
+    let mut constraints = air
+        .boundary_constraints(0)
+        .iter()
+        .chain(air.integrity_constraints(0))
+        .map(|constraint| *constraint.node_index())
+        .collect::<Vec<_>>();
+    constraints.sort();
+    assert_eq!(roots, constraints);
+}
@@ -1,4 +1,8 @@
-use super::super::compile;
+use miden_diagnostics::{DiagnosticsConfig, Verbosity};
+
+use crate::Operation;
+
+use super::super::{compile, expect_diagnostic, Compiler};
 
 #[test]
 fn constraint_comprehension() {
@@ -9,6 +13,8 @@ fn constraint_comprehension() {
         aux: [a, b, c[4], d[4]]
     public_inputs:
         stack_inputs: [16]
+    random_values:
+        rand: [1]
     boundary_constraints:
         enf c[2].first = 0
     integrity_constraints:
@@ -26,10 +32,162 @@ fn ic_comprehension_with_selectors() {
         aux: [a, b, c[4], d[4]]
     public_inputs:
         stack_inputs: [16]
+    random_values:
+        rand: [1]
     boundary_constraints:
         enf c[2].first = 0
     integrity_constraints:
+        enf is_binary(fmp[0])
         enf c = d for (c, d) in (c, d) when !fmp[0]";
 
     assert!(compile(source).is_ok());
 }
+
+#[test]
+fn ic_comprehension_with_range_binding_exponent() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, fmp[2], ctx]
+        aux: [a, b, c[4], d[4]]
+    public_inputs:
+        stack_inputs: [16]
+    random_values:
+        rand: [1]
+    boundary_constraints:
+        enf c[2].first = 0
+    integrity_constraints:
+        enf c^i = d for (c, d, i) in (c, d, 1..5)";
+
+    let air = compile(source).expect("compilation failed");
+    let graph = air.constraint_graph();
+    let degrees = air
+        .integrity_constraints(1)
+        .iter()
+        .map(|constraint| graph.degree(constraint.node_index()).base())
+        .collect::<Vec<_>>();
+    assert_eq!(degrees, vec![1, 2, 3, 4]);
+}
+
+#[test]
+fn ic_comprehension_with_trivial_exponents() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, c[2]]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf c' = c^i for (c, i) in (c, 0..2)";
+
+    // The exponents only become constant once the comprehension is unrolled
+    let air = compile(source).expect("compilation failed");
+    let graph = air.constraint_graph();
+    let has_exp = graph
+        .nodes()
+        .any(|(_, node)| matches!(node.op(), Operation::Exp(..)));
+    assert!(!has_exp);
+    let degrees = air
+        .integrity_constraints(0)
+        .iter()
+        .map(|constraint| graph.degree(constraint.node_index()).base())
+        .collect::<Vec<_>>();
+    assert_eq!(degrees, vec![1, 1]);
+}
+
+#[test]
+fn ic_comprehension_with_filter_on_even_indices() {
+    let source = "
+    def test
+    const EVEN = [1, 0, 1, 0, 1, 0]
+    trace_columns:
+        main: [clk, c[6]]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf x' = x + i for (x, i, even) in (c, 0..6, EVEN) where even";
+
+    let air = compile(source).expect("compilation failed");
+    assert_eq!(
+        air.explain().to_string(),
+        "\
+boundary[0] (segment main, first row): clk - 0 = 0
+integrity[0] (segment main, degree 1): c[0]' - (c[0] + 0) = 0
+integrity[1] (segment main, degree 1): c[2]' - (c[2] + 2) = 0
+integrity[2] (segment main, degree 1): c[4]' - (c[4] + 4) = 0"
+    );
+}
+
+#[test]
+fn ic_comprehension_with_filter_and_selector() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, s, c[4]]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf x' = x for (x, i) in (c, 0..4) where i - 1 when s";
+
+    // The filter is evaluated at compile time, so only the selector remains in the constraints
+    let air = compile(source).expect("compilation failed");
+    assert_eq!(
+        air.explain().to_string(),
+        "\
+boundary[0] (segment main, first row): clk - 0 = 0
+integrity[0] (segment main, degree 2): (c[0]' - c[0]) * s = 0
+integrity[1] (segment main, degree 2): (c[2]' - c[2]) * s = 0
+integrity[2] (segment main, degree 2): (c[3]' - c[3]) * s = 0"
+    );
+}
+
+#[test]
+fn err_ic_comprehension_with_non_constant_filter() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, s, c[4]]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf x' = x for x in c where s";
+
+    expect_diagnostic(source, "expected this filter to be a constant");
+}
+
+#[test]
+fn ic_comprehension_over_large_range_warns() {
+    // The range was presumably meant to be `0..16`, and unrolls to more constraints than the
+    // default threshold, so a warning is raised, but the program still compiles
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        enf a' = a + i * b for i in 0..1600";
+
+    let compiler = Compiler::new(DiagnosticsConfig {
+        verbosity: Verbosity::Warning,
+        warnings_as_errors: false,
+        no_warn: false,
+        display: Default::default(),
+    });
+    let air = compiler.compile(source).expect("compilation failed");
+    assert_eq!(air.integrity_constraints(0).len(), 1600);
+    assert!(compiler
+        .emitter
+        .captured()
+        .contains("this comprehension unrolls to 1600 iterations"));
+}
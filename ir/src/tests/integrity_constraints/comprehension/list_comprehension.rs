@@ -9,6 +9,8 @@ fn list_comprehension() {
         aux: [a, b, c[4], d[4]]
     public_inputs:
         stack_inputs: [16]
+    random_values:
+        rand: [1]
     boundary_constraints:
         enf c[2].first = 0
     integrity_constraints:
@@ -27,6 +29,8 @@ fn lc_with_const_exp() {
         aux: [a, b, c[4], d[4]]
     public_inputs:
         stack_inputs: [16]
+    random_values:
+        rand: [1]
     boundary_constraints:
         enf c[2].first = 0
     
@@ -48,6 +52,9 @@ fn lc_with_non_const_exp() {
     public_inputs:
         stack_inputs: [16]
     
+    random_values:
+        rand: [1]
+
     boundary_constraints:
         enf c[2].first = 0
     
@@ -58,6 +65,51 @@ fn lc_with_non_const_exp() {
     expect_diagnostic(source, "expected exponent to be a constant");
 }
 
+#[test]
+fn lc_with_integer_exp() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, fmp[2], ctx]
+        aux: [a, b, c[4], d[4]]
+    public_inputs:
+        stack_inputs: [16]
+    random_values:
+        rand: [1]
+    boundary_constraints:
+        enf c[2].first = 0
+    integrity_constraints:
+        let x = [c^(2 * i + 1) for (i, c) in (0..4, c)]
+        let y = sum([c^(i - 1) for (i, c) in (1..5, c)])
+        enf clk = x[3] + y";
+
+    let air = compile(source).expect("compilation failed");
+    let expected = "\
+boundary[0] (segment aux, first row): c[2] - 0 = 0
+integrity[0] (segment aux, degree 7): clk - (c[3] * c[3] * c[3] * c[3] * c[3] * c[3] * c[3] + 1 + c[1] + c[2] * c[2] + c[3] * c[3] * c[3]) = 0";
+    assert_eq!(air.explain().to_string(), expected);
+}
+
+#[test]
+fn err_lc_with_negative_exp() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, fmp[2], ctx]
+        aux: [a, b, c[4], d[4]]
+    public_inputs:
+        stack_inputs: [16]
+    random_values:
+        rand: [1]
+    boundary_constraints:
+        enf c[2].first = 0
+    integrity_constraints:
+        let x = [c^(i - 1) for (i, c) in (0..4, c)]
+        enf clk = x[3]";
+
+    expect_diagnostic(source, "exponents must not be negative");
+}
+
 #[test]
 fn lc_with_two_lists() {
     let source = "
@@ -67,6 +119,8 @@ fn lc_with_two_lists() {
         aux: [a, b, c[4], d[4]]
     public_inputs:
         stack_inputs: [16]
+    random_values:
+        rand: [1]
     boundary_constraints:
         enf c[2].first = 0
     integrity_constraints:
@@ -85,6 +139,8 @@ fn lc_with_two_slices() {
         aux: [a, b, c[4], d[4]]
     public_inputs:
         stack_inputs: [16]
+    random_values:
+        rand: [1]
     boundary_constraints:
         enf c[2].first = 0
     integrity_constraints:
@@ -121,6 +177,8 @@ fn err_index_out_of_range_lc_ident() {
         aux: [a, b, c[4], d[4]]
     public_inputs:
         stack_inputs: [16]
+    random_values:
+        rand: [1]
     boundary_constraints:
         enf c[2].first = 0
     
@@ -144,6 +202,9 @@ fn err_index_out_of_range_lc_slice() {
     public_inputs:
         stack_inputs: [16]
     
+    random_values:
+        rand: [1]
+
     boundary_constraints:
         enf c[2].first = 0
     
@@ -167,6 +228,9 @@ fn err_non_const_exp_ident_iterable() {
     public_inputs:
         stack_inputs: [16]
     
+    random_values:
+        rand: [1]
+
     boundary_constraints:
         enf c[2].first = 0
     
@@ -187,6 +251,9 @@ fn err_non_const_exp_slice_iterable() {
     public_inputs:
         stack_inputs: [16]
     
+    random_values:
+        rand: [1]
+
     boundary_constraints:
         enf c[2].first = 0
     
@@ -207,6 +274,9 @@ fn err_duplicate_member() {
     public_inputs:
         stack_inputs: [16]
     
+    random_values:
+        rand: [1]
+
     boundary_constraints:
         enf c[2].first = 0
     
@@ -216,3 +286,266 @@ fn err_duplicate_member() {
 
     expect_diagnostic(source, "this name is already bound in this comprehension");
 }
+
+#[test]
+fn lc_with_stepped_range() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, fmp[2], ctx]
+        aux: [a, b, c[4], d[4]]
+    public_inputs:
+        stack_inputs: [16]
+    random_values:
+        rand: [1]
+    boundary_constraints:
+        enf c[2].first = 0
+    integrity_constraints:
+        let x = [2^i * c for (i, c) in (0..8..2, c)]
+        let y = sum([i * d for (i, d) in (1..9..2, d)])
+        enf clk = x[3] + y";
+
+    let air = compile(source).expect("compilation failed");
+    let expected = "\
+boundary[0] (segment aux, first row): c[2] - 0 = 0
+integrity[0] (segment aux, degree 1): clk - (64 * c[3] + 1 * d[0] + 3 * d[1] + 5 * d[2] + 7 * d[3]) = 0";
+    assert_eq!(air.explain().to_string(), expected);
+}
+
+#[test]
+fn err_lc_with_stepped_range_length_mismatch() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, fmp[2], ctx]
+        aux: [a, b, c[4], d[4]]
+    public_inputs:
+        stack_inputs: [16]
+    random_values:
+        rand: [1]
+    boundary_constraints:
+        enf c[2].first = 0
+    integrity_constraints:
+        let x = [2^i * c for (i, c) in (0..8..3, c)]
+        enf clk = x[2]";
+
+    expect_diagnostic(source, "this iterable has 4 elements");
+}
+
+#[test]
+fn err_lc_iterable_length_mismatch() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, fmp[2], ctx]
+        aux: [a, b, c[4], d[3]]
+    public_inputs:
+        stack_inputs: [16]
+    random_values:
+        rand: [1]
+    boundary_constraints:
+        enf c[2].first = 0
+    integrity_constraints:
+        let x = [c * d for (c, d) in (c, d)]
+        enf clk = x[2]";
+
+    expect_diagnostic(
+        source,
+        "all iterables of this comprehension must have the same length",
+    );
+}
+
+#[test]
+fn lc_with_matrix_row_iterable() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b, c]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        let m = [[1, 2, 3], [4, 5, 6]]
+        let s = [sum(row) for row in m]
+        enf a' = s[0] * a + s[1] * b";
+
+    let air = compile(source).expect("compilation failed");
+    let expected = compile(
+        "
+    def test
+    trace_columns:
+        main: [a, b, c]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        enf a' = 6 * a + 15 * b",
+    )
+    .expect("compilation failed");
+    assert_eq!(air.fingerprint(), expected.fingerprint());
+}
+
+#[test]
+fn lc_with_matrix_row_iterable_folds_in_field() {
+    // The rows are folded modulo p = 2^64 - 2^32 + 1, i.e. (p - 1) + (p - 1) = p - 2, and
+    // (p - 1) * (p - 1) = 1. The comprehensions are not constant, so the rows are folded when
+    // they are inlined rather than by constant propagation.
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b, c[2]]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        let m = [[18446744069414584320, 18446744069414584320], [1, 2]]
+        let s = [sum(row) * x for (row, x) in (m, c)]
+        let t = [prod(row) * x for (row, x) in (m, c)]
+        enf a' = s[0] * a + t[0] * b";
+
+    let air = compile(source).expect("compilation failed");
+    let expected = compile(
+        "
+    def test
+    trace_columns:
+        main: [a, b, c[2]]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        enf a' = 18446744069414584319 * c[0] * a + 1 * c[0] * b",
+    )
+    .expect("compilation failed");
+    assert_eq!(air.fingerprint(), expected.fingerprint());
+}
+
+#[test]
+fn lc_with_non_const_matrix_row_iterable() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b, c]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        let m = [[a, b], [c, a]]
+        let s = [row[0] * row[1] for row in m]
+        let t = [prod(row) for row in m]
+        enf a' = s[0] + t[1]";
+
+    let air = compile(source).expect("compilation failed");
+    let expected = compile(
+        "
+    def test
+    trace_columns:
+        main: [a, b, c]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        enf a' = a * b + c * a",
+    )
+    .expect("compilation failed");
+    assert_eq!(air.fingerprint(), expected.fingerprint());
+}
+
+#[test]
+fn err_lc_matrix_row_index_out_of_bounds() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b, c]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        let m = [[1, 2, 3], [4, 5, 6]]
+        let s = [row[0] + row[3] for row in m]
+        enf a' = s[0] * a + s[1] * b";
+
+    expect_diagnostic(
+        source,
+        "attempted to access an index which is out of bounds",
+    );
+}
+
+#[test]
+fn lc_with_felt_cast_of_range_index() {
+    let cast = "
+    def test
+    trace_columns:
+        main: [a, b, c[4]]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        let x = [felt(i) * col + felt(i + 1) for (i, col) in (0..4, c)]
+        enf a' = sum(x)
+        enf col' = col + felt(i) for (i, col) in (0..4, c)";
+    let explicit = "
+    def test
+    trace_columns:
+        main: [a, b, c[4]]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        let x = [0 * c[0] + 1, 1 * c[1] + 2, 2 * c[2] + 3, 3 * c[3] + 4]
+        enf a' = sum(x)
+        enf c[0]' = c[0] + 0
+        enf c[1]' = c[1] + 1
+        enf c[2]' = c[2] + 2
+        enf c[3]' = c[3] + 3";
+
+    let cast = compile(cast).expect("compilation failed");
+    let explicit = compile(explicit).expect("compilation failed");
+    assert_eq!(
+        cast.integrity_constraints(0),
+        explicit.integrity_constraints(0)
+    );
+}
+
+#[test]
+fn err_felt_cast_of_vector() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b, c[4]]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        enf a' = felt(c)";
+
+    expect_diagnostic(
+        source,
+        "this function expects a single field element argument",
+    );
+}
+
+#[test]
+fn err_felt_cast_of_value_outside_field() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b, c[4]]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        enf a' = a + felt(18446744069414584321)";
+
+    expect_diagnostic(source, "this value is not an element of the field");
+}
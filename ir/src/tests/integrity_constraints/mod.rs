@@ -1,4 +1,8 @@
-use super::{compile, expect_diagnostic};
+use miden_diagnostics::{DiagnosticsConfig, Verbosity};
+
+use crate::{Operation, TraceAccess, Value};
+
+use super::{compile, expect_diagnostic, Compiler};
 
 mod comprehension;
 
@@ -66,6 +70,144 @@ fn ic_op_exp() {
     assert!(compile(source).is_ok());
 }
 
+#[test]
+fn ic_op_exp_one() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf clk' = clk^1";
+
+    let air = compile(source).expect("compilation failed");
+    let graph = air.constraint_graph();
+    let root = air.integrity_constraints(0)[0].node_index();
+    let Operation::Sub(_, rhs) = graph.node(root).op() else {
+        panic!("expected subtraction, got {:?}", graph.node(root).op());
+    };
+    assert_eq!(
+        graph.node(rhs).op(),
+        &Operation::Value(Value::TraceAccess(TraceAccess::new(0, 0, 0)))
+    );
+    assert_eq!(graph.degree(root).base(), 1);
+}
+
+#[test]
+fn ic_op_exp_zero() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf clk' = clk^0";
+
+    let air = compile(source).expect("compilation failed");
+    let graph = air.constraint_graph();
+    let root = air.integrity_constraints(0)[0].node_index();
+    let Operation::Sub(_, rhs) = graph.node(root).op() else {
+        panic!("expected subtraction, got {:?}", graph.node(root).op());
+    };
+    assert_eq!(graph.node(rhs).op(), &Operation::Value(Value::Constant(1)));
+}
+
+#[test]
+fn ic_exp_lowering_preserves_degree() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b, c]
+    public_inputs:
+        stack_inputs: [16]
+    periodic_columns:
+        k: [1, 0, 0, 0]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        enf a' = a^2
+        enf a' = (a + b)^3
+        enf b' = k * b^5
+        enf c' = c^8
+        enf c' = c^9";
+
+    let lowered = Compiler::default()
+        .compile(source)
+        .expect("compilation failed");
+    let unlowered = Compiler::default()
+        .compile_with_exp_lowering_threshold(source, 0)
+        .expect("compilation failed");
+
+    let degrees = |air: &crate::Air| {
+        air.integrity_constraints(0)
+            .iter()
+            .map(|constraint| air.constraint_graph().degree(constraint.node_index()))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(degrees(&lowered), degrees(&unlowered));
+
+    // Only the exponent above the threshold remains
+    let graph = lowered.constraint_graph();
+    let exps = graph
+        .nodes()
+        .filter_map(|(_, node)| match node.op() {
+            Operation::Exp(_, exp) => Some(*exp),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(exps, vec![9]);
+}
+
+#[test]
+fn ic_equality_chain() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b, c, d]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        enf a' = b = c + 1 = d";
+
+    let air = compile(source).expect("compilation failed");
+    assert_eq!(air.integrity_constraints(0).len(), 3);
+}
+
+#[test]
+fn ic_equality_chain_with_selector() {
+    let source = "
+    def test
+    trace_columns:
+        main: [s, a, b, c]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        enf a' = b = c when s";
+
+    let air = compile(source).expect("compilation failed");
+    let constraints = air.integrity_constraints(0);
+    assert_eq!(constraints.len(), 2);
+    // Both constraints are multiplied by the selector, and so have degree 2
+    for constraint in constraints {
+        assert_eq!(
+            air.constraint_graph()
+                .degree(constraint.node_index())
+                .base(),
+            2
+        );
+    }
+}
+
 #[test]
 fn err_non_const_exp_outside_lc() {
     // non const exponents are not allowed outside of list comprehensions
@@ -77,6 +219,9 @@ fn err_non_const_exp_outside_lc() {
     public_inputs:
         stack_inputs: [16]
     
+    random_values:
+        rand: [1]
+
     boundary_constraints:
         enf c[2].first = 0
     
@@ -85,3 +230,233 @@ fn err_non_const_exp_outside_lc() {
 
     expect_diagnostic(source, "expected exponent to be a constant");
 }
+
+#[test]
+fn err_exp_too_large() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf clk' = clk^4294967296";
+
+    expect_diagnostic(source, "this value is too large for an exponent");
+}
+
+#[test]
+fn err_exp_periodic_column() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk]
+    public_inputs:
+        stack_inputs: [16]
+    periodic_columns:
+        k: [1, 2]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf clk' = clk^k";
+
+    expect_diagnostic(
+        source,
+        "expected exponent to be a constant integer, but this is a reference to periodic column(s)",
+    );
+}
+
+#[test]
+fn ic_neq() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b, inv]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        enf a' != b with inv";
+
+    let air = compile(source).expect("compilation failed");
+    let expected = "\
+boundary[0] (segment main, first row): a - 0 = 0
+integrity[0] (segment main, degree 2): (a' - b) * inv - 1 = 0
+integrity[1] (segment main, degree 3): (a' - b) * ((a' - b) * inv - 1) - 0 = 0";
+
+    assert_eq!(air.explain().to_string(), expected);
+}
+
+#[test]
+fn ic_neq_with_selector() {
+    let source = "
+    def test
+    trace_columns:
+        main: [s, a, b, inv]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        enf a != b with inv when s";
+
+    let air = compile(source).expect("compilation failed");
+    let degrees = air.integrity_constraint_degrees(0);
+    assert_eq!(degrees.len(), 2);
+    assert_eq!(degrees[0].base(), 3);
+    assert_eq!(degrees[1].base(), 4);
+}
+
+#[test]
+fn err_neq_without_helper() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        enf a != b";
+
+    expect_diagnostic(source, "`!=` constraints require a helper column");
+}
+
+#[test]
+fn err_neq_invalid_helper() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b, inv]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        enf a != b with inv + 1";
+
+    expect_diagnostic(
+        source,
+        "expected the helper of a `!=` constraint to be a trace column",
+    );
+}
+
+#[test]
+fn duplicate_constraints() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        enf a' = a + b
+        enf b' = a
+        enf a' = a + b";
+
+    let compiler = Compiler::new(DiagnosticsConfig {
+        verbosity: Verbosity::Warning,
+        warnings_as_errors: false,
+        no_warn: false,
+        display: Default::default(),
+    });
+    let air = compiler.compile(source).expect("compilation failed");
+    assert_eq!(air.integrity_constraints(0).len(), 3);
+    let captured = compiler.emitter.captured();
+    assert!(captured.contains("duplicate constraint"));
+    assert!(captured.contains("this constraint is identical to a previous constraint"));
+    assert!(captured.contains("It is still evaluated by the prover"));
+
+    let compiler = Compiler::new(DiagnosticsConfig {
+        verbosity: Verbosity::Warning,
+        warnings_as_errors: false,
+        no_warn: false,
+        display: Default::default(),
+    });
+    let air = compiler
+        .compile_with(source, |pass| pass.with_duplicate_constraints_removed(true))
+        .expect("compilation failed");
+    let roots = air.integrity_constraints(0);
+    assert_eq!(roots.len(), 2);
+    assert_ne!(roots[0].node_index(), roots[1].node_index());
+    assert!(compiler
+        .emitter
+        .captured()
+        .contains("This constraint has been removed from the output."));
+}
+
+#[test]
+fn err_duplicate_constraints() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        enf a' = a + b
+        enf a' = a + b";
+
+    expect_diagnostic(source, "duplicate constraint");
+}
+
+#[test]
+fn constraint_spans() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = stack_inputs[0]
+    integrity_constraints:
+        let x = a + b
+        enf a' = x
+        enf b' = a * b when a";
+
+    let compiler = Compiler::default();
+    let air = compiler.compile(source).expect("compilation failed");
+    let spans = air
+        .boundary_constraints(0)
+        .iter()
+        .chain(air.integrity_constraints(0))
+        .map(|root| compiler.codemap.source_slice(&root.span()).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        spans,
+        vec!["a.first = stack_inputs[0]", "a' = x", "b' = a * b"]
+    );
+}
+
+#[test]
+fn integrity_constraint_between_vectors() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a[3], b[3]]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a[0].first = 0
+    integrity_constraints:
+        enf a' = b";
+
+    let air = compile(source).expect("compilation failed");
+    assert_eq!(
+        air.explain().to_string(),
+        "\
+boundary[0] (segment main, first row): a[0] - 0 = 0
+integrity[0] (segment main, degree 1): a[0]' - b[0] = 0
+integrity[1] (segment main, degree 1): a[1]' - b[1] = 0
+integrity[2] (segment main, degree 1): a[2]' - b[2] = 0"
+    );
+}
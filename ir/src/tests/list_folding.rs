@@ -10,6 +10,8 @@ fn list_folding_on_const() {
         aux: [a, b, c[4], d[4]]
     public_inputs:
         stack_inputs: [16]
+    random_values:
+        rand: [1]
     boundary_constraints:
         enf c[2].first = 0
     integrity_constraints:
@@ -29,6 +31,8 @@ fn list_folding_on_variable() {
         aux: [a, b, c[4], d[4]]
     public_inputs:
         stack_inputs: [16]
+    random_values:
+        rand: [1]
     boundary_constraints:
         enf c[2].first = 0
     integrity_constraints:
@@ -49,6 +53,8 @@ fn list_folding_on_vector() {
         aux: [a, b, c[4], d[4]]
     public_inputs:
         stack_inputs: [16]
+    random_values:
+        rand: [1]
     boundary_constraints:
         enf c[2].first = 0
     integrity_constraints:
@@ -69,6 +75,8 @@ fn list_folding_on_lc() {
         aux: [a, b, c[4], d[4]]
     public_inputs:
         stack_inputs: [16]
+    random_values:
+        rand: [1]
     boundary_constraints:
         enf c[2].first = 0
     integrity_constraints:
@@ -89,6 +97,9 @@ fn list_folding_in_lc() {
     public_inputs:
         stack_inputs: [16]
     
+    random_values:
+        rand: [1]
+
     boundary_constraints:
         enf c[2].first = 0
     
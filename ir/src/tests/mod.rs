@@ -1,13 +1,23 @@
 mod access;
+mod attributes;
 mod boundary_constraints;
 mod constant;
+mod debug;
+mod degree;
 mod evaluators;
+mod explain;
+mod features;
+mod fingerprint;
+mod functions;
+mod graph;
 mod integrity_constraints;
 mod list_folding;
+mod periodic_columns;
 mod pub_inputs;
 mod random_values;
 mod selectors;
 mod source_sections;
+mod summary;
 mod trace;
 mod variables;
 
@@ -55,6 +65,7 @@ struct Compiler {
     codemap: Arc<CodeMap>,
     emitter: Arc<SplitEmitter>,
     diagnostics: Arc<DiagnosticsHandler>,
+    features: Vec<&'static str>,
 }
 impl Default for Compiler {
     fn default() -> Self {
@@ -80,17 +91,46 @@ impl Compiler {
             codemap,
             emitter,
             diagnostics,
+            features: vec![],
         }
     }
 
+    /// Enables the given features when compiling
+    pub fn with_features(mut self, features: &[&'static str]) -> Self {
+        self.features.extend_from_slice(features);
+        self
+    }
+
     pub fn compile(&self, source: &str) -> Result<crate::Air, CompileError> {
+        self.compile_with_exp_lowering_threshold(
+            source,
+            crate::passes::DEFAULT_EXP_LOWERING_THRESHOLD,
+        )
+    }
+
+    pub fn compile_with_exp_lowering_threshold(
+        &self,
+        source: &str,
+        threshold: usize,
+    ) -> Result<crate::Air, CompileError> {
+        self.compile_with(source, |pass| pass.with_exp_lowering_threshold(threshold))
+    }
+
+    /// Compiles `source`, using `configure` to set the options of the translation to the IR
+    pub fn compile_with<F>(&self, source: &str, configure: F) -> Result<crate::Air, CompileError>
+    where
+        F: for<'a> FnOnce(crate::passes::AstToAir<'a>) -> crate::passes::AstToAir<'a>,
+    {
         air_parser::parse(&self.diagnostics, self.codemap.clone(), source)
             .map_err(CompileError::Parse)
             .and_then(|ast| {
-                let mut pipeline =
-                    air_parser::transforms::ConstantPropagation::new(&self.diagnostics)
-                        .chain(air_parser::transforms::Inlining::new(&self.diagnostics))
-                        .chain(crate::passes::AstToAir::new(&self.diagnostics));
+                let mut pipeline = air_parser::transforms::FeatureGating::new(&self.diagnostics)
+                    .with_features(&self.features)
+                    .chain(air_parser::transforms::ConstantPropagation::new(
+                        &self.diagnostics,
+                    ))
+                    .chain(air_parser::transforms::Inlining::new(&self.diagnostics))
+                    .chain(configure(crate::passes::AstToAir::new(&self.diagnostics)));
                 pipeline.run(ast)
             })
     }
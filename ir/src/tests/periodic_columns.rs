@@ -0,0 +1,168 @@
+use miden_diagnostics::{DiagnosticsConfig, Verbosity};
+
+use super::{compile, expect_diagnostic, Compiler};
+
+#[test]
+fn periodic_columns_used() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b]
+    public_inputs:
+        stack_inputs: [16]
+    periodic_columns:
+        k0: [1, 0]
+        k1: [1, 1, 0, 0]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        enf a' = a * k0
+        enf b' = b + k1";
+
+    let air = compile(source).expect("compilation failed");
+    assert_eq!(air.periodic_columns.len(), 2);
+}
+
+#[test]
+fn err_periodic_column_unused() {
+    // `k1` is referenced, but only by a binding which does not contribute to any constraint
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b]
+    public_inputs:
+        stack_inputs: [16]
+    periodic_columns:
+        k0: [1, 0]
+        k1: [1, 1, 0, 0]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        let _x = b * k1
+        enf a' = a * k0";
+
+    expect_diagnostic(source, "`k1` is never referenced by a constraint");
+}
+
+#[test]
+fn unused_periodic_columns_pruned() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b]
+    public_inputs:
+        stack_inputs: [16]
+    periodic_columns:
+        k0: [1, 0]
+        k1: [1, 1, 0, 0]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        let _x = b * k1
+        enf a' = a * k0";
+
+    let compiler = Compiler::new(DiagnosticsConfig {
+        verbosity: Verbosity::Warning,
+        warnings_as_errors: false,
+        no_warn: false,
+        display: Default::default(),
+    });
+    let air = compiler.compile(source).expect("compilation failed");
+    assert_eq!(air.periodic_columns.len(), 2);
+
+    let air = compiler
+        .compile_with(source, |pass| {
+            pass.with_unused_periodic_columns_pruned(true)
+        })
+        .expect("compilation failed");
+    let names = air
+        .periodic_columns
+        .keys()
+        .map(|name| name.to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["test::k0"]);
+    assert!(compiler
+        .emitter
+        .captured()
+        .contains("This column has been removed from the output."));
+}
+
+#[test]
+fn periodic_columns_in_boundary_constraints() {
+    // the value of a periodic column at the first row is the first value of its cycle, and at
+    // the last row, the last value of its cycle
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b, c]
+    public_inputs:
+        stack_inputs: [16]
+    periodic_columns:
+        k0: [3, 5, 7, 9]
+    boundary_constraints:
+        let x = k0 * 2
+        enf a.first = k0
+        enf b.last = x + stack_inputs[0]
+        enf c.last(1) = k0
+    integrity_constraints:
+        enf a' = a * k0";
+
+    let air = compile(source).expect("compilation failed");
+    assert_eq!(
+        air.explain().to_string(),
+        "\
+boundary[0] (segment main, first row): a - 3 = 0
+boundary[1] (segment main, last row): b - (18 + stack_inputs[0]) = 0
+boundary[2] (segment main, row last - 1): c - 7 = 0
+integrity[0] (segment main, degree 1): a' - a * k0 = 0"
+    );
+}
+
+#[test]
+fn periodic_columns_in_boundary_constraints_folded() {
+    // once the values of periodic columns are substituted, operations over them are folded again
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b, c]
+    public_inputs:
+        stack_inputs: [16]
+    periodic_columns:
+        k0: [1, 0]
+    boundary_constraints:
+        enf a.first = k0 + 0
+        enf b.first = stack_inputs[0] * k0
+        enf c.last = stack_inputs[1] + k0
+    integrity_constraints:
+        enf a' = a";
+
+    let air = compile(source).expect("compilation failed");
+    assert_eq!(
+        air.explain().to_string(),
+        "\
+boundary[0] (segment main, first row): a - 1 = 0
+boundary[1] (segment main, first row): b - stack_inputs[0] = 0
+boundary[2] (segment main, last row): c - stack_inputs[1] = 0
+integrity[0] (segment main, degree 1): a' - a = 0"
+    );
+}
+
+#[test]
+fn periodic_columns_only_in_boundary_constraints_used() {
+    // `k0` is not referenced by the graph once its value is substituted, but it is still used
+    let source = "
+    def test
+    trace_columns:
+        main: [a]
+    public_inputs:
+        stack_inputs: [16]
+    periodic_columns:
+        k0: [3, 5]
+    boundary_constraints:
+        enf a.first = k0
+    integrity_constraints:
+        enf a' = a";
+
+    let air = compile(source).expect("compilation failed");
+    assert_eq!(air.periodic_columns.len(), 1);
+}
@@ -1,4 +1,4 @@
-use super::compile;
+use super::{compile, expect_diagnostic};
 
 #[test]
 fn bc_with_public_inputs() {
@@ -15,3 +15,170 @@ fn bc_with_public_inputs() {
 
     assert!(compile(source).is_ok());
 }
+
+#[test]
+fn bc_with_public_input_slices() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        let x = sum(stack_inputs[0..4])
+        let y = sum([z * 2 for z in stack_inputs[1..3]])
+        enf clk.first = x
+        enf clk.last = y
+    integrity_constraints:
+        enf clk' = clk - 1";
+
+    let air = compile(source).expect("compilation failed");
+    let expected = "\
+boundary[0] (segment main, first row): clk - (stack_inputs[0] + stack_inputs[1] + stack_inputs[2] + stack_inputs[3]) = 0
+boundary[1] (segment main, last row): clk - (stack_inputs[1] * 2 + stack_inputs[2] * 2) = 0
+integrity[0] (segment main, degree 1): clk' - (clk - 1) = 0";
+    assert_eq!(air.explain().to_string(), expected);
+}
+
+#[test]
+fn err_bc_with_public_input_slice_out_of_bounds() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        let x = sum(stack_inputs[14..18])
+        enf clk.first = x
+    integrity_constraints:
+        enf clk' = clk - 1";
+
+    expect_diagnostic(
+        source,
+        "attempted to access an index which is out of bounds",
+    );
+}
+
+#[test]
+fn bc_with_public_input_matrix() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, a]
+    public_inputs:
+        roots: [2][4]
+    boundary_constraints:
+        let x = sum(roots[1])
+        enf clk.first = roots[1][3]
+        enf clk.last = x
+        enf a.first = roots[0][2]
+    integrity_constraints:
+        enf clk' = clk - 1";
+
+    let air = compile(source).expect("compilation failed");
+    let expected = "\
+boundary[0] (segment main, first row): clk - roots[7] = 0
+boundary[1] (segment main, last row): clk - (roots[4] + roots[5] + roots[6] + roots[7]) = 0
+boundary[2] (segment main, first row): a - roots[2] = 0
+integrity[0] (segment main, degree 1): clk' - (clk - 1) = 0";
+    assert_eq!(air.explain().to_string(), expected);
+    // the rows of the matrix are laid out one after another
+    let roots = air.public_inputs().next().unwrap();
+    assert_eq!((roots.size, roots.columns), (2, Some(4)));
+    assert_eq!(roots.num_elements(), Some(8));
+}
+
+#[test]
+fn err_bc_with_public_input_matrix_out_of_bounds() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk]
+    public_inputs:
+        roots: [2][4]
+    boundary_constraints:
+        enf clk.first = roots[2][0]
+    integrity_constraints:
+        enf clk' = clk - 1";
+
+    expect_diagnostic(
+        source,
+        "attempted to access an index which is out of bounds",
+    );
+}
+
+#[test]
+fn err_bc_with_public_input_matrix_row() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk]
+    public_inputs:
+        roots: [2][4]
+    boundary_constraints:
+        enf clk.first = roots[1]
+    integrity_constraints:
+        enf clk' = clk - 1";
+
+    expect_diagnostic(source, "type mismatch");
+}
+
+#[test]
+fn bc_with_named_public_input_slices() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b, c]
+    public_inputs:
+        header: [root[4], nonce, tail[2]]
+    boundary_constraints:
+        enf a.first = root[3]
+        enf b.first = nonce
+        enf c.first = sum(tail)
+        enf a.last = sum([x * 2 for x in root[1..3]])
+    integrity_constraints:
+        enf a' = a + 1";
+
+    let air = compile(source).expect("compilation failed");
+    let expected = "\
+boundary[0] (segment main, first row): a - header[3] = 0
+boundary[1] (segment main, first row): b - header[4] = 0
+boundary[2] (segment main, first row): c - (header[5] + header[6]) = 0
+boundary[3] (segment main, last row): a - (header[1] * 2 + header[2] * 2) = 0
+integrity[0] (segment main, degree 1): a' - (a + 1) = 0";
+    assert_eq!(air.explain().to_string(), expected);
+    assert_eq!(air.public_inputs().next().unwrap().size, 7);
+}
+
+#[test]
+fn err_bc_with_named_public_input_slice_out_of_bounds() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a]
+    public_inputs:
+        header: [root[4], nonce[4]]
+    boundary_constraints:
+        enf a.first = root[4]
+    integrity_constraints:
+        enf a' = a + 1";
+
+    expect_diagnostic(source, "`root` has size 4, but index 4 was accessed");
+}
+
+#[test]
+fn err_ic_with_named_public_input_slice() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a]
+    public_inputs:
+        header: [root[4], nonce[4]]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        enf a' = a + nonce[0]";
+
+    expect_diagnostic(source, "cannot access public input(s) here");
+}
@@ -1,3 +1,5 @@
+use crate::{Operation, Value};
+
 use super::{compile, expect_diagnostic};
 
 #[test]
@@ -151,6 +153,28 @@ fn err_random_values_without_aux_cols() {
     );
 }
 
+#[test]
+fn err_aux_cols_without_random_values() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a]
+        aux: [p]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 2
+        enf p.first = 1
+    integrity_constraints:
+        enf a' = a + 1
+        enf p' = p * a";
+
+    expect_diagnostic(
+        source,
+        "declaring aux trace_columns requires a random_values declaration",
+    );
+}
+
 #[test]
 fn err_random_values_in_bc_against_main_cols() {
     let source = "
@@ -170,3 +194,111 @@ fn err_random_values_in_bc_against_main_cols() {
 
     expect_diagnostic(source, "Boundary constraints require both sides of the constraint to apply to the same trace segment");
 }
+
+#[test]
+fn random_values_custom_name_lowering() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b[12]]
+        aux: [c, d]
+    public_inputs:
+        stack_inputs: [16]
+    random_values:
+        alphas: [16]
+    boundary_constraints:
+        enf c.first = $alphas[10] * 2
+    integrity_constraints:
+        enf c' = $alphas[3] + 1";
+
+    let air = compile(source).expect("compilation failed");
+    let graph = air.constraint_graph();
+    let random_values = graph
+        .nodes()
+        .filter_map(|(_, node)| match node.op() {
+            Operation::Value(Value::RandomValue(index)) => Some(*index),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(random_values, vec![10, 3]);
+}
+
+#[test]
+fn err_random_values_default_name_with_custom_name() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b[12]]
+        aux: [c, d]
+    public_inputs:
+        stack_inputs: [16]
+    random_values:
+        alphas: [16]
+    boundary_constraints:
+        enf c.first = $rand[10] * 2
+    integrity_constraints:
+        enf c' = $alphas[3] + 1";
+
+    expect_diagnostic(source, "this variable is not defined");
+}
+
+#[test]
+fn random_values_lowering_of_all_access_forms() {
+    // `$rand[i]`, `m`, and `n[i]` all refer to elements of the same random values array, and
+    // must be lowered to the same values
+    let source = "
+    def test
+    trace_columns:
+        main: [a]
+        aux: [c, d, e]
+    public_inputs:
+        stack_inputs: [16]
+    random_values:
+        rand: [m, n[4]]
+    boundary_constraints:
+        enf c.first = 1
+    integrity_constraints:
+        enf c' = $rand[0] + $rand[2]
+        enf d' = m + n[1]
+        enf e' = n[3] + $rand[4]";
+
+    let air = compile(source).expect("compilation failed");
+    assert_eq!(random_value_indices(&air), vec![0, 2, 4]);
+}
+
+#[test]
+fn random_values_lowering_of_let_bound_aliases() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a]
+        aux: [c, d, e]
+    public_inputs:
+        stack_inputs: [16]
+    random_values:
+        rand: [m, n[4]]
+    boundary_constraints:
+        enf c.first = 1
+    integrity_constraints:
+        let x = m
+        let y = n
+        let z = n[2..4]
+        enf c' = x + y[0]
+        enf d' = sum(y)
+        enf e' = z[1] * sum([v for v in z])";
+
+    let air = compile(source).expect("compilation failed");
+    assert_eq!(random_value_indices(&air), vec![0, 1, 2, 3, 4]);
+}
+
+/// Returns the indices of the random values referenced by the constraints of `air`, in the order
+/// in which they were first referenced
+fn random_value_indices(air: &crate::Air) -> Vec<usize> {
+    air.constraint_graph()
+        .nodes()
+        .filter_map(|(_, node)| match node.op() {
+            Operation::Value(Value::RandomValue(index)) => Some(*index),
+            _ => None,
+        })
+        .collect()
+}
@@ -1,4 +1,8 @@
-use super::compile;
+use miden_diagnostics::{DiagnosticsConfig, Verbosity};
+
+use crate::{IntegrityConstraintDegree, Operation};
+
+use super::{compile, expect_diagnostic, Compiler};
 
 #[test]
 fn single_selector() {
@@ -33,6 +37,7 @@ fn chained_selectors() {
         enf clk.first = 0
     
     integrity_constraints:
+        enf is_binary(x) for x in s
         enf clk' = clk when (s[0] & !s[1]) | !s[2]'";
 
     assert!(compile(source).is_ok());
@@ -52,6 +57,7 @@ fn multiconstraint_selectors() {
         enf clk.first = 0
     
     integrity_constraints:
+        enf is_binary(x) for x in s
         enf clk' = 0 when s[0] & !s[1]
         enf match:
             case s[0] & s[1]: clk' = clk
@@ -60,6 +66,44 @@ fn multiconstraint_selectors() {
     assert!(compile(source).is_ok());
 }
 
+#[test]
+fn shared_selector_subgraph() {
+    let source = "
+    def test
+    trace_columns:
+        main: [s[2], a, b]
+
+    public_inputs:
+        stack_inputs: [16]
+
+    boundary_constraints:
+        enf a.first = 0
+
+    integrity_constraints:
+        enf a' = a when s[0] & !s[1]
+        enf b' = a + b when s[0] & !s[1]
+        enf is_binary(x) for x in s";
+
+    let air = compile(source).expect("compilation failed");
+    let graph = air.constraint_graph();
+    let selectors = air.integrity_constraints(0)[..2]
+        .iter()
+        .map(|root| match graph.node(root.node_index()).op() {
+            Operation::Mul(_, selector) => *selector,
+            op => panic!("expected a selector to be applied to the constraint, got {op:?}"),
+        })
+        .collect::<Vec<_>>();
+
+    // Both constraints are multiplied by the same selector node, and since identical nodes are
+    // never inserted twice, the subgraph of the selector is not duplicated either
+    assert_eq!(selectors.len(), 2);
+    assert_eq!(selectors[0], selectors[1]);
+    assert_eq!(
+        air.integrity_constraint_degrees(0)[..2],
+        vec![IntegrityConstraintDegree::new(3); 2]
+    );
+}
+
 #[test]
 fn selectors_in_evaluators() {
     let source = "
@@ -87,6 +131,8 @@ fn multiple_selectors_in_evaluators() {
     let source = "
     def test
     ev evaluator_with_selector([s0, s1, clk]):
+        enf is_binary(s0)
+        enf is_binary(s1)
         enf clk' - clk = 0 when s0 & !s1
     
     trace_columns:
@@ -121,6 +167,7 @@ fn selector_with_evaluator_call() {
         enf clk.first = 0
     
     integrity_constraints:
+        enf is_binary(x) for x in s
         enf unchanged([clk]) when s[0] & !s[1]";
 
     assert!(compile(source).is_ok());
@@ -149,6 +196,7 @@ fn selectors_inside_match() {
         enf clk.first = 0
 
     integrity_constraints:
+        enf is_binary(x) for x in s
         enf next_is_zero([clk]) when s[0] & !s[1]
         enf match:
             case s[1] & s[2]: is_unchanged([clk, s[0]])
@@ -156,3 +204,142 @@ fn selectors_inside_match() {
 
     assert!(compile(source).is_ok());
 }
+
+#[test]
+fn match_with_two_cases() {
+    let source = "
+    def test
+    trace_columns:
+        main: [s, clk]
+
+    public_inputs:
+        stack_inputs: [16]
+
+    boundary_constraints:
+        enf clk.first = 0
+
+    integrity_constraints:
+        enf is_binary(s)
+        enf match:
+            case s: clk' = clk + 1
+            case !s: clk' = clk";
+
+    let compiler = Compiler::new(DiagnosticsConfig {
+        verbosity: Verbosity::Info,
+        warnings_as_errors: false,
+        no_warn: false,
+        display: Default::default(),
+    });
+    let air = compiler.compile(source).expect("compilation failed");
+    // each case is lowered to a constraint gated by its selector
+    assert_eq!(air.integrity_constraints(0).len(), 3);
+    assert!(!compiler
+        .emitter
+        .captured()
+        .contains("match is not exhaustive"));
+}
+
+#[test]
+fn match_which_is_not_exhaustive() {
+    let source = "
+    def test
+    trace_columns:
+        main: [s[2], clk]
+
+    public_inputs:
+        stack_inputs: [16]
+
+    boundary_constraints:
+        enf clk.first = 0
+
+    integrity_constraints:
+        enf is_binary(x) for x in s
+        enf match:
+            case s[0] & s[1]: clk' = clk + 1
+            case !s[0] & !s[1]: clk' = clk";
+
+    // this is permitted, but reported as a note
+    let compiler = Compiler::new(DiagnosticsConfig {
+        verbosity: Verbosity::Info,
+        warnings_as_errors: true,
+        no_warn: false,
+        display: Default::default(),
+    });
+    let air = compiler.compile(source).expect("compilation failed");
+    assert_eq!(air.integrity_constraints(0).len(), 4);
+    let captured = compiler.emitter.captured();
+    assert!(captured.contains("match is not exhaustive"));
+    assert!(captured.contains("no case applies when s[0] = 1, s[1] = 0"));
+}
+
+#[test]
+fn err_match_with_overlapping_cases() {
+    let source = "
+    def test
+    trace_columns:
+        main: [s[2], clk]
+
+    public_inputs:
+        stack_inputs: [16]
+
+    boundary_constraints:
+        enf clk.first = 0
+
+    integrity_constraints:
+        enf match:
+            case s[0]: clk' = clk + 1
+            case s[0] & !s[1]: clk' = clk
+            case !s[0]: clk' = 0";
+
+    expect_diagnostic(source, "overlapping match cases");
+    expect_diagnostic(
+        source,
+        "Both cases apply when s[0] = 1, s[1] = 0, but the selectors of an `enf match` must be mutually exclusive",
+    );
+}
+
+#[test]
+fn err_match_with_unconstrained_selector() {
+    let source = "
+    def test
+    trace_columns:
+        main: [s, clk]
+
+    public_inputs:
+        stack_inputs: [16]
+
+    boundary_constraints:
+        enf clk.first = 0
+
+    integrity_constraints:
+        enf match:
+            case s: clk' = clk + 1
+            case !s: clk' = clk";
+
+    expect_diagnostic(source, "selector is not known to be binary");
+}
+
+#[test]
+fn err_match_with_non_binary_selector() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b, clk]
+
+    public_inputs:
+        stack_inputs: [16]
+
+    boundary_constraints:
+        enf clk.first = 0
+
+    integrity_constraints:
+        enf match:
+            case a + b: clk' = clk + 1
+            case !a: clk' = clk";
+
+    expect_diagnostic(source, "invalid selector");
+    expect_diagnostic(
+        source,
+        "this arithmetic expression produces a field element",
+    );
+}
@@ -64,7 +64,7 @@ fn err_pub_inputs_omitted() {
 
 #[test]
 fn err_bc_empty() {
-    // if boundary constraints are empty, an error should be returned at parser level.
+    // if boundary constraints are empty, an error should be returned when the module is built.
     let source = "
     def test
     trace_columns:
@@ -75,7 +75,10 @@ fn err_bc_empty() {
     integrity_constraints:
         enf clk' = clk + 1";
 
-    expect_diagnostic(source, "expected one of: '\"enf\"', '\"let\"'");
+    expect_diagnostic(
+        source,
+        "boundary_constraints section must contain at least one constraint",
+    );
 }
 
 #[test]
@@ -98,7 +101,7 @@ fn err_bc_omitted() {
 
 #[test]
 fn err_ic_empty() {
-    // if integrity constraints are empty, an error should be returned at parser level.
+    // if integrity constraints are empty, an error should be returned when the module is built.
     let source = "
     def test
     trace_columns:
@@ -109,7 +112,10 @@ fn err_ic_empty() {
         enf clk.first = 0
     integrity_constraints:";
 
-    expect_diagnostic(source, "expected one of: '\"enf\"', '\"let\"'");
+    expect_diagnostic(
+        source,
+        "integrity_constraints section must contain at least one constraint",
+    );
 }
 
 #[test]
@@ -0,0 +1,112 @@
+use crate::{
+    AnnotatedConstraintSummary, ConstraintKind, PeriodicColumnSummary, PublicInputSummary,
+    TraceSegmentSummary,
+};
+
+use super::compile;
+
+#[test]
+fn air_summary() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b, c]
+        aux: [p]
+    public_inputs:
+        stack_inputs: [16]
+    periodic_columns:
+        k0: [1, 0, 0, 0]
+    random_values:
+        rand: [2]
+    boundary_constraints:
+        enf a.first = 0
+        enf a.last = 1
+        enf p.first = 1
+    integrity_constraints:
+        enf a' = a * b * c
+        enf k0 * (b + c) = 0
+        enf p' = p * $rand[0]";
+
+    let air = compile(source).expect("compilation failed");
+    let summary = air.summary();
+
+    assert_eq!(summary.name, "test");
+    assert_eq!(
+        summary.trace_segments,
+        vec![
+            TraceSegmentSummary {
+                width: 3,
+                num_boundary_constraints: 2,
+                num_integrity_constraints: 2,
+                max_constraint_degree: 3,
+                annotated_constraints: vec![],
+            },
+            TraceSegmentSummary {
+                width: 1,
+                num_boundary_constraints: 1,
+                num_integrity_constraints: 1,
+                max_constraint_degree: 1,
+                annotated_constraints: vec![],
+            },
+        ]
+    );
+    assert_eq!(
+        summary.periodic_columns,
+        vec![PeriodicColumnSummary {
+            name: "test::k0".to_string(),
+            cycle: 4,
+        }]
+    );
+    assert_eq!(
+        summary.public_inputs,
+        vec![PublicInputSummary {
+            name: "stack_inputs".to_string(),
+            size: 16,
+        }]
+    );
+    assert_eq!(summary.num_random_values, 2);
+}
+
+#[test]
+fn air_summary_with_attributes() {
+    let source = r#"
+    def test
+    trace_columns:
+        main: [a, b]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+        @[name="b_init"]
+        enf b.first = 1
+    integrity_constraints:
+        @[name="a_binary", group="range_check"]
+        enf a^2 = a
+        enf b' = b + a"#;
+
+    let air = compile(source).expect("compilation failed");
+    let summary = air.summary();
+
+    assert_eq!(
+        summary.trace_segments[0].annotated_constraints,
+        vec![
+            AnnotatedConstraintSummary {
+                kind: ConstraintKind::Boundary,
+                index: 1,
+                metadata: [("name".to_string(), "b_init".to_string())].into(),
+            },
+            AnnotatedConstraintSummary {
+                kind: ConstraintKind::Integrity,
+                index: 0,
+                metadata: [
+                    ("group".to_string(), "range_check".to_string()),
+                    ("name".to_string(), "a_binary".to_string()),
+                ]
+                .into(),
+            },
+        ]
+    );
+    assert!(summary
+        .to_string()
+        .contains("    integrity constraint 0: group=\"range_check\", name=\"a_binary\""));
+}
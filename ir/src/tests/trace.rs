@@ -1,3 +1,5 @@
+use crate::{ConstraintDomain, IntegrityConstraintDegree, Operation, TraceAccess, Value};
+
 use super::{compile, expect_diagnostic};
 
 #[test]
@@ -9,6 +11,8 @@ fn trace_columns_index_access() {
         aux: [c, d]
     public_inputs:
         stack_inputs: [16]
+    random_values:
+        rand: [1]
     boundary_constraints:
         enf a.first = 1
     integrity_constraints:
@@ -18,6 +22,74 @@ fn trace_columns_index_access() {
     assert!(compile(source).is_ok());
 }
 
+#[test]
+fn trace_columns_multiple_aux_segments() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b]
+        aux: [c]
+        aux2: [d, e]
+    public_inputs:
+        stack_inputs: [16]
+    random_values:
+        rand: [2]
+    boundary_constraints:
+        enf a.first = 1
+        enf d.first = 1
+    integrity_constraints:
+        enf a' = a + b
+        enf c' = c * (a + $rand[0])
+        enf d' = d * (b + $rand[1]) + c
+        enf $aux2[1]' = e + d";
+
+    let air = compile(source).expect("compilation failed");
+    assert_eq!(air.trace_segment_widths, vec![2, 1, 2]);
+    assert_eq!(air.num_boundary_constraints(0), 1);
+    assert_eq!(air.num_boundary_constraints(1), 0);
+    assert_eq!(air.num_boundary_constraints(2), 1);
+    assert_eq!(air.integrity_constraints(0).len(), 1);
+    assert_eq!(air.integrity_constraints(1).len(), 1);
+    assert_eq!(air.integrity_constraints(2).len(), 2);
+    assert_eq!(air.trace_segment_name(0), "main");
+    assert_eq!(air.trace_segment_name(1), "aux");
+    assert_eq!(air.trace_segment_name(2), "aux2");
+}
+
+#[test]
+fn trace_len_in_constraints() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+        enf a.last = $trace_len - 1
+    integrity_constraints:
+        let n = $trace_len
+        enf a' = a + 1
+        enf b * ($trace_len - b) = n";
+
+    let air = compile(source).expect("compilation failed");
+    // all references share a single node, which does not contribute to the degree
+    let graph = air.constraint_graph();
+    let nodes = graph
+        .nodes()
+        .filter(|(_, node)| matches!(node.op(), Operation::Value(Value::TraceLen)))
+        .count();
+    assert_eq!(nodes, 1);
+    assert_eq!(
+        air.integrity_constraint_degrees(0),
+        vec![
+            IntegrityConstraintDegree::new(1),
+            IntegrityConstraintDegree::new(2)
+        ]
+    );
+    assert_eq!(air.num_boundary_constraints(0), 2);
+}
+
 #[test]
 fn trace_cols_groups() {
     let source = "
@@ -71,6 +143,22 @@ fn err_ic_column_undeclared() {
     expect_diagnostic(source, "this variable is not defined");
 }
 
+#[test]
+fn err_column_undeclared_suggestion() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, ctx]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf clk' = clkk + 1";
+
+    expect_diagnostic(source, "did you mean `clk`?");
+}
+
 #[test]
 fn err_bc_trace_cols_access_out_of_bounds() {
     // out of bounds in boundary constraints
@@ -90,7 +178,7 @@ fn err_bc_trace_cols_access_out_of_bounds() {
 
     expect_diagnostic(
         source,
-        "attempted to access an index which is out of bounds",
+        "column group `a` has size 4, but index 4 was accessed",
     );
 }
 
@@ -114,7 +202,45 @@ fn err_ic_trace_cols_access_out_of_bounds() {
 
     expect_diagnostic(
         source,
-        "attempted to access an index which is out of bounds",
+        "column group `a` has size 4, but index 4 was accessed",
+    );
+}
+
+#[test]
+fn err_ic_trace_cols_slice_out_of_bounds() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, a[4]]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf clk' = sum(a[2..6])";
+
+    expect_diagnostic(
+        source,
+        "column group `a` has size 4, but the range 2..6 was accessed",
+    );
+}
+
+#[test]
+fn err_ic_trace_segment_access_out_of_bounds() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, a]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf $main[2]' = clk";
+
+    expect_diagnostic(
+        source,
+        "trace segment `$main` has size 2, but index 2 was accessed",
     );
 }
 
@@ -133,3 +259,74 @@ fn err_ic_trace_cols_group_used_as_scalar() {
 
     expect_diagnostic(source, "type mismatch");
 }
+
+#[test]
+fn ic_with_row_offsets() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 1
+    integrity_constraints:
+        enf a'' = a' + a
+        enf b' = b + a";
+
+    let air = compile(source).expect("compilation failed");
+    let domains = air
+        .integrity_constraints(0)
+        .iter()
+        .map(|constraint| constraint.domain())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        domains,
+        vec![
+            ConstraintDomain::EveryFrame(3),
+            ConstraintDomain::EveryFrame(2)
+        ]
+    );
+}
+
+#[test]
+fn trace_columns_explicit_indices() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk @ 0, fmp @ 5]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf fmp' = fmp + $main[3]";
+
+    let air = compile(source).expect("compilation failed");
+    assert_eq!(air.trace_segment_widths, vec![6]);
+    let graph = air.constraint_graph();
+    let columns = graph
+        .nodes()
+        .filter_map(|(_, node)| match node.op() {
+            Operation::Value(Value::TraceAccess(TraceAccess { column, .. })) => Some(*column),
+            _ => None,
+        })
+        .collect::<std::collections::BTreeSet<_>>();
+    assert_eq!(columns.into_iter().collect::<Vec<_>>(), vec![0, 3, 5]);
+}
+
+#[test]
+fn err_trace_columns_overlapping_indices() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk @ 1, fmp @ 1]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf fmp' = fmp + 1";
+
+    expect_diagnostic(source, "but this already binds column 1");
+}
@@ -1,4 +1,8 @@
-use super::{compile, expect_diagnostic};
+use miden_diagnostics::{DiagnosticsConfig, Verbosity};
+
+use crate::{Operation, TraceAccess, Value};
+
+use super::{compile, expect_diagnostic, Compiler};
 
 #[test]
 fn let_scalar_constant_in_boundary_constraint() {
@@ -56,6 +60,32 @@ fn multi_constraint_nested_let_with_expressions_in_boundary_constraint() {
     assert!(compile(source).is_ok());
 }
 
+#[test]
+fn let_vector_comprehension_in_boundary_constraint() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, a[4]]
+    public_inputs:
+        stack_inputs: [4]
+    boundary_constraints:
+        let x = [s * 2 + i for (s, i) in (stack_inputs, 0..4)]
+        enf c.first = y for (c, y) in (a, x)
+        enf clk.first = 0
+    integrity_constraints:
+        enf clk' = clk + 1";
+
+    let air = compile(source).expect("compilation failed");
+    let expected = "\
+boundary[0] (segment main, first row): a[0] - (stack_inputs[0] * 2 + 0) = 0
+boundary[1] (segment main, first row): a[1] - (stack_inputs[1] * 2 + 1) = 0
+boundary[2] (segment main, first row): a[2] - (stack_inputs[2] * 2 + 2) = 0
+boundary[3] (segment main, first row): a[3] - (stack_inputs[3] * 2 + 3) = 0
+boundary[4] (segment main, first row): clk - 0 = 0
+integrity[0] (segment main, degree 1): clk' - (clk + 1) = 0";
+    assert_eq!(air.explain().to_string(), expected);
+}
+
 #[test]
 fn let_scalar_constant_in_boundary_constraint_both_domains() {
     let source = "
@@ -105,7 +135,7 @@ fn nested_let_with_expressions_in_integrity_constraint() {
         enf clk.last = 1
     integrity_constraints:
         let a = 1
-        let b = [a, a*a]
+        let _b = [a, a*a]
         let c = [[clk' - clk, clk - a], [1 + 8, 2^2]]
         enf c[0][0] = 1";
 
@@ -152,7 +182,7 @@ fn invalid_matrix_literal_with_leading_vector_binding() {
         let d = [a[0], [3, 4]]
         enf clk' = d[0][0]";
 
-    expect_diagnostic(source, "expected one of: '\"!\"', '\"(\"', 'decl_ident_ref', 'function_identifier', 'identifier', 'int'");
+    expect_diagnostic(source, "expected one of: '\"!\"', '\"$trace_len\"', '\"(\"', '\"-\"', '\"felt\"', 'decl_ident_ref', 'function_identifier', 'identifier', 'int'");
 }
 
 #[test]
@@ -211,7 +241,7 @@ fn invalid_trailing_let() {
         enf clk' = clk + a
         let a = 1";
 
-    expect_diagnostic(source, "expected one of: '\"enf\"', '\"let\"'");
+    expect_diagnostic(source, "missing statements after `let`");
 }
 
 #[test]
@@ -342,3 +372,394 @@ fn trace_binding_access_in_integrity_constraint() {
 
     assert!(compile(source).is_ok());
 }
+
+#[test]
+fn err_let_shadows_trace_column() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        let a = b * 2
+        enf b' = a";
+
+    expect_diagnostic(source, "this binding shadows a previous declaration");
+}
+
+#[test]
+fn err_let_shadows_public_input() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        let stack_inputs = [1, 2]
+        enf a.first = stack_inputs[0]
+    integrity_constraints:
+        enf b' = a";
+
+    expect_diagnostic(source, "this binding shadows a previous declaration");
+}
+
+#[test]
+fn err_let_shadows_constant() {
+    let source = "
+    def test
+    const A = 1
+    trace_columns:
+        main: [a, b]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        let A = a + b
+        enf b' = A";
+
+    expect_diagnostic(source, "this binding shadows a previous declaration");
+}
+
+#[test]
+fn let_used_in_nested_scope() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        let x = a + b
+        let y = [x * i for i in 0..2]
+        enf b' = y[1]";
+
+    let compiler = Compiler::new(DiagnosticsConfig {
+        verbosity: Verbosity::Warning,
+        warnings_as_errors: false,
+        no_warn: false,
+        display: Default::default(),
+    });
+    compiler.compile(source).expect("compilation failed");
+    assert!(!compiler.emitter.captured().contains("unused variable"));
+}
+
+#[test]
+fn err_let_unused() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        let x = a + b
+        enf b' = a";
+
+    expect_diagnostic(source, "this binding is never used");
+}
+
+#[test]
+fn let_unused_with_underscore_prefix() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        let _x = 1
+        enf a.first = 0
+    integrity_constraints:
+        let _y = a + b
+        enf b' = a";
+
+    let compiler = Compiler::new(DiagnosticsConfig {
+        verbosity: Verbosity::Warning,
+        warnings_as_errors: false,
+        no_warn: false,
+        display: Default::default(),
+    });
+    compiler.compile(source).expect("compilation failed");
+    assert!(!compiler.emitter.captured().contains("unused variable"));
+}
+
+#[test]
+fn let_shadows_trace_column() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        let a = b
+        enf b' = a";
+
+    // Shadowing is only a warning, so the program compiles when warnings are not treated as errors
+    let compiler = Compiler::new(DiagnosticsConfig {
+        verbosity: Verbosity::Warning,
+        warnings_as_errors: false,
+        no_warn: false,
+        display: Default::default(),
+    });
+    let air = compiler.compile(source).expect("compilation failed");
+    assert!(compiler
+        .emitter
+        .captured()
+        .contains("this binding shadows a previous declaration"));
+
+    // The constraint must refer to the let-bound value, not the shadowed trace column
+    let graph = air.constraint_graph();
+    let root = air.integrity_constraints(0)[0].node_index();
+    let Operation::Sub(lhs, rhs) = graph.node(root).op() else {
+        panic!("expected subtraction, got {:?}", graph.node(root).op());
+    };
+    assert_eq!(
+        graph.node(lhs).op(),
+        &Operation::Value(Value::TraceAccess(TraceAccess::new(0, 1, 1)))
+    );
+    assert_eq!(
+        graph.node(rhs).op(),
+        &Operation::Value(Value::TraceAccess(TraceAccess::new(0, 1, 0)))
+    );
+}
+
+#[test]
+fn let_with_binary_annotation() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, s[2], t]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf is_binary(x) for x in s
+        enf is_binary(t)
+        let lo: binary = s[0]
+        let flag: binary = lo & !t | s[1]
+        let one: binary = 1
+        let step: felt = flag + one
+        enf clk' = clk + step when flag & one";
+
+    let air = compile(source).expect("compilation failed");
+    let expected = "\
+boundary[0] (segment main, first row): clk - 0 = 0
+integrity[0] (segment main, degree 2): s[0] * s[0] - s[0] = 0
+integrity[1] (segment main, degree 2): s[1] * s[1] - s[1] = 0
+integrity[2] (segment main, degree 2): t * t - t = 0
+integrity[3] (segment main, degree 6): (clk' - (clk + s[0] * (1 - t) + s[1] - s[0] * (1 - t) * s[1] + 1)) * (s[0] * (1 - t) + s[1] - s[0] * (1 - t) * s[1]) * 1 = 0";
+    assert_eq!(air.explain().to_string(), expected);
+}
+
+#[test]
+fn err_let_binary_annotation_on_unconstrained_column() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, s]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        let flag: binary = s
+        enf clk' = clk + 1 when flag";
+
+    expect_diagnostic(source, "this expression is not known to be binary");
+}
+
+#[test]
+fn err_let_binary_annotation_on_conditionally_constrained_column() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, s, t]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf is_binary(s) when t
+        let flag: binary = s
+        enf clk' = clk + 1 when flag";
+
+    expect_diagnostic(source, "this expression is not known to be binary");
+}
+
+#[test]
+fn err_let_binary_annotation_on_arithmetic_expression() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, s]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf is_binary(s)
+        let flag: binary = 1 - s
+        enf clk' = clk + 1 when flag";
+
+    expect_diagnostic(source, "but this annotation requires a binary value");
+}
+
+#[test]
+fn err_let_scalar_annotation_on_vector() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        let a: felt = [1, 2]
+        enf clk' = clk + a[0]";
+
+    expect_diagnostic(source, "this expression has type vector of length 2");
+}
+
+#[test]
+fn err_logical_operator_with_arithmetic_operand() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, s, t]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf clk' = clk + 1 when s & (t - clk)";
+
+    expect_diagnostic(
+        source,
+        "this arithmetic expression produces a field element",
+    );
+}
+
+#[test]
+fn err_logical_operator_with_felt_variable() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, s]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        let x = clk + 1
+        enf clk' = x when !x | s";
+
+    expect_diagnostic(source, "this variable is a field element");
+}
+
+#[test]
+fn err_logical_operator_with_non_binary_constant() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, s]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf clk' = clk + 1 when s & 2";
+
+    expect_diagnostic(source, "this constant is neither 0 nor 1");
+}
+
+#[test]
+fn err_logical_operator_with_random_value() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, s]
+        aux: [p]
+    public_inputs:
+        stack_inputs: [16]
+    random_values:
+        rand: [2]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf p' = p * $rand[0] when s & $rand[1]";
+
+    expect_diagnostic(source, "random values are field elements");
+}
+
+#[test]
+fn err_logical_operator_with_unconstrained_column() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b, s]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        enf is_binary(s)
+        enf b = a & s";
+
+    expect_diagnostic(source, "operand is not known to be binary");
+    expect_diagnostic(source, "but `&` requires binary operands");
+}
+
+#[test]
+fn err_logical_operator_with_non_binary_periodic_column() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, s]
+    public_inputs:
+        stack_inputs: [16]
+    periodic_columns:
+        k: [1, 2]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf is_binary(s)
+        enf clk' = clk + 1 when s | k";
+
+    expect_diagnostic(source, "operand is not known to be binary");
+}
+
+#[test]
+fn logical_operator_with_proven_binary_operands() {
+    // Columns may be constrained to be binary after they are used as operands, and periodic
+    // columns whose values are all 0 or 1 are binary
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, s, t]
+    public_inputs:
+        stack_inputs: [16]
+    periodic_columns:
+        k: [1, 0]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf clk' = clk + 1 when s & !t | k
+        enf is_binary(s)
+        enf is_binary(t)";
+
+    assert!(compile(source).is_ok());
+}
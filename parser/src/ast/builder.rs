@@ -0,0 +1,125 @@
+use super::*;
+
+/// A builder for constructing a [Program] programmatically, e.g. for fuzzing or property
+/// testing, rather than by parsing AirScript source code.
+///
+/// Unlike [Program::new], the builder enforces the structural guarantees documented on
+/// [Program] when [AirBuilder::build] is called, and returns an [InvalidProgramError] if any
+/// of them are violated, rather than leaving the violation to cause a panic later during
+/// compilation.
+///
+/// Items are added to the root module of the program, so constants, periodic columns, and
+/// functions are qualified with the name of the program.
+///
+/// NOTE: The builder does not perform semantic analysis, so it is up to the caller to ensure
+/// that all identifiers referenced in the constraints are resolved to their definitions, in the
+/// same way as they would be following [Program::load].
+pub struct AirBuilder {
+    program: Program,
+}
+impl AirBuilder {
+    /// Creates a new builder for a [Program] with the given name.
+    pub fn new(name: Identifier) -> Self {
+        let mut program = Program::new(name);
+        program.modules.push(name);
+        Self { program }
+    }
+
+    /// Adds a trace segment to the program.
+    ///
+    /// Segments must be added in the order of their ids, starting with the `main` segment.
+    pub fn with_trace_segment(mut self, segment: TraceSegment) -> Self {
+        self.program.trace_columns.push(segment);
+        self
+    }
+
+    /// Adds a public input to the program.
+    pub fn with_public_input(mut self, input: PublicInput) -> Self {
+        self.program.public_inputs.insert(input.name, input);
+        self
+    }
+
+    /// Sets the random values of the program.
+    pub fn with_random_values(mut self, random_values: RandomValues) -> Self {
+        self.program.random_values = Some(random_values);
+        self
+    }
+
+    /// Adds a constant to the program.
+    pub fn with_constant(mut self, constant: Constant) -> Self {
+        let name = self.qualify(NamespacedIdentifier::Binding(constant.name));
+        self.program.constants.insert(name, constant);
+        self
+    }
+
+    /// Adds a periodic column to the program.
+    pub fn with_periodic_column(mut self, column: PeriodicColumn) -> Self {
+        let name = self.qualify(NamespacedIdentifier::Binding(column.name));
+        self.program.periodic_columns.insert(name, column);
+        self
+    }
+
+    /// Adds an evaluator function to the program.
+    pub fn with_evaluator(mut self, evaluator: EvaluatorFunction) -> Self {
+        let name = self.qualify(NamespacedIdentifier::Function(evaluator.name));
+        self.program.evaluators.insert(name, evaluator);
+        self
+    }
+
+    /// Adds a pure function to the program.
+    pub fn with_function(mut self, function: Function) -> Self {
+        let name = self.qualify(NamespacedIdentifier::Function(function.name));
+        self.program.functions.insert(name, function);
+        self
+    }
+
+    /// Appends a statement to the boundary_constraints block of the program.
+    pub fn with_boundary_constraint(mut self, statement: Statement) -> Self {
+        self.program.boundary_constraints.push(statement);
+        self
+    }
+
+    /// Appends a statement to the integrity_constraints block of the program.
+    pub fn with_integrity_constraint(mut self, statement: Statement) -> Self {
+        self.program.integrity_constraints.push(statement);
+        self
+    }
+
+    /// Validates the program constructed so far, and returns it if it upholds all of the
+    /// guarantees documented on [Program].
+    pub fn build(self) -> Result<Program, InvalidProgramError> {
+        let program = self.program;
+
+        match program.trace_columns.first() {
+            Some(segment) if segment.name == "$main" => (),
+            _ => return Err(InvalidProgramError::MissingMainTrace),
+        }
+        for (id, segment) in program.trace_columns.iter().enumerate() {
+            if segment.id != id {
+                return Err(InvalidProgramError::MisorderedTraceSegment(segment.span()));
+            }
+        }
+        if let Some(random_values) = program.random_values.as_ref() {
+            if program.trace_columns.len() < 2 {
+                return Err(InvalidProgramError::MissingAuxTrace(random_values.span()));
+            }
+        } else if let Some(aux) = program.trace_columns.get(1) {
+            return Err(InvalidProgramError::MissingRandomValues(aux.span()));
+        }
+        if program.public_inputs.is_empty() {
+            return Err(InvalidProgramError::MissingPublicInputs);
+        }
+        if program.boundary_constraints.is_empty() {
+            return Err(InvalidProgramError::EmptyBoundaryConstraints);
+        }
+        if program.integrity_constraints.is_empty() {
+            return Err(InvalidProgramError::EmptyIntegrityConstraints);
+        }
+
+        Ok(program)
+    }
+
+    fn qualify(&self, item: NamespacedIdentifier) -> QualifiedIdentifier {
+        QualifiedIdentifier::new(self.program.name, item)
+    }
+}
@@ -0,0 +1,137 @@
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, Weak},
+};
+
+use miden_diagnostics::{CodeMap, FileName};
+
+use super::Module;
+
+/// A cache of parsed library modules, which can be shared across compilations so that modules
+/// imported by many programs, e.g. a standard library, are only read and parsed once.
+///
+/// Modules are keyed by the path of the file they were parsed from, and are reused as long as the
+/// contents of that file are unchanged. The contents of a file on disk are read again on every
+/// lookup, so an edited file is parsed again, while a file which is only present in the [CodeMap]
+/// is compared using the contents in the [CodeMap].
+///
+/// The spans of a parsed module refer to the [CodeMap] it was parsed into, so a cached module is
+/// only reused by compilations which share that [CodeMap]. Using a different [CodeMap] replaces
+/// the cached module, rather than producing diagnostics which refer to the wrong source file.
+#[derive(Default)]
+pub struct ModuleCache {
+    inner: Mutex<ModuleCacheInner>,
+}
+
+#[derive(Default)]
+struct ModuleCacheInner {
+    modules: HashMap<PathBuf, CachedModule>,
+    hits: usize,
+    misses: usize,
+}
+
+struct CachedModule {
+    /// The codemap the module was parsed into
+    codemap: Weak<CodeMap>,
+    /// The version of the file the module was parsed from
+    version: ModuleVersion,
+    /// The module, as it was parsed, i.e. before semantic analysis
+    module: Module,
+}
+
+/// Identifies the contents of the file a module was parsed from, by their hash
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub(crate) struct ModuleVersion(u64);
+impl ModuleVersion {
+    /// Returns the current version of the module at `path`, or `None` if the file is neither
+    /// on disk, nor present in the codemap.
+    ///
+    /// The contents of a file on disk are always read again, rather than taken from the codemap
+    /// or identified by modification time, so that an edit is never missed, even when the codemap
+    /// still holds the previous contents, or the edit happens within the resolution of the clock.
+    pub(crate) fn of(codemap: &CodeMap, path: &Path) -> Option<Self> {
+        let mut hasher = DefaultHasher::new();
+        match std::fs::read_to_string(path) {
+            Ok(source) => source.hash(&mut hasher),
+            Err(_) => codemap
+                .get_by_name(&FileName::Real(path.to_path_buf()))?
+                .source()
+                .hash(&mut hasher),
+        }
+        Some(Self(hasher.finish()))
+    }
+}
+
+impl ModuleCache {
+    /// Creates a new, empty [ModuleCache]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of imports which were resolved using a cached module
+    pub fn hits(&self) -> usize {
+        self.inner.lock().unwrap().hits
+    }
+
+    /// Returns the number of imports which required the module to be parsed
+    pub fn misses(&self) -> usize {
+        self.inner.lock().unwrap().misses
+    }
+
+    /// Returns the number of modules in the cache
+    pub fn len(&self) -> usize {
+        self.inner.lock().unwrap().modules.len()
+    }
+
+    /// Returns true if the cache is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes all modules from the cache
+    pub fn clear(&self) {
+        self.inner.lock().unwrap().modules.clear();
+    }
+
+    /// Returns a copy of the module parsed from `path` into `codemap`, if it is still current.
+    pub(crate) fn get(
+        &self,
+        codemap: &Arc<CodeMap>,
+        path: &Path,
+        version: ModuleVersion,
+    ) -> Option<Module> {
+        let mut inner = self.inner.lock().unwrap();
+        let module = inner
+            .modules
+            .get(path)
+            .filter(|cached| {
+                cached.version == version && Weak::ptr_eq(&cached.codemap, &Arc::downgrade(codemap))
+            })
+            .map(|cached| cached.module.clone());
+        match module {
+            Some(_) => inner.hits += 1,
+            None => inner.misses += 1,
+        }
+        module
+    }
+
+    /// Records `module` as having been parsed from the given version of `path` into `codemap`
+    pub(crate) fn insert(
+        &self,
+        codemap: &Arc<CodeMap>,
+        path: PathBuf,
+        version: ModuleVersion,
+        module: Module,
+    ) {
+        self.inner.lock().unwrap().modules.insert(
+            path,
+            CachedModule {
+                codemap: Arc::downgrade(codemap),
+                version,
+                module,
+            },
+        );
+    }
+}
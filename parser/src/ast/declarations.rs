@@ -24,7 +24,7 @@
 //!
 //! There is no notion of public/private visiblity, so any declaration of the above types may be
 //! imported into another module, and "wildcard" imports will import all importable items.
-use std::{collections::HashSet, fmt};
+use std::{collections::HashMap, fmt};
 
 use miden_diagnostics::{SourceSpan, Spanned};
 
@@ -41,6 +41,10 @@ pub enum Declaration {
     ///
     /// Evaluator functions can be defined in any module of the program
     EvaluatorFunction(EvaluatorFunction),
+    /// A pure function definition
+    ///
+    /// Functions can be defined in any module of the program
+    Function(Function),
     /// A `periodic_columns` section declaration
     ///
     /// This may appear any number of times in the program, and may be declared in any module.
@@ -70,6 +74,57 @@ pub enum Declaration {
     /// There may only be one of these in the entire program, and it must
     /// appear in the root AirScript module, i.e. in a module declared with `def`
     IntegrityConstraints(Span<Vec<Statement>>),
+    /// An assertion about the width of a trace segment, e.g. `assert trace_width(main) == 34`
+    ///
+    /// These may only appear in the root AirScript module, and are checked once the
+    /// `trace_columns` section of the module is known.
+    TraceWidthAssertion(TraceWidthAssertion),
+    /// An assertion about the size of a public input, e.g. `assert public_input(stack_inputs).size == 16`
+    ///
+    /// These may only appear in the root AirScript module, and are checked once the
+    /// `public_inputs` section of the module is known.
+    PublicInputSizeAssertion(PublicInputSizeAssertion),
+}
+
+/// Asserts that the trace segment named `segment` is exactly `width` columns wide.
+///
+/// This allows programs which must match a fixed trace layout to catch accidental changes to
+/// the number of columns at compile-time.
+#[derive(Debug, Clone, PartialEq, Eq, Spanned)]
+pub struct TraceWidthAssertion {
+    #[span]
+    pub span: SourceSpan,
+    pub segment: Identifier,
+    pub width: usize,
+}
+impl TraceWidthAssertion {
+    /// Returns a new instance of a [TraceWidthAssertion]
+    pub const fn new(span: SourceSpan, segment: Identifier, width: usize) -> Self {
+        Self {
+            span,
+            segment,
+            width,
+        }
+    }
+}
+
+/// Asserts that the public input named `input` has exactly `size` elements, or, if it is a
+/// matrix, `size` rows.
+///
+/// This allows programs which are developed against a fixed verifier interface to catch
+/// accidental changes to the size of their public inputs at compile-time.
+#[derive(Debug, Clone, PartialEq, Eq, Spanned)]
+pub struct PublicInputSizeAssertion {
+    #[span]
+    pub span: SourceSpan,
+    pub input: Identifier,
+    pub size: usize,
+}
+impl PublicInputSizeAssertion {
+    /// Returns a new instance of a [PublicInputSizeAssertion]
+    pub const fn new(span: SourceSpan, input: Identifier, size: usize) -> Self {
+        Self { span, input, size }
+    }
 }
 
 /// Stores a constant's name and value. There are three types of constants:
@@ -78,8 +133,13 @@ pub enum Declaration {
 /// * Vector: \[1, 2, 3\]
 /// * Matrix: \[\[1, 2, 3\], \[4, 5, 6\]\]
 #[derive(Debug, Clone, Spanned)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Constant {
     #[span]
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "crate::ast::serialize::span")
+    )]
     pub span: SourceSpan,
     pub name: Identifier,
     pub value: ConstantExpr,
@@ -108,6 +168,7 @@ impl PartialEq for Constant {
 /// * Vector: \[1, 2, 3\]
 /// * Matrix: \[\[1, 2, 3\], \[4, 5, 6\]\]
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum ConstantExpr {
     Scalar(u64),
     Vector(Vec<u64>),
@@ -158,9 +219,12 @@ pub enum Import {
     /// Imports all items from `module`
     All { module: ModuleId },
     /// Imports `items` from `module`
+    ///
+    /// The items are keyed by the name they are bound to in the importing module, which is
+    /// their alias if imported with `use module::item as alias`, or the name of the item otherwise.
     Partial {
         module: ModuleId,
-        items: HashSet<Identifier>,
+        items: HashMap<Identifier, Identifier>,
     },
 }
 impl Import {
@@ -184,7 +248,7 @@ impl PartialEq for Import {
                     module: r,
                     items: rs,
                 },
-            ) if l == r => ls.difference(rs).next().is_none(),
+            ) if l == r => ls.len() == rs.len() && ls.iter().all(|(k, v)| rs.get(k) == Some(v)),
             _ => false,
         }
     }
@@ -197,12 +261,14 @@ impl PartialEq for Import {
 pub enum Export<'a> {
     Constant(&'a crate::ast::Constant),
     Evaluator(&'a EvaluatorFunction),
+    Function(&'a Function),
 }
 impl Export<'_> {
     pub fn name(&self) -> Identifier {
         match self {
             Self::Constant(item) => item.name,
             Self::Evaluator(item) => item.name,
+            Self::Function(item) => item.name,
         }
     }
 
@@ -214,6 +280,7 @@ impl Export<'_> {
         match self {
             Self::Constant(item) => Some(item.ty()),
             Self::Evaluator(_) => None,
+            Self::Function(item) => Some(item.return_ty),
         }
     }
 }
@@ -225,25 +292,72 @@ impl Export<'_> {
 /// length of the values vector is expected to be a power of 2 with a minimum length of 2,
 /// which is enforced during semantic analysis.
 #[derive(Debug, Clone, Spanned)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct PeriodicColumn {
     #[span]
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "crate::ast::serialize::span")
+    )]
     pub span: SourceSpan,
     pub name: Identifier,
     pub values: Vec<u64>,
+    /// The comprehension generating the values of this column, if it was declared with one,
+    /// e.g. `[i^2 for i in 0..8]`. The values of such a column are only known once constants
+    /// have been propagated, until which `values` is empty.
+    pub generator: Option<Box<ListComprehension>>,
 }
 impl PeriodicColumn {
     pub const fn new(span: SourceSpan, name: Identifier, values: Vec<u64>) -> Self {
-        Self { span, name, values }
+        Self {
+            span,
+            name,
+            values,
+            generator: None,
+        }
+    }
+
+    /// Creates a new periodic column whose values are produced by a comprehension over one or more
+    /// ranges of the same length, e.g. `[i^2 for i in 0..8]`.
+    ///
+    /// The comprehension is evaluated during constant propagation, so its body may also refer to
+    /// constants, and its values are computed in the field, but the length of the column is known
+    /// from the ranges.
+    pub fn generate(
+        span: SourceSpan,
+        name: Identifier,
+        generator: ListComprehension,
+    ) -> Result<Self, InvalidExprError> {
+        let mut len = None;
+        for iterable in generator.iterables.iter() {
+            match iterable {
+                Expr::Range(range) if *len.get_or_insert(range.len()) == range.len() => (),
+                invalid => return Err(InvalidExprError::InvalidPeriodicIterable(invalid.span())),
+            }
+        }
+
+        Ok(Self {
+            span,
+            name,
+            values: vec![],
+            generator: Some(Box::new(generator)),
+        })
     }
 
     pub fn period(&self) -> usize {
-        self.values.len()
+        match self.generator.as_deref() {
+            Some(generator) => match &generator.iterables[0] {
+                Expr::Range(range) => range.len(),
+                _ => unreachable!("periodic columns are only generated from ranges"),
+            },
+            None => self.values.len(),
+        }
     }
 }
 impl Eq for PeriodicColumn {}
 impl PartialEq for PeriodicColumn {
     fn eq(&self, other: &Self) -> bool {
-        self.name == other.name && self.values == other.values
+        self.name == other.name && self.values == other.values && self.generator == other.generator
     }
 }
 
@@ -252,13 +366,25 @@ impl PartialEq for PeriodicColumn {
 /// This declaration is only permitted in the root module.
 ///
 /// Public inputs are represented by a named identifier which is used to identify a fixed
-/// size array of length `size`.
+/// size array of length `size`, or a fixed size matrix of `size` rows and `columns` columns.
+///
+/// Like random values, the elements of an array may instead be declared as named slices, e.g.
+/// `header: [root[4], nonce[4]]`, in which case `header` is an array of 8 elements, and `root`
+/// and `nonce` refer to the first and last 4 of those elements respectively.
 #[derive(Debug, Clone, Spanned)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct PublicInput {
     #[span]
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "crate::ast::serialize::span")
+    )]
     pub span: SourceSpan,
     pub name: Identifier,
     pub size: usize,
+    pub columns: Option<usize>,
+    /// Zero or more bindings for individual elements or slices of the array
+    pub bindings: Vec<PublicInputBinding>,
 }
 impl PublicInput {
     #[inline]
@@ -267,13 +393,155 @@ impl PublicInput {
             span,
             name,
             size: size.try_into().unwrap(),
+            columns: None,
+            bindings: vec![],
         }
     }
+
+    /// Creates a new public input array from the given named slices
+    pub fn with_bindings(
+        span: SourceSpan,
+        name: Identifier,
+        raw_bindings: Vec<Span<(Identifier, usize)>>,
+    ) -> Self {
+        let mut bindings = Vec::with_capacity(raw_bindings.len());
+        let mut offset = 0;
+        for binding in raw_bindings.into_iter() {
+            let (binding_name, size) = binding.item;
+            bindings.push(PublicInputBinding::new(
+                binding.span(),
+                binding_name,
+                size,
+                offset,
+            ));
+            offset += size;
+        }
+
+        Self {
+            span,
+            name,
+            size: offset,
+            columns: None,
+            bindings,
+        }
+    }
+
+    /// Creates a new public input representing a matrix of `rows` rows and `columns` columns
+    #[inline]
+    pub fn new_matrix(span: SourceSpan, name: Identifier, rows: u64, columns: u64) -> Self {
+        Self {
+            span,
+            name,
+            size: rows.try_into().unwrap(),
+            columns: Some(columns.try_into().unwrap()),
+            bindings: vec![],
+        }
+    }
+
+    /// Returns the type of this public input
+    pub fn ty(&self) -> Type {
+        match self.columns {
+            None => Type::Vector(self.size),
+            Some(columns) => Type::Matrix(self.size, columns),
+        }
+    }
+
+    /// Returns the total number of elements in this public input, or `None` if it overflows
+    /// `usize`, which is rejected during semantic analysis.
+    pub fn num_elements(&self) -> Option<usize> {
+        self.size.checked_mul(self.columns.unwrap_or(1))
+    }
 }
 impl Eq for PublicInput {}
 impl PartialEq for PublicInput {
     fn eq(&self, other: &Self) -> bool {
-        self.name == other.name && self.size == other.size
+        self.name == other.name
+            && self.size == other.size
+            && self.columns == other.columns
+            && self.bindings == other.bindings
+    }
+}
+
+/// A named slice of a public input array, e.g. `root` in `header: [root[4], nonce[4]]`.
+///
+/// References to the binding are resolved to references to the corresponding elements of the
+/// public input during semantic analysis.
+#[derive(Copy, Clone, Spanned)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PublicInputBinding {
+    #[span]
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "crate::ast::serialize::span")
+    )]
+    pub span: SourceSpan,
+    /// The name of this binding
+    pub name: Identifier,
+    /// The number of elements bound
+    pub size: usize,
+    /// The offset in the public input array where this binding begins
+    pub offset: usize,
+}
+impl PublicInputBinding {
+    pub const fn new(span: SourceSpan, name: Identifier, size: usize, offset: usize) -> Self {
+        Self {
+            span,
+            name,
+            size,
+            offset,
+        }
+    }
+
+    /// Returns the type of this binding
+    pub fn ty(&self) -> Type {
+        match self.size {
+            1 => Type::Felt,
+            n => Type::Vector(n),
+        }
+    }
+
+    /// Returns the equivalent access of the public input array, given an access of this binding
+    pub fn access(&self, access_type: AccessType) -> Result<AccessType, InvalidAccessError> {
+        let is_scalar = self.size == 1;
+        match access_type {
+            AccessType::Default if is_scalar => Ok(AccessType::Index(self.offset)),
+            AccessType::Default => Ok(AccessType::Slice(self.offset..(self.offset + self.size))),
+            AccessType::Slice(_) if is_scalar => Err(InvalidAccessError::SliceOfScalar),
+            AccessType::Slice(range) if range.end > self.size => {
+                Err(InvalidAccessError::IndexOutOfBounds)
+            }
+            AccessType::Slice(range) => Ok(AccessType::Slice(
+                (self.offset + range.start)..(self.offset + range.end),
+            )),
+            AccessType::Index(_) if is_scalar => Err(InvalidAccessError::IndexIntoScalar),
+            AccessType::Index(idx) if idx >= self.size => Err(InvalidAccessError::IndexOutOfBounds),
+            AccessType::Index(idx) => Ok(AccessType::Index(self.offset + idx)),
+            AccessType::Matrix(_, _) => Err(InvalidAccessError::IndexIntoScalar),
+        }
+    }
+}
+impl Eq for PublicInputBinding {}
+impl PartialEq for PublicInputBinding {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.size == other.size && self.offset == other.offset
+    }
+}
+impl fmt::Debug for PublicInputBinding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PublicInputBinding")
+            .field("name", &self.name)
+            .field("size", &self.size)
+            .field("offset", &self.offset)
+            .finish()
+    }
+}
+impl fmt::Display for PublicInputBinding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.size == 1 {
+            write!(f, "{}", self.name)
+        } else {
+            write!(f, "{}[{}]", self.name, self.size)
+        }
     }
 }
 
@@ -308,8 +576,13 @@ impl PartialEq for PublicInput {
 /// [RandomValues] instance in that case would be `13`.
 ///
 #[derive(Clone, Spanned)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct RandomValues {
     #[span]
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "crate::ast::serialize::span")
+    )]
     pub span: SourceSpan,
     /// The name bound to the `random_values` array
     pub name: Identifier,
@@ -389,8 +662,13 @@ impl fmt::Display for RandomValues {
 ///
 /// It is represented by a named identifier and its size.
 #[derive(Copy, Clone, Spanned)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct RandBinding {
     #[span]
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "crate::ast::serialize::span")
+    )]
     pub span: SourceSpan,
     /// The name of this binding
     pub name: Identifier,
@@ -494,8 +772,13 @@ impl fmt::Display for RandBinding {
 /// represents one or a group of columns in the execution trace that are passed to the evaluator
 /// function, and enforce integrity constraints on those trace columns.
 #[derive(Debug, Clone, Spanned)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct EvaluatorFunction {
     #[span]
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "crate::ast::serialize::span")
+    )]
     pub span: SourceSpan,
     pub name: Identifier,
     pub params: Vec<TraceSegment>,
@@ -516,6 +799,15 @@ impl EvaluatorFunction {
             body,
         }
     }
+
+    /// Returns true if the body of this evaluator consists of boundary constraints, rather than
+    /// integrity constraints.
+    ///
+    /// Such evaluators may only be called from the `boundary_constraints` section, or from other
+    /// evaluators of the same kind, and are called without specifying a boundary.
+    pub fn is_boundary(&self) -> bool {
+        self.body.iter().any(|stmt| stmt.has_boundary_constraints())
+    }
 }
 impl Eq for EvaluatorFunction {}
 impl PartialEq for EvaluatorFunction {
@@ -523,3 +815,59 @@ impl PartialEq for EvaluatorFunction {
         self.name == other.name && self.params == other.params && self.body == other.body
     }
 }
+
+/// Functions are pure, in that they take a list of values as parameters, and compute a value
+/// from them, without enforcing any constraints.
+///
+/// The body of a function is a tree of `let` statements which ends in the value returned by the
+/// function. Calls to functions may be used anywhere an expression of their result type is
+/// expected, and are inlined at their call sites during compilation.
+#[derive(Debug, Clone, Spanned)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Function {
+    #[span]
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "crate::ast::serialize::span")
+    )]
+    pub span: SourceSpan,
+    pub name: Identifier,
+    pub params: Vec<(Identifier, Type)>,
+    pub return_ty: Type,
+    pub body: Vec<Statement>,
+}
+impl Function {
+    /// Creates a new function.
+    pub const fn new(
+        span: SourceSpan,
+        name: Identifier,
+        params: Vec<(Identifier, Type)>,
+        return_ty: Type,
+        body: Vec<Statement>,
+    ) -> Self {
+        Self {
+            span,
+            name,
+            params,
+            return_ty,
+            body,
+        }
+    }
+
+    /// Returns the type signature of this function
+    pub fn signature(&self) -> FunctionType {
+        FunctionType::Function(
+            self.params.iter().map(|(_, ty)| *ty).collect(),
+            self.return_ty,
+        )
+    }
+}
+impl Eq for Function {}
+impl PartialEq for Function {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.params == other.params
+            && self.return_ty == other.return_ty
+            && self.body == other.body
+    }
+}
@@ -1,6 +1,6 @@
 use std::{cell::Cell, fmt};
 
-use super::Statement;
+use super::{Statement, Type};
 
 /// Displays an item surrounded by brackets, e.g. `[foo]`
 pub struct DisplayBracketed<T>(pub T);
@@ -18,14 +18,6 @@ impl<'a, T: fmt::Display> fmt::Display for DisplayList<'a, T> {
     }
 }
 
-/// Displays an item surrounded by parentheses, e.g. `(foo)`
-pub struct DisplayParenthesized<T>(pub T);
-impl<T: fmt::Display> fmt::Display for DisplayParenthesized<T> {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "({})", &self.0)
-    }
-}
-
 /// Displays a slice of items surrounded by parentheses, e.g. `(foo)`
 pub struct DisplayTuple<'a, T>(pub &'a [T]);
 impl<'a, T: fmt::Display> fmt::Display for DisplayTuple<'a, T> {
@@ -34,6 +26,19 @@ impl<'a, T: fmt::Display> fmt::Display for DisplayTuple<'a, T> {
     }
 }
 
+/// Displays a type as it is written in AirScript, e.g. `felt[4]`
+pub struct DisplayType<'a>(pub &'a Type);
+impl<'a> fmt::Display for DisplayType<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            Type::Binary => f.write_str("binary"),
+            Type::Felt | Type::U32 => f.write_str("felt"),
+            Type::Vector(n) => write!(f, "felt[{}]", n),
+            Type::Matrix(rows, cols) => write!(f, "felt[{}][{}]", rows, cols),
+        }
+    }
+}
+
 /// Displays one or more items separated by commas, e.g. `foo, bar`
 pub struct DisplayCsv<T>(Cell<Option<T>>);
 impl<T, I> DisplayCsv<I>
@@ -67,7 +72,7 @@ pub struct DisplayStatement<'a> {
     pub indent: usize,
 }
 impl DisplayStatement<'_> {
-    const INDENT: &str = "    ";
+    const INDENT: &'static str = "    ";
 
     fn write_indent(&self, f: &mut fmt::Formatter) -> fmt::Result {
         for _ in 0..self.indent {
@@ -81,7 +86,16 @@ impl<'a> fmt::Display for DisplayStatement<'a> {
         self.write_indent(f)?;
         match self.statement {
             Statement::Let(ref expr) => {
-                writeln!(f, "let {} = {}", expr.name, expr.value)?;
+                match expr.annotation.as_ref() {
+                    Some(ty) => writeln!(
+                        f,
+                        "let {}: {} = {}",
+                        expr.name,
+                        DisplayType(&ty.item),
+                        expr.value
+                    )?,
+                    None => writeln!(f, "let {} = {}", expr.name, expr.value)?,
+                }
                 for statement in expr.body.iter() {
                     writeln!(f, "{}", statement.display(self.indent))?;
                 }
@@ -96,7 +110,26 @@ impl<'a> fmt::Display for DisplayStatement<'a> {
             Statement::EnforceAll(ref expr) => {
                 write!(f, "enf {}", expr)
             }
+            Statement::EnforceMatch(ref expr) => {
+                write!(f, "enf match:")?;
+                for arm in expr.arms.iter() {
+                    writeln!(f)?;
+                    for _ in 0..=self.indent {
+                        f.write_str(Self::INDENT)?;
+                    }
+                    let selector = arm
+                        .selector
+                        .as_ref()
+                        .expect("match cases always have a selector");
+                    write!(f, "case {}: {}", selector, arm.body)?;
+                }
+                Ok(())
+            }
+            Statement::WithAttributes(ref attrs, ref stmt) => {
+                write!(f, "{} {}", attrs, stmt.display(0))
+            }
             Statement::Expr(ref expr) => write!(f, "{}", expr),
+            Statement::Debug(ref expr) => write!(f, "debug({})", expr),
         }
     }
 }
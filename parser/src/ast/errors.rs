@@ -5,12 +5,32 @@ use miden_diagnostics::{Diagnostic, Label, SourceSpan, ToDiagnostic};
 pub enum InvalidExprError {
     #[error("this value is too large for an exponent")]
     InvalidExponent(SourceSpan),
+    #[error("exponents must not be negative")]
+    NegativeExponent(SourceSpan),
     #[error("expected exponent to be a constant")]
     NonConstantExponent(SourceSpan),
     #[error("accessing column boundaries is not allowed here")]
     BoundedSymbolAccess(SourceSpan),
     #[error("expected scalar expression")]
     InvalidScalarExpr(SourceSpan),
+    #[error("this value is not an element of the field")]
+    InvalidFieldElement(SourceSpan, u64),
+    #[error("`!=` constraints require a helper column")]
+    MissingInverseHelper(SourceSpan),
+    #[error("expected the helper of a `!=` constraint to be a trace column")]
+    InvalidInverseHelper(SourceSpan),
+    #[error("use `=` for constraint equality, not `==`")]
+    EqualityOperator(SourceSpan),
+    #[error("expected a range of the same length as the other iterables")]
+    InvalidPeriodicIterable(SourceSpan),
+    #[error("expected a constant expression over the bindings of the comprehension and constants")]
+    NonConstantPeriodicValue(SourceSpan),
+    #[error("expected a constant vector or range")]
+    NonConstantFoldIterable(SourceSpan),
+    #[error("expected a non-empty vector or range")]
+    EmptyFoldIterable(SourceSpan),
+    #[error("expected this filter to be a constant")]
+    NonConstantComprehensionFilter(SourceSpan),
 }
 impl Eq for InvalidExprError {}
 impl PartialEq for InvalidExprError {
@@ -27,6 +47,11 @@ impl ToDiagnostic for InvalidExprError {
                 .with_labels(vec![
                     Label::primary(span.source_id(), span).with_message(message)
                 ]),
+            Self::NegativeExponent(span) => Diagnostic::error()
+                .with_message("invalid expression")
+                .with_labels(vec![
+                    Label::primary(span.source_id(), span).with_message(message)
+                ]),
             Self::NonConstantExponent(span) => Diagnostic::error()
                 .with_message("invalid expression")
                 .with_labels(vec![
@@ -46,6 +71,108 @@ impl ToDiagnostic for InvalidExprError {
                 .with_labels(vec![
                     Label::primary(span.source_id(), span).with_message(message)
                 ]),
+            Self::InvalidFieldElement(span, modulus) => Diagnostic::error()
+                .with_message("invalid constant")
+                .with_labels(vec![
+                    Label::primary(span.source_id(), span).with_message(message)
+                ])
+                .with_notes(vec![format!(
+                    "Constants must be less than the field modulus, which is {modulus}"
+                )]),
+            Self::MissingInverseHelper(span) => Diagnostic::error()
+                .with_message("invalid constraint")
+                .with_labels(vec![
+                    Label::primary(span.source_id(), span).with_message(message)
+                ])
+                .with_notes(vec![
+                    "The inverse of the difference must be witnessed by a trace column, which is given with `with`, e.g. `enf a != b with inv`"
+                        .to_string(),
+                ]),
+            Self::InvalidInverseHelper(span) => Diagnostic::error()
+                .with_message("invalid constraint")
+                .with_labels(vec![
+                    Label::primary(span.source_id(), span).with_message(message)
+                ]),
+            Self::EqualityOperator(span) => Diagnostic::error()
+                .with_message("invalid constraint")
+                .with_labels(vec![
+                    Label::primary(span.source_id(), span).with_message(message)
+                ])
+                .with_notes(vec![
+                    "Constraints are written as equalities using `=`, e.g. `enf a' = a + b`".to_string(),
+                ]),
+            Self::InvalidPeriodicIterable(span) | Self::NonConstantPeriodicValue(span) => {
+                Diagnostic::error()
+                    .with_message("invalid periodic column declaration")
+                    .with_labels(vec![
+                        Label::primary(span.source_id(), span).with_message(message)
+                    ])
+                    .with_notes(vec![
+                        "Periodic columns may be generated from ranges and constants, e.g. `[i^2 for i in 0..8]`"
+                            .to_string(),
+                    ])
+            }
+            Self::NonConstantFoldIterable(span) | Self::EmptyFoldIterable(span) => {
+                Diagnostic::error()
+                    .with_message("invalid call")
+                    .with_labels(vec![
+                        Label::primary(span.source_id(), span).with_message(message)
+                    ])
+                    .with_notes(vec![
+                        "`min` and `max` are evaluated at compile time, so they may only be applied to constants, e.g. `max([1, 5, 3])`"
+                            .to_string(),
+                    ])
+            }
+            Self::NonConstantComprehensionFilter(span) => Diagnostic::error()
+                .with_message("invalid comprehension")
+                .with_labels(vec![
+                    Label::primary(span.source_id(), span).with_message(message)
+                ])
+                .with_notes(vec![
+                    "Filters are evaluated at compile time, so they may only reference constants and the bindings of ranges or constant iterables. Use `when` for a selector which is evaluated at runtime."
+                        .to_string(),
+                ]),
+        }
+    }
+}
+
+/// Represents a violation of the guarantees of a [crate::ast::Program] constructed via
+/// [crate::ast::AirBuilder]
+#[derive(Debug, thiserror::Error)]
+pub enum InvalidProgramError {
+    #[error("program must contain a `main` trace segment, declared before any other segment")]
+    MissingMainTrace,
+    #[error("trace segments must be added in the order of their ids")]
+    MisorderedTraceSegment(SourceSpan),
+    #[error("declaring random_values requires an aux trace segment")]
+    MissingAuxTrace(SourceSpan),
+    #[error("declaring an aux trace segment requires random_values")]
+    MissingRandomValues(SourceSpan),
+    #[error("program must contain at least one public input")]
+    MissingPublicInputs,
+    #[error("at least one boundary constraint must be declared")]
+    EmptyBoundaryConstraints,
+    #[error("at least one integrity constraint must be declared")]
+    EmptyIntegrityConstraints,
+}
+impl Eq for InvalidProgramError {}
+impl PartialEq for InvalidProgramError {
+    fn eq(&self, other: &Self) -> bool {
+        core::mem::discriminant(self) == core::mem::discriminant(other)
+    }
+}
+impl ToDiagnostic for InvalidProgramError {
+    fn to_diagnostic(self) -> Diagnostic {
+        let message = format!("{}", &self);
+        match self {
+            Self::MisorderedTraceSegment(span)
+            | Self::MissingAuxTrace(span)
+            | Self::MissingRandomValues(span) => Diagnostic::error()
+                .with_message("invalid program")
+                .with_labels(vec![
+                    Label::primary(span.source_id(), span).with_message(message)
+                ]),
+            _ => Diagnostic::error().with_message(message),
         }
     }
 }
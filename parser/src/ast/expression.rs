@@ -20,6 +20,71 @@ use super::*;
 /// A range literal, equivalent to the interval `[start, end)`.
 pub type Range = std::ops::Range<usize>;
 
+/// A range expression, i.e. `start..end`, or `start..end..step`, which produces
+/// the integers in the interval `[start, end)`, beginning at `start` and advancing
+/// by `step` at a time.
+///
+/// Unlike [Range], which is used for slicing, a range expression may only be used
+/// as an iterable in a comprehension.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct RangeExpr {
+    pub start: usize,
+    pub end: usize,
+    /// The distance between successive elements of the range, must be non-zero
+    pub step: usize,
+}
+impl RangeExpr {
+    /// Creates a new range expression over `[start, end)` with a step of 1
+    pub const fn new(start: usize, end: usize) -> Self {
+        Self {
+            start,
+            end,
+            step: 1,
+        }
+    }
+
+    /// Returns a copy of this range expression with the given step
+    pub const fn with_step(mut self, step: usize) -> Self {
+        self.step = step;
+        self
+    }
+
+    /// Returns the number of elements produced by this range
+    pub fn len(&self) -> usize {
+        self.iter().len()
+    }
+
+    /// Returns true if this range produces no elements
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the element of this range at `index`
+    pub fn get(&self, index: usize) -> usize {
+        self.start + index * self.step
+    }
+
+    /// Returns an iterator over the elements produced by this range
+    pub fn iter(&self) -> core::iter::StepBy<Range> {
+        (self.start..self.end).step_by(self.step)
+    }
+}
+impl From<Range> for RangeExpr {
+    fn from(range: Range) -> Self {
+        Self::new(range.start, range.end)
+    }
+}
+impl fmt::Display for RangeExpr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}..{}", self.start, self.end)?;
+        if self.step != 1 {
+            write!(f, "..{}", self.step)?;
+        }
+        Ok(())
+    }
+}
+
 /// Represents any type of identifier in AirScript
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Spanned)]
 pub struct Identifier(Span<Symbol>);
@@ -97,6 +162,7 @@ impl From<ResolvableIdentifier> for Identifier {
 /// It is still probably best practice to avoid having name conflicts between functions
 /// and bindings, but that is a matter of style rather than one of necessity.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Spanned)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum NamespacedIdentifier {
     Function(#[span] Identifier),
     Binding(#[span] Identifier),
@@ -155,6 +221,12 @@ impl QualifiedIdentifier {
         self.as_ref().name()
     }
 
+    /// Returns the identifier of the builtin function `name`
+    pub fn builtin(name: Identifier) -> Self {
+        let builtin_module = Identifier::new(SourceSpan::UNKNOWN, crate::symbols::Builtin);
+        Self::new(builtin_module, NamespacedIdentifier::Function(name))
+    }
+
     /// Returns true if this identifier refers to a known builtin function
     pub fn is_builtin(&self) -> bool {
         use crate::symbols;
@@ -162,7 +234,15 @@ impl QualifiedIdentifier {
         if self.module.name() == "$builtin" {
             match self.item {
                 NamespacedIdentifier::Function(id) => {
-                    matches!(id.name(), symbols::Sum | symbols::Prod)
+                    matches!(
+                        id.name(),
+                        symbols::Sum
+                            | symbols::Prod
+                            | symbols::Min
+                            | symbols::Max
+                            | symbols::IsBinary
+                            | symbols::Felt
+                    )
                 }
                 _ => false,
             }
@@ -185,6 +265,7 @@ impl fmt::Display for QualifiedIdentifier {
 
 /// Represents an identifier which requires name resolution at some stage during lowering.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Spanned)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum ResolvableIdentifier {
     /// This identifier is resolved to a local binding (i.e. function parameter or let-bound var)
     Local(#[span] Identifier),
@@ -271,19 +352,52 @@ impl fmt::Display for ResolvableIdentifier {
 
 /// Expressions which are valid in the body of a `let` statement, or in a function call.
 #[derive(Clone, PartialEq, Eq, Spanned)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum Expr {
     /// A constant expression
-    Const(Span<ConstantExpr>),
+    Const(
+        #[cfg_attr(
+            feature = "serde",
+            serde(serialize_with = "crate::ast::serialize::spanned")
+        )]
+        Span<ConstantExpr>,
+    ),
     /// An expression which evaluates to a vector of integers in the given range
-    Range(Span<Range>),
+    Range(
+        #[cfg_attr(
+            feature = "serde",
+            serde(serialize_with = "crate::ast::serialize::spanned")
+        )]
+        Span<RangeExpr>,
+    ),
     /// A vector of expressions
     ///
     /// A vector may be used to represent matrices in some situations, but such matrices
     /// must always be composed of scalar values. It is not permitted to have arbitrarily
     /// deep vectors.
-    Vector(Span<Vec<Expr>>),
+    Vector(
+        #[cfg_attr(
+            feature = "serde",
+            serde(serialize_with = "crate::ast::serialize::spanned")
+        )]
+        Span<Vec<Expr>>,
+    ),
     /// A matrix of scalar expressions
-    Matrix(Span<Vec<Vec<ScalarExpr>>>),
+    Matrix(
+        #[cfg_attr(
+            feature = "serde",
+            serde(serialize_with = "crate::ast::serialize::spanned")
+        )]
+        Span<Vec<Vec<ScalarExpr>>>,
+    ),
+    /// The length of the execution trace, i.e. `$trace_len`, which is only known at runtime
+    TraceLen(
+        #[cfg_attr(
+            feature = "serde",
+            serde(serialize_with = "crate::ast::serialize::span_of")
+        )]
+        Span<()>,
+    ),
     /// A reference to a named value of any type
     SymbolAccess(SymbolAccess),
     /// A binary operator over scalar values
@@ -309,9 +423,10 @@ impl Expr {
     pub fn ty(&self) -> Option<Type> {
         match self {
             Self::Const(constant) => Some(constant.ty()),
-            Self::Range(range) => Some(Type::Vector(range.item.end - range.item.start)),
+            Self::Range(range) => Some(Type::Vector(range.len())),
+            Self::TraceLen(_) => Some(Type::Felt),
             Self::Vector(vector) => match vector.first().and_then(|e| e.ty()) {
-                Some(Type::Felt) => Some(Type::Vector(vector.len())),
+                Some(Type::Felt | Type::Binary | Type::U32) => Some(Type::Vector(vector.len())),
                 Some(Type::Vector(n)) => Some(Type::Matrix(vector.len(), n)),
                 Some(_) => None,
                 None => Some(Type::Vector(0)),
@@ -322,6 +437,7 @@ impl Expr {
                 Some(Type::Matrix(rows, cols))
             }
             Self::SymbolAccess(ref access) => access.ty,
+            Self::Binary(ref expr) if expr.op.is_logical() => Some(Type::Binary),
             Self::Binary(_) => Some(Type::Felt),
             Self::Call(ref call) => call.ty,
             Self::ListComprehension(ref lc) => lc.ty,
@@ -335,6 +451,7 @@ impl fmt::Debug for Expr {
             Self::Range(ref expr) => f.debug_tuple("Range").field(&expr.item).finish(),
             Self::Vector(ref expr) => f.debug_tuple("Vector").field(&expr.item).finish(),
             Self::Matrix(ref expr) => f.debug_tuple("Matrix").field(&expr.item).finish(),
+            Self::TraceLen(_) => f.write_str("TraceLen"),
             Self::SymbolAccess(ref expr) => f.debug_tuple("SymbolAccess").field(expr).finish(),
             Self::Binary(ref expr) => f.debug_tuple("Binary").field(expr).finish(),
             Self::Call(ref expr) => f.debug_tuple("Call").field(expr).finish(),
@@ -348,7 +465,7 @@ impl fmt::Display for Expr {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Self::Const(ref expr) => write!(f, "{}", &expr),
-            Self::Range(ref range) => write!(f, "{}..{}", range.start, range.end),
+            Self::Range(ref range) => write!(f, "{}", &range.item),
             Self::Vector(ref expr) => write!(f, "{}", DisplayList(expr.as_slice())),
             Self::Matrix(ref expr) => {
                 f.write_str("[")?;
@@ -361,6 +478,7 @@ impl fmt::Display for Expr {
                 f.write_str("]")
             }
             Self::ListComprehension(ref expr) => write!(f, "{}", DisplayBracketed(expr)),
+            Self::TraceLen(_) => f.write_str("$trace_len"),
             Self::SymbolAccess(ref expr) => write!(f, "{}", expr),
             Self::Binary(ref expr) => write!(f, "{}", expr),
             Self::Call(ref expr) => write!(f, "{}", expr),
@@ -401,6 +519,7 @@ impl TryFrom<ScalarExpr> for Expr {
                 spanned.span(),
                 ConstantExpr::Scalar(spanned.item),
             ))),
+            ScalarExpr::TraceLen(span) => Ok(Expr::TraceLen(span)),
             ScalarExpr::SymbolAccess(access) => Ok(Expr::SymbolAccess(access)),
             ScalarExpr::Binary(expr) => Ok(Expr::Binary(expr)),
             ScalarExpr::Call(expr) => Ok(Expr::Call(expr)),
@@ -415,9 +534,24 @@ impl TryFrom<ScalarExpr> for Expr {
 /// i.e. they have no vector or matrix elements. Only scalar expressions are valid
 /// in a constraint statement.
 #[derive(Clone, PartialEq, Eq, Spanned)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum ScalarExpr {
     /// A constant scalar value, i.e. integer
-    Const(Span<u64>),
+    Const(
+        #[cfg_attr(
+            feature = "serde",
+            serde(serialize_with = "crate::ast::serialize::spanned")
+        )]
+        Span<u64>,
+    ),
+    /// The length of the execution trace, i.e. `$trace_len`, which is only known at runtime
+    TraceLen(
+        #[cfg_attr(
+            feature = "serde",
+            serde(serialize_with = "crate::ast::serialize::span_of")
+        )]
+        Span<()>,
+    ),
     /// A reference to a named value
     ///
     /// NOTE: Symbol accesses in a `ScalarExpr` context must produce scalar values.
@@ -434,7 +568,7 @@ pub enum ScalarExpr {
     ///
     /// 1. The call is the top-level expression of a constraint, and is to an evaluator function
     /// 2. The call is not the top-level expression of a constraint, and is to a pure function
-    /// that produces a scalar value type.
+    ///    that produces a scalar value type.
     ///
     /// If neither of the above are true, the call is invalid in a `ScalarExpr` context
     Call(Call),
@@ -453,11 +587,33 @@ impl ScalarExpr {
     /// with a span covering the source of the conflict.
     pub fn ty(&self) -> Result<Option<Type>, SourceSpan> {
         match self {
+            // Integer literals are integers if they fit in a u32, but are otherwise field elements
+            Self::Const(value) if u32::try_from(value.item).is_ok() => Ok(Some(Type::U32)),
             Self::Const(_) => Ok(Some(Type::Felt)),
+            Self::TraceLen(_) => Ok(Some(Type::Felt)),
             Self::SymbolAccess(ref sym) => Ok(sym.ty),
             Self::BoundedSymbolAccess(ref sym) => Ok(sym.column.ty),
             Self::Binary(ref expr) => match (expr.lhs.ty()?, expr.rhs.ty()?) {
                 (None, _) | (_, None) => Ok(None),
+                // Arithmetic over integers produces an integer
+                (Some(Type::U32), Some(Type::U32))
+                    if matches!(
+                        expr.op,
+                        BinaryOp::Add | BinaryOp::Sub | BinaryOp::Mul | BinaryOp::Exp
+                    ) =>
+                {
+                    Ok(Some(Type::U32))
+                }
+                // Logical operators always produce binary values
+                (Some(lty), Some(rty))
+                    if expr.op.is_logical() && lty.is_scalar() && rty.is_scalar() =>
+                {
+                    Ok(Some(Type::Binary))
+                }
+                // Any other operation over scalars produces a field element
+                (Some(lty), Some(rty)) if lty.is_scalar() && rty.is_scalar() => {
+                    Ok(Some(Type::Felt))
+                }
                 (Some(lty), Some(rty)) if lty == rty => Ok(Some(lty)),
                 _ => Err(expr.span()),
             },
@@ -477,6 +633,7 @@ impl TryFrom<Expr> for ScalarExpr {
                     _ => Err(InvalidExprError::InvalidScalarExpr(span)),
                 }
             }
+            Expr::TraceLen(span) => Ok(Self::TraceLen(span)),
             Expr::SymbolAccess(sym) => Ok(Self::SymbolAccess(sym)),
             Expr::Binary(bin) => Ok(Self::Binary(bin)),
             Expr::Call(call) => Ok(Self::Call(call)),
@@ -488,6 +645,7 @@ impl fmt::Debug for ScalarExpr {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Self::Const(i) => f.debug_tuple("Const").field(&i.item).finish(),
+            Self::TraceLen(_) => f.write_str("TraceLen"),
             Self::SymbolAccess(ref expr) => f.debug_tuple("SymbolAccess").field(expr).finish(),
             Self::BoundedSymbolAccess(ref expr) => {
                 f.debug_tuple("BoundedSymbolAccess").field(expr).finish()
@@ -501,6 +659,7 @@ impl fmt::Display for ScalarExpr {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Self::Const(ref value) => write!(f, "{}", value),
+            Self::TraceLen(_) => f.write_str("$trace_len"),
             Self::SymbolAccess(ref expr) => write!(f, "{}", expr),
             Self::BoundedSymbolAccess(ref expr) => write!(f, "{}.{}", &expr.column, &expr.boundary),
             Self::Binary(ref expr) => write!(f, "{}", expr),
@@ -511,8 +670,13 @@ impl fmt::Display for ScalarExpr {
 
 /// Represents an expression requiring evaluation of a binary operator
 #[derive(Clone, Spanned)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct BinaryExpr {
     #[span]
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "crate::ast::serialize::span")
+    )]
     pub span: SourceSpan,
     pub op: BinaryOp,
     pub lhs: Box<ScalarExpr>,
@@ -527,6 +691,16 @@ impl BinaryExpr {
             rhs: Box::new(rhs),
         }
     }
+
+    /// Constructs the logical negation of `rhs`, i.e. `!rhs`
+    pub fn not(span: SourceSpan, rhs: ScalarExpr) -> Self {
+        Self::new(
+            span,
+            BinaryOp::Not,
+            ScalarExpr::Const(Span::new(span, 1)),
+            rhs,
+        )
+    }
 }
 impl Eq for BinaryExpr {}
 impl PartialEq for BinaryExpr {
@@ -545,11 +719,15 @@ impl fmt::Debug for BinaryExpr {
 }
 impl fmt::Display for BinaryExpr {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{} {} {}", &self.lhs, &self.op, &self.rhs)
+        match self.op {
+            BinaryOp::Not => write!(f, "!{}", &self.rhs),
+            op => write!(f, "{} {} {}", &self.lhs, op, &self.rhs),
+        }
     }
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum BinaryOp {
     /// Addition
     Add,
@@ -563,6 +741,20 @@ pub enum BinaryOp {
     ///
     /// NOTE: This is only used in constraints to assert equality, it is invalid in other contexts
     Eq,
+    /// Logical conjunction of two binary values
+    And,
+    /// Logical disjunction of two binary values
+    Or,
+    /// Logical negation of a binary value, i.e. `!rhs`
+    ///
+    /// NOTE: The left-hand operand is always the constant `1`, as negation is `1 - rhs`
+    Not,
+}
+impl BinaryOp {
+    /// Returns true if this is one of the logical operators, i.e. `&`, `|` or `!`
+    pub fn is_logical(&self) -> bool {
+        matches!(self, Self::And | Self::Or | Self::Not)
+    }
 }
 impl fmt::Display for BinaryOp {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -572,27 +764,35 @@ impl fmt::Display for BinaryOp {
             Self::Mul => f.write_str("*"),
             Self::Exp => f.write_str("^"),
             Self::Eq => f.write_str("="),
+            Self::And => f.write_str("&"),
+            Self::Or => f.write_str("|"),
+            Self::Not => f.write_str("!"),
         }
     }
 }
 
 /// Describes the type of boundary in the boundary constraint.
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum Boundary {
     First,
     Last,
+    /// The row which is the given number of rows before the last row, e.g. `last(1)`
+    BeforeLast(usize),
 }
 impl fmt::Display for Boundary {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match &self {
             Self::First => write!(f, "first"),
             Self::Last => write!(f, "last"),
+            Self::BeforeLast(offset) => write!(f, "last({offset})"),
         }
     }
 }
 
 /// Represents the way an identifier is accessed/referenced in the source.
 #[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum AccessType {
     /// Access refers to the entire bound value
     Default,
@@ -644,15 +844,20 @@ pub enum InvalidAccessError {
 /// * A evaluator/function parameter
 /// * A let-bound variable
 #[derive(Clone, Spanned)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct SymbolAccess {
     #[span]
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "crate::ast::serialize::span")
+    )]
     pub span: SourceSpan,
     /// The symbol being accessed
     pub name: ResolvableIdentifier,
     /// The type of access
     pub access_type: AccessType,
-    /// Used when the accessing a trace column with `'`, indicates the offset from
-    /// the current row in the trace. Defaults to zero.
+    /// Used when the accessing a trace column with `'` (or `''`, `'3`, etc.), indicates the
+    /// offset from the current row in the trace. Defaults to zero.
     ///
     /// NOTE: When accessed with an offset, trace columns are treated as scalar values,
     /// not as trace columns proper. What this means is that such an access cannot be
@@ -705,7 +910,7 @@ impl SymbolAccess {
         match access_type {
             AccessType::Default => Ok(self.clone()),
             AccessType::Index(idx) => match ty {
-                Type::Felt => Err(InvalidAccessError::IndexIntoScalar),
+                Type::Felt | Type::Binary | Type::U32 => Err(InvalidAccessError::IndexIntoScalar),
                 Type::Vector(len) if idx >= len => Err(InvalidAccessError::IndexOutOfBounds),
                 Type::Vector(_) => Ok(Self {
                     access_type: AccessType::Index(idx),
@@ -722,7 +927,9 @@ impl SymbolAccess {
             AccessType::Slice(range) => {
                 let rlen = range.end - range.start;
                 match ty {
-                    Type::Felt => Err(InvalidAccessError::IndexIntoScalar),
+                    Type::Felt | Type::Binary | Type::U32 => {
+                        Err(InvalidAccessError::IndexIntoScalar)
+                    }
                     Type::Vector(len) if range.end > len => {
                         Err(InvalidAccessError::IndexOutOfBounds)
                     }
@@ -742,7 +949,9 @@ impl SymbolAccess {
                 }
             }
             AccessType::Matrix(row, col) => match ty {
-                Type::Felt | Type::Vector(_) => Err(InvalidAccessError::IndexIntoScalar),
+                Type::Felt | Type::Binary | Type::U32 | Type::Vector(_) => {
+                    Err(InvalidAccessError::IndexIntoScalar)
+                }
                 Type::Matrix(rows, cols) if row >= rows || col >= cols => {
                     Err(InvalidAccessError::IndexOutOfBounds)
                 }
@@ -764,7 +973,7 @@ impl SymbolAccess {
         match access_type {
             AccessType::Default => Ok(self.clone()),
             AccessType::Index(idx) => match ty {
-                Type::Felt => unreachable!(),
+                Type::Felt | Type::Binary | Type::U32 => unreachable!(),
                 Type::Vector(len) if idx >= len => Err(InvalidAccessError::IndexOutOfBounds),
                 Type::Vector(_) => Ok(Self {
                     access_type: AccessType::Index(base_range.start + idx),
@@ -785,7 +994,7 @@ impl SymbolAccess {
                 let end = range.start + range.end;
                 let shifted = start..end;
                 match ty {
-                    Type::Felt => unreachable!(),
+                    Type::Felt | Type::Binary | Type::U32 => unreachable!(),
                     Type::Vector(_) if range.end > blen => {
                         Err(InvalidAccessError::IndexOutOfBounds)
                     }
@@ -805,7 +1014,9 @@ impl SymbolAccess {
                 }
             }
             AccessType::Matrix(row, col) => match ty {
-                Type::Felt | Type::Vector(_) => Err(InvalidAccessError::IndexIntoScalar),
+                Type::Felt | Type::Binary | Type::U32 | Type::Vector(_) => {
+                    Err(InvalidAccessError::IndexIntoScalar)
+                }
                 Type::Matrix(rows, cols) if row >= rows || col >= cols => {
                     Err(InvalidAccessError::IndexOutOfBounds)
                 }
@@ -827,7 +1038,7 @@ impl SymbolAccess {
         match access_type {
             AccessType::Default => Ok(self.clone()),
             AccessType::Index(idx) => match ty {
-                Type::Felt => Err(InvalidAccessError::IndexIntoScalar),
+                Type::Felt | Type::Binary | Type::U32 => Err(InvalidAccessError::IndexIntoScalar),
                 Type::Vector(len) if idx >= len => Err(InvalidAccessError::IndexOutOfBounds),
                 Type::Vector(_) => Ok(Self {
                     access_type: AccessType::Matrix(base_idx, idx),
@@ -874,7 +1085,7 @@ impl fmt::Display for SymbolAccess {
             AccessType::Slice(range) => write!(f, "[{}..{}]", range.start, range.end)?,
             AccessType::Matrix(row, col) => write!(f, "[{}][{}]", row, col)?,
         }
-        // TODO: When we change the syntax to support arbitrary offsets, we'll need to update this
+        // Offsets are rendered with one quote per row, e.g. `a''`, rather than as `a'2`
         for _ in 0..self.offset {
             f.write_str("'")?;
         }
@@ -886,8 +1097,13 @@ impl fmt::Display for SymbolAccess {
 ///
 /// The underlying symbol must refer to a trace column, or the access is invalid.
 #[derive(Clone, Spanned)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct BoundedSymbolAccess {
     #[span]
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "crate::ast::serialize::span")
+    )]
     pub span: SourceSpan,
     /// The boundary on which this access will be evaluated
     pub boundary: Boundary,
@@ -925,8 +1141,13 @@ impl fmt::Debug for BoundedSymbolAccess {
 pub type ComprehensionContext = Vec<(Identifier, Expr)>;
 
 #[derive(Clone, Spanned)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct ListComprehension {
     #[span]
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "crate::ast::serialize::span")
+    )]
     pub span: SourceSpan,
     /// The names to be bound to each element of their corresponding iterable in `iterables`
     ///
@@ -946,6 +1167,12 @@ pub struct ListComprehension {
     /// When the comprehension is used as a constraint, this field is only valid for
     /// use in integrity constraints.
     pub selector: Option<ScalarExpr>,
+    /// An optional filter, e.g. `where i - 2`, which is evaluated at compile time to determine
+    /// which iterations of the comprehension are expanded, skipping those for which it is zero.
+    ///
+    /// Unlike the selector, the filter must be a constant expression once the bindings of the
+    /// comprehension are known, and it is only valid when the comprehension is used as a constraint.
+    pub filter: Option<Box<ScalarExpr>>,
     /// The type of the result of this list comprehension, e.g. `vector[5]`
     ///
     /// This is set during semantic analysis
@@ -967,9 +1194,16 @@ impl ListComprehension {
             iterables,
             body: Box::new(body),
             selector,
+            filter: None,
             ty: None,
         }
     }
+
+    /// Applies a compile-time filter to the iterations of this comprehension
+    pub fn with_filter(mut self, filter: ScalarExpr) -> Self {
+        self.filter = Some(Box::new(filter));
+        self
+    }
 }
 impl Eq for ListComprehension {}
 impl PartialEq for ListComprehension {
@@ -978,6 +1212,7 @@ impl PartialEq for ListComprehension {
             && self.iterables == other.iterables
             && self.body == other.body
             && self.selector == other.selector
+            && self.filter == other.filter
     }
 }
 impl fmt::Debug for ListComprehension {
@@ -987,6 +1222,7 @@ impl fmt::Debug for ListComprehension {
             .field("iterables", &self.iterables)
             .field("body", self.body.as_ref())
             .field("selector", &self.selector)
+            .field("filter", &self.filter)
             .finish()
     }
 }
@@ -1008,6 +1244,10 @@ impl fmt::Display for ListComprehension {
             )?;
         }
 
+        if let Some(filter) = self.filter.as_ref() {
+            write!(f, " where {}", filter)?;
+        }
+
         if let Some(selector) = self.selector.as_ref() {
             write!(f, " when {}", selector)
         } else {
@@ -1034,20 +1274,33 @@ impl fmt::Display for ListComprehension {
 /// always have a type of `None`. Pure functions on the other hand must always produce a value,
 /// so such calls will always have a valid type. The only time when calls to pure functions will
 /// have a `None` type is prior to name resolution in the semantic analysis pass.
+///
+/// Evaluators may also be applied at a trace boundary in the `boundary_constraints` section,
+/// e.g. `enf foo([a, b]).first`, in which case each constraint of the evaluator is enforced
+/// against the given boundary of the column it constrains.
 #[derive(Clone, Spanned)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Call {
     #[span]
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "crate::ast::serialize::span")
+    )]
     pub span: SourceSpan,
     pub callee: ResolvableIdentifier,
     pub args: Vec<Expr>,
+    /// The boundary at which a call to an evaluator is applied, if any
+    ///
+    /// This is only valid for calls to evaluators in boundary constraints
+    pub boundary: Option<Boundary>,
     /// Used to store the type produced by a call to a pure function
     ///
     /// The reason this field is an `Option` is two-fold:
     ///
     /// * Calls to evaluators produce no value, and thus have no type
     /// * When parsed, the callee has not yet been resolved, so we don't know the
-    /// type of the function being called. During semantic analysis, the callee is
-    /// resolved and this field is set to the result type of that function.
+    ///   type of the function being called. During semantic analysis, the callee is
+    ///   resolved and this field is set to the result type of that function.
     pub ty: Option<Type>,
 }
 impl Call {
@@ -1057,15 +1310,25 @@ impl Call {
         match callee.name() {
             symbols::Sum => Self::sum(span, args),
             symbols::Prod => Self::prod(span, args),
+            symbols::Min => Self::min(span, args),
+            symbols::Max => Self::max(span, args),
             _ => Self {
                 span,
                 callee: ResolvableIdentifier::Unresolved(NamespacedIdentifier::Function(callee)),
                 args,
+                boundary: None,
                 ty: None,
             },
         }
     }
 
+    /// Applies this call at the given trace boundary, e.g. `foo([a]).first`
+    pub fn with_boundary(mut self, span: SourceSpan, boundary: Boundary) -> Self {
+        self.span = span;
+        self.boundary = Some(boundary);
+        self
+    }
+
     /// Returns true if the callee is a builtin function, e.g. `sum`
     #[inline]
     pub fn is_builtin(&self) -> bool {
@@ -1084,14 +1347,31 @@ impl Call {
         Self::new_builtin(span, "prod", args, Type::Felt)
     }
 
+    /// Constructs a function call for the `min` reducer/fold, which is only valid over constants
+    #[inline]
+    pub fn min(span: SourceSpan, args: Vec<Expr>) -> Self {
+        Self::new_builtin(span, "min", args, Type::Felt)
+    }
+
+    /// Constructs a function call for the `max` reducer/fold, which is only valid over constants
+    #[inline]
+    pub fn max(span: SourceSpan, args: Vec<Expr>) -> Self {
+        Self::new_builtin(span, "max", args, Type::Felt)
+    }
+
+    /// Constructs a cast of `arg` to a field element, e.g. `felt(i)`
+    #[inline]
+    pub fn felt(span: SourceSpan, arg: Expr) -> Self {
+        Self::new_builtin(span, "felt", vec![arg], Type::Felt)
+    }
+
     fn new_builtin(span: SourceSpan, name: &str, args: Vec<Expr>, ty: Type) -> Self {
-        let builtin_module = Identifier::new(SourceSpan::UNKNOWN, Symbol::intern("$builtin"));
         let name = Identifier::new(span, Symbol::intern(name));
-        let id = QualifiedIdentifier::new(builtin_module, NamespacedIdentifier::Function(name));
         Self {
             span,
-            callee: ResolvableIdentifier::Resolved(id),
+            callee: ResolvableIdentifier::Resolved(QualifiedIdentifier::builtin(name)),
             args,
+            boundary: None,
             ty: Some(ty),
         }
     }
@@ -1099,7 +1379,10 @@ impl Call {
 impl Eq for Call {}
 impl PartialEq for Call {
     fn eq(&self, other: &Self) -> bool {
-        self.callee == other.callee && self.args == other.args && self.ty == other.ty
+        self.callee == other.callee
+            && self.args == other.args
+            && self.boundary == other.boundary
+            && self.ty == other.ty
     }
 }
 impl fmt::Debug for Call {
@@ -1107,12 +1390,17 @@ impl fmt::Debug for Call {
         f.debug_struct("Call")
             .field("callee", &self.callee)
             .field("args", &self.args)
+            .field("boundary", &self.boundary)
             .field("ty", &self.ty)
             .finish()
     }
 }
 impl fmt::Display for Call {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}{}", self.callee, DisplayTuple(self.args.as_slice()))
+        write!(f, "{}{}", self.callee, DisplayTuple(self.args.as_slice()))?;
+        if let Some(boundary) = self.boundary {
+            write!(f, ".{}", boundary)?;
+        }
+        Ok(())
     }
 }
@@ -1,13 +1,19 @@
+mod builder;
+mod cache;
 mod declarations;
 mod display;
 mod errors;
 mod expression;
 mod module;
+#[cfg(feature = "serde")]
+mod serialize;
 mod statement;
 mod trace;
 mod types;
 pub mod visit;
 
+pub use self::builder::*;
+pub use self::cache::ModuleCache;
 pub use self::declarations::*;
 pub(crate) use self::display::*;
 pub use self::errors::*;
@@ -40,6 +46,7 @@ use crate::{
 /// All of the details described in the documentation for [Program] and [Library]
 /// apply to their respective variants here.
 #[derive(Debug)]
+#[allow(clippy::large_enum_variant)]
 pub enum Source {
     /// The source code which was parsed produced a valid [Program],
     /// i.e. it contained a root module, and optionally, one or more
@@ -63,7 +70,8 @@ pub enum Source {
 /// are not referred to from the root module directly or transitively, are not present in
 /// the [Program] structure. Currently, analysis doesn't check for dead code within functions
 /// or constraint blocks, so that is the only area in which dead code may still exist.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Program {
     /// The name of an AirScript program is the name of its root module.
     pub name: Identifier,
@@ -71,6 +79,8 @@ pub struct Program {
     pub constants: BTreeMap<QualifiedIdentifier, Constant>,
     /// The set of used evaluator functions referenced in this program.
     pub evaluators: BTreeMap<QualifiedIdentifier, EvaluatorFunction>,
+    /// The set of used pure functions referenced in this program.
+    pub functions: BTreeMap<QualifiedIdentifier, Function>,
     /// The set of used periodic columns referenced in this program.
     pub periodic_columns: BTreeMap<QualifiedIdentifier, PeriodicColumn>,
     /// The set of public inputs defined in the root module
@@ -94,6 +104,11 @@ pub struct Program {
     ///
     /// It is guaranteed that this is non-empty
     pub integrity_constraints: Vec<Statement>,
+    /// The modules this program was loaded from, i.e. the root module and every module it
+    /// imports, directly or transitively, in dependency order.
+    ///
+    /// NOTE: This is not considered when comparing programs for equality.
+    pub modules: Vec<ModuleId>,
 }
 impl Program {
     /// Creates a new, empty [Program].
@@ -115,12 +130,14 @@ impl Program {
             name,
             constants: Default::default(),
             evaluators: Default::default(),
+            functions: Default::default(),
             periodic_columns: Default::default(),
             public_inputs: Default::default(),
             random_values: None,
             trace_columns: vec![],
             boundary_constraints: vec![],
             integrity_constraints: vec![],
+            modules: vec![],
         }
     }
 
@@ -205,6 +222,7 @@ impl Program {
             analysis.run(&mut module)?;
 
             // Put the module back
+            program.modules.push(module.name);
             library.modules.insert(module.name, module);
         }
 
@@ -237,6 +255,12 @@ impl Program {
                     NamespacedIdentifier::Function(evaluator.name),
                 ));
             }
+            for function in root_module.functions.values() {
+                root_nodes.push_back(QualifiedIdentifier::new(
+                    root,
+                    NamespacedIdentifier::Function(function.name),
+                ));
+            }
         }
 
         let mut visited = HashSet::<QualifiedIdentifier>::default();
@@ -265,7 +289,12 @@ impl Program {
                             .entry(referenced)
                             .or_insert_with(|| referenced_module.evaluators[&id].clone());
                     }
-                    DependencyType::Function => unimplemented!(),
+                    DependencyType::Function => {
+                        program
+                            .functions
+                            .entry(referenced)
+                            .or_insert_with(|| referenced_module.functions[&id].clone());
+                    }
                     DependencyType::PeriodicColumn => {
                         program
                             .periodic_columns
@@ -288,6 +317,7 @@ impl PartialEq for Program {
         self.name == other.name
             && self.constants == other.constants
             && self.evaluators == other.evaluators
+            && self.functions == other.functions
             && self.periodic_columns == other.periodic_columns
             && self.public_inputs == other.public_inputs
             && self.random_values == other.random_values
@@ -308,7 +338,20 @@ impl fmt::Display for Program {
 
         writeln!(f, "public_inputs:")?;
         for public_input in self.public_inputs.values() {
-            writeln!(f, "    {}: [{}]", public_input.name, public_input.size)?;
+            match public_input.columns {
+                None if !public_input.bindings.is_empty() => writeln!(
+                    f,
+                    "    {}: {}",
+                    public_input.name,
+                    DisplayList(public_input.bindings.as_slice())
+                )?,
+                None => writeln!(f, "    {}: [{}]", public_input.name, public_input.size)?,
+                Some(columns) => writeln!(
+                    f,
+                    "    {}: [{}][{}]",
+                    public_input.name, public_input.size, columns
+                )?,
+            }
         }
         f.write_str("\n")?;
 
@@ -321,15 +364,16 @@ impl fmt::Display for Program {
         if !self.periodic_columns.is_empty() {
             writeln!(f, "periodic_columns:")?;
             for (qid, column) in self.periodic_columns.iter() {
-                if qid.module == self.name {
-                    writeln!(
-                        f,
-                        "    {}: {}",
-                        &qid.item,
-                        DisplayList(column.values.as_slice())
-                    )?;
+                let name: &dyn fmt::Display = if qid.module == self.name {
+                    &qid.item
                 } else {
-                    writeln!(f, "    {}: {}", qid, DisplayList(column.values.as_slice()))?;
+                    qid
+                };
+                match column.generator.as_deref() {
+                    Some(generator) => {
+                        writeln!(f, "    {}: {}", name, DisplayBracketed(generator))?
+                    }
+                    None => writeln!(f, "    {}: {}", name, DisplayList(column.values.as_slice()))?,
                 }
             }
             f.write_str("\n")?;
@@ -377,6 +421,28 @@ impl fmt::Display for Program {
             f.write_str("\n")?;
         }
 
+        for (qid, function) in self.functions.iter() {
+            f.write_str("fn ")?;
+            if qid.module == self.name {
+                write!(f, "{}", &qid.item)?;
+            } else {
+                write!(f, "{}", qid)?;
+            }
+            f.write_str("(")?;
+            for (i, (name, ty)) in function.params.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(", ")?;
+                }
+                write!(f, "{}: {}", name, DisplayType(ty))?;
+            }
+            writeln!(f, ") -> {}:", DisplayType(&function.return_ty))?;
+
+            for statement in function.body.iter() {
+                writeln!(f, "{}", statement.display(1))?;
+            }
+            f.write_str("\n")?;
+        }
+
         Ok(())
     }
 }
@@ -391,10 +457,16 @@ pub struct Library {
     pub modules: HashMap<ModuleId, Module>,
 }
 impl Library {
+    /// Constructs a [Library] from the given modules, parsing any modules they import which are
+    /// not among them.
+    ///
+    /// If a [ModuleCache] is provided, imported modules are taken from the cache when they have
+    /// not changed since they were last parsed, and are added to it otherwise.
     pub fn new(
         diagnostics: &DiagnosticsHandler,
         codemap: Arc<CodeMap>,
         mut modules: Vec<Module>,
+        cache: Option<&ModuleCache>,
     ) -> Result<Self, SemanticAnalysisError> {
         use std::collections::hash_map::Entry;
 
@@ -482,12 +554,28 @@ impl Library {
             for import in imports.drain(..) {
                 if let Entry::Vacant(entry) = lib.modules.entry(import) {
                     let filename = source_dir.join(format!("{}.air", import.as_str()));
-                    // Check if the module exists in the codemap first, so that we can add files directly
-                    // to the codemap during testing for convenience
-                    let result = match codemap.get_by_name(&FileName::Real(filename.clone())) {
-                        Some(file) => crate::parse_module(diagnostics, codemap.clone(), file),
+                    // If the module is cached, and the file it was parsed from is unchanged, it
+                    // does not need to be parsed again
+                    let version = cache.and_then(|_| cache::ModuleVersion::of(&codemap, &filename));
+                    let cached = cache
+                        .zip(version)
+                        .and_then(|(cache, version)| cache.get(&codemap, &filename, version));
+                    let result = match cached {
+                        Some(module) => Ok(module),
                         None => {
-                            crate::parse_module_from_file(diagnostics, codemap.clone(), &filename)
+                            let result = match crate::load_file(&codemap, &filename) {
+                                Ok(file) => crate::parse_module(diagnostics, codemap.clone(), file),
+                                Err(source) => Err(ParseError::FileError {
+                                    source,
+                                    path: filename.clone(),
+                                }),
+                            };
+                            if let (Ok(module), Some((cache, version))) =
+                                (result.as_ref(), cache.zip(version))
+                            {
+                                cache.insert(&codemap, filename, version, module.clone());
+                            }
+                            result
                         }
                     };
                     match result {
@@ -36,7 +36,7 @@ pub enum ModuleType {
 /// * Fields which are only allowed in root modules are empty/unset in library modules
 /// * Fields which must be present in root modules are guaranteed to be present in a root module
 /// * It is guaranteed that at least one boundary constraint and one integrity constraint are present
-/// in a root module
+///   in a root module
 /// * No duplicate module-level declarations were present
 /// * All globally-visible declarations are unique
 ///
@@ -45,7 +45,7 @@ pub enum ModuleType {
 /// partially validated here, in that we check for obviously overlapping imports, but cannot
 /// fully validate them until later. Likewise we do not validate constraints, look for invalid
 /// variable usages, etc.
-#[derive(Debug, Spanned)]
+#[derive(Debug, Clone, Spanned)]
 pub struct Module {
     #[span]
     pub span: SourceSpan,
@@ -54,6 +54,7 @@ pub struct Module {
     pub imports: BTreeMap<ModuleId, Import>,
     pub constants: BTreeMap<Identifier, Constant>,
     pub evaluators: BTreeMap<Identifier, EvaluatorFunction>,
+    pub functions: BTreeMap<Identifier, Function>,
     pub periodic_columns: BTreeMap<Identifier, PeriodicColumn>,
     pub public_inputs: BTreeMap<Identifier, PublicInput>,
     pub random_values: Option<RandomValues>,
@@ -79,6 +80,7 @@ impl Module {
             imports: Default::default(),
             constants: Default::default(),
             evaluators: Default::default(),
+            functions: Default::default(),
             periodic_columns: Default::default(),
             public_inputs: Default::default(),
             random_values: None,
@@ -110,6 +112,11 @@ impl Module {
         // checked in later analysis.
         let mut names = HashSet::<NamespacedIdentifier>::default();
 
+        // Assertions about the trace layout and public inputs are checked once all declarations
+        // have been visited, as they may precede the sections they refer to
+        let mut trace_width_assertions = vec![];
+        let mut public_input_size_assertions = vec![];
+
         for declaration in declarations.drain(..) {
             match declaration {
                 Declaration::Import(import) => {
@@ -121,6 +128,9 @@ impl Module {
                 Declaration::EvaluatorFunction(evaluator) => {
                     module.declare_evaluator(diagnostics, &mut names, evaluator)?;
                 }
+                Declaration::Function(function) => {
+                    module.declare_function(diagnostics, &mut names, function)?;
+                }
                 Declaration::PeriodicColumns(mut columns) => {
                     for column in columns.drain(..) {
                         module.declare_periodic_column(diagnostics, &mut names, column)?;
@@ -147,6 +157,24 @@ impl Module {
                 Declaration::IntegrityConstraints(statements) => {
                     module.declare_integrity_constraints(diagnostics, statements)?;
                 }
+                Declaration::TraceWidthAssertion(assertion) => {
+                    if module.is_library() {
+                        invalid_section_in_library(diagnostics, "trace_width", assertion.span());
+                        return Err(SemanticAnalysisError::RootSectionInLibrary(
+                            assertion.span(),
+                        ));
+                    }
+                    trace_width_assertions.push(assertion);
+                }
+                Declaration::PublicInputSizeAssertion(assertion) => {
+                    if module.is_library() {
+                        invalid_section_in_library(diagnostics, "public_input", assertion.span());
+                        return Err(SemanticAnalysisError::RootSectionInLibrary(
+                            assertion.span(),
+                        ));
+                    }
+                    public_input_size_assertions.push(assertion);
+                }
             }
         }
 
@@ -167,6 +195,14 @@ impl Module {
                 return Err(SemanticAnalysisError::Invalid);
             }
 
+            for assertion in trace_width_assertions.iter() {
+                module.check_trace_width(diagnostics, assertion)?;
+            }
+
+            for assertion in public_input_size_assertions.iter() {
+                module.check_public_input_size(diagnostics, assertion)?;
+            }
+
             if module.boundary_constraints.is_none() || module.integrity_constraints.is_none() {
                 return Err(SemanticAnalysisError::MissingConstraints);
             }
@@ -175,18 +211,32 @@ impl Module {
                 return Err(SemanticAnalysisError::MissingPublicInputs);
             }
 
-            if module.random_values.is_some()
-                && !module.trace_columns.iter().any(|ts| ts.name == "$aux")
+            if let Some(random_values) = module
+                .random_values
+                .as_ref()
+                .filter(|_| module.trace_columns.len() < 2)
             {
                 diagnostics
                     .diagnostic(Severity::Error)
                     .with_message(
                         "declaring random_values requires an aux trace_columns declaration",
                     )
-                    .with_primary_label(
-                        module.random_values.as_ref().unwrap().span(),
-                        "this declaration is invalid",
-                    )
+                    .with_primary_label(random_values.span(), "this declaration is invalid")
+                    .emit();
+                return Err(SemanticAnalysisError::Invalid);
+            }
+
+            if let Some(aux) = module
+                .trace_columns
+                .iter()
+                .find(|ts| ts.name == "$aux")
+                .filter(|_| module.random_values.is_none())
+            {
+                diagnostics
+                    .diagnostic(Severity::Error)
+                    .with_message("declaring aux trace_columns requires a random_values declaration")
+                    .with_primary_label(aux.span(), "this declaration is invalid")
+                    .with_note("Auxiliary trace columns are built using random values provided by the verifier, which must be declared in a random_values section")
                     .emit();
                 return Err(SemanticAnalysisError::Invalid);
             }
@@ -222,7 +272,7 @@ impl Module {
                                     .emit();
                             }
                             Import::Partial { items, .. } => {
-                                for item in items.iter() {
+                                for item in items.keys() {
                                     diagnostics
                                         .diagnostic(Severity::Warning)
                                         .with_message("redundant item import")
@@ -268,53 +318,39 @@ impl Module {
                             items: ref mut prev_items,
                             ..
                         } => {
-                            for item in items.drain() {
-                                if let Some(prev) = prev_items.get(&item) {
-                                    diagnostics
-                                        .diagnostic(Severity::Warning)
-                                        .with_message("redundant item import")
-                                        .with_primary_label(item.span(), "this import is redundant")
-                                        .with_secondary_label(
-                                            prev.span(),
-                                            "because it was already imported here",
-                                        )
-                                        .emit();
-                                    continue;
-                                }
-                                prev_items.insert(item);
-                                let name = if item.is_uppercase() {
-                                    NamespacedIdentifier::Binding(item)
-                                } else {
-                                    NamespacedIdentifier::Function(item)
-                                };
-                                if let Some(prev) = names.replace(name) {
+                            for (local, item) in items.drain() {
+                                if let Some((prev, prev_item)) = prev_items.get_key_value(&local) {
+                                    if prev_item == &item {
+                                        diagnostics
+                                            .diagnostic(Severity::Warning)
+                                            .with_message("redundant item import")
+                                            .with_primary_label(
+                                                local.span(),
+                                                "this import is redundant",
+                                            )
+                                            .with_secondary_label(
+                                                prev.span(),
+                                                "because it was already imported here",
+                                            )
+                                            .emit();
+                                        continue;
+                                    }
                                     conflicting_declaration(
                                         diagnostics,
                                         "import",
                                         prev.span(),
-                                        item.span(),
+                                        local.span(),
                                     );
-                                    return Err(SemanticAnalysisError::NameConflict(item.span()));
+                                    return Err(SemanticAnalysisError::NameConflict(local.span()));
                                 }
+                                declare_imported_name(diagnostics, names, local, item)?;
+                                prev_items.insert(local, item);
                             }
                         }
                     },
                     Entry::Vacant(entry) => {
-                        for item in items.iter().copied() {
-                            let name = if item.is_uppercase() {
-                                NamespacedIdentifier::Binding(item)
-                            } else {
-                                NamespacedIdentifier::Function(item)
-                            };
-                            if let Some(prev) = names.replace(name) {
-                                conflicting_declaration(
-                                    diagnostics,
-                                    "import",
-                                    prev.span(),
-                                    item.span(),
-                                );
-                                return Err(SemanticAnalysisError::NameConflict(item.span()));
-                            }
+                        for (local, item) in items.iter() {
+                            declare_imported_name(diagnostics, names, *local, *item)?;
                         }
                         entry.insert(Import::Partial {
                             module: name,
@@ -395,6 +431,22 @@ impl Module {
         Ok(())
     }
 
+    fn declare_function(
+        &mut self,
+        diagnostics: &DiagnosticsHandler,
+        names: &mut HashSet<NamespacedIdentifier>,
+        function: Function,
+    ) -> Result<(), SemanticAnalysisError> {
+        if let Some(prev) = names.replace(NamespacedIdentifier::Function(function.name)) {
+            conflicting_declaration(diagnostics, "function", prev.span(), function.name.span());
+            return Err(SemanticAnalysisError::NameConflict(function.name.span()));
+        }
+
+        self.functions.insert(function.name, function);
+
+        Ok(())
+    }
+
     fn declare_periodic_column(
         &mut self,
         diagnostics: &DiagnosticsHandler,
@@ -437,10 +489,38 @@ impl Module {
             return Err(SemanticAnalysisError::RootSectionInLibrary(input.span()));
         }
 
+        if input.num_elements().is_none() {
+            diagnostics
+                .diagnostic(Severity::Error)
+                .with_message("public input is too large")
+                .with_primary_label(
+                    input.span(),
+                    format!(
+                        "`{}` has {} rows of {} columns, which is more elements than can be represented",
+                        input.name,
+                        input.size,
+                        input.columns.unwrap_or(1)
+                    ),
+                )
+                .emit();
+            return Err(SemanticAnalysisError::Invalid);
+        }
+
         if let Some(prev) = names.replace(NamespacedIdentifier::Binding(input.name)) {
             conflicting_declaration(diagnostics, "public input", prev.span(), input.name.span());
             Err(SemanticAnalysisError::NameConflict(input.name.span()))
         } else {
+            for binding in input.bindings.iter() {
+                if let Some(prev) = names.replace(NamespacedIdentifier::Binding(binding.name)) {
+                    conflicting_declaration(
+                        diagnostics,
+                        "public input binding",
+                        prev.span(),
+                        binding.name.span(),
+                    );
+                    return Err(SemanticAnalysisError::NameConflict(binding.name.span()));
+                }
+            }
             assert_eq!(self.public_inputs.insert(input.name, input), None);
             Ok(())
         }
@@ -485,6 +565,85 @@ impl Module {
         }
     }
 
+    fn check_trace_width(
+        &self,
+        diagnostics: &DiagnosticsHandler,
+        assertion: &TraceWidthAssertion,
+    ) -> Result<(), SemanticAnalysisError> {
+        let name = assertion.segment.as_str();
+        let name = name.strip_prefix('$').unwrap_or(name);
+        let segment = self
+            .trace_columns
+            .iter()
+            .find(|ts| ts.name == assertion.segment);
+        match segment {
+            None => {
+                diagnostics
+                    .diagnostic(Severity::Error)
+                    .with_message("undefined trace segment")
+                    .with_primary_label(
+                        assertion.segment.span(),
+                        format!("no trace segment named `{name}` has been declared"),
+                    )
+                    .emit();
+                Err(SemanticAnalysisError::Invalid)
+            }
+            Some(segment) if segment.size != assertion.width => {
+                diagnostics
+                    .diagnostic(Severity::Error)
+                    .with_message("trace width assertion failed")
+                    .with_primary_label(
+                        assertion.span(),
+                        format!(
+                            "expected `{name}` to have {} columns, but it has {}",
+                            assertion.width, segment.size
+                        ),
+                    )
+                    .with_secondary_label(segment.span(), "the segment is declared here")
+                    .emit();
+                Err(SemanticAnalysisError::Invalid)
+            }
+            Some(_) => Ok(()),
+        }
+    }
+
+    fn check_public_input_size(
+        &self,
+        diagnostics: &DiagnosticsHandler,
+        assertion: &PublicInputSizeAssertion,
+    ) -> Result<(), SemanticAnalysisError> {
+        let name = assertion.input;
+        match self.public_inputs.get(&name) {
+            None => {
+                diagnostics
+                    .diagnostic(Severity::Error)
+                    .with_message("undefined public input")
+                    .with_primary_label(
+                        name.span(),
+                        format!("no public input named `{name}` has been declared"),
+                    )
+                    .emit();
+                Err(SemanticAnalysisError::Invalid)
+            }
+            Some(input) if input.size != assertion.size => {
+                diagnostics
+                    .diagnostic(Severity::Error)
+                    .with_message("public input size assertion failed")
+                    .with_primary_label(
+                        assertion.span(),
+                        format!(
+                            "expected `{name}` to have size {}, but it has size {}",
+                            assertion.size, input.size
+                        ),
+                    )
+                    .with_secondary_label(input.span(), "the public input is declared here")
+                    .emit();
+                Err(SemanticAnalysisError::Invalid)
+            }
+            Some(_) => Ok(()),
+        }
+    }
+
     fn declare_trace_segments(
         &mut self,
         diagnostics: &DiagnosticsHandler,
@@ -545,7 +704,7 @@ impl Module {
         if !statements.iter().any(|s| s.has_constraints()) {
             diagnostics
                 .diagnostic(Severity::Error)
-                .with_message("at least one boundary constraint must be declared")
+                .with_message("boundary_constraints section must contain at least one constraint")
                 .with_primary_label(span, "missing constraint declaration in this section")
                 .emit();
             return Err(SemanticAnalysisError::Invalid);
@@ -575,7 +734,7 @@ impl Module {
         if !statements.iter().any(|s| s.has_constraints()) {
             diagnostics
                 .diagnostic(Severity::Error)
-                .with_message("at least one integrity constraint must be declared")
+                .with_message("integrity_constraints section must contain at least one constraint")
                 .with_primary_label(span, "missing constraint declaration in this section")
                 .emit();
             return Err(SemanticAnalysisError::Invalid);
@@ -602,6 +761,7 @@ impl Module {
             .values()
             .map(Export::Constant)
             .chain(self.evaluators.values().map(Export::Evaluator))
+            .chain(self.functions.values().map(Export::Function))
     }
 
     /// Get the export with the given identifier, if it can be found
@@ -609,7 +769,10 @@ impl Module {
         if id.is_uppercase() {
             self.constants.get(id).map(Export::Constant)
         } else {
-            self.evaluators.get(id).map(Export::Evaluator)
+            self.evaluators
+                .get(id)
+                .map(Export::Evaluator)
+                .or_else(|| self.functions.get(id).map(Export::Function))
         }
     }
 }
@@ -621,6 +784,7 @@ impl PartialEq for Module {
             && self.imports == other.imports
             && self.constants == other.constants
             && self.evaluators == other.evaluators
+            && self.functions == other.functions
             && self.periodic_columns == other.periodic_columns
             && self.public_inputs == other.public_inputs
             && self.random_values == other.random_values
@@ -630,6 +794,43 @@ impl PartialEq for Module {
     }
 }
 
+/// Records `local` as the name an imported `item` is bound to in the current module, raising an
+/// error if it conflicts with another declaration, or if an alias does not follow the naming
+/// convention of the imported item, i.e. uppercase for constants.
+fn declare_imported_name(
+    diagnostics: &DiagnosticsHandler,
+    names: &mut HashSet<NamespacedIdentifier>,
+    local: Identifier,
+    item: Identifier,
+) -> Result<(), SemanticAnalysisError> {
+    if local.is_uppercase() != item.is_uppercase() {
+        diagnostics
+            .diagnostic(Severity::Error)
+            .with_message("invalid import alias")
+            .with_primary_label(
+                local.span(),
+                if item.is_uppercase() {
+                    "aliases of constants must be uppercase"
+                } else {
+                    "aliases of functions must not be uppercase"
+                },
+            )
+            .with_secondary_label(item.span(), "the imported item")
+            .emit();
+        return Err(SemanticAnalysisError::Invalid);
+    }
+    let name = if item.is_uppercase() {
+        NamespacedIdentifier::Binding(local)
+    } else {
+        NamespacedIdentifier::Function(local)
+    };
+    if let Some(prev) = names.replace(name) {
+        conflicting_declaration(diagnostics, "import", prev.span(), local.span());
+        return Err(SemanticAnalysisError::NameConflict(local.span()));
+    }
+    Ok(())
+}
+
 fn invalid_section_in_library(diagnostics: &DiagnosticsHandler, ty: &str, span: SourceSpan) {
     diagnostics
         .diagnostic(Severity::Error)
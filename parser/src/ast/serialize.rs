@@ -0,0 +1,93 @@
+//! Serialization of the AST, for consumption by external tools, e.g. via `airc dump-ast --json`.
+//!
+//! Identifiers are serialized as their names, with qualified identifiers in `module::name` form,
+//! and spans are serialized as the byte offsets of their start and end in the source file.
+use miden_diagnostics::{SourceSpan, Span, Spanned};
+use serde::{
+    ser::{SerializeMap, SerializeStruct},
+    Serialize, Serializer,
+};
+
+use super::{Attributes, Identifier, QualifiedIdentifier};
+
+impl Serialize for Identifier {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl Serialize for QualifiedIdentifier {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl Serialize for Attributes {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        struct SerializeAttrs<'a>(&'a Attributes);
+        impl<'a> Serialize for SerializeAttrs<'a> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let mut map = serializer.serialize_map(Some(self.0.attrs.len()))?;
+                for (key, value) in self.0.iter() {
+                    map.serialize_entry(key.as_str(), value.as_str())?;
+                }
+                map.end()
+            }
+        }
+
+        let mut state = serializer.serialize_struct("Attributes", 2)?;
+        state.serialize_field("span", &SerializeSpan(&self.span))?;
+        state.serialize_field("attrs", &SerializeAttrs(self))?;
+        state.end()
+    }
+}
+
+struct SerializeSpan<'a>(&'a SourceSpan);
+impl<'a> Serialize for SerializeSpan<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        span(self.0, serializer)
+    }
+}
+
+/// Serializes a [SourceSpan] as the byte offsets of its start and end
+pub(crate) fn span<S: Serializer>(span: &SourceSpan, serializer: S) -> Result<S::Ok, S::Error> {
+    let mut state = serializer.serialize_struct("SourceSpan", 2)?;
+    state.serialize_field("start", &span.start_index().to_usize())?;
+    state.serialize_field("end", &span.end_index().to_usize())?;
+    state.end()
+}
+
+/// Serializes a [Span] as its span and the value it wraps
+pub(crate) fn spanned<T, S>(spanned: &Span<T>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Serialize,
+    S: Serializer,
+{
+    let mut state = serializer.serialize_struct("Span", 2)?;
+    state.serialize_field("span", &SerializeSpan(&spanned.span()))?;
+    state.serialize_field("value", &spanned.item)?;
+    state.end()
+}
+
+/// Serializes only the span of a [Span], for values which carry no data, e.g. `$trace_len`
+pub(crate) fn span_of<T, S: Serializer>(
+    spanned: &Span<T>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    span(&spanned.span(), serializer)
+}
+
+/// Serializes an optional [Span], see [spanned]
+pub(crate) fn optional_spanned<T, S>(
+    value: &Option<Span<T>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    T: Serialize,
+    S: Serializer,
+{
+    match value {
+        Some(value) => spanned(value, serializer),
+        None => serializer.serialize_none(),
+    }
+}
@@ -5,9 +5,9 @@
 //! * The `integrity_constraints` section
 //!
 //! Statements do not return any value, unlike expressions.
-use std::fmt;
+use std::{collections::BTreeMap, fmt};
 
-use miden_diagnostics::{SourceSpan, Spanned};
+use miden_diagnostics::{SourceSpan, Span, Spanned};
 
 use super::*;
 
@@ -16,6 +16,7 @@ use super::*;
 /// These expressions are called statements because they do not evaluate
 /// to a value, instead they are evaluated sequentially.
 #[derive(Debug, Clone, PartialEq, Eq, Spanned)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum Statement {
     /// Binds an identifier to an expression in the following statements, e.g. `let x = y * 2`
     ///
@@ -60,6 +61,24 @@ pub enum Statement {
     /// Just like `Enforce`, except the constraint is contained in the body of a list comprehension,
     /// and must be enforced on every value produced by that comprehension.
     EnforceAll(ListComprehension),
+    /// Declares a set of constraints, each of which is enforced only when its selector holds, e.g.
+    /// `enf match: case s: x' = x`.
+    ///
+    /// Each case is represented in `EnforceAll` form, just like `enf x' = x when s`, but the cases
+    /// are kept together so that the semantic analyzer can check that their selectors are binary
+    /// and mutually exclusive. Inlining then expands each case like any other conditional constraint,
+    /// so this variant is not present in the AST after inlining.
+    EnforceMatch(Match),
+    /// Attaches attributes to a constraint, e.g. `@[name="range_check"] enf x^2 = x`
+    ///
+    /// The attributes are carried through to the constraints produced from the wrapped statement,
+    /// i.e. inlining pushes them down to each constraint in the expansion of the statement.
+    WithAttributes(Attributes, #[span] Box<Statement>),
+    /// Reports the degree and lowered form of an expression during compilation, e.g. `debug(a * b)`
+    ///
+    /// This does not enforce any constraint, and so has no effect on the compiled program. It is
+    /// only permitted in integrity constraints, including the bodies of evaluators.
+    Debug(ScalarExpr),
 }
 impl Statement {
     /// Checks this statement to see if it contains any constraints
@@ -69,9 +88,28 @@ impl Statement {
     /// one or more constraints in its body.
     pub fn has_constraints(&self) -> bool {
         match self {
-            Self::Enforce(_) | Self::EnforceIf(_, _) | Self::EnforceAll(_) => true,
+            Self::Enforce(_)
+            | Self::EnforceIf(_, _)
+            | Self::EnforceAll(_)
+            | Self::EnforceMatch(_) => true,
             Self::Let(Let { body, .. }) => body.iter().any(|s| s.has_constraints()),
-            Self::Expr(_) => false,
+            Self::WithAttributes(_, stmt) => stmt.has_constraints(),
+            Self::Expr(_) | Self::Debug(_) => false,
+        }
+    }
+
+    /// Checks this statement to see if it contains any boundary constraints, i.e. constraints
+    /// on a column boundary such as `a.first = 0`, or calls to evaluators applied at a boundary
+    ///
+    /// This is used to distinguish evaluators whose bodies consist of boundary constraints from
+    /// those which consist of integrity constraints.
+    pub fn has_boundary_constraints(&self) -> bool {
+        match self {
+            Self::Enforce(expr) => is_boundary_constraint(expr),
+            Self::EnforceAll(lc) => is_boundary_constraint(lc.body.as_ref()),
+            Self::Let(Let { body, .. }) => body.iter().any(|s| s.has_boundary_constraints()),
+            Self::WithAttributes(_, stmt) => stmt.has_boundary_constraints(),
+            Self::EnforceIf(_, _) | Self::EnforceMatch(_) | Self::Expr(_) | Self::Debug(_) => false,
         }
     }
 
@@ -83,10 +121,138 @@ impl Statement {
     }
 }
 
+/// Moves the statements following the first `let` in `block` to the end of its body, so that the
+/// `let` is the last statement of the block, and does the same for the body of the `let`.
+///
+/// A `let` scopes only its body, so the moved statements become visible to its binding. This is
+/// used where a block is assembled from several pieces, e.g. the statements of an included file
+/// or the expansion of a call, each of which may end in a `let`.
+pub(crate) fn nest_trailing_statements(block: &mut Vec<Statement>) {
+    let Some(index) = block
+        .iter()
+        .position(|statement| matches!(statement, Statement::Let(_)))
+    else {
+        return;
+    };
+    let mut trailing = block.split_off(index + 1);
+    let Some(Statement::Let(expr)) = block.last_mut() else {
+        unreachable!()
+    };
+    expr.body.append(&mut trailing);
+    nest_trailing_statements(&mut expr.body);
+}
+
+/// Returns true if `expr` is a constraint on a column boundary, e.g. `a.first = 0`, or a call to an
+/// evaluator applied at a boundary, e.g. `foo([a]).first`
+fn is_boundary_constraint(expr: &ScalarExpr) -> bool {
+    match expr {
+        ScalarExpr::Binary(BinaryExpr {
+            op: BinaryOp::Eq,
+            lhs,
+            ..
+        }) => matches!(lhs.as_ref(), ScalarExpr::BoundedSymbolAccess(_)),
+        ScalarExpr::Call(call) => call.boundary.is_some(),
+        _ => false,
+    }
+}
+
+/// The attributes attached to a constraint, e.g. `@[name="range_check", group="bitwise"]`
+///
+/// Any key is accepted, and the values are carried through as-is to the constraints in the IR, for
+/// use by downstream tooling. The only attributes interpreted by the compiler are `cyclic`, which
+/// marks an integrity constraint as applying to every row of the trace, without exemptions, and
+/// `feature`, which removes the constraint unless the named feature is enabled.
+#[derive(Debug, Clone, Spanned)]
+pub struct Attributes {
+    #[span]
+    pub span: SourceSpan,
+    pub attrs: BTreeMap<Identifier, Span<Symbol>>,
+}
+impl Attributes {
+    pub fn new(span: SourceSpan, attrs: BTreeMap<Identifier, Span<Symbol>>) -> Self {
+        Self { span, attrs }
+    }
+
+    /// Returns the value of the attribute named `name`, if present
+    pub fn get(&self, name: &str) -> Option<Symbol> {
+        self.attrs
+            .iter()
+            .find_map(|(key, value)| (key.as_str() == name).then_some(value.item))
+    }
+
+    /// Returns an iterator over the attributes, ordered by name
+    pub fn iter(&self) -> impl Iterator<Item = (Identifier, Symbol)> + '_ {
+        self.attrs.iter().map(|(key, value)| (*key, value.item))
+    }
+
+    /// Adds the attributes of `outer` which are not already set here, so that when attributes
+    /// are applied at several levels, the innermost value of each attribute takes precedence
+    pub fn inherit(&mut self, outer: &Attributes) {
+        for (key, value) in outer.attrs.iter() {
+            self.attrs.entry(*key).or_insert(*value);
+        }
+    }
+}
+impl Eq for Attributes {}
+impl PartialEq for Attributes {
+    fn eq(&self, other: &Self) -> bool {
+        self.attrs == other.attrs
+    }
+}
+impl fmt::Display for Attributes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("@[")?;
+        for (i, (key, value)) in self.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{key}=\"{value}\"")?;
+        }
+        f.write_str("]")
+    }
+}
+
+/// An `enf match` statement, consisting of one or more cases of the form `case <selector>: <constraint>`
+#[derive(Clone, Spanned)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Match {
+    #[span]
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "crate::ast::serialize::span")
+    )]
+    pub span: SourceSpan,
+    /// The cases of this match, in the order they were declared
+    ///
+    /// Each case is a comprehension over a single element, whose selector is always present.
+    pub arms: Vec<ListComprehension>,
+}
+impl Match {
+    pub fn new(span: SourceSpan, arms: Vec<ListComprehension>) -> Self {
+        Self { span, arms }
+    }
+}
+impl Eq for Match {}
+impl PartialEq for Match {
+    fn eq(&self, other: &Self) -> bool {
+        self.arms == other.arms
+    }
+}
+impl fmt::Debug for Match {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Match").field("arms", &self.arms).finish()
+    }
+}
+
 /// A `let` statement binds `name` to the value of `expr` in `body`.
 #[derive(Clone, Spanned)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Let {
     #[span]
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "crate::ast::serialize::span")
+    )]
     pub span: SourceSpan,
     /// The identifier to be bound
     pub name: Identifier,
@@ -115,6 +281,12 @@ pub struct Let {
     /// is that block. A [Let] will always be the final statement in its containing block,
     /// e.g. `integrity_constraints`, but may be preceded by any number of non-[Let] statements.
     pub body: Vec<Statement>,
+    /// The type annotation given for the binding, if any, e.g. `let x: binary = a & b`
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "crate::ast::serialize::optional_spanned")
+    )]
+    pub annotation: Option<Span<Type>>,
 }
 impl Let {
     pub fn new(span: SourceSpan, name: Identifier, value: Expr, body: Vec<Statement>) -> Self {
@@ -123,9 +295,16 @@ impl Let {
             name,
             value,
             body,
+            annotation: None,
         }
     }
 
+    /// Sets the type annotation of this binding
+    pub fn with_annotation(mut self, annotation: Option<Span<Type>>) -> Self {
+        self.annotation = annotation;
+        self
+    }
+
     pub fn ty(&self) -> Option<Type> {
         self.body.last().and_then(|stmt| match stmt {
             Statement::Let(ref nested) => nested.ty(),
@@ -137,7 +316,11 @@ impl Let {
 impl Eq for Let {}
 impl PartialEq for Let {
     fn eq(&self, other: &Self) -> bool {
-        self.name == other.name && self.value == other.value && self.body == other.body
+        self.name == other.name
+            && self.value == other.value
+            && self.body == other.body
+            && self.annotation.as_ref().map(|ty| ty.item)
+                == other.annotation.as_ref().map(|ty| ty.item)
     }
 }
 impl fmt::Debug for Let {
@@ -146,6 +329,7 @@ impl fmt::Debug for Let {
             .field("name", &self.name)
             .field("value", &self.value)
             .field("body", &self.body)
+            .field("annotation", &self.annotation.as_ref().map(|ty| ty.item))
             .finish()
     }
 }
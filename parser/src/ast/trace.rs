@@ -1,6 +1,8 @@
-use std::fmt;
+use std::{collections::BTreeMap, fmt};
 
-use miden_diagnostics::{SourceSpan, Spanned};
+use miden_diagnostics::{DiagnosticsHandler, Severity, SourceSpan, Spanned};
+
+use crate::sema::SemanticAnalysisError;
 
 use super::*;
 
@@ -11,8 +13,13 @@ pub type TraceSegmentId = usize;
 pub type TraceColumnIndex = usize;
 
 #[derive(Clone, Spanned)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct TraceSegment {
     #[span]
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "crate::ast::serialize::span")
+    )]
     pub span: SourceSpan,
     /// The index of this segment in the trace_columns declaration
     pub id: TraceSegmentId,
@@ -24,9 +31,17 @@ pub struct TraceSegment {
     pub size: usize,
     /// Bindings declared in this segment, without the segment-wide binding, e.g. `$main`
     pub bindings: Vec<TraceBinding>,
-    /// A vector of `size` elements which tracks for every column whether a
-    /// constraint has been applied to that column, and on what boundaries.
-    pub boundary_constrained: Vec<Span<ColumnBoundaryFlags>>,
+    /// Tracks which boundaries of which columns have been constrained, along with the span
+    /// of the constraint responsible, for use in diagnostics.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub constrained_boundaries: BTreeMap<ConstrainedBoundary, SourceSpan>,
+    /// The features which gate bindings in this segment, keyed by the name of the binding, e.g.
+    /// `dbg` in `main: [a, @[feature="debug"] dbg]`
+    ///
+    /// Gated bindings are removed from the segment when their feature is not enabled, see
+    /// [crate::transforms::FeatureGating].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub features: BTreeMap<Identifier, Span<Symbol>>,
 }
 impl TraceSegment {
     /// Constructs a new [TraceSegment] given a span, segment id, name, and a vector of (Identifier, size) pairs.
@@ -63,16 +78,127 @@ impl TraceSegment {
             name,
             size,
             bindings,
-            boundary_constrained: vec![
-                Span::new(SourceSpan::UNKNOWN, ColumnBoundaryFlags::EMPTY);
-                size
-            ],
+            constrained_boundaries: Default::default(),
+            features: Default::default(),
         }
     }
 
+    /// Constructs a new [TraceSegment] like [TraceSegment::new], except that each binding may be
+    /// placed at an explicit column index, e.g. `main: [clk @ 0, fmp @ 5]`.
+    ///
+    /// A binding without an explicit index is placed immediately after the preceding binding,
+    /// and the size of the segment is one past the last column which is bound. Columns which are
+    /// not bound by name are still part of the segment, and may be accessed through the segment
+    /// itself, e.g. `$main[1]`.
+    ///
+    /// Returns an error if any column is bound more than once, or if a binding extends beyond
+    /// the maximum width of a trace segment, i.e. `u16::MAX` columns.
+    pub fn with_column_indices(
+        diagnostics: &DiagnosticsHandler,
+        span: SourceSpan,
+        id: TraceSegmentId,
+        name: Identifier,
+        raw_bindings: Vec<Span<(Identifier, usize, Option<usize>)>>,
+    ) -> Result<Self, SemanticAnalysisError> {
+        let mut bindings = Vec::with_capacity(raw_bindings.len());
+        let mut offset = 0;
+        for binding in raw_bindings.into_iter() {
+            let (name, size, index) = binding.item;
+            if let Some(index) = index {
+                offset = index;
+            }
+            let end = match offset.checked_add(size) {
+                Some(end) if end <= u16::MAX as usize => end,
+                _ => {
+                    diagnostics
+                        .diagnostic(Severity::Error)
+                        .with_message("trace segment is too wide")
+                        .with_primary_label(
+                            binding.span(),
+                            format!(
+                                "this binds {size} column(s) starting at column {offset}, which \
+                                 extends beyond the last column of the segment"
+                            ),
+                        )
+                        .with_note(format!(
+                            "A trace segment may have at most {} columns.",
+                            u16::MAX
+                        ))
+                        .emit();
+                    return Err(SemanticAnalysisError::Invalid);
+                }
+            };
+            let ty = match size {
+                1 => Type::Felt,
+                n => Type::Vector(n),
+            };
+            bindings.push(TraceBinding::new(
+                binding.span(),
+                name,
+                id,
+                offset,
+                size,
+                ty,
+            ));
+            offset = end;
+        }
+
+        // Bindings may be declared in any order, so sort them by column to find any overlap
+        let mut layout = bindings.iter().collect::<Vec<_>>();
+        layout.sort_by_key(|binding| binding.offset);
+        for pair in layout.windows(2) {
+            let (prev, next) = (pair[0], pair[1]);
+            if prev.offset + prev.size > next.offset {
+                let (earlier, later) = if prev.span.start() < next.span.start() {
+                    (prev, next)
+                } else {
+                    (next, prev)
+                };
+                diagnostics
+                    .diagnostic(Severity::Error)
+                    .with_message("overlapping trace column bindings")
+                    .with_primary_label(
+                        later.span(),
+                        format!("this binds {}", DisplayColumns(later)),
+                    )
+                    .with_secondary_label(
+                        earlier.span(),
+                        format!("but this already binds {}", DisplayColumns(earlier)),
+                    )
+                    .with_note("Each column of a trace segment may only be bound once.")
+                    .emit();
+                return Err(SemanticAnalysisError::Invalid);
+            }
+        }
+
+        let size = layout
+            .last()
+            .map(|binding| binding.offset + binding.size)
+            .unwrap_or(0);
+        Ok(Self {
+            span,
+            id,
+            name,
+            size,
+            bindings,
+            constrained_boundaries: Default::default(),
+            features: Default::default(),
+        })
+    }
+
+    /// Gates the bindings named in `features` by the corresponding feature, e.g. `debug`
+    pub fn with_features<I>(mut self, features: I) -> Self
+    where
+        I: IntoIterator<Item = (Identifier, Span<Symbol>)>,
+    {
+        self.features.extend(features);
+        self
+    }
+
     /// Returns true if `column` is constrained on `boundary`
     pub fn is_boundary_constrained(&self, column: TraceColumnIndex, boundary: Boundary) -> bool {
-        self.boundary_constrained[column].is_constrained(boundary)
+        self.constrained_boundaries
+            .contains_key(&ConstrainedBoundary { column, boundary })
     }
 
     /// Marks `column` as constrained on `boundary`, and associates it with a span
@@ -87,15 +213,30 @@ impl TraceSegment {
         column: TraceColumnIndex,
         boundary: Boundary,
     ) -> Option<SourceSpan> {
-        let flags = &mut self.boundary_constrained[column];
-        if flags.is_constrained(boundary) {
-            Some(flags.span())
-        } else {
-            *flags = Span::new(span, flags.item | boundary);
-            None
+        use std::collections::btree_map::Entry;
+
+        match self
+            .constrained_boundaries
+            .entry(ConstrainedBoundary { column, boundary })
+        {
+            Entry::Occupied(entry) => Some(*entry.get()),
+            Entry::Vacant(entry) => {
+                entry.insert(span);
+                None
+            }
         }
     }
 
+    /// Returns the set of boundaries on which `column` is constrained
+    pub fn boundary_flags(&self, column: TraceColumnIndex) -> ColumnBoundaryFlags {
+        [Boundary::First, Boundary::Last]
+            .into_iter()
+            .filter(|boundary| self.is_boundary_constrained(column, *boundary))
+            .fold(ColumnBoundaryFlags::EMPTY, |flags, boundary| {
+                flags | boundary
+            })
+    }
+
     #[inline]
     pub fn is_empty(&self) -> bool {
         self.size == 0
@@ -108,10 +249,7 @@ impl fmt::Debug for TraceSegment {
             .field("name", &self.name)
             .field("size", &self.size)
             .field("bindings", &self.bindings)
-            .field(
-                "boundary_constrained",
-                &FormatConstrainedFlags(&self.boundary_constrained),
-            )
+            .field("boundary_constrained", &FormatConstrainedFlags(self))
             .finish()
     }
 }
@@ -175,6 +313,7 @@ impl std::ops::BitOr<Boundary> for ColumnBoundaryFlags {
                 | match boundary {
                     Boundary::First => Self::FIRST.0,
                     Boundary::Last => Self::LAST.0,
+                    Boundary::BeforeLast(_) => Self::EMPTY.0,
                 },
         )
     }
@@ -186,17 +325,25 @@ impl std::ops::BitAnd<Boundary> for ColumnBoundaryFlags {
         let bit = match boundary {
             Boundary::First => Self::FIRST.0,
             Boundary::Last => Self::LAST.0,
+            Boundary::BeforeLast(_) => return false,
         };
         self.0 & bit == bit
     }
 }
 
+/// Identifies a boundary of a column in a trace segment which has been constrained
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ConstrainedBoundary {
+    pub column: TraceColumnIndex,
+    pub boundary: Boundary,
+}
+
 /// Used to help format the boundary constraint flags
-struct FormatConstrainedFlags<'a>(&'a [Span<ColumnBoundaryFlags>]);
+struct FormatConstrainedFlags<'a>(&'a TraceSegment);
 impl<'a> fmt::Debug for FormatConstrainedFlags<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_list()
-            .entries(self.0.iter().map(|c| c.item))
+            .entries((0..self.0.size).map(|column| self.0.boundary_flags(column)))
             .finish()
     }
 }
@@ -205,8 +352,13 @@ impl<'a> fmt::Debug for FormatConstrainedFlags<'a> {
 /// a name. For single columns, the size is 1. For groups, the size is the number of columns in the
 /// group. The offset is the column index in the trace where the first column of the binding starts.
 #[derive(Copy, Clone, Spanned)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct TraceBinding {
     #[span]
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "crate::ast::serialize::span")
+    )]
     pub span: SourceSpan,
     /// The name of this binding, if applicable
     pub name: Option<Identifier>,
@@ -322,3 +474,14 @@ impl fmt::Display for TraceBinding {
         }
     }
 }
+
+/// Displays the columns bound by a [TraceBinding], e.g. `column 5` or `columns 2..4`
+struct DisplayColumns<'a>(&'a TraceBinding);
+impl<'a> fmt::Display for DisplayColumns<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0.size {
+            1 => write!(f, "column {}", self.0.offset),
+            size => write!(f, "columns {}..{}", self.0.offset, self.0.offset + size),
+        }
+    }
+}
@@ -2,9 +2,14 @@ use super::*;
 
 /// The types of values which can be represented in an AirScript program
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum Type {
     /// A field element
     Felt,
+    /// A field element which is known to be either 0 or 1
+    Binary,
+    /// An unsigned 32-bit integer, as used for exponents
+    U32,
     /// A vector of N integers
     Vector(usize),
     /// A matrix of N rows and M columns
@@ -15,7 +20,7 @@ impl Type {
     #[inline]
     pub fn is_aggregate(&self) -> bool {
         match self {
-            Self::Felt => false,
+            Self::Felt | Self::Binary | Self::U32 => false,
             Self::Vector(_) | Self::Matrix(_, _) => true,
         }
     }
@@ -23,7 +28,19 @@ impl Type {
     /// Returns true if this type is a scalar
     #[inline]
     pub fn is_scalar(&self) -> bool {
-        matches!(self, Self::Felt)
+        matches!(self, Self::Felt | Self::Binary | Self::U32)
+    }
+
+    /// Returns true if this type is known to only hold the values 0 or 1
+    #[inline]
+    pub fn is_binary(&self) -> bool {
+        matches!(self, Self::Binary)
+    }
+
+    /// Returns true if this type is an integer, i.e. is valid as an exponent
+    #[inline]
+    pub fn is_integer(&self) -> bool {
+        matches!(self, Self::U32)
     }
 
     /// Returns true if this type is a valid iterable in a comprehension
@@ -42,7 +59,7 @@ impl Type {
     pub fn access(&self, access_type: AccessType) -> Result<Self, InvalidAccessError> {
         match *self {
             ty if access_type == AccessType::Default => Ok(ty),
-            Self::Felt => Err(InvalidAccessError::IndexIntoScalar),
+            Self::Felt | Self::Binary | Self::U32 => Err(InvalidAccessError::IndexIntoScalar),
             Self::Vector(len) => match access_type {
                 AccessType::Slice(range) if range.end > len => {
                     Err(InvalidAccessError::IndexOutOfBounds)
@@ -73,6 +90,8 @@ impl fmt::Display for Type {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Self::Felt => f.write_str("field element"),
+            Self::Binary => f.write_str("binary value"),
+            Self::U32 => f.write_str("u32 integer"),
             Self::Vector(n) => write!(f, "vector of length {}", n),
             Self::Matrix(rows, cols) => write!(f, "matrix of {} rows and {} columns", rows, cols),
         }
@@ -86,7 +105,6 @@ pub enum FunctionType {
     /// a complex type signature due to the nature of trace bindings
     Evaluator(Vec<TraceSegment>),
     /// A standard function with one or more inputs, and a result
-    #[allow(dead_code)]
     Function(Vec<Type>, Type),
 }
 impl FunctionType {
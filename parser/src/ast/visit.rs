@@ -7,6 +7,10 @@
 //! drive the visitor down to children of the corresponding node type, if desired. For that purpose,
 //! this module exposes a number of `visit_mut_*` functions which can be called to perform the
 //! default visitor traversal for that node.
+//!
+//! The default traversals match exhaustively on the variants of [ast::Statement], [ast::Expr], and
+//! [ast::ScalarExpr], so a variant added to the AST must be handled here, after which it is
+//! reached by every pass built on [VisitMut], including passes defined outside of this crate.
 use core::ops::ControlFlow;
 
 use crate::ast;
@@ -122,6 +126,9 @@ pub trait VisitMut<T> {
     ) -> ControlFlow<T> {
         visit_mut_evaluator_function(self, expr)
     }
+    fn visit_mut_function(&mut self, expr: &mut ast::Function) -> ControlFlow<T> {
+        visit_mut_function(self, expr)
+    }
     fn visit_mut_periodic_column(&mut self, expr: &mut ast::PeriodicColumn) -> ControlFlow<T> {
         visit_mut_periodic_column(self, expr)
     }
@@ -181,6 +188,12 @@ pub trait VisitMut<T> {
     fn visit_mut_enforce_all(&mut self, expr: &mut ast::ListComprehension) -> ControlFlow<T> {
         self.visit_mut_list_comprehension(expr)
     }
+    fn visit_mut_enforce_match(&mut self, expr: &mut ast::Match) -> ControlFlow<T> {
+        visit_mut_enforce_match(self, expr)
+    }
+    fn visit_mut_debug(&mut self, expr: &mut ast::ScalarExpr) -> ControlFlow<T> {
+        self.visit_mut_scalar_expr(expr)
+    }
     fn visit_mut_integrity_constraints(
         &mut self,
         exprs: &mut Vec<ast::Statement>,
@@ -225,7 +238,7 @@ pub trait VisitMut<T> {
     }
 }
 
-impl<'a, V, T> VisitMut<T> for &'a mut V
+impl<V, T> VisitMut<T> for &mut V
 where
     V: ?Sized + VisitMut<T>,
 {
@@ -244,6 +257,9 @@ where
     ) -> ControlFlow<T> {
         (**self).visit_mut_evaluator_function(expr)
     }
+    fn visit_mut_function(&mut self, expr: &mut ast::Function) -> ControlFlow<T> {
+        (**self).visit_mut_function(expr)
+    }
     fn visit_mut_periodic_column(&mut self, expr: &mut ast::PeriodicColumn) -> ControlFlow<T> {
         (**self).visit_mut_periodic_column(expr)
     }
@@ -308,6 +324,12 @@ where
     fn visit_mut_enforce_all(&mut self, expr: &mut ast::ListComprehension) -> ControlFlow<T> {
         (**self).visit_mut_enforce_all(expr)
     }
+    fn visit_mut_enforce_match(&mut self, expr: &mut ast::Match) -> ControlFlow<T> {
+        (**self).visit_mut_enforce_match(expr)
+    }
+    fn visit_mut_debug(&mut self, expr: &mut ast::ScalarExpr) -> ControlFlow<T> {
+        (**self).visit_mut_debug(expr)
+    }
     fn visit_mut_expr(&mut self, expr: &mut ast::Expr) -> ControlFlow<T> {
         (**self).visit_mut_expr(expr)
     }
@@ -359,6 +381,9 @@ where
     for evaluator in module.evaluators.values_mut() {
         visitor.visit_mut_evaluator_function(evaluator)?;
     }
+    for function in module.functions.values_mut() {
+        visitor.visit_mut_function(function)?;
+    }
     for column in module.periodic_columns.values_mut() {
         visitor.visit_mut_periodic_column(column)?;
     }
@@ -439,6 +464,17 @@ where
     visitor.visit_mut_statement_block(&mut expr.body)
 }
 
+pub fn visit_mut_function<V, T>(visitor: &mut V, expr: &mut ast::Function) -> ControlFlow<T>
+where
+    V: ?Sized + VisitMut<T>,
+{
+    visitor.visit_mut_identifier(&mut expr.name)?;
+    for (param, _) in expr.params.iter_mut() {
+        visitor.visit_mut_identifier(param)?;
+    }
+    visitor.visit_mut_statement_block(&mut expr.body)
+}
+
 pub fn visit_mut_evaluator_trace_segment<V, T>(
     visitor: &mut V,
     expr: &mut ast::TraceSegment,
@@ -472,7 +508,11 @@ pub fn visit_mut_periodic_column<V, T>(
 where
     V: ?Sized + VisitMut<T>,
 {
-    visitor.visit_mut_identifier(&mut expr.name)
+    visitor.visit_mut_identifier(&mut expr.name)?;
+    if let Some(generator) = expr.generator.as_deref_mut() {
+        visitor.visit_mut_list_comprehension(generator)?;
+    }
+    ControlFlow::Continue(())
 }
 
 pub fn visit_mut_public_input<V, T>(visitor: &mut V, expr: &mut ast::PublicInput) -> ControlFlow<T>
@@ -529,10 +569,23 @@ where
             visitor.visit_mut_enforce_if(expr, selector)
         }
         ast::Statement::EnforceAll(ref mut expr) => visitor.visit_mut_enforce_all(expr),
+        ast::Statement::EnforceMatch(ref mut expr) => visitor.visit_mut_enforce_match(expr),
+        ast::Statement::WithAttributes(_, ref mut stmt) => visitor.visit_mut_statement(stmt),
         ast::Statement::Expr(ref mut expr) => visitor.visit_mut_expr(expr),
+        ast::Statement::Debug(ref mut expr) => visitor.visit_mut_debug(expr),
     }
 }
 
+pub fn visit_mut_enforce_match<V, T>(visitor: &mut V, expr: &mut ast::Match) -> ControlFlow<T>
+where
+    V: ?Sized + VisitMut<T>,
+{
+    for arm in expr.arms.iter_mut() {
+        visitor.visit_mut_enforce_all(arm)?;
+    }
+    ControlFlow::Continue(())
+}
+
 pub fn visit_mut_let<V, T>(visitor: &mut V, expr: &mut ast::Let) -> ControlFlow<T>
 where
     V: ?Sized + VisitMut<T>,
@@ -550,7 +603,9 @@ where
     V: ?Sized + VisitMut<T>,
 {
     match expr {
-        ast::Expr::Const(_) | ast::Expr::Range(_) => ControlFlow::Continue(()),
+        ast::Expr::Const(_) | ast::Expr::Range(_) | ast::Expr::TraceLen(_) => {
+            ControlFlow::Continue(())
+        }
         ast::Expr::Vector(ref mut exprs) => {
             for expr in exprs.iter_mut() {
                 visitor.visit_mut_expr(expr)?;
@@ -577,7 +632,7 @@ where
     V: ?Sized + VisitMut<T>,
 {
     match expr {
-        ast::ScalarExpr::Const(_) => ControlFlow::Continue(()),
+        ast::ScalarExpr::Const(_) | ast::ScalarExpr::TraceLen(_) => ControlFlow::Continue(()),
         ast::ScalarExpr::SymbolAccess(ref mut expr) => visitor.visit_mut_symbol_access(expr),
         ast::ScalarExpr::BoundedSymbolAccess(ref mut expr) => {
             visitor.visit_mut_bounded_symbol_access(expr)
@@ -608,6 +663,9 @@ where
     for iterable in expr.iterables.iter_mut() {
         visitor.visit_mut_expr(iterable)?;
     }
+    if let Some(filter) = expr.filter.as_mut() {
+        visitor.visit_mut_scalar_expr(filter)?;
+    }
     if let Some(selector) = expr.selector.as_mut() {
         visitor.visit_mut_scalar_expr(selector)?;
     }
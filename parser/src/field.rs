@@ -0,0 +1,103 @@
+use core::fmt;
+
+/// Describes the prime field over which the constraints of a program are expressed.
+///
+/// Constant expressions are evaluated in this field, i.e. all arithmetic on constants is
+/// performed modulo [PrimeField::modulus], rather than using native `u64` arithmetic. By
+/// default this is the 64-bit "Goldilocks" field used by Miden, with modulus `2^64 - 2^32 + 1`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct PrimeField {
+    modulus: u64,
+}
+impl Default for PrimeField {
+    fn default() -> Self {
+        Self::GOLDILOCKS
+    }
+}
+impl PrimeField {
+    /// The 64-bit field with modulus `2^64 - 2^32 + 1`
+    pub const GOLDILOCKS: Self = Self {
+        modulus: 0xFFFF_FFFF_0000_0001,
+    };
+
+    /// The 62-bit field with modulus `2^62 - 111 * 2^39 + 1`
+    pub const F62: Self = Self {
+        modulus: 0x3FFF_C880_0000_0001,
+    };
+
+    /// Creates a new field with the given modulus, which is expected to be prime.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `modulus` is less than 2.
+    pub const fn new(modulus: u64) -> Self {
+        assert!(modulus > 1, "the field modulus must be greater than 1");
+        Self { modulus }
+    }
+
+    /// Returns the modulus of this field
+    #[inline]
+    pub const fn modulus(&self) -> u64 {
+        self.modulus
+    }
+
+    /// Returns true if `value` is the canonical representative of an element of this field, i.e.
+    /// it is less than the modulus
+    #[inline]
+    pub const fn contains(&self, value: u64) -> bool {
+        value < self.modulus
+    }
+
+    /// Returns the canonical representative of `value` in this field
+    #[inline]
+    pub const fn reduce(&self, value: u64) -> u64 {
+        value % self.modulus
+    }
+
+    /// Computes `lhs + rhs` in this field
+    pub fn add(&self, lhs: u64, rhs: u64) -> u64 {
+        ((self.reduce(lhs) as u128 + self.reduce(rhs) as u128) % self.modulus as u128) as u64
+    }
+
+    /// Computes `lhs - rhs` in this field
+    pub fn sub(&self, lhs: u64, rhs: u64) -> u64 {
+        let lhs = self.reduce(lhs);
+        let rhs = self.reduce(rhs);
+        if lhs >= rhs {
+            lhs - rhs
+        } else {
+            self.modulus - (rhs - lhs)
+        }
+    }
+
+    /// Computes `lhs * rhs` in this field
+    pub fn mul(&self, lhs: u64, rhs: u64) -> u64 {
+        ((lhs as u128 * rhs as u128) % self.modulus as u128) as u64
+    }
+
+    /// Computes `base^exp` in this field
+    pub fn pow(&self, base: u64, mut exp: u64) -> u64 {
+        let mut base = self.reduce(base);
+        let mut result = self.reduce(1);
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = self.mul(result, base);
+            }
+            base = self.mul(base, base);
+            exp >>= 1;
+        }
+        result
+    }
+
+    /// Returns true if the canonical representative `value` stands for a negative integer, i.e.
+    /// if it lies in the upper half of the field, as is the case for the result of `0 - 1`.
+    #[inline]
+    pub const fn is_negative(&self, value: u64) -> bool {
+        value > self.modulus / 2
+    }
+}
+impl fmt::Display for PrimeField {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "GF({})", self.modulus)
+    }
+}
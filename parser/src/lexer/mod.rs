@@ -21,6 +21,8 @@ pub enum LexicalError {
     },
     #[error("encountered unexpected character '{found}'")]
     UnexpectedCharacter { start: SourceIndex, found: char },
+    #[error("unterminated string literal")]
+    UnclosedString { span: SourceSpan },
 }
 impl PartialEq for LexicalError {
     fn eq(&self, other: &Self) -> bool {
@@ -32,6 +34,7 @@ impl PartialEq for LexicalError {
                 Self::UnexpectedCharacter { found: lhs, .. },
                 Self::UnexpectedCharacter { found: rhs, .. },
             ) => lhs == rhs,
+            (Self::UnclosedString { .. }, Self::UnclosedString { .. }) => true,
             _ => false,
         }
     }
@@ -51,6 +54,10 @@ impl ToDiagnostic for LexicalError {
                     start.source_id(),
                     SourceSpan::new(start, start),
                 )]),
+            Self::UnclosedString { span } => Diagnostic::error()
+                .with_message("unterminated string literal")
+                .with_labels(vec![Label::primary(span.source_id(), span)
+                    .with_message("expected a closing '\"' before the end of the line")]),
         }
     }
 }
@@ -82,10 +89,14 @@ pub enum Token {
     /// A reference to an identifier used for a section declaration, such as the random values
     /// array or a trace segment like "main" or "aux".
     DeclIdentRef(Symbol),
+    /// The reserved identifier `$trace_len`, which evaluates to the length of the execution trace
+    TraceLen,
     /// A function identifier
     FunctionIdent(Symbol),
     /// Integers should only contain numeric characters.
     Num(u64),
+    /// A string literal, without its surrounding quotes
+    Str(Symbol),
 
     // DECLARATION KEYWORDS
     // --------------------------------------------------------------------------------------------
@@ -95,6 +106,8 @@ pub enum Token {
     Mod,
     /// Used to import items from an AIR module.
     Use,
+    /// Used to import an item from an AIR module under a different name.
+    As,
     /// Used to declare intermediate variables in the AIR constraints module.
     Let,
     /// Used to declare constants in the AIR constraints module.
@@ -113,6 +126,10 @@ pub enum Token {
     RandomValues,
     /// Keyword to declare the evaluator function section in the AIR constraints module.
     Ev,
+    /// Keyword to declare a pure function in the AIR constraints module.
+    Fn,
+    /// Keyword to return the result of a pure function.
+    Return,
 
     // BOUNDARY CONSTRAINT KEYWORDS
     // --------------------------------------------------------------------------------------------
@@ -140,12 +157,30 @@ pub enum Token {
     Match,
     Case,
     When,
+    /// Keyword which introduces the compile-time filter of a constraint comprehension
+    Where,
+    /// Keyword for statements which report the lowered form of an expression
+    Debug,
+    /// Keyword which introduces the helper column of a `!=` constraint
+    With,
+    /// Keyword which merges the statements of another file into the current block, e.g. `include "a.air"`
+    Include,
+    /// Keyword for compile-time assertions about the program, e.g. `assert trace_width(main) == 34`
+    Assert,
+
+    // TYPE KEYWORDS
+    // --------------------------------------------------------------------------------------------
+    /// Keyword for the type of field elements
+    Felt,
+    /// Keyword for the type of field elements which are either 0 or 1
+    Binary,
 
     // PUNCTUATION
     // --------------------------------------------------------------------------------------------
     Quote,
     Colon,
     ColonColon,
+    Semicolon,
     Comma,
     Dot,
     DotDot,
@@ -154,13 +189,17 @@ pub enum Token {
     LBracket,
     RBracket,
     Equal,
+    EqualEqual,
     Plus,
     Minus,
+    Arrow,
     Star,
     Caret,
     Ampersand,
     Bar,
     Bang,
+    BangEqual,
+    At,
 }
 impl Token {
     pub fn from_keyword_or_ident(s: &str) -> Self {
@@ -168,6 +207,7 @@ impl Token {
             "def" => Self::Def,
             "mod" => Self::Mod,
             "use" => Self::Use,
+            "as" => Self::As,
             "let" => Self::Let,
             "const" => Self::Const,
             "trace_columns" => Self::TraceColumns,
@@ -177,6 +217,8 @@ impl Token {
             "periodic_columns" => Self::PeriodicColumns,
             "random_values" => Self::RandomValues,
             "ev" => Self::Ev,
+            "fn" => Self::Fn,
+            "return" => Self::Return,
             "boundary_constraints" => Self::BoundaryConstraints,
             "integrity_constraints" => Self::IntegrityConstraints,
             "first" => Self::First,
@@ -187,6 +229,13 @@ impl Token {
             "match" => Self::Match,
             "case" => Self::Case,
             "when" => Self::When,
+            "where" => Self::Where,
+            "include" => Self::Include,
+            "assert" => Self::Assert,
+            "debug" => Self::Debug,
+            "with" => Self::With,
+            "felt" => Self::Felt,
+            "binary" => Self::Binary,
             other => Self::Ident(Symbol::intern(other)),
         }
     }
@@ -220,6 +269,11 @@ impl PartialEq for Token {
                     return i == i2;
                 }
             }
+            Self::Str(s) => {
+                if let Self::Str(s2) = other {
+                    return s == s2;
+                }
+            }
             _ => return mem::discriminant(self) == mem::discriminant(other),
         }
         false
@@ -233,11 +287,14 @@ impl fmt::Display for Token {
             Self::Comment => write!(f, "COMMENT"),
             Self::Ident(ref id) => write!(f, "{}", id),
             Self::DeclIdentRef(ref id) => write!(f, "{}", id),
+            Self::TraceLen => write!(f, "$trace_len"),
             Self::FunctionIdent(ref id) => write!(f, "{}", id),
             Self::Num(ref i) => write!(f, "{}", i),
+            Self::Str(ref s) => write!(f, "\"{}\"", s),
             Self::Def => write!(f, "def"),
             Self::Mod => write!(f, "mod"),
             Self::Use => write!(f, "use"),
+            Self::As => write!(f, "as"),
             Self::Let => write!(f, "let"),
             Self::Const => write!(f, "const"),
             Self::TraceColumns => write!(f, "trace_columns"),
@@ -247,6 +304,8 @@ impl fmt::Display for Token {
             Self::PeriodicColumns => write!(f, "periodic_columns"),
             Self::RandomValues => write!(f, "random_values"),
             Self::Ev => write!(f, "ev"),
+            Self::Fn => write!(f, "fn"),
+            Self::Return => write!(f, "return"),
             Self::BoundaryConstraints => write!(f, "boundary_constraints"),
             Self::First => write!(f, "first"),
             Self::Last => write!(f, "last"),
@@ -257,9 +316,17 @@ impl fmt::Display for Token {
             Self::Match => write!(f, "match"),
             Self::Case => write!(f, "case"),
             Self::When => write!(f, "when"),
+            Self::Where => write!(f, "where"),
+            Self::Include => write!(f, "include"),
+            Self::Assert => write!(f, "assert"),
+            Self::Debug => write!(f, "debug"),
+            Self::With => write!(f, "with"),
+            Self::Felt => write!(f, "felt"),
+            Self::Binary => write!(f, "binary"),
             Self::Quote => write!(f, "'"),
             Self::Colon => write!(f, ":"),
             Self::ColonColon => write!(f, "::"),
+            Self::Semicolon => write!(f, ";"),
             Self::Comma => write!(f, ","),
             Self::Dot => write!(f, "."),
             Self::DotDot => write!(f, ".."),
@@ -268,13 +335,17 @@ impl fmt::Display for Token {
             Self::LBracket => write!(f, "["),
             Self::RBracket => write!(f, "]"),
             Self::Equal => write!(f, "="),
+            Self::EqualEqual => write!(f, "=="),
             Self::Plus => write!(f, "+"),
             Self::Minus => write!(f, "-"),
+            Self::Arrow => write!(f, "->"),
             Self::Star => write!(f, "*"),
             Self::Caret => write!(f, "^"),
             Self::Ampersand => write!(f, "&"),
             Self::Bar => write!(f, "|"),
             Self::Bang => write!(f, "!"),
+            Self::BangEqual => write!(f, "!="),
+            Self::At => write!(f, "@"),
         }
     }
 }
@@ -334,6 +405,9 @@ pub struct Lexer<S> {
     /// produced after that point is Token::Eof, or None, depending on how you are
     /// consuming the lexer
     eof: bool,
+
+    /// When set, comments are produced as [Token::Comment] rather than being skipped
+    comments: bool,
 }
 impl<S> Lexer<S>
 where
@@ -351,11 +425,20 @@ where
             token_start: start + ByteOffset(0),
             token_end: start + ByteOffset(0),
             eof: false,
+            comments: false,
         };
         lexer.advance();
         lexer
     }
 
+    /// Toggles whether comments are produced as tokens, rather than skipped (the default).
+    ///
+    /// The parser has no use for comments, but tools such as syntax highlighters do.
+    pub fn with_comments(mut self, comments: bool) -> Self {
+        self.comments = comments;
+        self
+    }
+
     pub fn lex(&mut self) -> Option<<Self as Iterator>::Item> {
         if self.eof && self.token == Token::Eof {
             return None;
@@ -471,6 +554,7 @@ where
 
         match self.read() {
             ',' => pop!(self, Token::Comma),
+            ';' => pop!(self, Token::Semicolon),
             '.' => match self.peek() {
                 '.' => pop2!(self, Token::DotDot),
                 _ => pop!(self, Token::Dot),
@@ -484,22 +568,36 @@ where
             ')' => pop!(self, Token::RParen),
             '[' => pop!(self, Token::LBracket),
             ']' => pop!(self, Token::RBracket),
-            '=' => pop!(self, Token::Equal),
+            '=' => match self.peek() {
+                '=' => pop2!(self, Token::EqualEqual),
+                _ => pop!(self, Token::Equal),
+            },
             '+' => pop!(self, Token::Plus),
-            '-' => pop!(self, Token::Minus),
+            '-' => match self.peek() {
+                '>' => pop2!(self, Token::Arrow),
+                _ => pop!(self, Token::Minus),
+            },
             '*' => pop!(self, Token::Star),
             '^' => pop!(self, Token::Caret),
             '&' => pop!(self, Token::Ampersand),
             '|' => pop!(self, Token::Bar),
-            '!' => pop!(self, Token::Bang),
+            '!' => match self.peek() {
+                '=' => pop2!(self, Token::BangEqual),
+                _ => pop!(self, Token::Bang),
+            },
+            '@' => pop!(self, Token::At),
+            '"' => self.lex_string(),
             '$' => self.lex_special_identifier(),
             '0'..='9' => self.lex_number(),
-            'a'..='z' => self.lex_keyword_or_ident(),
-            'A'..='Z' => self.lex_identifier(),
-            c => Token::Error(LexicalError::UnexpectedCharacter {
-                start: self.span().start(),
-                found: c,
-            }),
+            'a'..='z' | 'A'..='Z' | '_' => self.lex_keyword_or_ident(),
+            c => {
+                // consume the character, so that lexing can resume after it
+                self.skip();
+                Token::Error(LexicalError::UnexpectedCharacter {
+                    start: self.span().start(),
+                    found: c,
+                })
+            }
         }
     }
 
@@ -528,9 +626,15 @@ where
         let c = self.pop();
         debug_assert!(c == '$');
 
-        // Must start with an alphabetic character
+        // Must start with an alphabetic character, unless this is a positional reference to a
+        // parameter, e.g. `$0`, in which case it consists only of digits
         match self.read() {
-            c if c.is_ascii_alphabetic() => (),
+            c if c.is_ascii_alphabetic() => self.skip_ident(),
+            c if c.is_ascii_digit() => {
+                while self.read().is_ascii_digit() {
+                    self.skip();
+                }
+            }
             c => {
                 return Token::Error(LexicalError::UnexpectedCharacter {
                     start: self.span().start(),
@@ -539,15 +643,20 @@ where
             }
         }
 
-        self.skip_ident();
-
-        Token::DeclIdentRef(Symbol::intern(self.slice()))
+        match self.slice() {
+            "$trace_len" => Token::TraceLen,
+            name => Token::DeclIdentRef(Symbol::intern(name)),
+        }
     }
 
+    /// Lexes a keyword or identifier, regardless of the case of its first character.
+    ///
+    /// An identifier immediately followed by `(` is lexed as a function identifier, but keywords
+    /// never are, so that e.g. `enf(...)` is still lexed as the `enf` keyword.
     #[inline]
     fn lex_keyword_or_ident(&mut self) -> Token {
         let c = self.pop();
-        debug_assert!(c.is_ascii_alphabetic() && c.is_lowercase());
+        debug_assert!(c.is_ascii_alphabetic() || c == '_');
 
         self.skip_ident();
 
@@ -558,20 +667,6 @@ where
         }
     }
 
-    #[inline]
-    fn lex_identifier(&mut self) -> Token {
-        let c = self.pop();
-        debug_assert!(c.is_ascii_alphabetic());
-
-        self.skip_ident();
-
-        if self.read() == '(' {
-            Token::FunctionIdent(Symbol::intern(self.slice()))
-        } else {
-            Token::Ident(Symbol::intern(self.slice()))
-        }
-    }
-
     fn skip_ident(&mut self) {
         loop {
             match self.read() {
@@ -583,6 +678,27 @@ where
         }
     }
 
+    /// Lexes a string literal, which may not contain escapes or span multiple lines.
+    #[inline]
+    fn lex_string(&mut self) -> Token {
+        let c = self.pop();
+        debug_assert!(c == '"');
+
+        loop {
+            match self.read() {
+                '"' => break,
+                '\n' | '\0' => {
+                    return Token::Error(LexicalError::UnclosedString { span: self.span() })
+                }
+                _ => self.skip(),
+            }
+        }
+        self.skip();
+
+        let slice = self.slice();
+        Token::Str(Symbol::intern(&slice[1..slice.len() - 1]))
+    }
+
     #[inline]
     fn lex_number(&mut self) -> Token {
         let mut num = String::new();
@@ -598,7 +714,7 @@ where
             Ok(i) => Token::Num(i),
             Err(err) => Token::Error(LexicalError::InvalidInt {
                 span: self.span(),
-                reason: err.kind().clone(),
+                reason: *err.kind(),
             }),
         }
     }
@@ -612,6 +728,9 @@ where
 
     fn next(&mut self) -> Option<Self::Item> {
         let mut res = self.lex();
+        if self.comments {
+            return res;
+        }
         while let Some(Ok((_, Token::Comment, _))) = res {
             res = self.lex();
         }
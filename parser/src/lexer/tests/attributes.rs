@@ -0,0 +1,32 @@
+use super::{expect_error_at_location, expect_valid_tokenization, LexicalError, Symbol, Token};
+
+#[test]
+fn constraint_attributes() {
+    let source = r#"@[name="range_check", group=""] enf a = 0"#;
+    let tokens = vec![
+        Token::At,
+        Token::LBracket,
+        Token::Ident(Symbol::intern("name")),
+        Token::Equal,
+        Token::Str(Symbol::intern("range_check")),
+        Token::Comma,
+        Token::Ident(Symbol::intern("group")),
+        Token::Equal,
+        Token::Str(Symbol::intern("")),
+        Token::RBracket,
+        Token::Enf,
+        Token::Ident(Symbol::intern("a")),
+        Token::Equal,
+        Token::Num(0),
+    ];
+    expect_valid_tokenization(source, tokens);
+}
+
+#[test]
+fn error_unclosed_string() {
+    let source = "@[name=\"range_check]\nenf a = 0";
+    let expected = LexicalError::UnclosedString {
+        span: miden_diagnostics::SourceSpan::UNKNOWN,
+    };
+    expect_error_at_location(source, expected, 0, 7);
+}
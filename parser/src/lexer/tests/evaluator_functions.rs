@@ -69,10 +69,10 @@ fn ev_fn_with_main_and_aux_cols() {
     ev ev_fn([main_state[12]], [aux_state[12]]):
         let ms = [x^7 for x in main_state]
         let ms_sum = sum([x^7 for x in main_state])
-        let as = [x^7 for x in aux_state]
+        let aux_pow = [x^7 for x in aux_state]
         
         enf main_state[0] = ms[0] + ms_sum
-        enf aux_state[0] = as[0] * $rand[0]";
+        enf aux_state[0] = aux_pow[0] * $rand[0]";
 
     let tokens = [
         Token::Ev,
@@ -121,7 +121,7 @@ fn ev_fn_with_main_and_aux_cols() {
         Token::RBracket,
         Token::RParen,
         Token::Let,
-        Token::Ident(Symbol::intern("as")),
+        Token::Ident(Symbol::intern("aux_pow")),
         Token::Equal,
         Token::LBracket,
         Token::Ident(Symbol::intern("x")),
@@ -150,7 +150,7 @@ fn ev_fn_with_main_and_aux_cols() {
         Token::Num(0),
         Token::RBracket,
         Token::Equal,
-        Token::Ident(Symbol::intern("as")),
+        Token::Ident(Symbol::intern("aux_pow")),
         Token::LBracket,
         Token::Num(0),
         Token::RBracket,
@@ -0,0 +1,63 @@
+use super::{expect_valid_tokenization, Symbol, Token};
+
+// FUNCTION VALID TOKENIZATION
+// ================================================================================================
+
+#[test]
+fn fn_with_scalar_params() {
+    let source = "
+    fn madd(a: felt, b: felt[2]) -> felt:
+        let x = a * b[0]
+        return x + b[1]";
+
+    let tokens = [
+        Token::Fn,
+        Token::FunctionIdent(Symbol::intern("madd")),
+        Token::LParen,
+        Token::Ident(Symbol::intern("a")),
+        Token::Colon,
+        Token::Felt,
+        Token::Comma,
+        Token::Ident(Symbol::intern("b")),
+        Token::Colon,
+        Token::Felt,
+        Token::LBracket,
+        Token::Num(2),
+        Token::RBracket,
+        Token::RParen,
+        Token::Arrow,
+        Token::Felt,
+        Token::Colon,
+        Token::Let,
+        Token::Ident(Symbol::intern("x")),
+        Token::Equal,
+        Token::Ident(Symbol::intern("a")),
+        Token::Star,
+        Token::Ident(Symbol::intern("b")),
+        Token::LBracket,
+        Token::Num(0),
+        Token::RBracket,
+        Token::Return,
+        Token::Ident(Symbol::intern("x")),
+        Token::Plus,
+        Token::Ident(Symbol::intern("b")),
+        Token::LBracket,
+        Token::Num(1),
+        Token::RBracket,
+    ];
+    expect_valid_tokenization(source, tokens.to_vec());
+}
+
+#[test]
+fn arrow_is_distinct_from_minus() {
+    let source = "a - b -> c";
+
+    let tokens = [
+        Token::Ident(Symbol::intern("a")),
+        Token::Minus,
+        Token::Ident(Symbol::intern("b")),
+        Token::Arrow,
+        Token::Ident(Symbol::intern("c")),
+    ];
+    expect_valid_tokenization(source, tokens.to_vec());
+}
@@ -100,16 +100,111 @@ fn valid_tokenization_indexed_trace_access() {
     expect_valid_tokenization(source, tokens);
 }
 
+#[test]
+fn valid_tokenization_positional_params() {
+    let source = "enf $0' = $1 + $10";
+    let tokens = vec![
+        Token::Enf,
+        Token::DeclIdentRef(Symbol::intern("$0")),
+        Token::Quote,
+        Token::Equal,
+        Token::DeclIdentRef(Symbol::intern("$1")),
+        Token::Plus,
+        Token::DeclIdentRef(Symbol::intern("$10")),
+    ];
+    expect_valid_tokenization(source, tokens);
+}
+
+#[test]
+fn valid_tokenization_trace_len() {
+    let source = "enf a' = a + $trace_len - $trace_length";
+    let tokens = vec![
+        Token::Enf,
+        Token::Ident(Symbol::intern("a")),
+        Token::Quote,
+        Token::Equal,
+        Token::Ident(Symbol::intern("a")),
+        Token::Plus,
+        Token::TraceLen,
+        Token::Minus,
+        Token::DeclIdentRef(Symbol::intern("$trace_length")),
+    ];
+    expect_valid_tokenization(source, tokens);
+}
+
+#[test]
+fn function_identifiers_regardless_of_case() {
+    let source = "enf foo([a]) + Bar([b]) + BAZ([c])";
+    let tokens = vec![
+        Token::Enf,
+        Token::FunctionIdent(Symbol::intern("foo")),
+        Token::LParen,
+        Token::LBracket,
+        Token::Ident(Symbol::intern("a")),
+        Token::RBracket,
+        Token::RParen,
+        Token::Plus,
+        Token::FunctionIdent(Symbol::intern("Bar")),
+        Token::LParen,
+        Token::LBracket,
+        Token::Ident(Symbol::intern("b")),
+        Token::RBracket,
+        Token::RParen,
+        Token::Plus,
+        Token::FunctionIdent(Symbol::intern("BAZ")),
+        Token::LParen,
+        Token::LBracket,
+        Token::Ident(Symbol::intern("c")),
+        Token::RBracket,
+        Token::RParen,
+    ];
+    expect_valid_tokenization(source, tokens);
+}
+
+#[test]
+fn keywords_followed_by_paren() {
+    // keywords are never function identifiers, even when immediately followed by `(`
+    let source = "enf(a) match(b) felt(c)";
+    let tokens = vec![
+        Token::Enf,
+        Token::LParen,
+        Token::Ident(Symbol::intern("a")),
+        Token::RParen,
+        Token::Match,
+        Token::LParen,
+        Token::Ident(Symbol::intern("b")),
+        Token::RParen,
+        Token::Felt,
+        Token::LParen,
+        Token::Ident(Symbol::intern("c")),
+        Token::RParen,
+    ];
+    expect_valid_tokenization(source, tokens);
+}
+
+#[test]
+fn uppercase_identifiers_are_not_keywords() {
+    let source = "Enf MATCH(a)";
+    let tokens = vec![
+        Token::Ident(Symbol::intern("Enf")),
+        Token::FunctionIdent(Symbol::intern("MATCH")),
+        Token::LParen,
+        Token::Ident(Symbol::intern("a")),
+        Token::RParen,
+    ];
+    expect_valid_tokenization(source, tokens);
+}
+
 // SCAN ERRORS
 // ================================================================================================
 
 #[test]
 fn error_identifier_with_invalid_characters() {
-    let source = "enf clk@' = clk + 1";
-    // "@" is not in the allowed characters.
+    let source = "enf clk~' = clk + 1";
+    // "~" is not in the allowed characters.
     let expected = LexicalError::UnexpectedCharacter {
         start: SourceIndex::UNKNOWN,
-        found: '@',
+        found: '~',
     };
     expect_error_at_location(source, expected, 0, 7);
 }
@@ -118,11 +213,11 @@ fn error_identifier_with_invalid_characters() {
 fn return_first_invalid_character_error() {
     use miden_diagnostics::ByteIndex;
 
-    let source = "enf clk@' = clk@ + 1";
-    // "@" is not in the allowed characters.
+    let source = "enf clk~' = clk~ + 1";
+    // "~" is not in the allowed characters.
     let err = expect_any_error(source);
     match err {
-        LexicalError::UnexpectedCharacter { start, found: '@' } => {
+        LexicalError::UnexpectedCharacter { start, found: '~' } => {
             let expected = SourceIndex::new(start.source_id(), ByteIndex(7));
             assert_eq!(start, expected);
         }
@@ -3,15 +3,18 @@ use crate::parser::ParseError;
 use crate::Symbol;
 
 mod arithmetic_ops;
+mod attributes;
 mod boundary_constraints;
 mod constants;
 mod evaluator_functions;
+mod functions;
 mod identifiers;
 mod list_comprehension;
 mod modules;
 mod periodic_columns;
 mod pub_inputs;
 mod random_values;
+mod tokenize;
 mod variables;
 
 // TEST HELPERS
@@ -50,7 +53,9 @@ fn expect_error_at_location(source: &str, expected: LexicalError, line: u32, col
         .expect("expected lexical error, but lexing completed successfully");
 
     let loc = match &err {
-        LexicalError::InvalidInt { span, .. } => codemap.location(span).unwrap(),
+        LexicalError::InvalidInt { span, .. } | LexicalError::UnclosedString { span } => {
+            codemap.location(span).unwrap()
+        }
         LexicalError::UnexpectedCharacter { start, .. } => {
             let span = miden_diagnostics::SourceSpan::new(*start, *start);
             codemap.location(&span).unwrap()
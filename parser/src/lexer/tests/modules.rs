@@ -76,6 +76,22 @@ fn root_module_with_imports_tokenization() {
     expect_valid_tokenization(source, tokens);
 }
 
+#[test]
+fn import_with_alias_tokenization() {
+    let source = r#"
+    use my_constraints::first_is_zero as is_zero
+    "#;
+    let tokens = vec![
+        Token::Use,
+        Token::Ident(Symbol::intern("my_constraints")),
+        Token::ColonColon,
+        Token::Ident(Symbol::intern("first_is_zero")),
+        Token::As,
+        Token::Ident(Symbol::intern("is_zero")),
+    ];
+    expect_valid_tokenization(source, tokens);
+}
+
 #[test]
 fn library_module_tokenization() {
     let source = r#"
@@ -0,0 +1,41 @@
+use crate::lexer::{Symbol, Token};
+
+// STREAMING TOKENIZATION
+// ================================================================================================
+
+#[test]
+fn tokenize_with_spans_and_comments() {
+    let source = "def test # the program name\nenf clk' = 1";
+
+    let tokens = crate::tokenize(source)
+        .map(|res| {
+            let (start, token, end) = res.unwrap();
+            (
+                token,
+                &source[start.index().to_usize()..end.index().to_usize()],
+            )
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        tokens,
+        vec![
+            (Token::Def, "def"),
+            (Token::Ident(Symbol::intern("test")), "test"),
+            (Token::Comment, "# the program name"),
+            (Token::Enf, "enf"),
+            (Token::Ident(Symbol::intern("clk")), "clk"),
+            (Token::Quote, "'"),
+            (Token::Equal, "="),
+            (Token::Num(1), "1"),
+        ]
+    );
+}
+
+#[test]
+fn tokenize_reports_lexical_errors() {
+    let source = "enf clk = 1 ~ 2";
+
+    let errors = crate::tokenize(source).filter(Result::is_err).count();
+    assert_eq!(errors, 1);
+}
@@ -2,20 +2,26 @@
 extern crate lalrpop_util;
 
 pub mod ast;
+pub mod field;
 mod lexer;
 mod parser;
 mod sema;
 pub mod symbols;
 pub mod transforms;
 
+pub use self::field::PrimeField;
+pub use self::lexer::{Lexed, LexicalError, Token};
 pub use self::parser::{ParseError, Parser};
 pub use self::sema::{LexicalScope, SemanticAnalysisError};
 pub use self::symbols::Symbol;
 
 use std::path::Path;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
-use miden_diagnostics::{CodeMap, DiagnosticsHandler};
+use miden_diagnostics::{
+    term::termcolor::Buffer, CodeMap, DiagnosticsHandler, Emitter, FileName, Severity, SourceFile,
+    SourceSpan,
+};
 
 /// Parses the provided source and returns the AST.
 pub fn parse(
@@ -51,6 +57,30 @@ pub fn parse_file<P: AsRef<Path>>(
     }
 }
 
+/// Parses the provided source and returns the AST, like [parse], except that the modules it
+/// imports are taken from `cache` when they are unchanged since they were last parsed.
+///
+/// This is intended for use when compiling many programs which share imports, e.g. a directory
+/// of AIRs which all use the same library modules, in which case the same [CodeMap] should be
+/// used for each program, see [ast::ModuleCache] for details.
+pub fn parse_with_cache(
+    diagnostics: &DiagnosticsHandler,
+    codemap: Arc<CodeMap>,
+    source: &str,
+    cache: &ast::ModuleCache,
+) -> Result<ast::Program, ParseError> {
+    let id = codemap.add("nofile", source.to_string());
+    let file = codemap.get(id).unwrap();
+    match parser::parse_program_with_cache(diagnostics, codemap, file, cache) {
+        Ok(ast) => Ok(ast),
+        Err(ParseError::Lexer(err)) => {
+            diagnostics.emit(err);
+            Err(ParseError::Failed)
+        }
+        Err(err) => Err(err),
+    }
+}
+
 /// Parses the provided source string with a default [CodeMap] and [DiagnosticsHandler].
 ///
 /// This is primarily provided for use in tests, you should generally prefer [parse]
@@ -71,25 +101,128 @@ pub fn parse_str(source: &str) -> Result<ast::Program, ParseError> {
     parse(&diagnostics, codemap, source)
 }
 
-/// Parses a [Module] from the given path.
+/// Parses the provided source string with a default [CodeMap], returning the diagnostics raised
+/// while parsing, rather than printing them.
 ///
-/// This is primarily intended for use in the import resolution phase.
-pub(crate) fn parse_module_from_file<P: AsRef<Path>>(
-    diagnostics: &DiagnosticsHandler,
-    codemap: Arc<CodeMap>,
-    path: P,
-) -> Result<ast::Module, ParseError> {
-    let parser = Parser::new((), codemap);
-    match parser.parse_file::<ast::Module, _, _>(diagnostics, path) {
-        ok @ Ok(_) => ok,
-        Err(ParseError::Lexer(err)) => {
+/// Unlike [parse_str], warnings are not treated as errors, so they may be returned along with a
+/// successfully parsed program. When parsing fails, the error is reported as the last of the
+/// diagnostics, and [ParseError::Failed] is returned.
+pub fn parse_with_capture(
+    source: &str,
+) -> (Result<ast::Program, ParseError>, Vec<CapturedDiagnostic>) {
+    use miden_diagnostics::{DiagnosticsConfig, Verbosity};
+
+    let codemap = Arc::new(CodeMap::new());
+    let emitter = Arc::new(DiagnosticCollector::default());
+    let config = DiagnosticsConfig {
+        verbosity: Verbosity::Warning,
+        warnings_as_errors: false,
+        no_warn: false,
+        display: Default::default(),
+    };
+    let diagnostics = DiagnosticsHandler::new(config, codemap.clone(), emitter.clone());
+    let result = parse(&diagnostics, codemap, source).map_err(|err| {
+        if !matches!(err, ParseError::Failed) {
             diagnostics.emit(err);
-            Err(ParseError::Failed)
         }
-        err @ Err(_) => err,
+        ParseError::Failed
+    });
+
+    let captured = emitter.diagnostics.lock().unwrap().drain(..).collect();
+    (result, captured)
+}
+
+/// A diagnostic returned by [parse_with_capture]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapturedDiagnostic {
+    pub severity: Severity,
+    /// The main message of the diagnostic, e.g. `unexpected token`
+    pub message: String,
+    /// The diagnostic as it would have been printed, including its labels and notes
+    pub rendered: String,
+}
+impl CapturedDiagnostic {
+    fn from_rendered(rendered: String) -> Self {
+        // The first line of a rendered diagnostic is of the form `<severity>[<code>]: <message>`,
+        // where the code is optional
+        let header = rendered.lines().next().unwrap_or_default();
+        let (severity, message) = header.split_once(": ").unwrap_or(("error", header));
+        let severity = match severity.split('[').next().unwrap_or_default() {
+            "bug" => Severity::Bug,
+            "warning" => Severity::Warning,
+            "note" => Severity::Note,
+            "help" => Severity::Help,
+            _ => Severity::Error,
+        };
+        let message = message.to_string();
+
+        Self {
+            severity,
+            message,
+            rendered,
+        }
+    }
+}
+
+/// An [Emitter] which collects each diagnostic, rendered without color, for [parse_with_capture]
+#[derive(Default)]
+struct DiagnosticCollector {
+    diagnostics: Mutex<Vec<CapturedDiagnostic>>,
+}
+impl Emitter for DiagnosticCollector {
+    fn buffer(&self) -> Buffer {
+        Buffer::no_color()
+    }
+
+    fn print(&self, buffer: Buffer) -> std::io::Result<()> {
+        let rendered = String::from_utf8_lossy(&buffer.into_inner()).into_owned();
+        self.diagnostics
+            .lock()
+            .unwrap()
+            .push(CapturedDiagnostic::from_rendered(rendered));
+        Ok(())
     }
 }
 
+/// Splits the provided source into a stream of tokens, without parsing it.
+///
+/// Each token is produced along with the [miden_diagnostics::SourceIndex] of its start and end,
+/// from which the byte offsets of the token in `source` can be obtained. Unlike when parsing,
+/// comments are not skipped, but produced as [Token::Comment], which makes this suitable for
+/// use by tools such as syntax highlighters.
+pub fn tokenize(source: &str) -> impl Iterator<Item = Lexed> {
+    use miden_parsing::{FileMapSource, Scanner, Source};
+
+    let codemap = CodeMap::new();
+    let id = codemap.add("nofile", source.to_string());
+    let file = codemap.get(id).unwrap();
+    lexer::Lexer::new(Scanner::new(FileMapSource::new(file))).with_comments(true)
+}
+
+/// Returns the current contents of the file at `path`, as a file in `codemap`.
+///
+/// A file on disk is always read again, and if its contents differ from those already in the
+/// codemap under the same name, the codemap entry is replaced, so that a codemap shared across
+/// compilations never yields stale contents for a file which has since been edited. A file which
+/// is not on disk is taken from the codemap, so that files can be added directly to the codemap
+/// during testing for convenience.
+pub(crate) fn load_file(codemap: &CodeMap, path: &Path) -> std::io::Result<Arc<SourceFile>> {
+    let name = FileName::Real(path.to_path_buf());
+    let cached = codemap.get_by_name(&name);
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(err) => return cached.ok_or(err),
+    };
+    let id = match cached {
+        Some(file) if file.source() == content => return Ok(file),
+        // Real files are de-duplicated by name on `add`, so a changed file must be added as a new
+        // entry, which then replaces the stale one when looking the file up by name
+        Some(_) => codemap.add_child(name, content, SourceSpan::UNKNOWN),
+        None => codemap.add(name, content),
+    };
+    Ok(codemap.get(id).unwrap())
+}
+
 /// Parses a [Module] from a file already in the codemap
 ///
 /// This is primarily intended for use in the import resolution phase.
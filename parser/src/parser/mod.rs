@@ -14,12 +14,16 @@ lalrpop_mod!(
     "/parser/grammar.rs"
 );
 
-use std::sync::Arc;
+use std::{
+    path::{Component, Path, PathBuf},
+    sync::Arc,
+};
 
 use miden_diagnostics::{
-    CodeMap, Diagnostic, DiagnosticsHandler, Label, SourceIndex, SourceSpan, ToDiagnostic,
+    CodeMap, Diagnostic, DiagnosticsHandler, FileName, Label, Severity, SourceFile, SourceIndex,
+    SourceSpan, ToDiagnostic,
 };
-use miden_parsing::{Scanner, Source};
+use miden_parsing::{FileMapSource, Scanner, Source};
 
 use crate::{
     ast,
@@ -204,7 +208,8 @@ impl miden_parsing::Parse for ast::Source {
         tokens: S,
     ) -> Result<Self, Self::Error> {
         let mut next_var = 0;
-        let result = Self::Parser::new().parse(diagnostics, &codemap, &mut next_var, tokens);
+        let result =
+            Self::Parser::new().parse(diagnostics, &codemap, &mut next_var, None, &[], tokens);
         match result {
             Ok(ast) => {
                 if diagnostics.has_errors() {
@@ -246,19 +251,123 @@ impl miden_parsing::Parse for ast::Program {
         codemap: Arc<CodeMap>,
         tokens: S,
     ) -> Result<Self, Self::Error> {
-        let mut next_var = 0;
-        let result = Self::Parser::new().parse(diagnostics, &codemap, &mut next_var, tokens);
-        match result {
-            Ok(ast) => {
-                if diagnostics.has_errors() {
-                    return Err(ParseError::Failed);
+        parse_program_tokens(diagnostics, codemap, tokens, None)
+    }
+}
+
+/// Parses an [ast::Program] from `source`, resolving the modules it imports via `cache`.
+pub(crate) fn parse_program_with_cache(
+    diagnostics: &DiagnosticsHandler,
+    codemap: Arc<CodeMap>,
+    source: Arc<SourceFile>,
+    cache: &ast::ModuleCache,
+) -> Result<ast::Program, ParseError> {
+    let scanner = Scanner::new(FileMapSource::new(source));
+    let lexer = Lexer::new(scanner);
+    parse_program_tokens(diagnostics, codemap, lexer, Some(cache))
+}
+
+fn parse_program_tokens<S: IntoIterator<Item = Lexed>>(
+    diagnostics: &DiagnosticsHandler,
+    codemap: Arc<CodeMap>,
+    tokens: S,
+    cache: Option<&ast::ModuleCache>,
+) -> Result<ast::Program, ParseError> {
+    let mut next_var = 0;
+    let result = grammar::ProgramParser::new().parse(
+        diagnostics,
+        &codemap,
+        &mut next_var,
+        cache,
+        &[],
+        tokens,
+    );
+    match result {
+        Ok(ast) => {
+            if diagnostics.has_errors() {
+                return Err(ParseError::Failed);
+            }
+            Ok(ast)
+        }
+        Err(lalrpop_util::ParseError::User { error }) => Err(error),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Parses the statements of the file named by an `include` statement at `span`.
+///
+/// The path is resolved relative to the file containing the include statement, or to the
+/// current working directory if that file was not read from disk. `includes` holds the files
+/// which are in the process of being included, excluding the including file itself, and is
+/// used to detect circular includes.
+pub(crate) fn parse_include(
+    diagnostics: &DiagnosticsHandler,
+    codemap: &Arc<CodeMap>,
+    next_var: &mut usize,
+    includes: &[PathBuf],
+    span: SourceSpan,
+    path: crate::Symbol,
+) -> Result<Vec<ast::Statement>, ParseError> {
+    let including = match codemap.name(span.source_id()) {
+        Ok(FileName::Real(path)) => Some(path),
+        _ => None,
+    };
+    let source_dir = match including.as_ref().and_then(|path| path.parent()) {
+        Some(dir) => dir.to_path_buf(),
+        None => std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
+    };
+    let filename = normalize_path(&source_dir.join(path.as_str()));
+
+    if including.as_ref() == Some(&filename) || includes.contains(&filename) {
+        diagnostics
+            .diagnostic(Severity::Error)
+            .with_message("circular include")
+            .with_primary_label(span, "this file is already being included")
+            .with_note(format!(
+                "{} includes itself, directly or through other included files",
+                filename.display()
+            ))
+            .emit();
+        return Err(ParseError::Failed);
+    }
+
+    let file = match crate::load_file(codemap, &filename) {
+        Ok(file) => file,
+        Err(err) => {
+            diagnostics
+                .diagnostic(Severity::Error)
+                .with_message("unable to read included file")
+                .with_primary_label(span, format!("{}: {}", filename.display(), err))
+                .emit();
+            return Err(ParseError::Failed);
+        }
+    };
+
+    let mut includes = includes.to_vec();
+    includes.extend(including);
+    let scanner = Scanner::new(FileMapSource::new(file));
+    let lexer = Lexer::new(scanner);
+    grammar::IncludedStatementsParser::new()
+        .parse(diagnostics, codemap, next_var, None, &includes, lexer)
+        .map_err(ParseError::from)
+}
+
+/// Removes `.` components from `path`, and resolves `..` components lexically, so that the same
+/// file is always referred to by the same path when detecting circular includes.
+fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => (),
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    normalized.push(component);
                 }
-                Ok(ast)
             }
-            Err(lalrpop_util::ParseError::User { error }) => Err(error),
-            Err(err) => Err(err.into()),
+            component => normalized.push(component),
         }
     }
+    normalized
 }
 
 impl miden_parsing::Parse for ast::Module {
@@ -290,7 +399,8 @@ impl miden_parsing::Parse for ast::Module {
         tokens: S,
     ) -> Result<Self, Self::Error> {
         let mut next_var = 0;
-        let result = Self::Parser::new().parse(diagnostics, &codemap, &mut next_var, tokens);
+        let result =
+            Self::Parser::new().parse(diagnostics, &codemap, &mut next_var, None, &[], tokens);
         match result {
             Ok(ast) => {
                 if diagnostics.has_errors() {
@@ -242,6 +242,61 @@ fn non_const_exponentiation() {
     ParseTest::new().expect_module_ast(source, expected);
 }
 
+#[test]
+fn negation() {
+    // the operation must be put into a source section, or parsing will fail
+    let source = "
+    mod test
+
+    ev test([clk]):
+        enf -(clk' + clk) = -1";
+
+    // negation is represented as subtraction from zero
+    let mut expected = Module::new(ModuleType::Library, SourceSpan::UNKNOWN, ident!(test));
+    expected.evaluators.insert(
+        ident!(test),
+        EvaluatorFunction::new(
+            SourceSpan::UNKNOWN,
+            ident!(test),
+            vec![trace_segment!(0, "%0", [(clk, 1)])],
+            vec![enforce!(eq!(
+                sub!(int!(0), add!(access!(clk, 1), access!(clk))),
+                sub!(int!(0), int!(1))
+            ))],
+        ),
+    );
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
+#[test]
+fn negation_precedence() {
+    // the operation must be put into a source section, or parsing will fail
+    let source = "
+    mod test
+
+    ev test([clk]):
+        enf -clk^2 * clk - -clk = 0";
+
+    // negation binds less tightly than exponentiation, but more tightly than multiplication
+    let mut expected = Module::new(ModuleType::Library, SourceSpan::UNKNOWN, ident!(test));
+    expected.evaluators.insert(
+        ident!(test),
+        EvaluatorFunction::new(
+            SourceSpan::UNKNOWN,
+            ident!(test),
+            vec![trace_segment!(0, "%0", [(clk, 1)])],
+            vec![enforce!(eq!(
+                sub!(
+                    mul!(sub!(int!(0), exp!(access!(clk), int!(2))), access!(clk)),
+                    sub!(int!(0), access!(clk))
+                ),
+                int!(0)
+            ))],
+        ),
+    );
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
 #[test]
 fn err_ops_without_matching_closing_parens() {
     // the operation must be put into a source section, or parsing will fail
@@ -95,6 +95,33 @@ fn boundary_constraint_at_last() {
     ParseTest::new().expect_module_ast(&source, expected);
 }
 
+#[test]
+fn boundary_constraint_before_last() {
+    let source = format!(
+        "
+    {BASE_MODULE}
+
+    boundary_constraints:
+        enf clk.last(1) = 14
+        enf clk.last(0) = 15
+        enf clk.last - 1 = 16"
+    );
+
+    let mut expected = test_module();
+    expected.boundary_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![
+            enforce!(eq!(bounded_access!(clk, Boundary::BeforeLast(1)), int!(14))),
+            enforce!(eq!(bounded_access!(clk, Boundary::Last), int!(15))),
+            enforce!(eq!(
+                sub!(bounded_access!(clk, Boundary::Last), int!(1)),
+                int!(16)
+            )),
+        ],
+    ));
+    ParseTest::new().expect_module_ast(&source, expected);
+}
+
 #[test]
 fn error_invalid_boundary() {
     let source = format!(
@@ -366,7 +393,10 @@ fn err_missing_boundary_constraint() {
         let b = [a, 2 * a]
         let c = [[a - 1, a^2], [b[0], b[1]]]"
     );
-    ParseTest::new().expect_module_diagnostic(&source, "expected one of: '\"enf\"', '\"let\"'");
+    ParseTest::new().expect_module_diagnostic(
+        &source,
+        "boundary_constraints section must contain at least one constraint",
+    );
 }
 
 #[test]
@@ -378,5 +408,8 @@ fn err_empty_boundary_constraints() {
     boundary_constraints:
     "
     );
-    assert_module_error!(&source, crate::parser::ParseError::UnexpectedEof { .. });
+    ParseTest::new().expect_module_diagnostic(
+        &source,
+        "boundary_constraints section must contain at least one constraint",
+    );
 }
@@ -0,0 +1,86 @@
+use miden_diagnostics::SourceSpan;
+
+use crate::ast::*;
+
+use super::ParseTest;
+
+// AIR BUILDER
+// ================================================================================================
+
+#[test]
+fn build_minimal_program() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [clk]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf clk.first = 0
+
+    integrity_constraints:
+        enf clk' = clk + 1";
+
+    let program = AirBuilder::new(ident!(test))
+        .with_trace_segment(trace_segment!(0, "$main", [(clk, 1)]))
+        .with_public_input(PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 2))
+        .with_boundary_constraint(enforce!(eq!(
+            bounded_access!(clk, Boundary::First, Type::Felt),
+            int!(0)
+        )))
+        .with_integrity_constraint(enforce!(eq!(
+            access!(clk, 1, Type::Felt),
+            add!(access!(clk, Type::Felt), int!(1))
+        )))
+        .build()
+        .expect("expected the program to be valid");
+
+    ParseTest::new().expect_program_ast(source, program);
+}
+
+#[test]
+fn err_build_program_without_integrity_constraints() {
+    let result = AirBuilder::new(ident!(test))
+        .with_trace_segment(trace_segment!(0, "$main", [(clk, 1)]))
+        .with_public_input(PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 2))
+        .with_boundary_constraint(enforce!(eq!(
+            bounded_access!(clk, Boundary::First, Type::Felt),
+            int!(0)
+        )))
+        .build();
+
+    assert_eq!(
+        result.unwrap_err(),
+        InvalidProgramError::EmptyIntegrityConstraints
+    );
+}
+
+#[test]
+fn err_build_program_without_main_trace() {
+    let result = AirBuilder::new(ident!(test))
+        .with_public_input(PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 2))
+        .with_boundary_constraint(enforce!(eq!(int!(0), int!(0))))
+        .with_integrity_constraint(enforce!(eq!(int!(0), int!(0))))
+        .build();
+
+    assert_eq!(result.unwrap_err(), InvalidProgramError::MissingMainTrace);
+}
+
+#[test]
+fn err_build_program_with_random_values_without_aux_trace() {
+    let result = AirBuilder::new(ident!(test))
+        .with_trace_segment(trace_segment!(0, "$main", [(clk, 1)]))
+        .with_public_input(PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 2))
+        .with_random_values(random_values!("$rand", 2))
+        .with_boundary_constraint(enforce!(eq!(
+            bounded_access!(clk, Boundary::First, Type::Felt),
+            int!(0)
+        )))
+        .with_integrity_constraint(enforce!(eq!(access!(clk, Type::Felt), int!(0))))
+        .build();
+
+    assert_matches!(result, Err(InvalidProgramError::MissingAuxTrace(_)));
+}
@@ -88,3 +88,73 @@ fn call_fold_list_comprehension() {
 
     ParseTest::new().expect_module_ast(source, expected);
 }
+
+#[test]
+fn call_is_binary() {
+    let source = "
+    mod test
+
+    ev test([a, c[2]]):
+        enf is_binary(a)
+        enf is_binary(c) for c in c";
+
+    let mut expected = Module::new(ModuleType::Library, SourceSpan::UNKNOWN, ident!(test));
+    let body = vec![
+        enforce!(call!(is_binary(expr!(access!(a))))),
+        enforce_all!(lc!(((c, expr!(access!(c)))) => call!(is_binary(expr!(access!(c)))))),
+    ];
+    expected.evaluators.insert(
+        ident!(test),
+        EvaluatorFunction::new(
+            SourceSpan::UNKNOWN,
+            ident!(test),
+            vec![trace_segment!(0, "%0", [(a, 1), (c, 2)])],
+            body,
+        ),
+    );
+
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
+#[test]
+fn err_call_is_binary_with_vector() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [a, c[2]]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf a.first = 0
+
+    integrity_constraints:
+        enf is_binary(c)";
+
+    ParseTest::new().expect_program_diagnostic(source, "but this argument is of aggregate type");
+}
+
+#[test]
+fn err_call_is_binary_as_value() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [a, c[2]]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf a.first = 0
+
+    integrity_constraints:
+        enf a = is_binary(a)";
+
+    ParseTest::new().expect_program_diagnostic(
+        source,
+        "evaluators produce no value, and may only be called as constraints",
+    );
+}
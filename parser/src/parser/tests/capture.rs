@@ -0,0 +1,41 @@
+use miden_diagnostics::Severity;
+
+use crate::{parse_with_capture, ParseError};
+
+#[test]
+fn syntax_error_is_captured() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk]
+    integrity_constraints:
+        enf clk' = clk + = 1";
+
+    let (result, diagnostics) = parse_with_capture(source);
+    assert_eq!(result.unwrap_err(), ParseError::Failed);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].severity, Severity::Error);
+    assert_eq!(diagnostics[0].message, "unexpected token");
+    assert!(diagnostics[0].rendered.contains("expected one of"));
+}
+
+#[test]
+fn warnings_are_captured_on_success() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        let x = 1
+        enf clk' = clk + 1";
+
+    let (result, diagnostics) = parse_with_capture(source);
+    assert!(result.is_ok());
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].severity, Severity::Warning);
+    assert_eq!(diagnostics[0].message, "unused variable");
+}
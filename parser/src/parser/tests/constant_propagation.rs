@@ -116,3 +116,550 @@ fn test_constant_propagation() {
 
     assert_eq!(program, expected);
 }
+
+/// A constant imported by name is referenced without qualification, just like a constant
+/// declared in the importing module, and is folded into the expressions which use it.
+#[test]
+fn test_constant_propagation_of_imported_constant() {
+    let root = r#"
+    def root
+
+    use lib::EXP
+    use lib::V
+
+    trace_columns:
+        main: [a, b]
+
+    public_inputs:
+        inputs: [0]
+
+    integrity_constraints:
+        enf a' = a^EXP + V[1]
+        enf b' = b * EXP
+
+    boundary_constraints:
+        enf a.first = EXP
+    "#;
+    let lib = r#"
+    mod lib
+
+    const EXP = 2
+    const V = [1, 3]
+    "#;
+
+    let test = ParseTest::new();
+    let path = std::env::current_dir().unwrap().join("lib.air");
+    test.add_virtual_file(path, lib.to_string());
+
+    let program = match test.parse_program(root) {
+        Err(err) => {
+            test.diagnostics.emit(err);
+            panic!("expected parsing to succeed, see diagnostics for details");
+        }
+        Ok(ast) => ast,
+    };
+
+    let mut pass = ConstantPropagation::new(&test.diagnostics);
+    let program = pass.run(program).unwrap();
+
+    let mut expected = Program::new(ident!(root));
+    expected
+        .trace_columns
+        .push(trace_segment!(0, "$main", [(a, 1), (b, 1)]));
+    expected.public_inputs.insert(
+        ident!(inputs),
+        PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 0),
+    );
+    expected
+        .constants
+        .insert(ident!(lib, EXP), constant!(EXP = 2));
+    expected
+        .constants
+        .insert(ident!(lib, V), constant!(V = [1, 3]));
+    // When constant propagation is done, the boundary constraints should look like:
+    //     enf a.first = 2
+    expected.boundary_constraints.push(enforce!(eq!(
+        bounded_access!(a, Boundary::First, Type::Felt),
+        int!(2)
+    )));
+    // When constant propagation is done, the integrity constraints should look like:
+    //     enf a' = a^2 + 3
+    //     enf b' = b * 2
+    expected.integrity_constraints.push(enforce!(eq!(
+        access!(a, 1, Type::Felt),
+        add!(exp!(access!(a, Type::Felt), int!(2)), int!(3))
+    )));
+    expected.integrity_constraints.push(enforce!(eq!(
+        access!(b, 1, Type::Felt),
+        mul!(access!(b, Type::Felt), int!(2))
+    )));
+
+    assert_eq!(program, expected);
+}
+
+#[test]
+fn test_constant_propagation_of_folds_over_ranges() {
+    let root = r#"
+    def root
+
+    trace_columns:
+        main: [a, b]
+
+    public_inputs:
+        inputs: [0]
+
+    integrity_constraints:
+        let x = sum(0..4)
+        let y = prod([i + 1 for i in 0..8..2])
+        enf a' = a + x
+        enf b' = b * y
+
+    boundary_constraints:
+        enf a.first = 0
+    "#;
+
+    let test = ParseTest::new();
+    let program = match test.parse_program(root) {
+        Err(err) => {
+            test.diagnostics.emit(err);
+            panic!("expected parsing to succeed, see diagnostics for details");
+        }
+        Ok(ast) => ast,
+    };
+
+    let mut pass = ConstantPropagation::new(&test.diagnostics);
+    let program = pass.run(program).unwrap();
+
+    let mut expected = Program::new(ident!(root));
+    expected
+        .trace_columns
+        .push(trace_segment!(0, "$main", [(a, 1), (b, 1)]));
+    expected.public_inputs.insert(
+        ident!(inputs),
+        PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 0),
+    );
+    expected.boundary_constraints.push(enforce!(eq!(
+        bounded_access!(a, Boundary::First, Type::Felt),
+        int!(0)
+    )));
+    // When constant propagation is done, the integrity constraints should look like:
+    //     enf a' = a + 6
+    //     enf b' = b * 105
+    expected.integrity_constraints.push(enforce!(eq!(
+        access!(a, 1, Type::Felt),
+        add!(access!(a, Type::Felt), int!(6))
+    )));
+    expected.integrity_constraints.push(enforce!(eq!(
+        access!(b, 1, Type::Felt),
+        mul!(access!(b, Type::Felt), int!(105))
+    )));
+
+    assert_eq!(program, expected);
+}
+
+/// Constant expressions are folded using field arithmetic, so results which would overflow or
+/// underflow a `u64` are reduced modulo the field prime, rather than wrapping or being left
+/// unfolded.
+#[test]
+fn test_constant_propagation_reduces_modulo_field_prime() {
+    let root = r#"
+    def root
+
+    trace_columns:
+        main: [a, b, c, d]
+
+    public_inputs:
+        inputs: [0]
+
+    const MAX = 18446744069414584320
+
+    integrity_constraints:
+        enf a = MAX + 2
+        enf b = 0 - 1
+        enf c = 2^64
+        enf d = 4294967296 * 4294967296 + MAX * MAX
+
+    boundary_constraints:
+        enf a.first = 0
+    "#;
+
+    let test = ParseTest::new();
+    let program = match test.parse_program(root) {
+        Err(err) => {
+            test.diagnostics.emit(err);
+            panic!("expected parsing to succeed, see diagnostics for details");
+        }
+        Ok(ast) => ast,
+    };
+
+    let mut pass = ConstantPropagation::new(&test.diagnostics);
+    let program = pass.run(program).unwrap();
+
+    let mut expected = Program::new(ident!(root));
+    expected
+        .trace_columns
+        .push(trace_segment!(0, "$main", [(a, 1), (b, 1), (c, 1), (d, 1)]));
+    expected.public_inputs.insert(
+        ident!(inputs),
+        PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 0),
+    );
+    expected
+        .constants
+        .insert(ident!(root, MAX), constant!(MAX = 18446744069414584320));
+    expected.boundary_constraints.push(enforce!(eq!(
+        bounded_access!(a, Boundary::First, Type::Felt),
+        int!(0)
+    )));
+    // When constant propagation is done, the integrity constraints should look like:
+    //     enf a = 1
+    //     enf b = 18446744069414584320
+    //     enf c = 4294967295
+    //     enf d = 4294967296
+    expected
+        .integrity_constraints
+        .push(enforce!(eq!(access!(a, Type::Felt), int!(1))));
+    expected.integrity_constraints.push(enforce!(eq!(
+        access!(b, Type::Felt),
+        int!(18446744069414584320)
+    )));
+    expected
+        .integrity_constraints
+        .push(enforce!(eq!(access!(c, Type::Felt), int!(4294967295))));
+    expected
+        .integrity_constraints
+        .push(enforce!(eq!(access!(d, Type::Felt), int!(4294967296))));
+
+    assert_eq!(program, expected);
+}
+
+/// Constant expressions are folded in the field given to the pass
+#[test]
+fn test_constant_propagation_with_custom_field() {
+    let root = r#"
+    def root
+
+    trace_columns:
+        main: [a, b]
+
+    public_inputs:
+        inputs: [0]
+
+    integrity_constraints:
+        enf a = 16 + 5
+        enf b = 3 - 5
+
+    boundary_constraints:
+        enf a.first = 0
+    "#;
+
+    let test = ParseTest::new();
+    let program = match test.parse_program(root) {
+        Err(err) => {
+            test.diagnostics.emit(err);
+            panic!("expected parsing to succeed, see diagnostics for details");
+        }
+        Ok(ast) => ast,
+    };
+
+    let mut pass =
+        ConstantPropagation::new(&test.diagnostics).with_field(crate::PrimeField::new(17));
+    let program = pass.run(program).unwrap();
+
+    let mut expected = Program::new(ident!(root));
+    expected
+        .trace_columns
+        .push(trace_segment!(0, "$main", [(a, 1), (b, 1)]));
+    expected.public_inputs.insert(
+        ident!(inputs),
+        PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 0),
+    );
+    expected.boundary_constraints.push(enforce!(eq!(
+        bounded_access!(a, Boundary::First, Type::Felt),
+        int!(0)
+    )));
+    expected
+        .integrity_constraints
+        .push(enforce!(eq!(access!(a, Type::Felt), int!(4))));
+    expected
+        .integrity_constraints
+        .push(enforce!(eq!(access!(b, Type::Felt), int!(15))));
+
+    assert_eq!(program, expected);
+}
+
+/// Negated constants are folded to their additive inverse in the field given to the pass
+#[test]
+fn test_constant_propagation_negation() {
+    let root = r#"
+    def root
+
+    trace_columns:
+        main: [a, b]
+
+    public_inputs:
+        inputs: [0]
+
+    integrity_constraints:
+        enf a = -1
+        enf b = -3 * 2
+
+    boundary_constraints:
+        enf a.first = 0
+    "#;
+
+    let test = ParseTest::new();
+    let program = match test.parse_program(root) {
+        Err(err) => {
+            test.diagnostics.emit(err);
+            panic!("expected parsing to succeed, see diagnostics for details");
+        }
+        Ok(ast) => ast,
+    };
+
+    let mut pass =
+        ConstantPropagation::new(&test.diagnostics).with_field(crate::PrimeField::new(17));
+    let program = pass.run(program).unwrap();
+
+    let mut expected = Program::new(ident!(root));
+    expected
+        .trace_columns
+        .push(trace_segment!(0, "$main", [(a, 1), (b, 1)]));
+    expected.public_inputs.insert(
+        ident!(inputs),
+        PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 0),
+    );
+    expected.boundary_constraints.push(enforce!(eq!(
+        bounded_access!(a, Boundary::First, Type::Felt),
+        int!(0)
+    )));
+    expected
+        .integrity_constraints
+        .push(enforce!(eq!(access!(a, Type::Felt), int!(16))));
+    expected
+        .integrity_constraints
+        .push(enforce!(eq!(access!(b, Type::Felt), int!(11))));
+
+    assert_eq!(program, expected);
+}
+
+/// Constants which are not canonical elements of the field given to the pass are rejected
+#[test]
+fn test_constant_propagation_rejects_constants_outside_field() {
+    let root = r#"
+    def root
+
+    trace_columns:
+        main: [a]
+
+    public_inputs:
+        inputs: [0]
+
+    integrity_constraints:
+        enf a = 16 + 17
+
+    boundary_constraints:
+        enf a.first = 0
+    "#;
+
+    let test = ParseTest::new();
+    let program = match test.parse_program(root) {
+        Err(err) => {
+            test.diagnostics.emit(err);
+            panic!("expected parsing to succeed, see diagnostics for details");
+        }
+        Ok(ast) => ast,
+    };
+
+    let mut pass =
+        ConstantPropagation::new(&test.diagnostics).with_field(crate::PrimeField::new(17));
+    assert_eq!(
+        pass.run(program),
+        Err(crate::SemanticAnalysisError::InvalidExpr(
+            InvalidExprError::InvalidFieldElement(SourceSpan::UNKNOWN, 17)
+        ))
+    );
+}
+
+/// `min` and `max` over constant vectors and ranges are folded to the minimum/maximum element
+#[test]
+fn test_constant_propagation_min_max() {
+    let root = r#"
+    def root
+
+    trace_columns:
+        main: [a, b]
+
+    public_inputs:
+        inputs: [0]
+
+    integrity_constraints:
+        enf a = max([1, 5, 3])
+        enf b = min(2..5)
+
+    boundary_constraints:
+        enf a.first = 0
+    "#;
+
+    let test = ParseTest::new();
+    let program = match test.parse_program(root) {
+        Err(err) => {
+            test.diagnostics.emit(err);
+            panic!("expected parsing to succeed, see diagnostics for details");
+        }
+        Ok(ast) => ast,
+    };
+
+    let mut pass = ConstantPropagation::new(&test.diagnostics);
+    let program = pass.run(program).unwrap();
+
+    let mut expected = Program::new(ident!(root));
+    expected
+        .trace_columns
+        .push(trace_segment!(0, "$main", [(a, 1), (b, 1)]));
+    expected.public_inputs.insert(
+        ident!(inputs),
+        PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 0),
+    );
+    expected.boundary_constraints.push(enforce!(eq!(
+        bounded_access!(a, Boundary::First, Type::Felt),
+        int!(0)
+    )));
+    expected
+        .integrity_constraints
+        .push(enforce!(eq!(access!(a, Type::Felt), int!(5))));
+    expected
+        .integrity_constraints
+        .push(enforce!(eq!(access!(b, Type::Felt), int!(2))));
+
+    assert_eq!(program, expected);
+}
+
+/// `min` and `max` cannot be applied to values which are only known at runtime
+#[test]
+fn test_constant_propagation_rejects_non_constant_min_max() {
+    let root = r#"
+    def root
+
+    trace_columns:
+        main: [a, c[3]]
+
+    public_inputs:
+        inputs: [0]
+
+    integrity_constraints:
+        enf a = max(c)
+
+    boundary_constraints:
+        enf a.first = 0
+    "#;
+
+    let test = ParseTest::new();
+    let program = match test.parse_program(root) {
+        Err(err) => {
+            test.diagnostics.emit(err);
+            panic!("expected parsing to succeed, see diagnostics for details");
+        }
+        Ok(ast) => ast,
+    };
+
+    let mut pass = ConstantPropagation::new(&test.diagnostics);
+    assert_eq!(
+        pass.run(program),
+        Err(crate::SemanticAnalysisError::InvalidExpr(
+            InvalidExprError::NonConstantFoldIterable(SourceSpan::UNKNOWN)
+        ))
+    );
+}
+
+/// The values of generated periodic columns are computed in the field, and may refer to constants
+#[test]
+fn test_constant_propagation_of_generated_periodic_columns() {
+    let root = r#"
+    def root
+
+    trace_columns:
+        main: [a]
+
+    public_inputs:
+        inputs: [0]
+
+    const C = 3
+
+    periodic_columns:
+        k0: [C * i for i in 0..8]
+        k1: [2 - i for i in 0..4]
+        k2: [i^40 for i in 3..5]
+
+    integrity_constraints:
+        enf a' = a * k0 + k1 + k2
+
+    boundary_constraints:
+        enf a.first = 0
+    "#;
+
+    let test = ParseTest::new();
+    let program = match test.parse_program(root) {
+        Err(err) => {
+            test.diagnostics.emit(err);
+            panic!("expected parsing to succeed, see diagnostics for details");
+        }
+        Ok(ast) => ast,
+    };
+
+    let mut pass = ConstantPropagation::new(&test.diagnostics);
+    let program = pass.run(program).unwrap();
+
+    let values = |name| {
+        let column = &program.periodic_columns[&name];
+        assert_eq!(column.generator, None);
+        column.values.clone()
+    };
+    assert_eq!(values(ident!(root, k0)), vec![0, 3, 6, 9, 12, 15, 18, 21]);
+    assert_eq!(
+        values(ident!(root, k1)),
+        vec![2, 1, 0, 18446744069414584320]
+    );
+    assert_eq!(
+        values(ident!(root, k2)),
+        vec![12157665459056928801, 281474976645120]
+    );
+}
+
+#[test]
+fn test_constant_propagation_rejects_non_constant_periodic_values() {
+    let root = r#"
+    def root
+
+    trace_columns:
+        main: [a]
+
+    public_inputs:
+        inputs: [0]
+
+    periodic_columns:
+        k0: [i * a for i in 0..4]
+
+    integrity_constraints:
+        enf a' = a * k0
+
+    boundary_constraints:
+        enf a.first = 0
+    "#;
+
+    let test = ParseTest::new();
+    let program = match test.parse_program(root) {
+        Err(err) => {
+            test.diagnostics.emit(err);
+            panic!("expected parsing to succeed, see diagnostics for details");
+        }
+        Ok(ast) => ast,
+    };
+
+    let mut pass = ConstantPropagation::new(&test.diagnostics);
+    assert_eq!(
+        pass.run(program),
+        Err(crate::SemanticAnalysisError::InvalidExpr(
+            InvalidExprError::NonConstantPeriodicValue(SourceSpan::UNKNOWN)
+        ))
+    );
+}
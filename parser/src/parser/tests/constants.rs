@@ -146,6 +146,15 @@ fn err_const_vectors_with_non_int_values() {
     ParseTest::new().expect_unrecognized_token(source);
 }
 
+#[test]
+fn err_const_vectors_with_negative_values() {
+    let source = "
+    mod test
+
+    const A = [1, -1]";
+    ParseTest::new().expect_module_diagnostic(source, "constants must not be negative");
+}
+
 #[test]
 fn err_vector_with_trailing_comma() {
     let source = "
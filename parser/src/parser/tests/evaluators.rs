@@ -28,6 +28,27 @@ fn ev_fn_main_cols() {
     ParseTest::new().expect_module_ast(source, expected);
 }
 
+#[test]
+fn ev_fn_uppercase_name() {
+    let source = "
+    mod test
+
+    ev AdvanceClock([clk]):
+        enf clk' = clk + 1";
+
+    let mut expected = Module::new(ModuleType::Library, SourceSpan::UNKNOWN, ident!(test));
+    expected.evaluators.insert(
+        ident!(AdvanceClock),
+        EvaluatorFunction::new(
+            SourceSpan::UNKNOWN,
+            ident!(AdvanceClock),
+            vec![trace_segment!(0, "%0", [(clk, 1)])],
+            vec![enforce!(eq!(access!(clk, 1), add!(access!(clk), int!(1))))],
+        ),
+    );
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
 #[test]
 fn ev_fn_aux_cols() {
     let source = "
@@ -117,6 +138,49 @@ fn ev_fn_call_simple() {
     ParseTest::new().expect_module_ast(source, expected);
 }
 
+#[test]
+fn ev_fn_call_at_boundary() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [clk]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf reset_clock([clk]).first
+
+    integrity_constraints:
+        enf advance_clock([clk])";
+
+    let mut expected = Module::new(ModuleType::Root, SourceSpan::UNKNOWN, ident!(test));
+    expected
+        .trace_columns
+        .push(trace_segment!(0, "$main", [(clk, 1)]));
+    expected.public_inputs.insert(
+        ident!(inputs),
+        PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 2),
+    );
+    let reset_clock = Call::new(
+        SourceSpan::UNKNOWN,
+        ident!(reset_clock),
+        vec![vector!(access!(clk))],
+    )
+    .with_boundary(SourceSpan::UNKNOWN, Boundary::First);
+    expected.boundary_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(ScalarExpr::Call(reset_clock))],
+    ));
+    expected.integrity_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(call!(advance_clock(vector!(access!(clk)))))],
+    ));
+
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
 #[test]
 fn ev_fn_call() {
     let source = "
@@ -0,0 +1,184 @@
+use miden_diagnostics::{SourceSpan, Span};
+
+use crate::ast::*;
+
+use super::ParseTest;
+
+// INCLUDES
+// ================================================================================================
+
+#[test]
+fn integrity_constraints_with_include() {
+    let source = r#"
+    def test
+
+    trace_columns:
+        main: [clk, a]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf clk.first = 0
+
+    integrity_constraints:
+        enf clk' = clk + 1
+        include "steps.air"
+        enf a = a * a"#;
+    let steps = "
+        let b = clk * 2
+        enf a' = b";
+
+    let test = ParseTest::new();
+    let path = std::env::current_dir().unwrap().join("steps.air");
+    test.add_virtual_file(path, steps.to_string());
+
+    let mut expected = Module::new(ModuleType::Root, SourceSpan::UNKNOWN, ident!(test));
+    expected
+        .trace_columns
+        .push(trace_segment!(0, "$main", [(clk, 1), (a, 1)]));
+    expected.public_inputs.insert(
+        ident!(inputs),
+        PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 2),
+    );
+    expected.boundary_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(
+            bounded_access!(clk, Boundary::First),
+            int!(0)
+        ))],
+    ));
+    expected.integrity_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![
+            enforce!(eq!(access!(clk, 1), add!(access!(clk), int!(1)))),
+            // The statements following the include are nested in the body of its trailing `let`
+            let_!(b = expr!(mul!(access!(clk), int!(2)))
+                => enforce!(eq!(access!(a, 1), access!(b))),
+                   enforce!(eq!(access!(a), mul!(access!(a), access!(a))))),
+        ],
+    ));
+    test.expect_module_ast(source, expected);
+}
+
+#[test]
+fn include_ending_with_let() {
+    let source = r#"
+    def test
+
+    trace_columns:
+        main: [clk, a]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf clk.first = 0
+
+    integrity_constraints:
+        include "bindings.air"
+        enf a' = b"#;
+    let bindings = "
+        let b = clk * 2";
+
+    let test = ParseTest::new();
+    let path = std::env::current_dir().unwrap().join("bindings.air");
+    test.add_virtual_file(path, bindings.to_string());
+
+    let mut expected = Module::new(ModuleType::Root, SourceSpan::UNKNOWN, ident!(test));
+    expected
+        .trace_columns
+        .push(trace_segment!(0, "$main", [(clk, 1), (a, 1)]));
+    expected.public_inputs.insert(
+        ident!(inputs),
+        PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 2),
+    );
+    expected.boundary_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(
+            bounded_access!(clk, Boundary::First),
+            int!(0)
+        ))],
+    ));
+    expected.integrity_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![let_!(b = expr!(mul!(access!(clk), int!(2)))
+            => enforce!(eq!(access!(a, 1), access!(b))))],
+    ));
+    test.expect_module_ast(source, expected);
+}
+
+#[test]
+fn err_include_ending_with_let_at_end_of_block() {
+    let source = r#"
+    def test
+
+    trace_columns:
+        main: [clk, a]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf clk.first = 0
+
+    integrity_constraints:
+        enf a' = clk
+        include "bindings.air""#;
+    let bindings = "
+        let b = clk * 2";
+
+    let test = ParseTest::new();
+    let path = std::env::current_dir().unwrap().join("bindings.air");
+    test.add_virtual_file(path, bindings.to_string());
+    test.expect_program_diagnostic(source, "missing statements after `let`");
+}
+
+#[test]
+fn err_circular_include() {
+    let source = r#"
+    def test
+
+    trace_columns:
+        main: [clk]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf clk.first = 0
+
+    integrity_constraints:
+        include "first.air""#;
+    let first = r#"
+        enf clk' = clk + 1
+        include "second.air""#;
+    let second = r#"
+        include "./first.air""#;
+
+    let test = ParseTest::new();
+    let cwd = std::env::current_dir().unwrap();
+    test.add_virtual_file(cwd.join("first.air"), first.to_string());
+    test.add_virtual_file(cwd.join("second.air"), second.to_string());
+    test.expect_module_diagnostic(source, "circular include");
+}
+
+#[test]
+fn err_missing_include() {
+    let source = r#"
+    def test
+
+    trace_columns:
+        main: [clk]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf clk.first = 0
+
+    integrity_constraints:
+        include "missing.air""#;
+
+    ParseTest::new().expect_module_diagnostic(source, "unable to read included file");
+}
@@ -1079,22 +1079,24 @@ fn test_inlining_constraints_with_folded_comprehensions_in_evaluator() {
         int!(0)
     )));
     // When constant propagation and inlining is done, integrity_constraints should look like:
-    //     let lc%0 = b[2]^7
-    //     let lc%1 = b[3]^7
-    //     let y = lc%0 + lc%1
     //     let lc%2 = b[2]^7
     //     let lc%3 = b[3]^7
-    //     let z = lc%2 + lc%3
-    //     enf b[1] = y + z
+    //     let %fn0 = lc%2 + lc%3
+    //     let lc%4 = b[2]^7
+    //     let lc%5 = b[3]^7
+    //     let %fn1 = lc%4 + lc%5
+    //     enf b[1] = %fn0 + %fn1
+    //
+    // The locals `y` and `z` of the evaluator are renamed when it is inlined
     expected
         .integrity_constraints
-        .push(let_!("%lc0" = expr!(exp!(access!(b[2], Type::Felt), int!(7)))
-            => let_!("%lc1" = expr!(exp!(access!(b[3], Type::Felt), int!(7)))
-            => let_!(y = expr!(add!(access!("%lc0", Type::Felt), access!("%lc1", Type::Felt)))
-            => let_!("%lc2" = expr!(exp!(access!(b[2], Type::Felt), int!(7)))
+        .push(let_!("%lc2" = expr!(exp!(access!(b[2], Type::Felt), int!(7)))
             => let_!("%lc3" = expr!(exp!(access!(b[3], Type::Felt), int!(7)))
-            => let_!(z = expr!(mul!(access!("%lc2", Type::Felt), access!("%lc3", Type::Felt)))
-            => enforce!(eq!(access!(b[1], Type::Felt), add!(access!(y, Type::Felt), access!(z, Type::Felt)))))))))));
+            => let_!("%fn0" = expr!(add!(access!("%lc2", Type::Felt), access!("%lc3", Type::Felt)))
+            => let_!("%lc4" = expr!(exp!(access!(b[2], Type::Felt), int!(7)))
+            => let_!("%lc5" = expr!(exp!(access!(b[3], Type::Felt), int!(7)))
+            => let_!("%fn1" = expr!(mul!(access!("%lc4", Type::Felt), access!("%lc5", Type::Felt)))
+            => enforce!(eq!(access!(b[1], Type::Felt), add!(access!("%fn0", Type::Felt), access!("%fn1", Type::Felt)))))))))));
     // The evaluator definition is never modified by constant propagation or inlining
     let body = vec![
         let_!(y = expr!(call!(sum(expr!(lc!(((col, expr!(access!(ys, Type::Vector(2))))) => exp!(access!(col, Type::Felt), int!(7)))))))
@@ -1113,3 +1115,207 @@ fn test_inlining_constraints_with_folded_comprehensions_in_evaluator() {
 
     assert_eq!(program, expected);
 }
+
+/// This test verifies that calls to the `is_binary` builtin are lowered to
+/// the constraint `x^2 = x`, including in comprehensions and with selectors
+#[test]
+fn test_inlining_is_binary() {
+    let root = r#"
+    def root
+
+    trace_columns:
+        main: [s, a, b[2]]
+
+    public_inputs:
+        inputs: [0]
+
+    integrity_constraints:
+        enf is_binary(a)
+        enf is_binary(b) for b in b
+        enf is_binary(a') when s
+
+    boundary_constraints:
+        enf a.first = 0
+    "#;
+
+    let test = ParseTest::new();
+    let program = match test.parse_program(root) {
+        Err(err) => {
+            test.diagnostics.emit(err);
+            panic!("expected parsing to succeed, see diagnostics for details");
+        }
+        Ok(ast) => ast,
+    };
+
+    let mut pipeline =
+        ConstantPropagation::new(&test.diagnostics).chain(Inlining::new(&test.diagnostics));
+    let program = pipeline.run(program).unwrap();
+
+    let mut expected = Program::new(ident!(root));
+    expected
+        .trace_columns
+        .push(trace_segment!(0, "$main", [(s, 1), (a, 1), (b, 2)]));
+    expected.public_inputs.insert(
+        ident!(inputs),
+        PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 0),
+    );
+    expected.boundary_constraints.push(enforce!(eq!(
+        bounded_access!(a, Boundary::First, Type::Felt),
+        int!(0)
+    )));
+    // When constant propagation and inlining is done, integrity_constraints should look like:
+    //     enf a^2 = a
+    //     enf b[0]^2 = b[0]
+    //     enf b[1]^2 = b[1]
+    //     enf (a'^2 = a') when s
+    expected.integrity_constraints.push(enforce!(eq!(
+        exp!(access!(a, Type::Felt), int!(2)),
+        access!(a, Type::Felt)
+    )));
+    expected.integrity_constraints.push(enforce!(eq!(
+        exp!(access!(b[0], Type::Felt), int!(2)),
+        access!(b[0], Type::Felt)
+    )));
+    expected.integrity_constraints.push(enforce!(eq!(
+        exp!(access!(b[1], Type::Felt), int!(2)),
+        access!(b[1], Type::Felt)
+    )));
+    //
+    // NOTE: `a` is known to be binary by the time `a'` is referenced
+    expected.integrity_constraints.push(enforce!(
+        eq!(
+            exp!(access!(a, 1, Type::Binary), int!(2)),
+            access!(a, 1, Type::Binary)
+        ),
+        when access!(s, Type::Felt)
+    ));
+
+    assert_eq!(program, expected);
+}
+
+/// This test inlines a pure function called from an integrity constraint.
+///
+/// The call is first bound to a local, and then the function body is expanded as a let tree
+/// in which the parameters and locals of the function have been renamed. Arguments which are
+/// plain trace column accesses are aliased, but accesses with a row offset are kept as locals.
+#[test]
+fn test_inlining_function_call() {
+    let root = r#"
+    def root
+
+    fn madd(x: felt, y: felt, z: felt) -> felt:
+        let p = x * y
+        return p + z
+
+    trace_columns:
+        main: [a, b]
+
+    public_inputs:
+        inputs: [0]
+
+    integrity_constraints:
+        enf a' = madd(a', b, 1)
+
+    boundary_constraints:
+        enf a.first = 0
+    "#;
+
+    let test = ParseTest::new();
+    let program = match test.parse_program(root) {
+        Err(err) => {
+            test.diagnostics.emit(err);
+            panic!("expected parsing to succeed, see diagnostics for details");
+        }
+        Ok(ast) => ast,
+    };
+
+    let mut pipeline =
+        ConstantPropagation::new(&test.diagnostics).chain(Inlining::new(&test.diagnostics));
+    let program = pipeline.run(program).unwrap();
+
+    let mut expected = Program::new(ident!(root));
+    expected
+        .trace_columns
+        .push(trace_segment!(0, "$main", [(a, 1), (b, 1)]));
+    expected.public_inputs.insert(
+        ident!(inputs),
+        PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 0),
+    );
+    expected.boundary_constraints.push(enforce!(eq!(
+        bounded_access!(a, Boundary::First, Type::Felt),
+        int!(0)
+    )));
+    // When constant propagation and inlining is done, integrity_constraints should look like:
+    //     let %fn1 = a'
+    //     let %fn3 = 1
+    //     let %fn4 = %fn1 * b
+    //     let %lc0 = %fn4 + %fn3
+    //     enf a' = %lc0
+    expected
+        .integrity_constraints
+        .push(let_!("%fn1" = expr!(access!(a, 1, Type::Felt))
+            => let_!("%fn3" = expr!(int!(1))
+            => let_!("%fn4" = expr!(mul!(access!("%fn1", Type::Felt), access!(b, Type::Felt)))
+            => let_!("%lc0" = expr!(add!(access!("%fn4", Type::Felt), access!("%fn3", Type::Felt)))
+            => enforce!(eq!(access!(a, 1, Type::Felt), access!("%lc0", Type::Felt))))))));
+    // The function definition is never modified by inlining
+    let body = vec![
+        let_!(p = expr!(mul!(access!(x, Type::Felt), access!(y, Type::Felt)))
+        => Statement::Expr(expr!(add!(access!(p, Type::Felt), access!(z, Type::Felt))))),
+    ];
+    expected.functions.insert(
+        function_ident!(root, madd),
+        Function::new(
+            SourceSpan::UNKNOWN,
+            ident!(madd),
+            vec![
+                (ident!(x), Type::Felt),
+                (ident!(y), Type::Felt),
+                (ident!(z), Type::Felt),
+            ],
+            Type::Felt,
+            body,
+        ),
+    );
+
+    assert_eq!(program, expected);
+}
+
+/// This test checks that unrolling a comprehension with more iterations than the configured
+/// threshold raises a warning, but that comprehensions within the threshold do not.
+#[test]
+fn test_inlining_large_comprehension_warning() {
+    let root = r#"
+    def root
+
+    trace_columns:
+        main: [a, b[4]]
+
+    public_inputs:
+        inputs: [0]
+
+    integrity_constraints:
+        enf a' = sum([a * i for i in 0..8])
+        enf x' = x for x in b
+
+    boundary_constraints:
+        enf a.first = 0
+    "#;
+
+    let test = ParseTest::new();
+    let program = match test.parse_program(root) {
+        Err(err) => {
+            test.diagnostics.emit(err);
+            panic!("expected parsing to succeed, see diagnostics for details");
+        }
+        Ok(ast) => ast,
+    };
+
+    let mut pipeline = ConstantPropagation::new(&test.diagnostics)
+        .chain(Inlining::new(&test.diagnostics).with_unroll_warning_threshold(4));
+    pipeline.run(program).unwrap();
+
+    let captured = test.captured();
+    assert!(captured.contains("this comprehension unrolls to 8 iterations"));
+    assert!(!captured.contains("this comprehension unrolls to 4 iterations"));
+}
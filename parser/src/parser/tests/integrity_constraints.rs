@@ -1,6 +1,6 @@
 use miden_diagnostics::{SourceSpan, Span};
 
-use crate::ast::*;
+use crate::{ast::*, Symbol};
 
 use super::ParseTest;
 
@@ -46,6 +46,49 @@ fn integrity_constraints() {
     ParseTest::new().expect_module_ast(source, expected);
 }
 
+#[test]
+fn integrity_constraint_with_trace_len() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [clk]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf clk.first = 0
+
+    integrity_constraints:
+        enf clk' = clk + $trace_len";
+
+    let trace_len = ScalarExpr::TraceLen(Span::new(SourceSpan::UNKNOWN, ()));
+    let mut expected = Module::new(ModuleType::Root, SourceSpan::UNKNOWN, ident!(test));
+    expected
+        .trace_columns
+        .push(trace_segment!(0, "$main", [(clk, 1)]));
+    expected.public_inputs.insert(
+        ident!(inputs),
+        PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 2),
+    );
+    expected.boundary_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(
+            bounded_access!(clk, Boundary::First),
+            int!(0)
+        ))],
+    ));
+    expected.integrity_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(
+            access!(clk, 1),
+            add!(access!(clk), trace_len)
+        ))],
+    ));
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
 #[test]
 fn err_integrity_constraints_invalid() {
     let source = "
@@ -61,11 +104,93 @@ fn err_integrity_constraints_invalid() {
         enf clk.first = 0
 
     integrity_constraints:
-        enf clk' = clk = 1";
+        enf clk' = clk + = 1";
 
     ParseTest::new().expect_unrecognized_token(source);
 }
 
+#[test]
+fn integrity_constraint_equality_chain() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [a, b, c]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf a.first = 0
+
+    integrity_constraints:
+        enf a' = b = c + 1";
+
+    let mut expected = Module::new(ModuleType::Root, SourceSpan::UNKNOWN, ident!(test));
+    expected
+        .trace_columns
+        .push(trace_segment!(0, "$main", [(a, 1), (b, 1), (c, 1)]));
+    expected.public_inputs.insert(
+        ident!(inputs),
+        PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 2),
+    );
+    expected.boundary_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(bounded_access!(a, Boundary::First), int!(0)))],
+    ));
+    expected.integrity_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![
+            enforce!(eq!(access!(a, 1), access!(b))),
+            enforce!(eq!(access!(b), add!(access!(c), int!(1)))),
+        ],
+    ));
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
+#[test]
+fn integrity_constraint_equality_chain_with_selector() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [s, a, b, c]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf a.first = 0
+
+    integrity_constraints:
+        enf a' = b = c when s";
+
+    let mut expected = Module::new(ModuleType::Root, SourceSpan::UNKNOWN, ident!(test));
+    expected
+        .trace_columns
+        .push(trace_segment!(0, "$main", [(s, 1), (a, 1), (b, 1), (c, 1)]));
+    expected.public_inputs.insert(
+        ident!(inputs),
+        PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 2),
+    );
+    expected.boundary_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(bounded_access!(a, Boundary::First), int!(0)))],
+    ));
+    expected.integrity_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![
+            enforce_all!(
+                lc!((("%0", range!(0..1))) => eq!(access!(a, 1), access!(b)), when access!(s))
+            ),
+            enforce_all!(
+                lc!((("%1", range!(0..1))) => eq!(access!(b), access!(c)), when access!(s))
+            ),
+        ],
+    ));
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
 #[test]
 fn multiple_integrity_constraints() {
     let source = "
@@ -109,6 +234,140 @@ fn multiple_integrity_constraints() {
     ParseTest::new().expect_module_ast(source, expected);
 }
 
+#[test]
+fn integrity_constraints_with_optional_semicolons() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [clk]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf clk.first = 0;
+
+    integrity_constraints:
+        let x = clk + 1; enf clk' = x;
+        enf clk' - clk = 1
+        enf clk' * (clk +
+            1) = (x
+            * x);";
+
+    let mut expected = Module::new(ModuleType::Root, SourceSpan::UNKNOWN, ident!(test));
+    expected
+        .trace_columns
+        .push(trace_segment!(0, "$main", [(clk, 1)]));
+    expected.public_inputs.insert(
+        ident!(inputs),
+        PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 2),
+    );
+    expected.boundary_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(
+            bounded_access!(clk, Boundary::First),
+            int!(0)
+        ))],
+    ));
+    expected.integrity_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![let_!(x = expr!(add!(access!(clk), int!(1))) =>
+            enforce!(eq!(access!(clk, 1), access!(x))),
+            enforce!(eq!(sub!(access!(clk, 1), access!(clk)), int!(1))),
+            enforce!(eq!(mul!(access!(clk, 1), add!(access!(clk), int!(1))), mul!(access!(x), access!(x)))))],
+    ));
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
+#[test]
+fn err_integrity_constraints_with_empty_statement() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [clk]
+
+    integrity_constraints:
+        enf clk' = clk + 1;;";
+
+    ParseTest::new().expect_module_diagnostic(source, "unexpected token");
+}
+
+#[test]
+fn err_integrity_constraint_with_double_equals() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [a, b]
+
+    integrity_constraints:
+        enf a == b";
+
+    ParseTest::new().expect_module_diagnostic(source, "use `=` for constraint equality, not `==`");
+}
+
+#[test]
+fn integrity_constraints_with_row_offsets() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [a, b]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf a.first = 0
+
+    integrity_constraints:
+        enf a'' = a' + a
+        enf b'3 = b'1";
+
+    let mut expected = Module::new(ModuleType::Root, SourceSpan::UNKNOWN, ident!(test));
+    expected
+        .trace_columns
+        .push(trace_segment!(0, "$main", [(a, 1), (b, 1)]));
+    expected.public_inputs.insert(
+        ident!(inputs),
+        PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 2),
+    );
+    expected.boundary_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(bounded_access!(a, Boundary::First), int!(0)))],
+    ));
+    expected.integrity_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![
+            enforce!(eq!(access!(a, 2), add!(access!(a, 1), access!(a)))),
+            enforce!(eq!(access!(b, 3), access!(b, 1))),
+        ],
+    ));
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
+#[test]
+fn err_integrity_constraint_with_zero_row_offset() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [a]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf a.first = 0
+
+    integrity_constraints:
+        enf a'0 = a";
+
+    ParseTest::new().expect_module_diagnostic(source, "row offsets must be greater than zero");
+}
+
 #[test]
 fn integrity_constraint_with_periodic_col() {
     let source = "
@@ -313,6 +572,9 @@ fn integrity_constraint_with_indexed_trace_access() {
     public_inputs:
         inputs: [2]
 
+    random_values:
+        rand: [1]
+
     boundary_constraints:
         enf clk.first = 0
 
@@ -331,6 +593,7 @@ fn integrity_constraint_with_indexed_trace_access() {
         ident!(inputs),
         PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 2),
     );
+    expected.random_values = Some(random_values!("$rand", 1));
     expected.boundary_constraints = Some(Span::new(
         SourceSpan::UNKNOWN,
         vec![enforce!(eq!(
@@ -354,6 +617,112 @@ fn integrity_constraint_with_indexed_trace_access() {
     ParseTest::new().expect_module_ast(source, expected);
 }
 
+// CONSTRAINT ATTRIBUTES
+// ================================================================================================
+
+#[test]
+fn integrity_constraint_with_attributes() {
+    let source = r#"
+    def test
+
+    trace_columns:
+        main: [clk]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf clk.first = 0
+
+    integrity_constraints:
+        @[name="clk_step"] @[x_custom="anything goes"]
+        enf clk' = clk + 1"#;
+
+    let mut expected = Module::new(ModuleType::Root, SourceSpan::UNKNOWN, ident!(test));
+    expected
+        .trace_columns
+        .push(trace_segment!(0, "$main", [(clk, 1)]));
+    expected.public_inputs.insert(
+        ident!(inputs),
+        PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 2),
+    );
+    expected.boundary_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(
+            bounded_access!(clk, Boundary::First),
+            int!(0)
+        ))],
+    ));
+    let attrs = Attributes::new(
+        SourceSpan::UNKNOWN,
+        [
+            (
+                ident!(name),
+                Span::new(SourceSpan::UNKNOWN, Symbol::intern("clk_step")),
+            ),
+            (
+                ident!(x_custom),
+                Span::new(SourceSpan::UNKNOWN, Symbol::intern("anything goes")),
+            ),
+        ]
+        .into(),
+    );
+    expected.integrity_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![Statement::WithAttributes(
+            attrs,
+            Box::new(enforce!(eq!(access!(clk, 1), add!(access!(clk), int!(1))))),
+        )],
+    ));
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
+#[test]
+fn err_attribute_without_value() {
+    let source = r#"
+    def test
+
+    trace_columns:
+        main: [clk]
+
+    integrity_constraints:
+        @[name]
+        enf clk' = clk + 1"#;
+
+    ParseTest::new().expect_unrecognized_token(source);
+}
+
+#[test]
+fn err_attribute_with_non_string_value() {
+    let source = r#"
+    def test
+
+    trace_columns:
+        main: [clk]
+
+    integrity_constraints:
+        @[name=1]
+        enf clk' = clk + 1"#;
+
+    ParseTest::new().expect_unrecognized_token(source);
+}
+
+#[test]
+fn err_attribute_on_let() {
+    let source = r#"
+    def test
+
+    trace_columns:
+        main: [clk]
+
+    integrity_constraints:
+        @[name="x"]
+        let x = 1
+        enf clk' = clk + x"#;
+
+    ParseTest::new().expect_unrecognized_token(source);
+}
+
 // CONSTRAINT COMPREHENSION
 // ================================================================================================
 
@@ -638,14 +1007,10 @@ fn ic_match_constraint() {
     ));
     expected.integrity_constraints = Some(Span::new(
         SourceSpan::UNKNOWN,
-        vec![
-            enforce_all!(
-                lc!((("%1", range!(0..1))) => call!(is_binary(vector!(access!(c[0])))), when and!(access!(s[0]), access!(s[1])))
-            ),
-            enforce_all!(
-                lc!((("%2", range!(0..1))) => eq!(access!(c[1]), access!(c[2])), when access!(s[0]))
-            ),
-        ],
+        vec![enforce_match!(
+            lc!((("%1", range!(0..1))) => call!(is_binary(vector!(access!(c[0])))), when and!(access!(s[0]), access!(s[1]))),
+            lc!((("%2", range!(0..1))) => eq!(access!(c[1]), access!(c[2])), when access!(s[0]))
+        )],
     ));
     ParseTest::new().expect_module_ast(source, expected);
 }
@@ -653,6 +1018,62 @@ fn ic_match_constraint() {
 // INVALID INTEGRITY CONSTRAINT COMPREHENSION
 // ================================================================================================
 
+#[test]
+fn ic_match_constraint_with_attributes() {
+    let source = r#"
+    def test
+
+    trace_columns:
+        main: [s, clk]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf clk.first = 0
+
+    integrity_constraints:
+        @[group="clk"]
+        enf match:
+            case s: clk' = clk + 1
+            case !s: clk' = clk"#;
+
+    let mut expected = Module::new(ModuleType::Root, SourceSpan::UNKNOWN, ident!(test));
+    expected
+        .trace_columns
+        .push(trace_segment!(0, "$main", [(s, 1), (clk, 1)]));
+    expected.public_inputs.insert(
+        ident!(inputs),
+        PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 2),
+    );
+    expected.boundary_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(
+            bounded_access!(clk, Boundary::First),
+            int!(0)
+        ))],
+    ));
+    let attrs = Attributes::new(
+        SourceSpan::UNKNOWN,
+        [(
+            ident!(group),
+            Span::new(SourceSpan::UNKNOWN, Symbol::intern("clk")),
+        )]
+        .into(),
+    );
+    expected.integrity_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![Statement::WithAttributes(
+            attrs,
+            Box::new(enforce_match!(
+                lc!((("%0", range!(0..1))) => eq!(access!(clk, 1), add!(access!(clk), int!(1))), when access!(s)),
+                lc!((("%1", range!(0..1))) => eq!(access!(clk, 1), access!(clk)), when not!(access!(s)))
+            )),
+        )],
+    ));
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
 #[test]
 fn err_ic_comprehension_one_member_two_iterables() {
     let source = "
@@ -698,7 +1119,10 @@ fn err_missing_integrity_constraint() {
         let a = 2^2
         let b = [a, 2 * a]
         let c = [[a - 1, a^2], [b[0], b[1]]]";
-    ParseTest::new().expect_module_diagnostic(source, "expected one of: '\"enf\"', '\"let\"'");
+    ParseTest::new().expect_module_diagnostic(
+        source,
+        "integrity_constraints section must contain at least one constraint",
+    );
 }
 
 #[test]
@@ -710,7 +1134,7 @@ fn ic_invalid() {
         main: [clk]
 
     integrity_constraints:
-        enf clk' = clk = 1";
+        enf clk' = = clk";
     ParseTest::new().expect_unrecognized_token(source);
 }
 
@@ -723,7 +1147,7 @@ fn error_invalid_next_usage() {
         main: [clk]
 
     integrity_constraints:
-        enf clk'' = clk + 1";
+        enf clk'a = clk + 1";
     ParseTest::new().expect_unrecognized_token(source);
 }
 
@@ -739,5 +1163,8 @@ fn err_empty_integrity_constraints() {
         
     boundary_constraints:
         enf clk.first = 1";
-    ParseTest::new().expect_unrecognized_token(source);
+    ParseTest::new().expect_module_diagnostic(
+        source,
+        "integrity_constraints section must contain at least one constraint",
+    );
 }
@@ -91,6 +91,104 @@ fn bc_identifier_and_range_lc() {
     ParseTest::new().expect_module_ast(source, expected);
 }
 
+#[test]
+fn bc_identifier_and_range_lc_with_felt_cast() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [a, b, c[4]]
+
+    public_inputs:
+        inputs: [2]
+
+    integrity_constraints:
+        enf a = 0
+
+    boundary_constraints:
+        let x = [felt(i) * c for (i, c) in (0..3, c)]
+        enf a.first = x[0] + x[1] + x[2] + x[3]";
+
+    let mut expected = Module::new(ModuleType::Root, SourceSpan::UNKNOWN, ident!(test));
+    expected
+        .trace_columns
+        .push(trace_segment!(0, "$main", [(a, 1), (b, 1), (c, 4)]));
+    expected.public_inputs.insert(
+        ident!(inputs),
+        PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 2),
+    );
+    expected.integrity_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(access!(a), int!(0)))],
+    ));
+    let cast = ScalarExpr::Call(Call::felt(SourceSpan::UNKNOWN, expr!(access!(i))));
+    expected.boundary_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![
+            let_!(x = lc!(((i, range!(0..3)), (c, expr!(access!(c)))) => mul!(cast, access!(c))).into() =>
+                  enforce!(eq!(bounded_access!(a, Boundary::First), add!(add!(add!(access!(x[0]), access!(x[1])), access!(x[2])), access!(x[3]))))),
+        ],
+    ));
+
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
+#[test]
+fn bc_identifier_and_stepped_range_lc() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [a, b, c[4]]
+
+    public_inputs:
+        inputs: [2]
+
+    integrity_constraints:
+        enf a = 0
+
+    boundary_constraints:
+        let x = [2^i * c for (i, c) in (0..8..2, c)]
+        enf a.first = x[0] + x[1] + x[2] + x[3]";
+
+    let mut expected = Module::new(ModuleType::Root, SourceSpan::UNKNOWN, ident!(test));
+    expected
+        .trace_columns
+        .push(trace_segment!(0, "$main", [(a, 1), (b, 1), (c, 4)]));
+    expected.public_inputs.insert(
+        ident!(inputs),
+        PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 2),
+    );
+    expected.integrity_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(access!(a), int!(0)))],
+    ));
+    expected.boundary_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![
+            let_!(x = lc!(((i, range!(0..8, 2)), (c, expr!(access!(c)))) => mul!(exp!(int!(2), access!(i)), access!(c))).into() =>
+                  enforce!(eq!(bounded_access!(a, Boundary::First), add!(add!(add!(access!(x[0]), access!(x[1])), access!(x[2])), access!(x[3]))))),
+        ],
+    ));
+
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
+#[test]
+fn err_lc_range_with_zero_step() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [a, b, c[4]]
+
+    integrity_constraints:
+        let x = [2^i * c for (i, c) in (0..8..0, c)]
+        enf a = x[0]";
+
+    ParseTest::new().expect_module_diagnostic(source, "the step of a range must be non-zero");
+}
+
 #[test]
 fn bc_iterable_slice_lc() {
     let source = "
@@ -492,3 +590,58 @@ fn err_ic_lc_two_members_one_iterable() {
     ParseTest::new()
         .expect_module_diagnostic(source, "bindings and iterables lengths are mismatched");
 }
+
+#[test]
+fn ic_filtered_lc() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [a, c[4]]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf a.first = 0
+
+    integrity_constraints:
+        enf x' = x for (x, i) in (c, 0..4) where i - 2 when a";
+
+    let mut expected = Module::new(ModuleType::Root, SourceSpan::UNKNOWN, ident!(test));
+    expected
+        .trace_columns
+        .push(trace_segment!(0, "$main", [(a, 1), (c, 4)]));
+    expected.public_inputs.insert(
+        ident!(inputs),
+        PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 2),
+    );
+    expected.boundary_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(bounded_access!(a, Boundary::First), int!(0)))],
+    ));
+    expected.integrity_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce_all!(
+            lc!(((x, expr!(access!(c))), (i, range!(0..4))) => eq!(access!(x, 1), access!(x)), when access!(a))
+                .with_filter(sub!(access!(i), int!(2)))
+        )],
+    ));
+
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
+#[test]
+fn err_filter_in_list_comprehension() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [a, c[4]]
+
+    integrity_constraints:
+        let x = [x for x in c where 1]
+        enf a = x[0]";
+
+    ParseTest::new().expect_module_diagnostic(source, "unexpected token");
+}
@@ -372,6 +372,7 @@ macro_rules! call {
             span: miden_diagnostics::SourceSpan::UNKNOWN,
             callee: ResolvableIdentifier::Resolved(function_ident!($module, $callee)),
             args: vec![$($param),+],
+            boundary: None,
             ty: None,
         })
     }
@@ -439,6 +440,13 @@ macro_rules! let_ {
     ($name:literal = $value:expr => $($body:expr),+) => {
         Statement::Let(Let::new(miden_diagnostics::SourceSpan::UNKNOWN, ident!($name), $value, vec![$($body),+]))
     };
+
+    ($name:ident : $ty:ident = $value:expr => $($body:expr),+) => {
+        Statement::Let(
+            Let::new(miden_diagnostics::SourceSpan::UNKNOWN, ident!($name), $value, vec![$($body),+])
+                .with_annotation(Some(Span::new(miden_diagnostics::SourceSpan::UNKNOWN, Type::$ty)))
+        )
+    };
 }
 
 macro_rules! enforce {
@@ -457,6 +465,12 @@ macro_rules! enforce_all {
     };
 }
 
+macro_rules! enforce_match {
+    ($($arm:expr),+) => {
+        Statement::EnforceMatch(Match::new(miden_diagnostics::SourceSpan::UNKNOWN, vec![$($arm),+]))
+    };
+}
+
 macro_rules! lc {
     (($(($binding:ident, $iterable:expr)),+) => $body:expr) => {{
         let context = vec![
@@ -497,25 +511,45 @@ macro_rules! lc {
 
 macro_rules! range {
     ($range:expr) => {
-        Expr::Range(Span::new(SourceSpan::UNKNOWN, $range))
+        Expr::Range(Span::new(SourceSpan::UNKNOWN, RangeExpr::from($range)))
+    };
+
+    ($range:expr, $step:expr) => {
+        Expr::Range(Span::new(
+            SourceSpan::UNKNOWN,
+            RangeExpr::from($range).with_step($step),
+        ))
     };
 }
 
 macro_rules! and {
     ($lhs:expr, $rhs:expr) => {
-        mul!($lhs, $rhs)
+        ScalarExpr::Binary(BinaryExpr::new(
+            miden_diagnostics::SourceSpan::UNKNOWN,
+            BinaryOp::And,
+            $lhs,
+            $rhs,
+        ))
     };
 }
 
 macro_rules! or {
-    ($lhs:expr, $rhs:expr) => {{
-        sub!(add!($lhs, $rhs), mul!($lhs, $rhs))
-    }};
+    ($lhs:expr, $rhs:expr) => {
+        ScalarExpr::Binary(BinaryExpr::new(
+            miden_diagnostics::SourceSpan::UNKNOWN,
+            BinaryOp::Or,
+            $lhs,
+            $rhs,
+        ))
+    };
 }
 
 macro_rules! not {
     ($rhs:expr) => {
-        sub!(int!(1), $rhs)
+        ScalarExpr::Binary(BinaryExpr::not(
+            miden_diagnostics::SourceSpan::UNKNOWN,
+            $rhs,
+        ))
     };
 }
 
@@ -584,8 +618,19 @@ macro_rules! import_all {
 
 macro_rules! import {
     ($module:ident, $item:ident) => {{
-        let mut items: std::collections::HashSet<Identifier> = std::collections::HashSet::default();
-        items.insert(ident!($item));
+        let mut items: std::collections::HashMap<Identifier, Identifier> =
+            std::collections::HashMap::default();
+        items.insert(ident!($item), ident!($item));
+        Import::Partial {
+            module: ident!($module),
+            items,
+        }
+    }};
+
+    ($module:ident, $item:ident as $alias:ident) => {{
+        let mut items: std::collections::HashMap<Identifier, Identifier> =
+            std::collections::HashMap::default();
+        items.insert(ident!($alias), ident!($item));
         Import::Partial {
             module: ident!($module),
             items,
@@ -595,11 +640,14 @@ macro_rules! import {
 
 mod arithmetic_ops;
 mod boundary_constraints;
+mod builder;
 mod calls;
+mod capture;
 mod constant_propagation;
 mod constants;
 mod evaluators;
 mod identifiers;
+mod includes;
 mod inlining;
 mod integrity_constraints;
 mod list_comprehension;
@@ -609,8 +657,11 @@ mod pub_inputs;
 mod random_values;
 mod sections;
 mod selectors;
+#[cfg(feature = "serde")]
+mod serialize;
 mod trace_columns;
 mod variables;
+mod visit;
 
 // FULL AIR FILE
 // ================================================================================================
@@ -28,6 +28,128 @@ fn import_declaration() {
     ParseTest::new().expect_module_ast(source, expected);
 }
 
+#[test]
+fn import_declaration_with_alias() {
+    let source = "
+    mod test
+
+    use foo::bar as baz
+    use foo::QUX as QUUX
+    ";
+    let mut expected = Module::new(ModuleType::Library, SourceSpan::UNKNOWN, ident!(test));
+    let mut import = import!(foo, bar as baz);
+    if let Import::Partial { ref mut items, .. } = import {
+        items.insert(ident!(QUUX), ident!(QUX));
+    }
+    expected.imports.insert(ident!(foo), import);
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
+#[test]
+fn import_with_alias_resolves_to_imported_item() {
+    let root = r#"
+    def root
+
+    use lib::constrain as constrain_next
+    use lib::EXP as POWER
+
+    trace_columns:
+        main: [a]
+
+    public_inputs:
+        inputs: [0]
+
+    integrity_constraints:
+        enf constrain_next([a])
+        enf a' = a^POWER
+
+    boundary_constraints:
+        enf a.first = 0
+    "#;
+    let lib = r#"
+    mod lib
+
+    const EXP = 2
+
+    ev constrain([x]):
+        enf x' = x^EXP
+    "#;
+
+    let test = ParseTest::new();
+    let path = std::env::current_dir().unwrap().join("lib.air");
+    test.add_virtual_file(path, lib.to_string());
+
+    let program = match test.parse_program(root) {
+        Err(err) => {
+            test.diagnostics.emit(err);
+            panic!("expected parsing to succeed, see diagnostics for details");
+        }
+        Ok(ast) => ast,
+    };
+
+    assert!(program
+        .evaluators
+        .contains_key(&function_ident!(lib, constrain)));
+    assert!(program.constants.contains_key(&ident!(lib, EXP)));
+    assert_eq!(
+        program.integrity_constraints[0],
+        enforce!(call!(lib::constrain(vector!(access!(a, Type::Felt)))))
+    );
+    assert_eq!(
+        program.integrity_constraints[1],
+        enforce!(eq!(
+            access!(a, 1, Type::Felt),
+            exp!(access!(a, Type::Felt), access!(lib, EXP, Type::Felt))
+        ))
+    );
+}
+
+#[test]
+fn err_import_alias_conflicts_with_declaration() {
+    let root = r#"
+    def root
+
+    use lib::constrain as check
+
+    trace_columns:
+        main: [a]
+
+    public_inputs:
+        inputs: [0]
+
+    integrity_constraints:
+        enf check([a])
+
+    boundary_constraints:
+        enf a.first = 0
+
+    ev check([x]):
+        enf x' = x
+    "#;
+    let lib = r#"
+    mod lib
+
+    ev constrain([x]):
+        enf x' = x + 1
+    "#;
+
+    let test = ParseTest::new();
+    let path = std::env::current_dir().unwrap().join("lib.air");
+    test.add_virtual_file(path, lib.to_string());
+
+    test.expect_program_diagnostic(root, "this conflicts with a previous declaration");
+}
+
+#[test]
+fn err_import_alias_of_constant_not_uppercase() {
+    let source = "
+    mod test
+
+    use foo::BAR as bar
+    ";
+    ParseTest::new().expect_module_diagnostic(source, "aliases of constants must be uppercase");
+}
+
 // This test performs a realistic test involving compilation of a program consisting of
 // items in 3 different modules, which tests the following:
 //
@@ -107,3 +229,142 @@ fn modules_integration_test() {
     ParseTest::new()
         .expect_program_ast_from_file("src/parser/tests/input/import_example.air", expected);
 }
+
+#[test]
+fn module_cache_parses_shared_import_once() {
+    let root = |name: &str| {
+        format!(
+            "
+    def {name}
+
+    use shared::*
+
+    trace_columns:
+        main: [a]
+
+    public_inputs:
+        inputs: [1]
+
+    integrity_constraints:
+        enf step([a])
+
+    boundary_constraints:
+        enf a.first = 0
+    "
+        )
+    };
+    let lib = r#"
+    mod shared
+
+    ev step([x]):
+        enf x' = x + 1
+    "#;
+
+    let test = ParseTest::new();
+    let path = std::env::current_dir().unwrap().join("shared.air");
+    test.add_virtual_file(path, lib.to_string());
+
+    let cache = ModuleCache::new();
+    for name in ["alpha", "beta"] {
+        let program = match test.parse_program_with_cache(&root(name), &cache) {
+            Err(err) => {
+                test.diagnostics.emit(err);
+                panic!("expected parsing to succeed, see diagnostics for details");
+            }
+            Ok(ast) => ast,
+        };
+        assert!(program
+            .evaluators
+            .contains_key(&function_ident!(shared, step)));
+    }
+
+    assert_eq!(cache.misses(), 1);
+    assert_eq!(cache.hits(), 1);
+    assert_eq!(cache.len(), 1);
+}
+
+#[test]
+fn module_cache_is_not_shared_across_codemaps() {
+    let root = r#"
+    def root
+
+    use shared::*
+
+    trace_columns:
+        main: [a]
+
+    public_inputs:
+        inputs: [1]
+
+    integrity_constraints:
+        enf step([a])
+
+    boundary_constraints:
+        enf a.first = 0
+    "#;
+    let lib = r#"
+    mod shared
+
+    ev step([x]):
+        enf x' = x + 1
+    "#;
+
+    let cache = ModuleCache::new();
+    for _ in 0..2 {
+        let test = ParseTest::new();
+        let path = std::env::current_dir().unwrap().join("shared.air");
+        test.add_virtual_file(path, lib.to_string());
+        assert!(test.parse_program_with_cache(root, &cache).is_ok());
+    }
+
+    assert_eq!(cache.misses(), 2);
+    assert_eq!(cache.hits(), 0);
+}
+
+#[test]
+fn module_cache_reparses_edited_files() {
+    let root = r#"
+    def root
+
+    use shared::*
+
+    trace_columns:
+        main: [a]
+
+    public_inputs:
+        inputs: [1]
+
+    integrity_constraints:
+        enf a' = a + K
+
+    boundary_constraints:
+        enf a.first = 0
+    "#;
+
+    let dir = std::env::temp_dir().join(format!("air-parser-module-cache-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let root_path = dir.join("root.air");
+    let lib_path = dir.join("shared.air");
+    std::fs::write(&root_path, root).unwrap();
+
+    // The same codemap is used for both parses, so it still holds the original contents of the
+    // library module when it is parsed the second time
+    let test = ParseTest::new();
+    let cache = ModuleCache::new();
+    let key = QualifiedIdentifier::new(ident!(shared), NamespacedIdentifier::Binding(ident!(K)));
+    for value in [1, 2] {
+        std::fs::write(&lib_path, format!("mod shared\nconst K = {value}\n")).unwrap();
+        let program = match test.parse_program_from_file_with_cache(&root_path, &cache) {
+            Err(err) => {
+                test.diagnostics.emit(err);
+                panic!("expected parsing to succeed, see diagnostics for details");
+            }
+            Ok(ast) => ast,
+        };
+        assert_eq!(program.constants[&key].value, ConstantExpr::Scalar(value));
+    }
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(cache.misses(), 2);
+    assert_eq!(cache.hits(), 0);
+}
@@ -1,4 +1,4 @@
-use miden_diagnostics::SourceSpan;
+use miden_diagnostics::{SourceSpan, Span};
 
 use crate::ast::*;
 
@@ -53,3 +53,49 @@ fn err_periodic_columns_length() {
         "periodic columns must have a non-zero cycle length which is a power of two",
     );
 }
+
+#[test]
+fn generated_periodic_columns() {
+    let source = "
+    mod test
+
+    periodic_columns:
+        k0: [i^2 for i in 0..8]
+        k1: [i * j + 1 for (i, j) in (0..4, 4..8)]";
+
+    // The values of generated columns are only known after constant propagation
+    let mut expected = Module::new(ModuleType::Library, SourceSpan::UNKNOWN, ident!(test));
+    expected.periodic_columns.insert(
+        ident!(k0),
+        PeriodicColumn::generate(
+            SourceSpan::UNKNOWN,
+            ident!(k0),
+            lc!(((i, range!(0..8))) => exp!(access!(i), int!(2))),
+        )
+        .unwrap(),
+    );
+    expected.periodic_columns.insert(
+        ident!(k1),
+        PeriodicColumn::generate(
+            SourceSpan::UNKNOWN,
+            ident!(k1),
+            lc!(((i, range!(0..4)), (j, range!(4..8))) => add!(mul!(access!(i), access!(j)), int!(1))),
+        )
+        .unwrap(),
+    );
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
+#[test]
+fn err_generated_periodic_columns_length() {
+    let source = "
+    mod test
+
+    periodic_columns:
+        k0: [i for i in 0..6]";
+
+    ParseTest::new().expect_module_diagnostic(
+        source,
+        "periodic columns must have a non-zero cycle length which is a power of two",
+    );
+}
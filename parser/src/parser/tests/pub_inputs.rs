@@ -51,6 +51,229 @@ fn public_inputs() {
     ParseTest::new().expect_module_ast(source, expected);
 }
 
+#[test]
+fn public_inputs_matrix() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [clk]
+
+    public_inputs:
+        roots: [2][4]
+        stack_inputs: [16]
+
+    boundary_constraints:
+        enf clk.first = roots[1][3]
+
+    integrity_constraints:
+        enf clk = 0";
+
+    let mut expected = Module::new(ModuleType::Root, SourceSpan::UNKNOWN, ident!(test));
+    expected
+        .trace_columns
+        .push(trace_segment!(0, "$main", [(clk, 1)]));
+    expected.public_inputs.insert(
+        ident!(roots),
+        PublicInput::new_matrix(SourceSpan::UNKNOWN, ident!(roots), 2, 4),
+    );
+    expected.public_inputs.insert(
+        ident!(stack_inputs),
+        PublicInput::new(SourceSpan::UNKNOWN, ident!(stack_inputs), 16),
+    );
+    expected.boundary_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(
+            bounded_access!(clk, Boundary::First),
+            access!(roots[1][3])
+        ))],
+    ));
+    expected.integrity_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(access!(clk), int!(0)))],
+    ));
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
+#[test]
+fn public_inputs_with_bindings() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [clk]
+
+    public_inputs:
+        header: [root[4], nonce]
+
+    boundary_constraints:
+        enf clk.first = 0
+
+    integrity_constraints:
+        enf clk = 0";
+
+    let mut expected = Module::new(ModuleType::Root, SourceSpan::UNKNOWN, ident!(test));
+    expected
+        .trace_columns
+        .push(trace_segment!(0, "$main", [(clk, 1)]));
+    expected.public_inputs.insert(
+        ident!(header),
+        PublicInput::with_bindings(
+            SourceSpan::UNKNOWN,
+            ident!(header),
+            vec![
+                Span::new(SourceSpan::UNKNOWN, (ident!(root), 4)),
+                Span::new(SourceSpan::UNKNOWN, (ident!(nonce), 1)),
+            ],
+        ),
+    );
+    expected.boundary_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(
+            bounded_access!(clk, Boundary::First),
+            int!(0)
+        ))],
+    ));
+    expected.integrity_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(access!(clk), int!(0)))],
+    ));
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
+#[test]
+fn public_input_size_assertions() {
+    let source = "
+    def test
+
+    assert public_input(program_hash).size == 4
+
+    trace_columns:
+        main: [clk]
+
+    public_inputs:
+        program_hash: [4]
+        stack_inputs: [16]
+
+    assert public_input(stack_inputs).size == 16
+
+    boundary_constraints:
+        enf clk.first = 0
+
+    integrity_constraints:
+        enf clk = 0";
+
+    let mut expected = Module::new(ModuleType::Root, SourceSpan::UNKNOWN, ident!(test));
+    expected
+        .trace_columns
+        .push(trace_segment!(0, "$main", [(clk, 1)]));
+    expected.public_inputs.insert(
+        ident!(program_hash),
+        PublicInput::new(SourceSpan::UNKNOWN, ident!(program_hash), 4),
+    );
+    expected.public_inputs.insert(
+        ident!(stack_inputs),
+        PublicInput::new(SourceSpan::UNKNOWN, ident!(stack_inputs), 16),
+    );
+    expected.boundary_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(
+            bounded_access!(clk, Boundary::First),
+            int!(0)
+        ))],
+    ));
+    expected.integrity_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(access!(clk), int!(0)))],
+    ));
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
+#[test]
+fn err_public_input_size_mismatch() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [clk]
+
+    public_inputs:
+        stack_inputs: [12]
+
+    assert public_input(stack_inputs).size == 16
+
+    boundary_constraints:
+        enf clk.first = 0
+
+    integrity_constraints:
+        enf clk = 0";
+
+    ParseTest::new().expect_module_diagnostic(source, "public input size assertion failed");
+}
+
+#[test]
+fn err_public_input_size_undefined_input() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [clk]
+
+    public_inputs:
+        stack_inputs: [16]
+
+    assert public_input(program_hash).size == 4
+
+    boundary_constraints:
+        enf clk.first = 0
+
+    integrity_constraints:
+        enf clk = 0";
+
+    ParseTest::new().expect_module_diagnostic(source, "undefined public input");
+}
+
+#[test]
+fn err_public_input_binding_conflict() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [clk]
+
+    public_inputs:
+        header: [root[4], nonce]
+        root: [4]
+
+    boundary_constraints:
+        enf clk.first = 0
+
+    integrity_constraints:
+        enf clk = 0";
+
+    ParseTest::new().expect_module_diagnostic(source, "this conflicts with a previous declaration");
+}
+
+#[test]
+fn err_public_input_matrix_too_large() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [clk]
+
+    public_inputs:
+        inputs: [9223372036854775808][4]
+
+    boundary_constraints:
+        enf clk.first = 0
+
+    integrity_constraints:
+        enf clk = 0";
+
+    ParseTest::new().expect_module_diagnostic(source, "public input is too large");
+}
+
 #[test]
 fn error_no_public_input() {
     let source = "
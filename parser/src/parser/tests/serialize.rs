@@ -0,0 +1,54 @@
+use super::ParseTest;
+
+// AST SERIALIZATION
+// ================================================================================================
+
+#[test]
+fn program_serializes_to_json() {
+    let source = "
+    def test
+
+    const A = [1, 2]
+
+    trace_columns:
+        main: [clk, a[2]]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf clk.first = 0
+
+    integrity_constraints:
+        let x: felt = a[0] + A[1]
+        enf clk' = clk + x";
+
+    let test = ParseTest::new();
+    let program = match test.parse_program(source) {
+        Err(err) => {
+            test.diagnostics.emit(err);
+            panic!("expected parsing to succeed, see diagnostics for details");
+        }
+        Ok(ast) => ast,
+    };
+    let value = serde_json::to_value(&program).unwrap();
+
+    // Identifiers are serialized by name, and spans as byte offsets into the source
+    assert_eq!(value["name"], "test");
+    assert_eq!(value["constants"]["test::A"]["value"]["Vector"][0], 1);
+    let bindings = &value["trace_columns"][0]["bindings"];
+    assert_eq!(bindings[1]["name"], "a");
+    assert_eq!(bindings[1]["size"], 2);
+    let span = &value["public_inputs"]["inputs"]["span"];
+    let (start, end) = (
+        span["start"].as_u64().unwrap() as usize,
+        span["end"].as_u64().unwrap() as usize,
+    );
+    assert_eq!(&source[start..end], "inputs: [2]");
+
+    // The program is serialized before inlining, so let statements are preserved
+    let statement = &value["integrity_constraints"][0]["Let"];
+    assert_eq!(statement["name"], "x");
+    assert_eq!(statement["annotation"]["value"], "Felt");
+    assert_eq!(statement["body"][0]["Enforce"]["Binary"]["op"], "Eq");
+}
@@ -46,6 +46,53 @@ fn trace_columns() {
     ParseTest::new().expect_module_ast(source, expected);
 }
 
+#[test]
+fn trace_columns_gated_by_features() {
+    let source = r#"
+    def test
+
+    trace_columns:
+        main: [clk, @[feature="debug"] fmp, ctx]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf clk.first = 0
+
+    integrity_constraints:
+        enf clk = 0
+    "#;
+    let module = ParseTest::new()
+        .parse_module(source)
+        .expect("expected module to parse");
+    let segment = &module.trace_columns[0];
+    assert_eq!(
+        segment,
+        &trace_segment!(0, "$main", [(clk, 1), (fmp, 1), (ctx, 1)])
+    );
+    let features = segment
+        .features
+        .iter()
+        .map(|(name, feature)| (name.as_str(), feature.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(features, vec![("fmp", "debug")]);
+}
+
+#[test]
+fn err_trace_column_invalid_attribute() {
+    let source = r#"
+    def test
+
+    trace_columns:
+        main: [clk, @[feature="debug", name="fmp"] fmp]
+
+    integrity_constraints:
+        enf clk = 0
+    "#;
+    ParseTest::new().expect_module_diagnostic(source, "invalid trace column attribute");
+}
+
 #[test]
 fn trace_columns_main_and_aux() {
     let source = r#"
@@ -58,6 +105,9 @@ fn trace_columns_main_and_aux() {
     public_inputs:
         inputs: [2]
 
+    random_values:
+        rand: [1]
+
     boundary_constraints:
         enf clk.first = 0
 
@@ -75,6 +125,7 @@ fn trace_columns_main_and_aux() {
         ident!(inputs),
         PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 2),
     );
+    expected.random_values = Some(random_values!("$rand", 1));
     expected.boundary_constraints = Some(Span::new(
         SourceSpan::UNKNOWN,
         vec![enforce!(eq!(
@@ -89,6 +140,57 @@ fn trace_columns_main_and_aux() {
     ParseTest::new().expect_module_ast(source, expected);
 }
 
+#[test]
+fn trace_columns_multiple_aux_segments() {
+    let source = r#"
+    def test
+
+    trace_columns:
+        main: [clk, fmp, ctx]
+        aux: [rc_bus, ch_bus]
+        aux2: [p]
+
+    public_inputs:
+        inputs: [2]
+
+    random_values:
+        rand: [1]
+
+    boundary_constraints:
+        enf clk.first = 0
+
+    integrity_constraints:
+        enf p' = $aux2[0]
+    "#;
+    let mut expected = Module::new(ModuleType::Root, SourceSpan::UNKNOWN, ident!(test));
+    expected
+        .trace_columns
+        .push(trace_segment!(0, "$main", [(clk, 1), (fmp, 1), (ctx, 1)]));
+    expected
+        .trace_columns
+        .push(trace_segment!(1, "$aux", [(rc_bus, 1), (ch_bus, 1)]));
+    expected
+        .trace_columns
+        .push(trace_segment!(2, "$aux2", [(p, 1)]));
+    expected.public_inputs.insert(
+        ident!(inputs),
+        PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 2),
+    );
+    expected.random_values = Some(random_values!("$rand", 1));
+    expected.boundary_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(
+            bounded_access!(clk, Boundary::First),
+            int!(0)
+        ))],
+    ));
+    expected.integrity_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(access!(p, 1), access!("$aux2"[0])))],
+    ));
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
 #[test]
 fn trace_columns_groups() {
     let source = r#"
@@ -101,6 +203,9 @@ fn trace_columns_groups() {
     public_inputs:
         inputs: [2]
 
+    random_values:
+        rand: [1]
+
     boundary_constraints:
         enf clk.first = 0
 
@@ -123,6 +228,7 @@ fn trace_columns_groups() {
         ident!(inputs),
         PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 2),
     );
+    expected.random_values = Some(random_values!("$rand", 1));
     expected.boundary_constraints = Some(Span::new(
         SourceSpan::UNKNOWN,
         vec![enforce!(eq!(
@@ -140,6 +246,99 @@ fn trace_columns_groups() {
     ParseTest::new().expect_module_ast(source, expected);
 }
 
+#[test]
+fn trace_columns_explicit_indices() {
+    let source = r#"
+    def test
+
+    trace_columns:
+        main: [clk @ 0, fmp[2] @ 5, ctx]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf clk.first = 0
+
+    integrity_constraints:
+        enf clk = 0
+    "#;
+    let mut expected = Module::new(ModuleType::Root, SourceSpan::UNKNOWN, ident!(test));
+    let mut main = trace_segment!(0, "$main", [(clk, 1), (fmp, 2), (ctx, 1)]);
+    // `ctx` immediately follows `fmp`, leaving columns 1 through 4 unbound
+    main.bindings[1].offset = 5;
+    main.bindings[2].offset = 7;
+    main.size = 8;
+    expected.trace_columns.push(main);
+    expected.public_inputs.insert(
+        ident!(inputs),
+        PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 2),
+    );
+    expected.boundary_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(
+            bounded_access!(clk, Boundary::First),
+            int!(0)
+        ))],
+    ));
+    expected.integrity_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(access!(clk), int!(0)))],
+    ));
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
+#[test]
+fn err_trace_columns_overlapping_indices() {
+    let source = r#"
+    def test
+
+    trace_columns:
+        main: [clk, fmp[4] @ 2, ctx @ 4]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf clk.first = 0
+
+    integrity_constraints:
+        enf clk = 0
+    "#;
+
+    ParseTest::new().expect_module_diagnostic(source, "overlapping trace column bindings");
+}
+
+#[test]
+fn err_trace_columns_index_overflow() {
+    let source = r#"
+    def test
+
+    trace_columns:
+        main: [clk, fmp[2] @ 18446744073709551615]
+
+    integrity_constraints:
+        enf clk = 0
+    "#;
+
+    ParseTest::new().expect_module_diagnostic(source, "trace segment is too wide");
+}
+
+#[test]
+fn err_trace_columns_too_wide() {
+    let source = r#"
+    def test
+
+    trace_columns:
+        main: [clk, fmp @ 70000]
+
+    integrity_constraints:
+        enf clk = 0
+    "#;
+
+    ParseTest::new().expect_module_diagnostic(source, "trace segment is too wide");
+}
+
 #[test]
 fn err_empty_trace_columns() {
     let source = r#"
@@ -162,6 +361,8 @@ fn err_main_trace_cols_missing() {
         aux: [clk]
     public_inputs:
         stack_inputs: [16]
+    random_values:
+        rand: [1]
     integrity_constraints:
         enf clk' = clk + 1
     boundary_constraints:
@@ -171,3 +372,113 @@ fn err_main_trace_cols_missing() {
     ParseTest::new()
         .expect_module_diagnostic(source, "declaration of main trace columns is required");
 }
+
+// TRACE WIDTH ASSERTIONS
+// ================================================================================================
+
+#[test]
+fn trace_width_assertions() {
+    let source = r#"
+    def test
+
+    assert trace_width(main) == 3
+    assert trace_width(aux) == 2
+
+    trace_columns:
+        main: [clk, fmp, ctx]
+        aux: [rc_bus, ch_bus]
+
+    public_inputs:
+        inputs: [2]
+
+    random_values:
+        rand: [1]
+
+    boundary_constraints:
+        enf clk.first = 0
+
+    integrity_constraints:
+        enf clk = 0
+    "#;
+    let mut expected = Module::new(ModuleType::Root, SourceSpan::UNKNOWN, ident!(test));
+    expected
+        .trace_columns
+        .push(trace_segment!(0, "$main", [(clk, 1), (fmp, 1), (ctx, 1)]));
+    expected
+        .trace_columns
+        .push(trace_segment!(1, "$aux", [(rc_bus, 1), (ch_bus, 1)]));
+    expected.public_inputs.insert(
+        ident!(inputs),
+        PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 2),
+    );
+    expected.random_values = Some(random_values!("$rand", 1));
+    expected.boundary_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(
+            bounded_access!(clk, Boundary::First),
+            int!(0)
+        ))],
+    ));
+    expected.integrity_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(access!(clk), int!(0)))],
+    ));
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
+#[test]
+fn err_trace_width_mismatch() {
+    let source = r#"
+    def test
+
+    trace_columns:
+        main: [clk, fmp[4] @ 2, ctx]
+
+    assert trace_width(main) == 6
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf clk.first = 0
+
+    integrity_constraints:
+        enf clk = 0
+    "#;
+
+    ParseTest::new().expect_module_diagnostic(source, "trace width assertion failed");
+}
+
+#[test]
+fn err_trace_width_undefined_segment() {
+    let source = r#"
+    def test
+
+    trace_columns:
+        main: [clk, fmp, ctx]
+
+    assert trace_width(aux) == 2
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf clk.first = 0
+
+    integrity_constraints:
+        enf clk = 0
+    "#;
+
+    ParseTest::new().expect_module_diagnostic(source, "undefined trace segment");
+}
+
+#[test]
+fn err_trace_width_in_library() {
+    let source = r#"
+    mod test
+
+    assert trace_width(main) == 3
+    "#;
+
+    ParseTest::new().expect_module_diagnostic(source, "invalid trace_width declaration");
+}
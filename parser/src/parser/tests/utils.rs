@@ -4,7 +4,7 @@ use miden_diagnostics::{CodeMap, DiagnosticsConfig, DiagnosticsHandler, Emitter,
 use pretty_assertions::assert_eq;
 
 use crate::{
-    ast::{Module, Program},
+    ast::{Module, ModuleCache, Program},
     parser::{ParseError, Parser},
 };
 
@@ -94,6 +94,11 @@ impl ParseTest {
         self.parser.codemap.add(name.as_ref(), content);
     }
 
+    /// Returns the diagnostics emitted so far by this test, as rendered
+    pub fn captured(&self) -> String {
+        self.emitter.captured()
+    }
+
     pub fn parse_module_from_file(&self, path: &str) -> Result<Module, ParseError> {
         self.parser
             .parse_file::<Module, _, _>(&self.diagnostics, path)
@@ -114,6 +119,33 @@ impl ParseTest {
             .parse_string::<Program, _, _>(&self.diagnostics, source)
     }
 
+    pub fn parse_program_with_cache(
+        &self,
+        source: &str,
+        cache: &ModuleCache,
+    ) -> Result<Program, ParseError> {
+        crate::parse_with_cache(
+            &self.diagnostics,
+            self.parser.codemap.clone(),
+            source,
+            cache,
+        )
+    }
+
+    pub fn parse_program_from_file_with_cache(
+        &self,
+        path: &std::path::Path,
+        cache: &ModuleCache,
+    ) -> Result<Program, ParseError> {
+        let file = crate::load_file(&self.parser.codemap, path).unwrap();
+        crate::parser::parse_program_with_cache(
+            &self.diagnostics,
+            self.parser.codemap.clone(),
+            file,
+            cache,
+        )
+    }
+
     // TEST METHODS
     // --------------------------------------------------------------------------------------------
 
@@ -60,6 +60,33 @@ fn variables_with_or_operators() {
     ParseTest::new().expect_module_ast(source, expected);
 }
 
+#[test]
+fn variables_with_type_annotations() {
+    let source = "
+    mod test
+
+    ev test([clk, s]):
+        let flag: binary = !s
+        let step: felt = flag
+        enf clk' = clk + step";
+
+    let mut expected = Module::new(ModuleType::Library, SourceSpan::UNKNOWN, ident!(test));
+    let body = vec![let_!(flag: Binary = expr!(not!(access!(s))) =>
+                    let_!(step: Felt = expr!(access!(flag)) =>
+                          enforce!(eq!(access!(clk, 1), add!(access!(clk), access!(step))))))];
+    expected.evaluators.insert(
+        ident!(test),
+        EvaluatorFunction::new(
+            SourceSpan::UNKNOWN,
+            ident!(test),
+            vec![trace_segment!(0, "%0", [(clk, 1), (s, 1)])],
+            body,
+        ),
+    );
+
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
 // VARIABLES INVALID USAGE
 // ================================================================================================
 
@@ -128,3 +155,13 @@ fn err_matrix_variable_from_vector_and_reference() {
         let d = [[7, 8], a[0]]";
     ParseTest::new().expect_unrecognized_token(source);
 }
+
+#[test]
+fn err_variable_with_aggregate_type_annotation() {
+    let source = "
+    def test
+
+    integrity_constraints:
+        let a: [felt; 2] = [1, 2]";
+    ParseTest::new().expect_unrecognized_token(source);
+}
@@ -0,0 +1,91 @@
+use core::ops::ControlFlow;
+
+use miden_diagnostics::{SourceSpan, Span, Spanned};
+
+use crate::ast::{visit::VisitMut, *};
+
+use super::ParseTest;
+
+// EXTERNAL PASSES
+// ================================================================================================
+
+/// A pass of the kind which could be written outside of this crate, which negates every integer
+/// literal in a scalar expression, i.e. `c` is rewritten to `0 - c`.
+///
+/// Only the expressions of interest are handled here, while the default implementations of
+/// [VisitMut] drive the traversal to them through every kind of statement and expression.
+struct NegateLiterals;
+impl VisitMut<()> for NegateLiterals {
+    fn visit_mut_scalar_expr(&mut self, expr: &mut ScalarExpr) -> ControlFlow<()> {
+        match expr {
+            ScalarExpr::Const(value) => {
+                let span = value.span();
+                let value = core::mem::replace(expr, ScalarExpr::Const(Span::new(span, 0)));
+                *expr = ScalarExpr::Binary(BinaryExpr::new(
+                    span,
+                    BinaryOp::Sub,
+                    ScalarExpr::Const(Span::new(span, 0)),
+                    value,
+                ));
+                ControlFlow::Continue(())
+            }
+            _ => visit::visit_mut_scalar_expr(self, expr),
+        }
+    }
+}
+
+#[test]
+fn external_pass_negates_literals() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [clk, s, a]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf clk.first = 1
+
+    integrity_constraints:
+        enf clk' = clk + 1
+        enf a' = 2 * a when s";
+
+    let mut module = ParseTest::new()
+        .parse_module(source)
+        .expect("parsing failed");
+    assert_eq!(
+        visit::visit_mut_module(&mut NegateLiterals, &mut module),
+        ControlFlow::Continue(())
+    );
+
+    let mut expected = Module::new(ModuleType::Root, SourceSpan::UNKNOWN, ident!(test));
+    expected
+        .trace_columns
+        .push(trace_segment!(0, "$main", [(clk, 1), (s, 1), (a, 1)]));
+    expected.public_inputs.insert(
+        ident!(inputs),
+        PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 2),
+    );
+    expected.boundary_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(
+            bounded_access!(clk, Boundary::First),
+            sub!(int!(0), int!(1))
+        ))],
+    ));
+    expected.integrity_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![
+            enforce!(eq!(
+                access!(clk, 1),
+                add!(access!(clk), sub!(int!(0), int!(1)))
+            )),
+            enforce_all!(
+                lc!((("%0", range!(0..1))) => eq!(access!(a, 1), mul!(sub!(int!(0), int!(2)), access!(a))), when access!(s))
+            ),
+        ],
+    ));
+    assert_eq!(module, expected);
+}
@@ -46,6 +46,14 @@ impl BindingType {
         }
     }
 
+    /// Returns the binding type aliased by this one, if it is an alias, otherwise itself
+    pub fn unalias(self) -> Self {
+        match self {
+            Self::Alias(aliased) => aliased.unalias(),
+            ty => ty,
+        }
+    }
+
     /// Returns true if this binding type is a trace binding
     pub fn is_trace_binding(&self) -> bool {
         match self {
@@ -1,3 +1,4 @@
+use air_pass::NotConverged;
 use miden_diagnostics::{Diagnostic, Label, SourceSpan, Spanned, ToDiagnostic};
 
 use crate::ast::{Identifier, InvalidExprError, ModuleId};
@@ -33,6 +34,8 @@ pub enum SemanticAnalysisError {
     InvalidExpr(#[from] InvalidExprError),
     #[error("module is invalid, see diagnostics for details")]
     Invalid,
+    #[error(transparent)]
+    NotConverged(#[from] NotConverged),
 }
 impl Eq for SemanticAnalysisError {}
 impl PartialEq for SemanticAnalysisError {
@@ -90,6 +93,7 @@ impl ToDiagnostic for SemanticAnalysisError {
                     .with_message("failed import occurred here")]),
             Self::InvalidExpr(err) => err.to_diagnostic(),
             Self::Invalid => Diagnostic::error().with_message("module is invalid, see diagnostics for details"),
+            Self::NotConverged(err) => Diagnostic::error().with_message(err.to_string()),
         }
     }
 }
@@ -8,7 +8,8 @@ use crate::{
     sema::SemanticAnalysisError,
 };
 
-pub type Imported = HashMap<NamespacedIdentifier, ModuleId>;
+/// Maps the names bound by imports in a module to the items they refer to
+pub type Imported = HashMap<NamespacedIdentifier, QualifiedIdentifier>;
 
 pub struct ImportResolver<'a> {
     diagnostics: &'a DiagnosticsHandler,
@@ -59,7 +60,7 @@ impl<'a> VisitMut<SemanticAnalysisError> for ImportResolver<'a> {
                     for export in imported_from.exports() {
                         let name = export.name();
                         let item = Identifier::new(from.span(), name.name());
-                        self.import(module, *from, item, export)?;
+                        self.import(module, *from, item, item, export)?;
                     }
                 }
                 Import::Partial {
@@ -74,14 +75,12 @@ impl<'a> VisitMut<SemanticAnalysisError> for ImportResolver<'a> {
                         Ok(value) => value,
                         Err(err) => return ControlFlow::Break(err),
                     };
-                    for export in imported_from.exports() {
-                        let name = export.name();
-                        // We fetch the item from the set, rather than simply
-                        // check for containment, because we want the span associated
-                        // with the item in the set, not the span associated with the
-                        // export.
-                        if let Some(item) = items.get(&name) {
-                            self.import(module, *from, *item, export)?;
+                    for (local, item) in items.iter() {
+                        // We use the identifiers from the import, rather than those of
+                        // the export, because we want the spans associated with the
+                        // import in diagnostics.
+                        if let Some(export) = imported_from.get(item) {
+                            self.import(module, *from, *local, *item, export)?;
                         }
                     }
                 }
@@ -95,26 +94,33 @@ impl<'a> VisitMut<SemanticAnalysisError> for ImportResolver<'a> {
 }
 
 impl<'a> ImportResolver<'a> {
-    /// Imports a single item into the current module
+    /// Imports a single item into the current module, binding it to `local`
     fn import(
         &mut self,
         module: &mut Module,
         from: ModuleId,
+        local: Identifier,
         item: Identifier,
         export: Export<'_>,
     ) -> ControlFlow<SemanticAnalysisError> {
         match export {
-            Export::Constant(_) => self.import_constant(module, from, item),
-            Export::Evaluator(_) => self.import_evaluator(module, from, item),
+            Export::Constant(_) => {
+                let id = QualifiedIdentifier::new(from, NamespacedIdentifier::Binding(item));
+                self.import_constant(module, local, id)
+            }
+            Export::Evaluator(_) | Export::Function(_) => {
+                let id = QualifiedIdentifier::new(from, NamespacedIdentifier::Function(item));
+                self.import_function(module, local, id)
+            }
         }
     }
 
-    /// Imports a constant into the current module
+    /// Imports the constant `id` into the current module as `item`
     fn import_constant(
         &mut self,
         module: &mut Module,
-        from: ModuleId,
         item: Identifier,
+        id: QualifiedIdentifier,
     ) -> ControlFlow<SemanticAnalysisError> {
         use std::collections::hash_map::Entry;
 
@@ -127,16 +133,15 @@ impl<'a> ImportResolver<'a> {
             None => {
                 match self.imported.entry(namespaced_name) {
                     Entry::Occupied(entry) => {
-                        let id = entry.key();
-                        let originally_imported_from = entry.get();
-                        if originally_imported_from == &from {
+                        let prev = entry.key();
+                        if entry.get() == &id {
                             // Warn about redundant import
                             self.diagnostics
                                 .diagnostic(Severity::Warning)
                                 .with_message("redundant import")
                                 .with_primary_label(item.span(), "this import is unnecessary")
                                 .with_secondary_label(
-                                    id.span(),
+                                    prev.span(),
                                     "because it was already imported here",
                                 )
                                 .emit();
@@ -145,12 +150,12 @@ impl<'a> ImportResolver<'a> {
                             // Conflict is with another imported name, raise an error
                             ControlFlow::Break(SemanticAnalysisError::ImportConflict {
                                 item,
-                                prev: id.span(),
+                                prev: prev.span(),
                             })
                         }
                     }
                     Entry::Vacant(entry) => {
-                        entry.insert(from);
+                        entry.insert(id);
                         ControlFlow::Continue(())
                     }
                 }
@@ -158,34 +163,38 @@ impl<'a> ImportResolver<'a> {
         }
     }
 
-    /// Imports a constant into the current module
-    fn import_evaluator(
+    /// Imports the evaluator or pure function `id` into the current module as `item`
+    fn import_function(
         &mut self,
         module: &mut Module,
-        from: ModuleId,
         item: Identifier,
+        id: QualifiedIdentifier,
     ) -> ControlFlow<SemanticAnalysisError> {
         use std::collections::hash_map::Entry;
 
         let namespaced_name = NamespacedIdentifier::Function(item);
-        match module.evaluators.get(&item) {
+        let exists = module
+            .evaluators
+            .get(&item)
+            .map(|e| e.name)
+            .or_else(|| module.functions.get(&item).map(|f| f.name));
+        match exists {
             Some(exists) => ControlFlow::Break(SemanticAnalysisError::ImportConflict {
                 item,
-                prev: exists.name.span(),
+                prev: exists.span(),
             }),
             None => {
                 match self.imported.entry(namespaced_name) {
                     Entry::Occupied(entry) => {
-                        let id = entry.key();
-                        let originally_imported_from = entry.get();
-                        if originally_imported_from == &from {
+                        let prev = entry.key();
+                        if entry.get() == &id {
                             // Warn about redundant import
                             self.diagnostics
                                 .diagnostic(Severity::Warning)
                                 .with_message("redundant import")
                                 .with_primary_label(item.span(), "this import is unnecessary")
                                 .with_secondary_label(
-                                    id.span(),
+                                    prev.span(),
                                     "because it was already imported here",
                                 )
                                 .emit();
@@ -194,12 +203,12 @@ impl<'a> ImportResolver<'a> {
                             // Conflict is with another import, raise an error
                             ControlFlow::Break(SemanticAnalysisError::ImportConflict {
                                 item,
-                                prev: id.span(),
+                                prev: prev.span(),
                             })
                         }
                     }
                     Entry::Vacant(entry) => {
-                        entry.insert(from);
+                        entry.insert(id);
                         ControlFlow::Continue(())
                     }
                 }
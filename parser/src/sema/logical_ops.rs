@@ -0,0 +1,51 @@
+use core::ops::ControlFlow;
+
+use miden_diagnostics::{SourceSpan, Spanned};
+
+use crate::ast::{visit::VisitMut, *};
+
+use super::SemanticAnalysisError;
+
+/// This pass rewrites the logical operators `!`, `&` and `|` into the arithmetic expressions
+/// they denote over binary values, i.e.:
+///
+/// * `!a` becomes `1 - a`
+/// * `a & b` becomes `a * b`
+/// * `a | b` becomes `a + b - a * b`
+///
+/// It is run once semantic analysis has validated that the operands of these operators are
+/// binary, so that subsequent passes only ever have to deal with arithmetic.
+pub(super) struct LowerLogicalOperators;
+impl VisitMut<SemanticAnalysisError> for LowerLogicalOperators {
+    fn visit_mut_binary_expr(
+        &mut self,
+        expr: &mut BinaryExpr,
+    ) -> ControlFlow<SemanticAnalysisError> {
+        self.visit_mut_scalar_expr(expr.lhs.as_mut())?;
+        self.visit_mut_scalar_expr(expr.rhs.as_mut())?;
+
+        match expr.op {
+            BinaryOp::Not => expr.op = BinaryOp::Sub,
+            BinaryOp::And => expr.op = BinaryOp::Mul,
+            BinaryOp::Or => {
+                let span = expr.span();
+                let lhs = expr.lhs.as_ref().clone();
+                let rhs = expr.rhs.as_ref().clone();
+                *expr = BinaryExpr::new(
+                    span,
+                    BinaryOp::Sub,
+                    binary(span, BinaryOp::Add, lhs.clone(), rhs.clone()),
+                    binary(span, BinaryOp::Mul, lhs, rhs),
+                );
+            }
+            _ => (),
+        }
+
+        ControlFlow::Continue(())
+    }
+}
+
+#[inline]
+fn binary(span: SourceSpan, op: BinaryOp, lhs: ScalarExpr, rhs: ScalarExpr) -> ScalarExpr {
+    ScalarExpr::Binary(BinaryExpr::new(span, op, lhs, rhs))
+}
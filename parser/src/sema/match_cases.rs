@@ -0,0 +1,105 @@
+use crate::ast::*;
+
+/// The maximum number of distinct values which may be referenced by the selectors of an
+/// `enf match` for the cases to be checked, as every assignment of those values is visited
+const MAX_SELECTOR_VALUES: usize = 12;
+
+/// The selectors of the cases of an `enf match`, viewed as boolean formulas over the distinct
+/// values they reference, e.g. `s[0] & !s[1]'` is a formula over `s[0]` and `s[1]'`.
+///
+/// Since the selectors are binary, each of these values is either 0 or 1, so by evaluating the
+/// selectors under every assignment of the values, we can determine which assignments are
+/// selected by more than one case, and which are not selected by any case.
+pub(super) struct MatchSelectors<'a> {
+    values: Vec<&'a SymbolAccess>,
+    selectors: Vec<&'a ScalarExpr>,
+}
+impl<'a> MatchSelectors<'a> {
+    /// Returns `None` if any of the selectors is something other than a logical combination of
+    /// constants and symbol accesses, or if they reference too many values to be checked.
+    pub fn new<I>(selectors: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = &'a ScalarExpr>,
+    {
+        let mut values = vec![];
+        let selectors = selectors.into_iter().collect::<Vec<_>>();
+        for selector in selectors.iter() {
+            collect_values(selector, &mut values)?;
+        }
+        if values.len() > MAX_SELECTOR_VALUES {
+            return None;
+        }
+
+        Some(Self { values, selectors })
+    }
+
+    /// Returns an iterator over every assignment of the values referenced by the selectors
+    ///
+    /// Each assignment is a bitset, where bit `i` is the value of the `i`th referenced value.
+    pub fn assignments(&self) -> impl Iterator<Item = u32> {
+        0..(1u32 << self.values.len())
+    }
+
+    /// Returns the indices of the cases whose selectors hold under `assignment`
+    pub fn selected(&self, assignment: u32) -> Vec<usize> {
+        self.selectors
+            .iter()
+            .enumerate()
+            .filter_map(|(i, selector)| self.eval(selector, assignment).then_some(i))
+            .collect()
+    }
+
+    /// Renders `assignment` for use in diagnostics, e.g. `s[0] = 1, s[1] = 0`
+    pub fn describe(&self, assignment: u32) -> String {
+        self.values
+            .iter()
+            .enumerate()
+            .map(|(i, value)| format!("{} = {}", value, (assignment >> i) & 1))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn eval(&self, expr: &ScalarExpr, assignment: u32) -> bool {
+        match expr {
+            ScalarExpr::Const(value) => value.item == 1,
+            ScalarExpr::SymbolAccess(access) => {
+                let index = self.values.iter().position(|v| *v == access).unwrap();
+                (assignment >> index) & 1 == 1
+            }
+            ScalarExpr::Binary(expr) => match expr.op {
+                BinaryOp::Not => !self.eval(expr.rhs.as_ref(), assignment),
+                BinaryOp::And => {
+                    self.eval(expr.lhs.as_ref(), assignment)
+                        && self.eval(expr.rhs.as_ref(), assignment)
+                }
+                BinaryOp::Or => {
+                    self.eval(expr.lhs.as_ref(), assignment)
+                        || self.eval(expr.rhs.as_ref(), assignment)
+                }
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// Adds the distinct values referenced by `expr` to `values`, or returns `None` if `expr` is not
+/// a logical combination of binary constants and symbol accesses.
+fn collect_values<'a>(expr: &'a ScalarExpr, values: &mut Vec<&'a SymbolAccess>) -> Option<()> {
+    match expr {
+        ScalarExpr::Const(value) if value.item <= 1 => Some(()),
+        ScalarExpr::SymbolAccess(access) => {
+            if !values.contains(&access) {
+                values.push(access);
+            }
+            Some(())
+        }
+        ScalarExpr::Binary(expr) if expr.op.is_logical() => {
+            if expr.op != BinaryOp::Not {
+                collect_values(expr.lhs.as_ref(), values)?;
+            }
+            collect_values(expr.rhs.as_ref(), values)
+        }
+        _ => None,
+    }
+}
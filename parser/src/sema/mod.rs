@@ -2,6 +2,8 @@ mod binding_type;
 mod dependencies;
 mod errors;
 mod import_resolver;
+mod logical_ops;
+mod match_cases;
 mod scope;
 mod semantic_analysis;
 
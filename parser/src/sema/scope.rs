@@ -21,9 +21,10 @@ pub type Env<K, V> = Box<HashMap<K, V>>;
 /// When searching for keys, the search begins in the current scope, and searches upwards
 /// in the scope tree until either the root is reached and the search terminates, or the
 /// key is found in some intervening scope.
-#[derive(Clone)]
+#[derive(Clone, Default)]
 pub enum LexicalScope<K, V> {
     /// An empty scope, this is the default state in which all [LexicalScope] start
+    #[default]
     Empty,
     /// Represents a non-empty, top-level (root) scope
     Root(Env<K, V>),
@@ -31,11 +32,6 @@ pub enum LexicalScope<K, V> {
     /// the environment of the current scope.
     Nested(Box<LexicalScope<K, V>>, Env<K, V>),
 }
-impl<K, V> Default for LexicalScope<K, V> {
-    fn default() -> Self {
-        Self::Empty
-    }
-}
 impl<K, V> LexicalScope<K, V> {
     /// Returns true if this scope is empty
     pub fn is_empty(&self) -> bool {
@@ -65,6 +61,16 @@ impl<K, V> LexicalScope<K, V> {
             }
         }
     }
+
+    /// Returns an iterator over the keys defined in this scope and all of its parents, including
+    /// those shadowed by a definition in a nested scope
+    pub fn keys(&self) -> Box<dyn Iterator<Item = &K> + '_> {
+        match self {
+            Self::Empty => Box::new(core::iter::empty()),
+            Self::Root(ref env) => Box::new(env.keys()),
+            Self::Nested(ref parent, ref env) => Box::new(env.keys().chain(parent.keys())),
+        }
+    }
 }
 impl<K, V> LexicalScope<K, V>
 where
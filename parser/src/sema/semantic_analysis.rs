@@ -1,5 +1,5 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     fmt, mem,
     ops::ControlFlow,
 };
@@ -12,7 +12,7 @@ use crate::{
     symbols::{self, Symbol},
 };
 
-use super::*;
+use super::{logical_ops::LowerLogicalOperators, match_cases::MatchSelectors, *};
 
 /// A helper enum for representing what constraint mode is active
 #[derive(Copy, Clone, PartialEq, Eq)]
@@ -43,6 +43,18 @@ impl fmt::Display for ConstraintMode {
     }
 }
 
+/// An operand of a logical operator, or a selector of an `enf match`, which could not be proven
+/// binary when it was visited
+struct UnprovenOperand {
+    span: SourceSpan,
+    /// The logical operator and the span of the expression it is applied in, or `None` if this
+    /// is the selector of an `enf match`
+    op: Option<(BinaryOp, SourceSpan)>,
+    /// The trace column referred to by the operand, which proves it binary if the column is
+    /// constrained by `enf is_binary(x)` anywhere in the same constraint section or evaluator
+    column: Option<(TraceSegmentId, usize)>,
+}
+
 /// This pass is used to perform a variety of semantic analysis tasks in a single traversal of a module AST
 ///
 /// * Resolves all identifiers to their fully-qualified names, or raises appropriate errors if unable
@@ -64,13 +76,37 @@ pub struct SemanticAnalysis<'a> {
     imported: Imported,
     globals: HashMap<Identifier, BindingType>,
     locals: LexicalScope<NamespacedIdentifier, BindingType>,
-    referenced: HashMap<QualifiedIdentifier, DependencyType>,
+    /// The items referenced from the current function or constraint section, ordered so that
+    /// the dependency graph, and everything derived from it, is built deterministically
+    referenced: BTreeMap<QualifiedIdentifier, DependencyType>,
     current_module: Option<ModuleId>,
     constraint_mode: ConstraintMode,
     saw_random_values: bool,
     has_undefined_variables: bool,
     has_type_errors: bool,
     in_constraint_comprehension: bool,
+    in_constraint_call: bool,
+    /// Set when visiting the body of a pure function, which may only reference its parameters,
+    /// constants, and other functions
+    in_function: bool,
+    /// The set of trace columns, by segment and offset, which have been constrained to binary
+    /// values via `enf is_binary(x)` in the current constraint section or evaluator
+    binary_columns: HashSet<(TraceSegmentId, usize)>,
+    /// The periodic columns of the current module whose values are all 0 or 1
+    binary_periodic_columns: HashSet<Identifier>,
+    /// The operands of logical operators and `enf match` selectors in the current constraint
+    /// section, evaluator or function which have not yet been proven binary
+    unproven_operands: Vec<UnprovenOperand>,
+    /// The spans of the `let` bindings whose values are known to be binary, including those which
+    /// alias a trace column, and thus have the type of that column
+    binary_bindings: HashSet<SourceSpan>,
+    /// The spans of the local bindings which have been referenced, used to warn on unused `let` bindings
+    used_bindings: HashSet<SourceSpan>,
+    /// The parameters of the evaluator or function currently being visited, in declaration order,
+    /// used to resolve positional parameter references, e.g. `$0`
+    params: Vec<Identifier>,
+    /// The evaluators declared in the current module whose bodies consist of boundary constraints
+    boundary_evaluators: HashSet<Identifier>,
 }
 impl<'a> SemanticAnalysis<'a> {
     /// Create a new instance of the semantic analyzer
@@ -96,6 +132,15 @@ impl<'a> SemanticAnalysis<'a> {
             has_undefined_variables: false,
             has_type_errors: false,
             in_constraint_comprehension: false,
+            in_constraint_call: false,
+            in_function: false,
+            binary_columns: Default::default(),
+            binary_periodic_columns: Default::default(),
+            unproven_operands: vec![],
+            binary_bindings: Default::default(),
+            used_bindings: Default::default(),
+            params: vec![],
+            boundary_evaluators: Default::default(),
         }
     }
 
@@ -105,6 +150,15 @@ impl<'a> SemanticAnalysis<'a> {
             return Err(err);
         }
 
+        // Functions are inlined at their call sites, so they must not be recursive
+        self.check_recursive_functions(module)?;
+
+        // Now that the module has been type checked, logical operators are lowered to their
+        // arithmetic equivalents, as no subsequent pass needs to distinguish them
+        if let ControlFlow::Break(err) = LowerLogicalOperators.visit_mut_module(module) {
+            return Err(err);
+        }
+
         // If this is the root module, we may have top-level dependencies
         if module.name == self.program.name {
             // Update the dependency graph with the collected information
@@ -209,10 +263,19 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
                 assert_eq!(
                     self.locals.insert(
                         NamespacedIdentifier::Binding(input.name),
-                        BindingType::PublicInput(Type::Vector(input.size))
+                        BindingType::PublicInput(input.ty())
                     ),
                     None
                 );
+                for binding in input.bindings.iter() {
+                    assert_eq!(
+                        self.locals.insert(
+                            NamespacedIdentifier::Binding(binding.name),
+                            BindingType::PublicInput(binding.ty())
+                        ),
+                        None
+                    );
+                }
             }
         }
 
@@ -251,6 +314,9 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
                 ),
                 None
             );
+            if function.is_boundary() {
+                self.boundary_evaluators.insert(*function_name);
+            }
         }
 
         // Next, we add any periodic columns to the set of local bindings.
@@ -277,12 +343,53 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
                 ),
                 None
             );
+            if periodic.generator.is_none() && periodic.values.iter().all(|v| *v <= 1) {
+                self.binary_periodic_columns.insert(periodic.name);
+            }
+        }
+
+        // Pure functions share the namespace of evaluators, and are registered the same way
+        for (function_name, function) in module.functions.iter() {
+            let namespaced_name = NamespacedIdentifier::Function(*function_name);
+            if let Some((prev, _)) = self.imported.get_key_value(&namespaced_name) {
+                self.declaration_import_conflict(namespaced_name.span(), prev.span())?;
+            }
+            assert_eq!(
+                self.locals
+                    .insert(namespaced_name, BindingType::Function(function.signature())),
+                None
+            );
+        }
+
+        // The comprehensions generating the values of periodic columns are expanded during
+        // constant propagation, so we resolve the constants they reference here, and record
+        // them as dependencies of the column, so that they are part of any program using it
+        for periodic in module.periodic_columns.values_mut() {
+            let Some(generator) = periodic.generator.as_deref_mut() else {
+                continue;
+            };
+            let referenced = mem::take(&mut self.referenced);
+            self.visit_mut_list_comprehension(generator)?;
+
+            let current_item = QualifiedIdentifier::new(
+                self.current_module.unwrap(),
+                NamespacedIdentifier::Binding(periodic.name),
+            );
+            for (referenced_item, ref_type) in self.referenced.iter() {
+                let referenced_item = self.deps.add_node(*referenced_item);
+                self.deps.add_edge(current_item, referenced_item, *ref_type);
+            }
+            self.referenced = referenced;
         }
 
         // From this point forward, we use the standard visitor traversal to visit every node
         // which can reference an identifier, and rewrite any references to imported names to
         // use the fully-qualified identifier. Likewise, any time we visit an imported item, we
         // rewrite its name to be fully-qualified,
+        for function in module.functions.values_mut() {
+            self.visit_mut_function(function)?;
+        }
+
         for evaluator in module.evaluators.values_mut() {
             self.visit_mut_evaluator_function(evaluator)?;
         }
@@ -313,8 +420,14 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
         &mut self,
         function: &mut EvaluatorFunction,
     ) -> ControlFlow<SemanticAnalysisError> {
-        // Only allow integrity constraints in this context
-        self.constraint_mode = ConstraintMode::Integrity;
+        // Only allow integrity constraints in this context, unless the body of the evaluator
+        // consists of boundary constraints, in which case only boundary constraints are allowed
+        self.constraint_mode = if function.is_boundary() {
+            ConstraintMode::Boundary
+        } else {
+            ConstraintMode::Integrity
+        };
+        self.binary_columns.clear();
         // Start a new lexical scope
         self.locals.enter();
         // Track referenced imports in a new context, as we want to update the dependency graph
@@ -325,6 +438,7 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
         for trace_segment in function.params.iter_mut() {
             for trace_binding in trace_segment.bindings.iter() {
                 let name = trace_binding.name.unwrap();
+                self.params.push(name);
                 let namespaced_name = NamespacedIdentifier::Binding(name);
                 self.locals.insert(
                     namespaced_name,
@@ -342,6 +456,7 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
 
         // Visit all of the statements in the body
         self.visit_mut_statement_block(&mut function.body)?;
+        self.check_unproven_operands();
 
         // Update the dependency graph for this function
         let current_item = QualifiedIdentifier::new(
@@ -357,12 +472,100 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
         self.referenced = referenced;
         // Restore the original lexical scope
         self.locals.exit();
+        self.params.clear();
         // Disallow constraints
         self.constraint_mode = ConstraintMode::None;
 
         ControlFlow::Continue(())
     }
 
+    fn visit_mut_function(
+        &mut self,
+        function: &mut Function,
+    ) -> ControlFlow<SemanticAnalysisError> {
+        // Functions are pure, so constraints are not permitted in their bodies
+        self.constraint_mode = ConstraintMode::None;
+        self.in_function = true;
+        // Start a new lexical scope
+        self.locals.enter();
+        // Track referenced imports in a new context, as we want to update the dependency graph
+        // for this function using only those imports referenced from this function body
+        let referenced = mem::take(&mut self.referenced);
+
+        // Add the set of parameters to the current scope, check for conflicts
+        let mut params = HashSet::<Identifier>::default();
+        for (param, ty) in function.params.iter() {
+            if let Some(prev) = params.get(param) {
+                self.diagnostics
+                    .diagnostic(Severity::Error)
+                    .with_message("duplicate parameter")
+                    .with_primary_label(param.span(), "this parameter name is already in use")
+                    .with_secondary_label(prev.span(), "previously declared here")
+                    .emit();
+                return ControlFlow::Break(SemanticAnalysisError::Invalid);
+            }
+            params.insert(*param);
+            self.params.push(*param);
+            self.locals.insert(
+                NamespacedIdentifier::Binding(*param),
+                BindingType::Local(*ty),
+            );
+        }
+
+        // Visit all of the statements in the body
+        self.visit_mut_statement_block(&mut function.body)?;
+        self.check_unproven_operands();
+
+        // The value returned by the function must have the declared result type
+        let result = function_result(&function.body);
+        let is_valid = match result.ty() {
+            Some(Type::Binary) => true,
+            Some(Type::Felt) if function.return_ty.is_binary() => self.is_provably_binary(result),
+            Some(ty) => {
+                ty.is_scalar() == function.return_ty.is_scalar()
+                    && (ty.is_scalar() || ty == function.return_ty)
+            }
+            // We've already raised a diagnostic for the invalid result
+            None => true,
+        };
+        if !is_valid {
+            self.has_type_errors = true;
+            let label = match result.ty() {
+                Some(Type::Felt) => "this expression is not known to be binary".to_string(),
+                Some(ty) => format!("this expression has type {}", ty),
+                None => unreachable!(),
+            };
+            self.diagnostics
+                .diagnostic(Severity::Error)
+                .with_message("type mismatch")
+                .with_primary_label(result.span(), label)
+                .with_secondary_label(
+                    function.name.span(),
+                    format!("but this function returns a {}", function.return_ty),
+                )
+                .emit();
+        }
+
+        // Update the dependency graph for this function
+        let current_item = QualifiedIdentifier::new(
+            self.current_module.unwrap(),
+            NamespacedIdentifier::Function(function.name),
+        );
+        for (referenced_item, ref_type) in self.referenced.iter() {
+            let referenced_item = self.deps.add_node(*referenced_item);
+            self.deps.add_edge(current_item, referenced_item, *ref_type);
+        }
+
+        // Restore the original references metadata
+        self.referenced = referenced;
+        // Restore the original lexical scope
+        self.locals.exit();
+        self.params.clear();
+        self.in_function = false;
+
+        ControlFlow::Continue(())
+    }
+
     fn visit_mut_boundary_constraints(
         &mut self,
         body: &mut Vec<Statement>,
@@ -370,10 +573,12 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
         // Only allow boundary constraints in this context
         self.constraint_mode = ConstraintMode::Boundary;
         self.saw_random_values = false;
+        self.binary_columns.clear();
         // Save the current bindings set, as we're entering a new lexical scope
         self.locals.enter();
         // Visit all of the statements, check variable usage, and track referenced imports
         self.visit_mut_statement_block(body)?;
+        self.check_unproven_operands();
         // Restore the original lexical scope
         self.locals.exit();
         // Disallow any constraints
@@ -389,10 +594,12 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
     ) -> ControlFlow<SemanticAnalysisError> {
         // Only allow integrity constraints in this context
         self.constraint_mode = ConstraintMode::Integrity;
+        self.binary_columns.clear();
         // Save the current bindings set, as we're entering a new lexical scope
         self.locals.enter();
         // Visit all of the statements, check variable usage, and track referenced imports
         self.visit_mut_statement_block(body)?;
+        self.check_unproven_operands();
         // Restore the original lexical scope
         self.locals.exit();
         // Disallow any constraints
@@ -401,6 +608,18 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
         ControlFlow::Continue(())
     }
 
+    /// A constraint between two vectors of the same length, e.g. `enf c.first = C`, is rewritten
+    /// to the equivalent constraint comprehension, i.e. `enf x.first = y for (x, y) in (c, C)`,
+    /// which is then validated like any other comprehension
+    fn visit_mut_statement(&mut self, stmt: &mut Statement) -> ControlFlow<SemanticAnalysisError> {
+        if let Statement::Enforce(ref expr) = stmt {
+            if let Some(comprehension) = self.splice_vector_constraint(expr)? {
+                *stmt = Statement::EnforceAll(comprehension);
+            }
+        }
+        visit::visit_mut_statement(self, stmt)
+    }
+
     /// Visit scalar constraints and ensure that they are valid semantically, and have correct types
     fn visit_mut_enforce(&mut self, expr: &mut ScalarExpr) -> ControlFlow<SemanticAnalysisError> {
         // Verify that constraints are permitted here
@@ -426,29 +645,165 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
         &mut self,
         expr: &mut ListComprehension,
     ) -> ControlFlow<SemanticAnalysisError> {
+        // Columns constrained under a selector are not known to be binary unconditionally
+        let binary_columns = expr.selector.as_ref().map(|_| self.binary_columns.clone());
+
         self.in_constraint_comprehension = true;
         let result = self.visit_mut_list_comprehension(expr);
         self.in_constraint_comprehension = false;
+        result?;
+
+        match binary_columns {
+            Some(binary_columns) => self.binary_columns = binary_columns,
+            None => self.mark_binary_iterable(expr),
+        }
+
+        ControlFlow::Continue(())
+    }
+
+    fn visit_mut_enforce_match(&mut self, expr: &mut Match) -> ControlFlow<SemanticAnalysisError> {
+        for arm in expr.arms.iter_mut() {
+            self.visit_mut_enforce_all(arm)?;
+        }
+
+        for arm in expr.arms.iter() {
+            if let Some(ref selector) = arm.selector {
+                self.validate_match_selector(selector);
+            }
+        }
+        self.check_match_cases(expr);
+
+        ControlFlow::Continue(())
+    }
+
+    fn visit_mut_enforce_if(
+        &mut self,
+        expr: &mut ScalarExpr,
+        selector: &mut ScalarExpr,
+    ) -> ControlFlow<SemanticAnalysisError> {
+        // Columns constrained under a selector are not known to be binary unconditionally
+        let binary_columns = self.binary_columns.clone();
+        self.visit_mut_enforce(expr)?;
+        self.visit_mut_scalar_expr(selector)?;
+        self.binary_columns = binary_columns;
+
+        ControlFlow::Continue(())
+    }
+
+    /// Debug statements are validated like the operands of an integrity constraint, but as they are
+    /// only lowered alongside integrity constraints, they are not permitted anywhere else
+    fn visit_mut_debug(&mut self, expr: &mut ScalarExpr) -> ControlFlow<SemanticAnalysisError> {
+        if !self.constraint_mode.is_integrity() {
+            self.diagnostics
+                .diagnostic(Severity::Error)
+                .with_message("invalid debug statement")
+                .with_primary_label(
+                    expr.span(),
+                    "debug statements are only permitted in integrity constraints",
+                )
+                .emit();
+            return ControlFlow::Break(SemanticAnalysisError::Invalid);
+        }
+
+        self.visit_mut_scalar_expr(expr)?;
 
-        result
+        match expr.ty() {
+            Ok(Some(ty)) if !ty.is_scalar() => {
+                self.has_type_errors = true;
+                self.diagnostics
+                    .diagnostic(Severity::Error)
+                    .with_message("invalid debug statement")
+                    .with_primary_label(
+                        expr.span(),
+                        format!("expected a scalar expression, but this has type {ty}"),
+                    )
+                    .emit();
+                ControlFlow::Break(SemanticAnalysisError::Invalid)
+            }
+            _ => ControlFlow::Continue(()),
+        }
     }
 
     fn visit_mut_let(&mut self, expr: &mut Let) -> ControlFlow<SemanticAnalysisError> {
+        // A `let` may only be left without a body at the end of an included file, when the
+        // statements following the include become its body
+        if expr.body.is_empty() {
+            self.diagnostics
+                .diagnostic(Severity::Error)
+                .with_message("missing statements after `let`")
+                .with_primary_label(
+                    expr.span(),
+                    "this binding must be followed by at least one statement in which it is in scope",
+                )
+                .with_note("A `let` at the end of an included file is followed by the statements after the `include`.")
+                .emit();
+            return ControlFlow::Break(SemanticAnalysisError::Invalid);
+        }
+
         // Visit the binding expression first
         self.visit_mut_expr(&mut expr.value)?;
 
         // Start new lexical scope for the body
         self.locals.enter();
 
-        // Check if the new binding shadows a previous local declaration
+        // Check if the new binding shadows a previous declaration
         let namespaced_name = NamespacedIdentifier::Binding(expr.name);
-        if let Some(prev) = self.locals.get_key(&namespaced_name) {
-            self.warn_declaration_shadowed(expr.name.span(), prev.span());
-        } else {
-            let binding_ty = self.expr_binding_type(&expr.value).unwrap();
-            self.locals
-                .insert(NamespacedIdentifier::Binding(expr.name), binding_ty);
+        if let Some(prev) = self.shadowed_declaration(&namespaced_name) {
+            self.warn_declaration_shadowed(expr.name.span(), prev);
+        }
+        let mut binding_ty = match self.expr_binding_type(&expr.value) {
+            Ok(binding_ty) => binding_ty,
+            // We've already raised a diagnostic for an invalid access in the bound value, so the
+            // binding takes on the type fabricated for that access, to keep gathering diagnostics
+            Err(_) => {
+                assert!(self.has_undefined_variables || self.has_type_errors);
+                BindingType::Local(expr.value.ty().unwrap_or(Type::Felt))
+            }
+        };
+        let is_binary = self.is_provably_binary(&expr.value);
+        match expr.annotation {
+            Some(annotation) => match expr.value.ty() {
+                Some(ty) if ty.is_aggregate() => {
+                    self.type_mismatch(
+                        Some(&ty),
+                        expr.value.span(),
+                        &annotation.item,
+                        annotation.span(),
+                        expr.span(),
+                    )?;
+                }
+                _ if annotation.is_binary() && !is_binary => {
+                    self.has_type_errors = true;
+                    self.diagnostics
+                        .diagnostic(Severity::Error)
+                        .with_message("type mismatch")
+                        .with_primary_label(
+                            expr.value.span(),
+                            "this expression is not known to be binary",
+                        )
+                        .with_secondary_label(
+                            annotation.span(),
+                            "but this annotation requires a binary value",
+                        )
+                        .with_note("Only the constants 0 and 1, logical expressions, and columns constrained by `enf is_binary(x)` are known to be binary")
+                        .emit();
+                }
+                // Scalar locals take on the annotated type, i.e. a binary value may be widened
+                // to a field element, and a constant 0 or 1 may be narrowed to a binary value
+                _ if matches!(binding_ty, BindingType::Local(ty) if ty.is_scalar()) => {
+                    binding_ty = BindingType::Local(annotation.item);
+                }
+                _ => (),
+            },
+            None if is_binary && binding_ty == BindingType::Local(Type::Felt) => {
+                binding_ty = BindingType::Local(Type::Binary);
+            }
+            None => (),
+        }
+        if is_binary {
+            self.binary_bindings.insert(expr.name.span());
         }
+        self.locals.insert(namespaced_name, binding_ty);
 
         // Visit the let body
         self.visit_mut_statement_block(&mut expr.body)?;
@@ -456,6 +811,11 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
         // Restore the original lexical scope
         self.locals.exit();
 
+        // Bindings which are intentionally unused can be prefixed with an underscore
+        if !self.used_bindings.remove(&expr.name.span()) && !expr.name.as_str().starts_with('_') {
+            self.warn_unused_binding(expr.name);
+        }
+
         ControlFlow::Continue(())
     }
 
@@ -494,11 +854,37 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
             bound.insert(binding);
 
             let iterable = &expr.iterables[i];
-            let iterable_ty = iterable.ty().unwrap();
+            // A comprehension produces one element per element of its iterables, which
+            // in the case of a matrix iterable, means one element per row of the matrix
+            let iterable_ty = match iterable.ty().unwrap() {
+                Type::Matrix(rows, _) => Type::Vector(rows),
+                ty => ty,
+            };
             if let Some(expected_ty) = result_ty.replace(iterable_ty) {
-                if expected_ty != iterable_ty {
+                if let (Type::Vector(expected_len), Type::Vector(len)) = (expected_ty, iterable_ty)
+                {
+                    if expected_len != len {
+                        self.has_type_errors = true;
+                        self.diagnostics
+                            .diagnostic(Severity::Error)
+                            .with_message("invalid comprehension")
+                            .with_primary_label(
+                                iterable.span(),
+                                format!("this iterable has {len} elements"),
+                            )
+                            .with_secondary_label(
+                                expr.iterables[0].span(),
+                                format!("but this iterable has {expected_len} elements"),
+                            )
+                            .with_secondary_label(
+                                expr.span(),
+                                "all iterables of this comprehension must have the same length",
+                            )
+                            .emit();
+                    }
+                } else if expected_ty != iterable_ty {
                     self.has_type_errors = true;
-                    self.type_mismatch(
+                    let _ = self.type_mismatch(
                         Some(&iterable_ty),
                         iterable.span(),
                         &expected_ty,
@@ -508,6 +894,11 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
                 }
             }
             match self.expr_binding_type(iterable) {
+                // The elements of a range are integers, so they may be used as exponents
+                Ok(_) if matches!(iterable, Expr::Range(_)) => {
+                    let binding_ty = BindingType::Local(Type::U32);
+                    binding_tys.push((binding, iterable.span(), Some(binding_ty)));
+                }
                 Ok(iterable_binding_ty) => {
                     let binding_ty = iterable_binding_ty
                         .access(AccessType::Index(0))
@@ -553,7 +944,10 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
             );
         }
 
-        // Visit the selector
+        // Visit the filter and selector
+        if let Some(filter) = expr.filter.as_mut() {
+            self.visit_mut_scalar_expr(filter)?;
+        }
         if let Some(selector) = expr.selector.as_mut() {
             self.visit_mut_scalar_expr(selector)?;
         }
@@ -575,6 +969,9 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
     }
 
     fn visit_mut_call(&mut self, expr: &mut Call) -> ControlFlow<SemanticAnalysisError> {
+        // Calls nested in the arguments of this call are never constraints
+        let is_constraint = core::mem::take(&mut self.in_constraint_call);
+
         // Ensure the callee exists, and resolve the type if possible
         self.visit_mut_resolvable_identifier(&mut expr.callee)?;
 
@@ -584,6 +981,21 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
             Ok(ref binding_ty) => {
                 let derived_from = binding_ty.span();
                 if let BindingType::Function(ref fty) = binding_ty.item {
+                    // Evaluators produce no value, so they may only be called as constraints
+                    if !is_constraint && matches!(fty, FunctionType::Evaluator(_)) {
+                        self.has_type_errors = true;
+                        self.diagnostics
+                            .diagnostic(Severity::Error)
+                            .with_message("invalid call")
+                            .with_primary_label(
+                                expr.span(),
+                                "evaluators produce no value, and may only be called as constraints",
+                            )
+                            .with_note("Calls to evaluators must be enforced directly, e.g. `enf evaluator(a)`")
+                            .emit();
+                        return ControlFlow::Break(SemanticAnalysisError::Invalid);
+                    }
+
                     // There must be an evaluator by this name
                     let qid = expr.callee.resolved().unwrap();
                     // Builtin functions are ignored here
@@ -596,8 +1008,10 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
                         if prev.is_some() {
                             assert_eq!(prev, Some(dependency_type));
                         }
-                        // TODO: When we have non-evaluator functions, we must fetch the type in its signature here,
-                        // and store it as the type of the Call expression
+                        // Calls to pure functions have the type of the function result
+                        if let Some(result) = fty.result() {
+                            expr.ty = Some(result);
+                        }
                     }
                 } else {
                     self.has_type_errors = true;
@@ -624,7 +1038,7 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
             self.visit_mut_expr(expr)?;
         }
 
-        // Validate arguments for builtin functions, which currently consist only of the sum/prod reducers
+        // Validate arguments for builtin functions, i.e. the sum/prod reducers and `is_binary`
         if expr.is_builtin() {
             self.validate_call_to_builtin(expr)?;
         }
@@ -634,10 +1048,18 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
         // * Must be trace bindings or aliases of same
         // * Must match the type signature of the callee
         if let Ok(ty) = callee_binding_ty {
-            if let BindingType::Function(FunctionType::Evaluator(ref params)) = ty.item {
-                for (arg, param) in expr.args.iter().zip(params.iter()) {
-                    self.validate_evaluator_argument(expr.span(), arg, param)?;
+            match ty.item {
+                BindingType::Function(FunctionType::Evaluator(ref params)) => {
+                    for (arg, param) in expr.args.iter().zip(params.iter()) {
+                        self.validate_evaluator_argument(expr.span(), arg, param)?;
+                    }
+                }
+                BindingType::Function(FunctionType::Function(ref params, _))
+                    if !expr.is_builtin() =>
+                {
+                    self.validate_function_arguments(expr, params.as_slice(), ty.span())?;
                 }
+                _ => (),
             }
         }
 
@@ -651,11 +1073,26 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
         self.visit_mut_scalar_expr(expr.lhs.as_mut())?;
         self.visit_mut_scalar_expr(expr.rhs.as_mut())?;
 
+        // Exponents must be integers
+        if expr.op == BinaryOp::Exp {
+            self.validate_exponent(expr.rhs.as_ref())?;
+        }
+
+        // Logical operators are only valid over binary values
+        if expr.op.is_logical() {
+            // The left-hand operand of a negation is always the constant 1
+            if expr.op != BinaryOp::Not {
+                self.validate_logical_operand(expr.op, expr.lhs.as_ref(), expr.span());
+            }
+            self.validate_logical_operand(expr.op, expr.rhs.as_ref(), expr.span());
+        }
+
         // Validate the operand types
         match (expr.lhs.ty(), expr.rhs.ty()) {
             (Ok(Some(lty)), Ok(Some(rty))) => {
-                if lty != rty {
-                    self.type_mismatch(
+                // Binary values are field elements, so any two scalars are compatible
+                if lty != rty && !(lty.is_scalar() && rty.is_scalar()) {
+                    let _ = self.type_mismatch(
                         Some(&lty),
                         expr.lhs.span(),
                         &rty,
@@ -692,6 +1129,7 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
         expr: &mut SymbolAccess,
     ) -> ControlFlow<SemanticAnalysisError> {
         self.visit_mut_resolvable_identifier(&mut expr.name)?;
+        self.resolve_public_input_binding(expr);
 
         let resolved_binding_ty = match self.resolvable_binding_type(&expr.name) {
             Ok(ty) => ty,
@@ -706,21 +1144,34 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
         // Check if:
         //
         // * This is an invalid trace access with offset in a boundary constraint
-        // * This is an invalid periodic column access in a boundary constraint
         // * This is an invalid public input access in an integrity constraint
+        //
+        // Periodic columns may be accessed in boundary constraints, in which case the value of the
+        // column at the constrained row is substituted when the constraint is lowered
         match &resolved_binding_ty.item {
-            BindingType::TraceColumn(_) | BindingType::TraceParam(_) => {
-                if self.constraint_mode.is_boundary() && expr.offset > 0 {
-                    self.has_type_errors = true;
-                    self.diagnostics.diagnostic(Severity::Error)
+            ty @ (BindingType::TraceColumn(_)
+            | BindingType::PublicInput(_)
+            | BindingType::RandomValue(_)
+            | BindingType::PeriodicColumn(_))
+                if self.in_function =>
+            {
+                self.has_type_errors = true;
+                self.diagnostics
+                    .diagnostic(Severity::Error)
+                    .with_message("invalid access")
+                    .with_primary_label(expr.span(), format!("cannot access {ty} here"))
+                    .with_note("Functions may only reference their parameters, constants, and other functions. Pass the value as an argument instead.")
+                    .emit();
+            }
+            BindingType::TraceColumn(_) | BindingType::TraceParam(_)
+                if self.constraint_mode.is_boundary() && expr.offset > 0 =>
+            {
+                self.has_type_errors = true;
+                self.diagnostics.diagnostic(Severity::Error)
                         .with_message("invalid expression")
                         .with_primary_label(expr.span(), "invalid access of a trace column with offset")
                         .with_note("It is not allowed to access trace columns with an offset in boundary constraints.")
                         .emit();
-                }
-            }
-            ty @ BindingType::PeriodicColumn(_) if self.constraint_mode.is_boundary() => {
-                self.invalid_access_in_constraint(expr.span(), ty);
             }
             ty @ BindingType::PublicInput(_) if self.constraint_mode.is_integrity() => {
                 self.invalid_access_in_constraint(expr.span(), ty);
@@ -762,16 +1213,76 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
                         assert_eq!(expr.ty.replace(Type::Vector(range.end - range.start)), None)
                     }
                     // All other access types can be derived from the binding type
-                    _ => assert_eq!(expr.ty.replace(binding_ty.ty().unwrap()), None),
+                    _ => {
+                        let ty = match binding_ty {
+                            BindingType::TraceColumn(ref tb) | BindingType::TraceParam(ref tb)
+                                if self.is_binary_column(&expr.name, tb) =>
+                            {
+                                Type::Binary
+                            }
+                            _ => binding_ty.ty().unwrap(),
+                        };
+                        assert_eq!(expr.ty.replace(ty), None)
+                    }
                 }
                 ControlFlow::Continue(())
             }
             Err(err) => {
                 self.has_type_errors = true;
+                let label = match (&resolved_binding_ty, &expr.access_type, err) {
+                    // Out of bounds accesses of trace columns are a common mistake, so we make
+                    // sure to tell the user how many columns there actually are
+                    (
+                        BindingType::TraceColumn(tb) | BindingType::TraceParam(tb),
+                        access_type,
+                        InvalidAccessError::IndexOutOfBounds,
+                    ) => {
+                        let name = expr.name.as_ref();
+                        let kind = if name.as_str().starts_with('$') {
+                            "trace segment"
+                        } else {
+                            "column group"
+                        };
+                        let accessed = match access_type {
+                            AccessType::Slice(range) => {
+                                format!("the range {}..{}", range.start, range.end)
+                            }
+                            AccessType::Index(idx) => format!("index {idx}"),
+                            _ => unreachable!(),
+                        };
+                        format!(
+                            "{kind} `{name}` has size {}, but {accessed} was accessed",
+                            tb.size
+                        )
+                    }
+                    // Likewise for constants, whose shape is known at compile time
+                    (
+                        BindingType::Constant(ty),
+                        access_type,
+                        InvalidAccessError::IndexOutOfBounds,
+                    ) => {
+                        let name = expr.name.as_ref();
+                        let shape = match ty {
+                            Type::Vector(len) => format!("has {len} elements"),
+                            Type::Matrix(rows, cols) => format!("is a {rows}x{cols} matrix"),
+                            _ => unreachable!(),
+                        };
+                        let accessed = match access_type {
+                            AccessType::Slice(range) => {
+                                format!("the range {}..{}", range.start, range.end)
+                            }
+                            AccessType::Index(idx) => format!("index {idx}"),
+                            AccessType::Matrix(row, col) => format!("index [{row}][{col}]"),
+                            AccessType::Default => unreachable!(),
+                        };
+                        format!("constant `{name}` {shape}, but {accessed} was accessed")
+                    }
+                    (_, _, err) => err.to_string(),
+                };
                 self.diagnostics
                     .diagnostic(Severity::Error)
                     .with_message("invalid variable access")
-                    .with_primary_label(expr.span(), err.to_string())
+                    .with_primary_label(expr.span(), label)
                     .with_secondary_label(derived_from, "references this declaration")
                     .emit();
                 // Continue with a fabricated type
@@ -805,9 +1316,16 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
                 ControlFlow::Continue(())
             }
             ResolvableIdentifier::Unresolved(namespaced_id) => {
+                // If this is a positional reference to a parameter, e.g. `$0`, resolve it as if the
+                // parameter had been referenced by name. If invalid, we've already raised a diagnostic
+                let namespaced_id = match self.resolve_positional_param(*namespaced_id) {
+                    Ok(namespaced_id) => namespaced_id,
+                    Err(_) => return ControlFlow::Continue(()),
+                };
+
                 // If locally defined, resolve it to the current module
-                let namespaced_id = *namespaced_id;
-                if let Some(binding_ty) = self.locals.get(&namespaced_id) {
+                if let Some((binding, binding_ty)) = self.locals.get_key_value(&namespaced_id) {
+                    self.used_bindings.insert(binding.span());
                     match binding_ty {
                         // This identifier is a local variable, alias to a declaration, or a function parameter
                         BindingType::Alias(_)
@@ -842,39 +1360,44 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
                 }
 
                 // If imported, resolve it to the imported module, and add it to the referenced set
-                if let Some((imported_id, imported_from)) =
-                    self.imported.get_key_value(&namespaced_id)
-                {
-                    let qualified_id = QualifiedIdentifier::new(*imported_from, *imported_id);
-                    *expr = ResolvableIdentifier::Resolved(qualified_id);
+                if let Some(qualified_id) = self.imported.get(&namespaced_id) {
+                    *expr = ResolvableIdentifier::Resolved(*qualified_id);
 
                     return ControlFlow::Continue(());
                 }
 
+                // If no function by this name is declared or imported, it may refer to a builtin
+                // which is not handled by the parser, i.e. `is_binary`
+                if let NamespacedIdentifier::Function(id) = namespaced_id {
+                    if id.name() == symbols::IsBinary {
+                        *expr = ResolvableIdentifier::Resolved(QualifiedIdentifier::builtin(id));
+                        return ControlFlow::Continue(());
+                    }
+                }
+
                 // If we reach here, we were unable to resolve this identifier, raise a diagnostic
                 self.has_undefined_variables = true;
-                match namespaced_id {
-                    NamespacedIdentifier::Function(_) => {
-                        self.diagnostics
-                            .diagnostic(Severity::Error)
-                            .with_message("reference to undefined function")
-                            .with_primary_label(
-                                namespaced_id.span(),
-                                "no function by this name is declared in scope",
-                            )
-                            .emit();
-                    }
-                    NamespacedIdentifier::Binding(_) => {
-                        self.diagnostics
-                            .diagnostic(Severity::Error)
-                            .with_message("reference to undefined variable")
-                            .with_primary_label(
-                                namespaced_id.span(),
-                                "this variable is not defined",
-                            )
-                            .emit();
-                    }
+                let (message, label) = match namespaced_id {
+                    NamespacedIdentifier::Function(_) => (
+                        "reference to undefined function",
+                        "no function by this name is declared in scope",
+                    ),
+                    NamespacedIdentifier::Binding(_) => (
+                        "reference to undefined variable",
+                        "this variable is not defined",
+                    ),
+                };
+                let mut diagnostic = self
+                    .diagnostics
+                    .diagnostic(Severity::Error)
+                    .with_message(message)
+                    .with_primary_label(namespaced_id.span(), label);
+                if let Some(suggestion) = self.suggest_name(&namespaced_id) {
+                    diagnostic = diagnostic.with_note(format!("did you mean `{suggestion}`?"));
                 }
+                diagnostic
+                    .with_note(self.searched_scopes(&namespaced_id))
+                    .emit();
 
                 ControlFlow::Continue(())
             }
@@ -883,22 +1406,125 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
 }
 
 impl<'a> SemanticAnalysis<'a> {
-    /// Validate arguments for builtin functions, which currently consist only of the sum/prod reducers
-    fn validate_call_to_builtin(&mut self, call: &Call) -> ControlFlow<SemanticAnalysisError> {
-        match call.callee.as_ref().name() {
-            // The known reducers - each takes a single argument, which must be an aggregate or comprehension
-            symbols::Sum | symbols::Prod => {
-                match call.args.as_slice() {
-                    [arg] => {
-                        match self.expr_binding_type(arg) {
-                            Ok(binding_ty) => {
-                                if !binding_ty.ty().map(|t| t.is_aggregate()).unwrap_or(false) {
-                                    self.has_type_errors = true;
-                                    self.diagnostics
-                                        .diagnostic(Severity::Error)
-                                        .with_message("invalid call")
-                                        .with_primary_label(
-                                            call.span(),
+    /// Returns the name in scope which is the closest match for the unresolved identifier `id`,
+    /// if any is close enough to plausibly be what was meant
+    fn suggest_name(&self, id: &NamespacedIdentifier) -> Option<Symbol> {
+        let is_function = matches!(id, NamespacedIdentifier::Function(_));
+        let same_namespace = |nid: &&NamespacedIdentifier| {
+            matches!(nid, NamespacedIdentifier::Function(_)) == is_function
+        };
+        let mut candidates = self
+            .locals
+            .keys()
+            .filter(same_namespace)
+            .chain(self.imported.keys().filter(same_namespace))
+            .map(|nid| nid.id().name())
+            .collect::<Vec<_>>();
+        if !is_function {
+            candidates.extend(self.globals.keys().map(|id| id.name()));
+        }
+
+        let name = id.id().name();
+        let max_distance = core::cmp::max(1, name.as_str().len() / 3);
+        candidates
+            .into_iter()
+            .filter(|candidate| *candidate != name)
+            .map(|candidate| (edit_distance(name.as_str(), candidate.as_str()), candidate))
+            .filter(|(distance, _)| *distance <= max_distance)
+            // Ties are broken by name, so that the suggestion does not depend on hash order
+            .min_by(|(a, x), (b, y)| a.cmp(b).then_with(|| x.as_str().cmp(y.as_str())))
+            .map(|(_, candidate)| candidate)
+    }
+
+    /// Describes the scopes which were searched when resolving the identifier `id`
+    fn searched_scopes(&self, id: &NamespacedIdentifier) -> String {
+        let is_function = matches!(id, NamespacedIdentifier::Function(_));
+        let mut scopes = vec!["the local scope".to_string()];
+        if !is_function && !self.globals.is_empty() {
+            scopes.push("the random values of the root module".to_string());
+        }
+        let mut modules = self
+            .imported
+            .iter()
+            .filter(|(nid, _)| matches!(nid, NamespacedIdentifier::Function(_)) == is_function)
+            .map(|(_, qid)| format!("`{}`", qid.module))
+            .collect::<Vec<_>>();
+        modules.sort();
+        modules.dedup();
+        if !modules.is_empty() {
+            scopes.push(format!("the items imported from {}", modules.join(", ")));
+        }
+        match scopes.split_last() {
+            Some((last, rest)) if !rest.is_empty() => {
+                format!("Searched {}, and {}", rest.join(", "), last)
+            }
+            _ => format!("Searched {}", scopes[0]),
+        }
+    }
+
+    /// If `id` is a positional parameter reference, e.g. `$1`, returns the identifier of the
+    /// corresponding parameter of the current evaluator or function, using the span of `id`.
+    ///
+    /// Any other identifier is returned unchanged. An error is returned if the reference is out
+    /// of range, or appears outside of an evaluator or function body.
+    fn resolve_positional_param(
+        &mut self,
+        id: NamespacedIdentifier,
+    ) -> Result<NamespacedIdentifier, SemanticAnalysisError> {
+        let NamespacedIdentifier::Binding(name) = id else {
+            return Ok(id);
+        };
+        let Some(index) = name
+            .as_str()
+            .strip_prefix('$')
+            .and_then(|index| index.parse::<usize>().ok())
+        else {
+            return Ok(id);
+        };
+
+        match self.params.get(index) {
+            Some(param) => Ok(NamespacedIdentifier::Binding(Identifier::new(
+                name.span(),
+                param.name(),
+            ))),
+            None => {
+                self.has_undefined_variables = true;
+                let label = if self.params.is_empty() {
+                    "positional parameter references are only valid in evaluator and function bodies".to_string()
+                } else {
+                    format!(
+                        "there are only {} parameters in scope, but this refers to parameter {}",
+                        self.params.len(),
+                        index
+                    )
+                };
+                self.diagnostics
+                    .diagnostic(Severity::Error)
+                    .with_message("invalid parameter reference")
+                    .with_primary_label(name.span(), label)
+                    .with_note("Parameters are numbered from zero, in the order they are declared.")
+                    .emit();
+                Err(SemanticAnalysisError::Invalid)
+            }
+        }
+    }
+
+    /// Validate arguments for builtin functions, i.e. the sum/prod/min/max reducers, `is_binary` and `felt`
+    fn validate_call_to_builtin(&mut self, call: &Call) -> ControlFlow<SemanticAnalysisError> {
+        match call.callee.as_ref().name() {
+            // The known reducers - each takes a single argument, which must be an aggregate or comprehension
+            symbols::Sum | symbols::Prod | symbols::Min | symbols::Max => {
+                match call.args.as_slice() {
+                    [arg] => {
+                        match self.expr_binding_type(arg) {
+                            Ok(binding_ty) => {
+                                if !binding_ty.ty().map(|t| t.is_aggregate()).unwrap_or(false) {
+                                    self.has_type_errors = true;
+                                    self.diagnostics
+                                        .diagnostic(Severity::Error)
+                                        .with_message("invalid call")
+                                        .with_primary_label(
+                                            call.span(),
                                             "this function expects an argument of aggregate type",
                                         )
                                         .with_secondary_label(
@@ -930,6 +1556,46 @@ impl<'a> SemanticAnalysis<'a> {
                     }
                 }
             }
+            // The binary constraint and casts to field elements take a single argument, which must be a scalar
+            symbols::IsBinary | symbols::Felt => match call.args.as_slice() {
+                [arg] => match self.expr_binding_type(arg) {
+                    Ok(binding_ty) => {
+                        if !binding_ty.ty().map(|t| t.is_scalar()).unwrap_or(false) {
+                            self.has_type_errors = true;
+                            self.diagnostics
+                                .diagnostic(Severity::Error)
+                                .with_message("invalid call")
+                                .with_primary_label(
+                                    call.span(),
+                                    "this function expects a single field element argument",
+                                )
+                                .with_secondary_label(
+                                    arg.span(),
+                                    "but this argument is of aggregate type",
+                                )
+                                .emit();
+                        }
+                    }
+                    Err(_) => {
+                        // We've already raised a diagnostic for this when visiting the access expression
+                        assert!(self.has_undefined_variables || self.has_type_errors);
+                    }
+                },
+                _ => {
+                    self.has_type_errors = true;
+                    self.diagnostics
+                        .diagnostic(Severity::Error)
+                        .with_message("invalid call")
+                        .with_primary_label(
+                            call.span(),
+                            format!(
+                                "the callee expects a single argument, but got {}",
+                                call.args.len()
+                            ),
+                        )
+                        .emit();
+                }
+            },
             other => unimplemented!("unrecognized builtin function: {}", other),
         }
         ControlFlow::Continue(())
@@ -951,8 +1617,8 @@ impl<'a> SemanticAnalysis<'a> {
                             // we must make sure the segments also match
                             let same_segment = tr.segment == param.id;
                             if !same_segment {
-                                let expected_segment = segment_id_to_name(param.id);
-                                let segment_name = segment_id_to_name(tr.segment);
+                                let expected_segment = self.segment_name(param.id);
+                                let segment_name = self.segment_name(tr.segment);
                                 self.has_type_errors = true;
                                 self.diagnostics
                                     .diagnostic(Severity::Error)
@@ -990,8 +1656,8 @@ impl<'a> SemanticAnalysis<'a> {
                                     if tr.segment == param.id {
                                         size += tr.size;
                                     } else {
-                                        let expected_segment = segment_id_to_name(param.id);
-                                        let segment_name = segment_id_to_name(tr.segment);
+                                        let expected_segment = self.segment_name(param.id);
+                                        let segment_name = self.segment_name(tr.segment);
                                         self.has_type_errors = true;
                                         self.diagnostics
                                             .diagnostic(Severity::Error)
@@ -1035,7 +1701,7 @@ impl<'a> SemanticAnalysis<'a> {
 
                         if size != param.size {
                             self.has_type_errors = true;
-                            self.type_mismatch(
+                            let _ = self.type_mismatch(
                                 Some(&Type::Vector(param.size)),
                                 arg.span(),
                                 &Type::Vector(size),
@@ -1054,7 +1720,7 @@ impl<'a> SemanticAnalysis<'a> {
                             param.size,
                             Type::Vector(param.size),
                         ));
-                        self.binding_mismatch(
+                        let _ = self.binding_mismatch(
                             &binding_ty,
                             arg.span(),
                             &expected,
@@ -1079,8 +1745,8 @@ impl<'a> SemanticAnalysis<'a> {
                             if tr.segment == param.id {
                                 size += tr.size;
                             } else {
-                                let expected_segment = segment_id_to_name(param.id);
-                                let segment_name = segment_id_to_name(tr.segment);
+                                let expected_segment = self.segment_name(param.id);
+                                let segment_name = self.segment_name(tr.segment);
                                 self.has_type_errors = true;
                                 self.diagnostics
                                     .diagnostic(Severity::Error)
@@ -1261,14 +1927,11 @@ impl<'a> SemanticAnalysis<'a> {
                     }
                 }
             }
-            ScalarExpr::Call(ref expr) => {
-                self.invalid_constraint(expr.span(), "expected an equality expression here")
-                    .with_note(
-                        "Calls to evaluator functions are only permitted in integrity constraints",
-                    )
-                    .emit();
-                ControlFlow::Break(SemanticAnalysisError::Invalid)
-            }
+            // Evaluators may be called in boundary constraints, as long as the boundary at which
+            // they are applied is given, or the evaluator itself consists of boundary constraints.
+            // Whether the body of the evaluator is valid at a boundary is checked when the call is
+            // inlined.
+            ScalarExpr::Call(ref mut expr) => self.visit_mut_evaluator_call(expr),
             expr => {
                 self.invalid_constraint(expr.span(), "expected an equality expression here")
                     .with_note(
@@ -1297,62 +1960,252 @@ impl<'a> SemanticAnalysis<'a> {
                 self.visit_mut_binary_expr(expr)
             }
             ScalarExpr::Call(ref mut expr) => {
-                // Visit the call normally, so we can resolve the callee identifier
-                self.visit_mut_call(expr)?;
-
-                // Check that the call references an evaluator
-                //
-                // If unresolved, we've already raised a diagnostic for the invalid call
-                match expr.callee {
-                    ResolvableIdentifier::Resolved(callee) => {
-                        match callee.id() {
-                            id @ NamespacedIdentifier::Function(_) => {
-                                match self.locals.get_key_value(&id) {
-                                    // Binding is to a local evaluator
-                                    Some((_, BindingType::Function(FunctionType::Evaluator(_)))) => ControlFlow::Continue(()),
-                                    // Binding is to a local non-evaluator function
-                                    Some((local_name, _)) => {
-                                        self.invalid_constraint(id.span(), "calls in constraints must be to evaluator functions")
-                                            .with_secondary_label(local_name.span(), "this function is not an evaluator")
-                                            .emit();
-                                        ControlFlow::Break(SemanticAnalysisError::Invalid)
-                                    }
-                                    None => {
-                                        // If the call was resolved, it must be to an imported function,
-                                        // and we will have already validated the reference
-                                        let (import_id, module_id) = self.imported.get_key_value(&id).unwrap();
-                                        let module = self.library.get(module_id).unwrap();
-                                        if module.evaluators.get(&id.id()).is_none() {
-                                            self.invalid_constraint(id.span(), "calls in constraints must be to evaluator functions")
-                                                .with_secondary_label(import_id.span(), "the function imported here is not an evaluator")
-                                                .emit();
-                                            return ControlFlow::Break(SemanticAnalysisError::Invalid);
-                                        }
-                                        ControlFlow::Continue(())
-                                    }
+                if let Some(boundary) = expr.boundary {
+                    self.invalid_constraint(expr.span(), format!("evaluators cannot be applied to the {boundary} row here"))
+                        .with_note("Evaluator calls may only be applied at a boundary in boundary constraints, e.g. `enf evaluator(a).first`")
+                        .emit();
+                    return ControlFlow::Break(SemanticAnalysisError::Invalid);
+                }
+                self.visit_mut_evaluator_call(expr)
+            }
+            expr => {
+                self.invalid_constraint(expr.span(), "expected either an equality expression, or a call to an evaluator here")
+                    .with_note("Integrity constraints must be expressed as an equality, e.g. `a = 0`, or a call, e.g. `evaluator(a)`")
+                    .emit();
+                ControlFlow::Break(SemanticAnalysisError::Invalid)
+            }
+        }
+    }
+
+    /// Visits a call used as a constraint, and ensures that the callee is an evaluator function
+    fn visit_mut_evaluator_call(&mut self, expr: &mut Call) -> ControlFlow<SemanticAnalysisError> {
+        // Visit the call normally, so we can resolve the callee identifier
+        self.in_constraint_call = true;
+        self.visit_mut_call(expr)?;
+
+        // Check that the call references an evaluator
+        //
+        // If unresolved, we've already raised a diagnostic for the invalid call
+        match expr.callee {
+            // The only builtin which may be used as a constraint is `is_binary`
+            ResolvableIdentifier::Resolved(callee) if callee.is_builtin() => {
+                if callee.name() == symbols::IsBinary {
+                    if let Some(boundary) = expr.boundary {
+                        self.invalid_constraint(expr.span(), format!("is_binary cannot be applied to the {boundary} row"))
+                            .with_note("Constrain the value of the column at the boundary directly instead, e.g. `enf a.first = 0`")
+                            .emit();
+                        return ControlFlow::Break(SemanticAnalysisError::Invalid);
+                    }
+                    if self.constraint_mode.is_boundary() {
+                        return self.missing_call_boundary(expr.span());
+                    }
+                    if let Some(Ok(BindingType::TraceColumn(tb) | BindingType::TraceParam(tb))) =
+                        expr.args.first().map(|arg| self.expr_binding_type(arg))
+                    {
+                        self.binary_columns.insert((tb.segment, tb.offset));
+                    }
+                    ControlFlow::Continue(())
+                } else {
+                    self.invalid_constraint(callee.span(), "calls in constraints must be to evaluator functions")
+                        .emit();
+                    ControlFlow::Break(SemanticAnalysisError::Invalid)
+                }
+            }
+            ResolvableIdentifier::Resolved(callee) => {
+                match callee.id() {
+                    id @ NamespacedIdentifier::Function(_) => {
+                        match self.locals.get_key_value(&id) {
+                            // Binding is to a local evaluator
+                            Some((_, BindingType::Function(FunctionType::Evaluator(_)))) => {
+                                self.validate_evaluator_call_context(expr, callee)
+                            }
+                            // Binding is to a local non-evaluator function
+                            Some((local_name, _)) => {
+                                self.invalid_constraint(id.span(), "calls in constraints must be to evaluator functions")
+                                    .with_secondary_label(local_name.span(), "this function is not an evaluator")
+                                    .emit();
+                                ControlFlow::Break(SemanticAnalysisError::Invalid)
+                            }
+                            None => {
+                                // If the call was resolved, it must be to an imported function,
+                                // and we will have already validated the reference
+                                // (which may have been imported under a different name)
+                                let (import_id, _) = self.imported.iter().find(|(_, qid)| **qid == callee).unwrap();
+                                let module = self.library.get(&callee.module).unwrap();
+                                if !module.evaluators.contains_key(callee.as_ref()) {
+                                    self.invalid_constraint(id.span(), "calls in constraints must be to evaluator functions")
+                                        .with_secondary_label(import_id.span(), "the function imported here is not an evaluator")
+                                        .emit();
+                                    return ControlFlow::Break(SemanticAnalysisError::Invalid);
                                 }
+                                self.validate_evaluator_call_context(expr, callee)
                             }
-                            // We take care to only allow constructing Call with a function identifier, but it
-                            // is possible for someone to unintentionally set the callee to a binding identifer, which is
-                            // a compiler internal error, hence the panic
-                            id => panic!("invalid callee identifier, expected function id, got binding: {:#?}", id),
                         }
                     }
-                    ResolvableIdentifier::Local(id) => {
-                        self.invalid_callee(id.span(), "local variables", "A local binding with this name is in scope, but no such function is declared in this module. Are you missing an import?")
-                    }
-                    ResolvableIdentifier::Global(id) => {
-                        self.invalid_callee(id.span(), "global declarations", "A global declaration with this name is in scope, but no such function is declared in this module. Are you missing an import?")
-                    }
-                    ResolvableIdentifier::Unresolved(_) => ControlFlow::Continue(()),
+                    // We take care to only allow constructing Call with a function identifier, but it
+                    // is possible for someone to unintentionally set the callee to a binding identifer, which is
+                    // a compiler internal error, hence the panic
+                    id => panic!("invalid callee identifier, expected function id, got binding: {:#?}", id),
                 }
             }
-            expr => {
-                self.invalid_constraint(expr.span(), "expected either an equality expression, or a call to an evaluator here")
-                    .with_note("Integrity constraints must be expressed as an equality, e.g. `a = 0`, or a call, e.g. `evaluator(a)`")
+            ResolvableIdentifier::Local(id) => {
+                self.invalid_callee(id.span(), "local variables", "A local binding with this name is in scope, but no such function is declared in this module. Are you missing an import?")
+            }
+            ResolvableIdentifier::Global(id) => {
+                self.invalid_callee(id.span(), "global declarations", "A global declaration with this name is in scope, but no such function is declared in this module. Are you missing an import?")
+            }
+            ResolvableIdentifier::Unresolved(_) => ControlFlow::Continue(()),
+        }
+    }
+
+    /// Returns true if `callee` is an evaluator whose body consists of boundary constraints
+    fn is_boundary_evaluator(&self, callee: QualifiedIdentifier) -> bool {
+        if Some(callee.module) == self.current_module {
+            return self.boundary_evaluators.contains(&callee.id().id());
+        }
+        self.library
+            .get(&callee.module)
+            .and_then(|module| module.evaluators.get(callee.as_ref()))
+            .map(|evaluator| evaluator.is_boundary())
+            .unwrap_or(false)
+    }
+
+    /// Validates that a call to the evaluator `callee` is valid in the current constraint mode
+    ///
+    /// Evaluators consisting of boundary constraints may only be called in a boundary context, and
+    /// without a boundary, while other evaluators called in a boundary context must specify the
+    /// boundary they are applied to.
+    fn validate_evaluator_call_context(
+        &mut self,
+        call: &Call,
+        callee: QualifiedIdentifier,
+    ) -> ControlFlow<SemanticAnalysisError> {
+        let is_boundary_evaluator = self.is_boundary_evaluator(callee);
+        match (self.constraint_mode, call.boundary) {
+            (ConstraintMode::Boundary, Some(boundary)) if is_boundary_evaluator => {
+                self.invalid_constraint(call.span(), format!("this evaluator cannot be applied to the {boundary} row"))
+                    .with_note("The body of this evaluator consists of boundary constraints, so it must be called without a boundary, e.g. `enf evaluator(a)`")
+                    .emit();
+                ControlFlow::Break(SemanticAnalysisError::Invalid)
+            }
+            (ConstraintMode::Boundary, None) if !is_boundary_evaluator => {
+                self.missing_call_boundary(call.span())
+            }
+            (ConstraintMode::Integrity, _) if is_boundary_evaluator => {
+                self.invalid_constraint(call.span(), "this evaluator enforces boundary constraints, which are not permitted here")
+                    .with_note("Evaluators whose bodies consist of boundary constraints may only be called from the `boundary_constraints` section, or from other such evaluators")
                     .emit();
                 ControlFlow::Break(SemanticAnalysisError::Invalid)
             }
+            _ => ControlFlow::Continue(()),
+        }
+    }
+
+    fn missing_call_boundary(&self, span: SourceSpan) -> ControlFlow<SemanticAnalysisError> {
+        self.invalid_constraint(span, "expected an equality expression here")
+            .with_note(
+                "Calls to evaluator functions in boundary constraints must specify the boundary they apply to, e.g. `evaluator(a).first`",
+            )
+            .emit();
+        ControlFlow::Break(SemanticAnalysisError::Invalid)
+    }
+
+    /// Validates the arguments of a call to a pure function against the function signature
+    fn validate_function_arguments(
+        &mut self,
+        call: &Call,
+        params: &[Type],
+        declared: SourceSpan,
+    ) -> ControlFlow<SemanticAnalysisError> {
+        if call.args.len() != params.len() {
+            self.has_type_errors = true;
+            self.diagnostics
+                .diagnostic(Severity::Error)
+                .with_message("invalid call")
+                .with_primary_label(
+                    call.span(),
+                    format!(
+                        "this function expects {} arguments, but {} were given",
+                        params.len(),
+                        call.args.len()
+                    ),
+                )
+                .with_secondary_label(declared, "the function is declared here")
+                .emit();
+            return ControlFlow::Break(SemanticAnalysisError::Invalid);
+        }
+
+        for (arg, param) in call.args.iter().zip(params.iter()) {
+            let is_valid = match arg.ty() {
+                Some(Type::Felt) if param.is_binary() => self.is_provably_binary(arg),
+                Some(ty) if ty.is_scalar() => param.is_scalar(),
+                Some(ty) => ty == *param,
+                // We've already raised a diagnostic for the invalid argument
+                None => true,
+            };
+            if !is_valid {
+                self.has_type_errors = true;
+                let label = match arg.ty() {
+                    Some(Type::Felt) if param.is_binary() => {
+                        "this argument is not known to be binary".to_string()
+                    }
+                    Some(ty) => format!("this argument has type {}", ty),
+                    None => unreachable!(),
+                };
+                self.diagnostics
+                    .diagnostic(Severity::Error)
+                    .with_message("type mismatch")
+                    .with_primary_label(arg.span(), label)
+                    .with_secondary_label(
+                        declared,
+                        format!("but the corresponding parameter is a {}", param),
+                    )
+                    .emit();
+            }
+        }
+
+        ControlFlow::Continue(())
+    }
+
+    /// Raises an error for each function of `module` which calls itself, directly or indirectly
+    ///
+    /// Functions are inlined at their call sites, which is impossible for recursive functions.
+    fn check_recursive_functions(&self, module: &Module) -> Result<(), SemanticAnalysisError> {
+        use petgraph::{algo::has_path_connecting, Direction};
+
+        let mut is_valid = true;
+        for function in module.functions.values() {
+            let id = QualifiedIdentifier::new(
+                module.name,
+                NamespacedIdentifier::Function(function.name),
+            );
+            if !self.deps.contains_node(id) {
+                continue;
+            }
+            let is_recursive = self
+                .deps
+                .neighbors_directed(id, Direction::Outgoing)
+                .any(|callee| has_path_connecting(&*self.deps, callee, id, None));
+            if is_recursive {
+                is_valid = false;
+                self.diagnostics
+                    .diagnostic(Severity::Error)
+                    .with_message("recursive function")
+                    .with_primary_label(
+                        function.name.span(),
+                        "this function calls itself, directly or indirectly",
+                    )
+                    .with_note(
+                        "Functions are inlined at their call sites, so they may not be recursive",
+                    )
+                    .emit();
+            }
+        }
+
+        if is_valid {
+            Ok(())
+        } else {
+            Err(SemanticAnalysisError::Invalid)
         }
     }
 
@@ -1370,6 +2223,24 @@ impl<'a> SemanticAnalysis<'a> {
         ControlFlow::Break(SemanticAnalysisError::NameConflict(decl))
     }
 
+    /// Returns the span of the declaration which would be shadowed by a new local binding of `name`,
+    /// if one is visible from the current scope, whether it be a local, imported, or global declaration.
+    fn shadowed_declaration(&self, name: &NamespacedIdentifier) -> Option<SourceSpan> {
+        self.locals
+            .get_key(name)
+            .map(|prev| prev.span())
+            .or_else(|| {
+                self.globals
+                    .get_key_value(&name.id())
+                    .map(|(prev, _)| prev.span())
+            })
+            .or_else(|| {
+                self.imported
+                    .get_key_value(name)
+                    .map(|(prev, _)| prev.span())
+            })
+    }
+
     fn warn_declaration_shadowed(&self, decl: SourceSpan, shadowed: SourceSpan) {
         self.diagnostics
             .diagnostic(Severity::Warning)
@@ -1379,6 +2250,17 @@ impl<'a> SemanticAnalysis<'a> {
             .emit();
     }
 
+    fn warn_unused_binding(&self, name: Identifier) {
+        self.diagnostics
+            .diagnostic(Severity::Warning)
+            .with_message("unused variable")
+            .with_primary_label(name.span(), "this binding is never used")
+            .with_note(format!(
+                "If this is intentional, prefix it with an underscore: `_{name}`"
+            ))
+            .emit();
+    }
+
     fn invalid_callee(
         &self,
         span: SourceSpan,
@@ -1394,6 +2276,99 @@ impl<'a> SemanticAnalysis<'a> {
         ControlFlow::Break(SemanticAnalysisError::Invalid)
     }
 
+    /// Returns the comprehension equivalent to `expr`, if it constrains a vector to be equal to
+    /// another vector, e.g. `enf c.first = C`, where `c` is a group of trace columns and `C` is a
+    /// constant vector.
+    ///
+    /// Each side of the constraint becomes an iterable of the comprehension, without its row offset,
+    /// which is instead applied to the element bound to it on each iteration.
+    fn splice_vector_constraint(
+        &mut self,
+        expr: &ScalarExpr,
+    ) -> ControlFlow<SemanticAnalysisError, Option<ListComprehension>> {
+        if self.constraint_mode == ConstraintMode::None {
+            return ControlFlow::Continue(None);
+        }
+        let ScalarExpr::Binary(BinaryExpr {
+            op: BinaryOp::Eq,
+            ref lhs,
+            ref rhs,
+            span,
+        }) = expr
+        else {
+            return ControlFlow::Continue(None);
+        };
+        let (lhs_access, boundary) = match lhs.as_ref() {
+            ScalarExpr::SymbolAccess(access) => (access, None),
+            ScalarExpr::BoundedSymbolAccess(access) => (&access.column, Some(access.boundary)),
+            _ => return ControlFlow::Continue(None),
+        };
+        let ScalarExpr::SymbolAccess(rhs_access) = rhs.as_ref() else {
+            return ControlFlow::Continue(None);
+        };
+        let (Some(Type::Vector(lhs_len)), Some(Type::Vector(rhs_len))) = (
+            self.peek_access_type(lhs_access),
+            self.peek_access_type(rhs_access),
+        ) else {
+            return ControlFlow::Continue(None);
+        };
+
+        if lhs_len != rhs_len {
+            self.has_type_errors = true;
+            self.invalid_constraint(rhs.span(), format!("this vector has {rhs_len} elements"))
+                .with_secondary_label(
+                    lhs.span(),
+                    format!("but this vector has {lhs_len} elements"),
+                )
+                .with_note("Vectors are constrained element-wise, and so must have the same length")
+                .emit();
+            return ControlFlow::Break(SemanticAnalysisError::Invalid);
+        }
+
+        let mut iterables = vec![];
+        let mut elements = vec![];
+        for (access, name) in [(lhs_access, "%lhs"), (rhs_access, "%rhs")] {
+            let binding = Identifier::new(access.span(), Symbol::intern(name));
+            let element =
+                SymbolAccess::new(access.span(), binding, AccessType::Default, access.offset);
+            let mut iterable = access.clone();
+            iterable.offset = 0;
+            iterables.push((binding, Expr::SymbolAccess(iterable)));
+            elements.push(element);
+        }
+        let rhs = ScalarExpr::SymbolAccess(elements.pop().unwrap());
+        let lhs = elements.pop().unwrap();
+        let lhs = match boundary {
+            Some(boundary) => {
+                ScalarExpr::BoundedSymbolAccess(BoundedSymbolAccess::new(lhs.span(), lhs, boundary))
+            }
+            None => ScalarExpr::SymbolAccess(lhs),
+        };
+        let body = ScalarExpr::Binary(BinaryExpr::new(*span, BinaryOp::Eq, lhs, rhs));
+
+        ControlFlow::Continue(Some(ListComprehension::new(*span, body, iterables, None)))
+    }
+
+    /// Returns the type of the value produced by `access`, if it is known, without resolving the
+    /// accessed name, or raising any diagnostics if it cannot be resolved
+    fn peek_access_type(&self, access: &SymbolAccess) -> Option<Type> {
+        let binding_ty = match access.name {
+            ResolvableIdentifier::Unresolved(ref namespaced_id) => self
+                .locals
+                .get(namespaced_id)
+                .or_else(|| self.globals.get(&namespaced_id.id()))
+                .cloned()
+                .or_else(|| {
+                    let qualified_id = self.imported.get(namespaced_id)?;
+                    self.resolved_binding_type(qualified_id)
+                        .ok()
+                        .map(|ty| ty.item)
+                })?,
+            ref id => self.resolvable_binding_type(id).ok()?.item,
+        };
+        binding_ty.access(access.access_type.clone()).ok()?.ty()
+    }
+
     fn invalid_constraint(
         &self,
         span: SourceSpan,
@@ -1466,10 +2441,307 @@ impl<'a> SemanticAnalysis<'a> {
             .emit();
     }
 
+    /// Raises a diagnostic if `operand` of the logical operator `op` is known not to be binary,
+    /// or records it to be warned about if it cannot be proven binary, see [Self::prove_binary].
+    fn validate_logical_operand(&mut self, op: BinaryOp, operand: &ScalarExpr, span: SourceSpan) {
+        let Some(reason) = self.non_binary_reason(operand) else {
+            self.prove_binary(operand, Some((op, span)));
+            return;
+        };
+        self.has_type_errors = true;
+        self.diagnostics
+            .diagnostic(Severity::Error)
+            .with_message("invalid operand")
+            .with_primary_label(operand.span(), reason)
+            .with_secondary_label(span, format!("but `{op}` requires binary operands"))
+            .with_note("Logical operators are only defined over values which are 0 or 1, e.g. columns constrained by `enf is_binary(x)`")
+            .emit();
+    }
+
+    /// Raises a diagnostic if the selector of a case of an `enf match` is known not to be binary
+    ///
+    /// This applies the same rules as for the operands of logical operators.
+    fn validate_match_selector(&mut self, selector: &ScalarExpr) {
+        let Some(reason) = self.non_binary_reason(selector) else {
+            self.prove_binary(selector, None);
+            return;
+        };
+        self.has_type_errors = true;
+        self.diagnostics
+            .diagnostic(Severity::Error)
+            .with_message("invalid selector")
+            .with_primary_label(selector.span(), reason)
+            .with_note("The selectors of an `enf match` must be binary, e.g. columns constrained by `enf is_binary(x)`, or logical combinations of them")
+            .emit();
+    }
+
+    /// Records `expr`, an operand of a logical operator or a selector, unless it is proven binary
+    ///
+    /// An operand is proven binary if it is the constant 0 or 1, a logical expression, a `let`
+    /// binding of a value which is proven binary, a periodic column whose values are all 0 or 1, or a trace column
+    /// constrained by an unconditional `enf is_binary(x)` in the same constraint section or
+    /// evaluator. As the constraint may follow the use of the column, trace columns are only
+    /// checked once the whole section or evaluator has been visited, see
+    /// [Self::check_unproven_operands].
+    fn prove_binary(&mut self, expr: &ScalarExpr, op: Option<(BinaryOp, SourceSpan)>) {
+        let column = match expr {
+            ScalarExpr::Const(value) if value.item <= 1 => return,
+            expr if matches!(expr.ty(), Ok(Some(Type::Binary))) => return,
+            ScalarExpr::SymbolAccess(ref access)
+                if self
+                    .locals
+                    .get_key_value(&access.name.namespaced())
+                    .map(|(binding, _)| self.binary_bindings.contains(&binding.span()))
+                    .unwrap_or(false) =>
+            {
+                return
+            }
+            ScalarExpr::SymbolAccess(ref access) => match self.access_binding_type(access) {
+                Ok(BindingType::PeriodicColumn(_))
+                    if self
+                        .binary_periodic_columns
+                        .contains(&access.name.namespaced().id()) =>
+                {
+                    return
+                }
+                Ok(BindingType::TraceColumn(tb) | BindingType::TraceParam(tb))
+                    if tb.is_scalar()
+                        && tb.name.map(|n| n == access.name.namespaced().id()) == Some(true) =>
+                {
+                    Some((tb.segment, tb.offset))
+                }
+                _ => None,
+            },
+            _ => None,
+        };
+        self.unproven_operands.push(UnprovenOperand {
+            span: expr.span(),
+            op,
+            column,
+        });
+    }
+
+    /// Warns about the operands of logical operators and the selectors recorded by
+    /// [Self::prove_binary], other than trace columns which have since been constrained to be
+    /// binary
+    ///
+    /// This is called at the end of each constraint section, evaluator and function.
+    fn check_unproven_operands(&mut self) {
+        for operand in mem::take(&mut self.unproven_operands) {
+            if operand
+                .column
+                .map(|column| self.binary_columns.contains(&column))
+                .unwrap_or(false)
+            {
+                continue;
+            }
+            let mut diagnostic = self
+                .diagnostics
+                .diagnostic(Severity::Warning)
+                .with_primary_label(operand.span, "this value is not known to be 0 or 1");
+            diagnostic = match operand.op {
+                Some((op, span)) => diagnostic
+                    .with_message("operand is not known to be binary")
+                    .with_secondary_label(span, format!("but `{op}` requires binary operands")),
+                None => diagnostic.with_message("selector is not known to be binary"),
+            };
+            diagnostic
+                .with_note("The result is meaningless unless the value is 0 or 1 on every row, e.g. constrain the column with `enf is_binary(x)`")
+                .emit();
+        }
+    }
+
+    /// Returns the reason why `expr` is known not to be binary, if it is
+    ///
+    /// Trace columns, periodic columns, constants and public inputs are not known to be
+    /// non-binary, whereas arithmetic expressions and the values derived from them are.
+    fn non_binary_reason(&self, expr: &ScalarExpr) -> Option<&'static str> {
+        match expr {
+            ScalarExpr::Const(value) if value.item > 1 => Some("this constant is neither 0 nor 1"),
+            ScalarExpr::Binary(ref expr) if !expr.op.is_logical() => {
+                Some("this arithmetic expression produces a field element")
+            }
+            ScalarExpr::Call(_) => Some("this call produces a field element"),
+            ScalarExpr::SymbolAccess(ref access) => match self.access_binding_type(access) {
+                Ok(BindingType::Local(Type::Felt)) => Some("this variable is a field element"),
+                Ok(BindingType::RandomValue(_)) => Some("random values are field elements"),
+                Ok(BindingType::Alias(aliased))
+                    if matches!(*aliased, BindingType::RandomValue(_)) =>
+                {
+                    Some("random values are field elements")
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Checks that the cases of an `enf match` are mutually exclusive, and reports the rows to
+    /// which no case applies, where this can be determined from the selectors alone.
+    ///
+    /// Overlapping cases are an error, as the constraints of both cases would be enforced on the
+    /// rows they overlap. A match which is not exhaustive is valid, since no constraint of the match
+    /// is enforced on the rows to which no case applies, but as this is easily overlooked, it is
+    /// reported as a note.
+    fn check_match_cases(&mut self, expr: &Match) {
+        let Some(selectors) =
+            MatchSelectors::new(expr.arms.iter().filter_map(|arm| arm.selector.as_ref()))
+        else {
+            return;
+        };
+
+        // Only the first assignment under which each pair of cases overlaps is reported
+        let mut overlapping = HashSet::new();
+        let mut unmatched = None;
+        for assignment in selectors.assignments() {
+            let selected = selectors.selected(assignment);
+            if selected.is_empty() && unmatched.is_none() {
+                unmatched = Some(assignment);
+            }
+            for (i, first) in selected.iter().enumerate() {
+                for second in selected[(i + 1)..].iter() {
+                    if !overlapping.insert((*first, *second)) {
+                        continue;
+                    }
+                    self.has_type_errors = true;
+                    self.diagnostics
+                        .diagnostic(Severity::Error)
+                        .with_message("overlapping match cases")
+                        .with_primary_label(
+                            expr.arms[*second].span(),
+                            "this case overlaps with a previous case",
+                        )
+                        .with_secondary_label(expr.arms[*first].span(), "previous case is here")
+                        .with_note(format!(
+                            "Both cases apply when {}, but the selectors of an `enf match` must be mutually exclusive",
+                            selectors.describe(assignment)
+                        ))
+                        .emit();
+                }
+            }
+        }
+
+        if let Some(assignment) = unmatched {
+            self.diagnostics
+                .diagnostic(Severity::Note)
+                .with_message("match is not exhaustive")
+                .with_primary_label(
+                    expr.span(),
+                    format!("no case applies when {}", selectors.describe(assignment)),
+                )
+                .with_note("None of the constraints of this match are enforced on such rows")
+                .emit();
+        }
+    }
+
+    /// Validates that `power`, the exponent of an exponentiation, is an integer
+    ///
+    /// Exponents must be constant by the time constraints are constructed, but an exponent may
+    /// refer to constants, or the bindings of a comprehension over a range, which only become
+    /// constant once the comprehension has been unrolled. Values which are never constant, such
+    /// as trace columns, are rejected here.
+    fn validate_exponent(&mut self, power: &ScalarExpr) -> ControlFlow<SemanticAnalysisError> {
+        match power {
+            ScalarExpr::Const(value) if u32::try_from(value.item).is_err() => ControlFlow::Break(
+                SemanticAnalysisError::InvalidExpr(InvalidExprError::InvalidExponent(value.span())),
+            ),
+            ScalarExpr::Binary(ref expr) => {
+                self.validate_exponent(expr.lhs.as_ref())?;
+                self.validate_exponent(expr.rhs.as_ref())
+            }
+            ScalarExpr::SymbolAccess(ref access) => {
+                let binding_ty = match self.access_binding_type(access) {
+                    Ok(BindingType::Alias(aliased)) => *aliased,
+                    Ok(binding_ty) => binding_ty,
+                    Err(_) => return ControlFlow::Continue(()),
+                };
+                match binding_ty {
+                    BindingType::TraceColumn(_)
+                    | BindingType::TraceParam(_)
+                    | BindingType::PeriodicColumn(_)
+                    | BindingType::PublicInput(_)
+                    | BindingType::RandomValue(_) => {
+                        self.has_type_errors = true;
+                        self.diagnostics
+                            .diagnostic(Severity::Error)
+                            .with_message("invalid exponent")
+                            .with_primary_label(
+                                access.span(),
+                                format!("expected exponent to be a constant integer, but this is a reference to {binding_ty}"),
+                            )
+                            .with_note("Exponents must be constants, or bindings of a comprehension over a range")
+                            .emit();
+                        ControlFlow::Continue(())
+                    }
+                    _ => ControlFlow::Continue(()),
+                }
+            }
+            _ => ControlFlow::Continue(()),
+        }
+    }
+
+    /// Returns true if `expr` is known to evaluate to either 0 or 1
+    fn is_provably_binary(&self, expr: &Expr) -> bool {
+        match expr {
+            Expr::Const(constant) => matches!(constant.item, ConstantExpr::Scalar(0 | 1)),
+            expr => expr.ty() == Some(Type::Binary),
+        }
+    }
+
+    /// Returns the name of the trace segment with the given id, as declared in the root module
+    fn segment_name(&self, id: TraceSegmentId) -> Symbol {
+        match self.program.trace_columns.get(id) {
+            Some(segment) => segment.name.name(),
+            None => Symbol::intern(format!("#{id}")),
+        }
+    }
+
+    /// Returns true if `name` refers directly to the single trace column `tb`, and that column
+    /// has been constrained to be binary.
+    ///
+    /// Aliases of trace columns, e.g. comprehension bindings, may refer to different columns
+    /// depending on context, so they are never considered binary.
+    fn is_binary_column(&self, name: &ResolvableIdentifier, tb: &TraceBinding) -> bool {
+        tb.is_scalar()
+            && tb
+                .name
+                .map(|n| n == name.namespaced().id())
+                .unwrap_or(false)
+            && self.binary_columns.contains(&(tb.segment, tb.offset))
+    }
+
+    /// Marks every column of the iterable bound to the argument of an unconditional
+    /// `enf is_binary(x) for x in xs` as binary
+    fn mark_binary_iterable(&mut self, expr: &ListComprehension) {
+        let ScalarExpr::Call(ref call) = expr.body.as_ref() else {
+            return;
+        };
+        if call.callee.resolved().map(|callee| callee.name()) != Some(symbols::IsBinary) {
+            return;
+        }
+        let Some(Expr::SymbolAccess(ref arg)) = call.args.first() else {
+            return;
+        };
+        if arg.access_type != AccessType::Default {
+            return;
+        }
+        let binding = arg.name.namespaced().id();
+        let Some(index) = expr.bindings.iter().position(|b| *b == binding) else {
+            return;
+        };
+        if let Ok(BindingType::TraceColumn(tb) | BindingType::TraceParam(tb)) =
+            self.expr_binding_type(&expr.iterables[index])
+        {
+            self.binary_columns
+                .extend((tb.offset..(tb.offset + tb.size)).map(|offset| (tb.segment, offset)));
+        }
+    }
+
     fn expr_binding_type(&self, expr: &Expr) -> Result<BindingType, InvalidAccessError> {
         match expr {
             Expr::Const(constant) => Ok(BindingType::Local(constant.ty())),
-            Expr::Range(range) => Ok(BindingType::Local(Type::Vector(range.end - range.start))),
+            Expr::Range(range) => Ok(BindingType::Local(Type::Vector(range.len()))),
+            Expr::TraceLen(_) => Ok(BindingType::Local(Type::Felt)),
             Expr::Vector(ref elems) => {
                 let mut binding_tys = Vec::with_capacity(elems.len());
                 for elem in elems.iter() {
@@ -1486,6 +2758,7 @@ impl<'a> SemanticAnalysis<'a> {
             Expr::SymbolAccess(ref expr) => self.access_binding_type(expr),
             Expr::Call(Call { ty: None, .. }) => Err(InvalidAccessError::InvalidBinding),
             Expr::Call(Call { ty: Some(ty), .. }) => Ok(BindingType::Local(*ty)),
+            Expr::Binary(ref expr) if expr.op.is_logical() => Ok(BindingType::Local(Type::Binary)),
             Expr::Binary(_) => Ok(BindingType::Local(Type::Felt)),
             Expr::ListComprehension(ref lc) => {
                 match lc.ty {
@@ -1506,6 +2779,65 @@ impl<'a> SemanticAnalysis<'a> {
         binding_ty.access(expr.access_type.clone())
     }
 
+    /// If `expr` references a named slice of a public input, e.g. `root` in
+    /// `header: [root[4], nonce[4]]`, rewrite it as an access of the corresponding elements of
+    /// the public input itself, e.g. `header[0..4]`, so that later passes need not be aware of
+    /// such bindings.
+    fn resolve_public_input_binding(&mut self, expr: &mut SymbolAccess) {
+        let ResolvableIdentifier::Local(id) = expr.name else {
+            return;
+        };
+        // The binding may be shadowed by a let-bound variable
+        if !matches!(
+            self.locals.get(&NamespacedIdentifier::Binding(id)),
+            Some(BindingType::PublicInput(_))
+        ) {
+            return;
+        }
+        let Some((input, binding)) = self.program.public_inputs.values().find_map(|input| {
+            input
+                .bindings
+                .iter()
+                .find(|binding| binding.name == id)
+                .map(|binding| (input.name, *binding))
+        }) else {
+            return;
+        };
+
+        match binding.access(expr.access_type.clone()) {
+            Ok(access_type) => {
+                expr.name = ResolvableIdentifier::Local(Identifier::new(id.span(), input.name()));
+                expr.access_type = access_type;
+            }
+            Err(err) => {
+                self.has_type_errors = true;
+                let label = match (&expr.access_type, err) {
+                    (AccessType::Slice(range), InvalidAccessError::IndexOutOfBounds) => format!(
+                        "`{id}` has size {}, but the range {}..{} was accessed",
+                        binding.size, range.start, range.end
+                    ),
+                    (AccessType::Index(idx), InvalidAccessError::IndexOutOfBounds) => format!(
+                        "`{id}` has size {}, but index {idx} was accessed",
+                        binding.size
+                    ),
+                    (_, err) => err.to_string(),
+                };
+                self.diagnostics
+                    .diagnostic(Severity::Error)
+                    .with_message("invalid variable access")
+                    .with_primary_label(expr.span(), label)
+                    .with_secondary_label(binding.span(), "references this declaration")
+                    .emit();
+                // Continue with a fabricated access of the public input
+                expr.name = ResolvableIdentifier::Local(Identifier::new(id.span(), input.name()));
+                expr.access_type = match expr.access_type {
+                    AccessType::Slice(_) => binding.access(AccessType::Default).unwrap(),
+                    _ => AccessType::Index(binding.offset),
+                };
+            }
+        }
+    }
+
     fn resolvable_binding_type(
         &self,
         id: &ResolvableIdentifier,
@@ -1556,7 +2888,7 @@ impl<'a> SemanticAnalysis<'a> {
             // If this is a builtin function, there is no definition,
             // so we hardcode the type information here
             match qid.name() {
-                symbols::Sum | symbols::Prod => {
+                symbols::Sum | symbols::Prod | symbols::Min | symbols::Max => {
                     // NOTE: We're using `usize::MAX` elements to indicate a vector of any size, but we
                     // should probably add this to the Type enum and handle it elsewhere. For the time
                     // being, functions are not implemented, so the only place this comes up is with these
@@ -1565,6 +2897,17 @@ impl<'a> SemanticAnalysis<'a> {
                         FunctionType::Function(vec![Type::Vector(usize::MAX)], Type::Felt);
                     Ok(Span::new(qid.span(), BindingType::Function(folder_ty)))
                 }
+                // `is_binary` is treated as an evaluator of a single column, but as its argument
+                // may be any scalar expression, it is validated separately
+                symbols::IsBinary => Ok(Span::new(
+                    qid.span(),
+                    BindingType::Function(FunctionType::Evaluator(vec![])),
+                )),
+                // Casts accept any scalar, i.e. binary values and integers as well as field elements
+                symbols::Felt => Ok(Span::new(
+                    qid.span(),
+                    BindingType::Function(FunctionType::Function(vec![Type::Felt], Type::Felt)),
+                )),
                 name => unimplemented!("unsupported builtin: {}", name),
             }
         } else {
@@ -1584,15 +2927,38 @@ impl<'a> SemanticAnalysis<'a> {
                         )
                     })
                 })
+                .or_else(|| {
+                    imported_from
+                        .functions
+                        .get(qid.as_ref())
+                        .map(|f| Span::new(f.span(), BindingType::Function(f.signature())))
+                })
                 .ok_or(InvalidAccessError::UndefinedVariable)
         }
     }
 }
 
-fn segment_id_to_name(id: TraceSegmentId) -> Symbol {
-    match id {
-        0 => symbols::Main,
-        1 => symbols::Aux,
-        _ => unimplemented!(),
+/// Returns the Levenshtein distance between `a` and `b`, i.e. the number of single character
+/// insertions, deletions, or substitutions needed to turn one into the other
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut prev = (0..=b.len()).collect::<Vec<_>>();
+    for (i, x) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, y) in b.iter().enumerate() {
+            let substitution = prev[j] + usize::from(x != *y);
+            current.push(substitution.min(prev[j + 1] + 1).min(current[j] + 1));
+        }
+        prev = current;
+    }
+    prev[b.len()]
+}
+
+/// Returns the value produced by the body of a function, i.e. the expression at the bottom of its `let` tree
+fn function_result(body: &[Statement]) -> &Expr {
+    match body.last() {
+        Some(Statement::Let(expr)) => function_result(expr.body.as_slice()),
+        Some(Statement::Expr(expr)) => expr,
+        _ => unreachable!("the body of a function must end in an expression"),
     }
 }
@@ -24,6 +24,14 @@ pub mod predefined {
     pub const Sum: Symbol = Symbol::new(3);
     /// The symbol `prod`
     pub const Prod: Symbol = Symbol::new(4);
+    /// The symbol `is_binary`
+    pub const IsBinary: Symbol = Symbol::new(5);
+    /// The symbol `felt`
+    pub const Felt: Symbol = Symbol::new(6);
+    /// The symbol `min`
+    pub const Min: Symbol = Symbol::new(7);
+    /// The symbol `max`
+    pub const Max: Symbol = Symbol::new(8);
 
     pub(super) const __SYMBOLS: &[(Symbol, &str)] = &[
         (Main, "$main"),
@@ -31,6 +39,10 @@ pub mod predefined {
         (Builtin, "$builtin"),
         (Sum, "sum"),
         (Prod, "prod"),
+        (IsBinary, "is_binary"),
+        (Felt, "felt"),
+        (Min, "min"),
+        (Max, "max"),
     ];
 }
 
@@ -4,10 +4,11 @@ use std::{
 };
 
 use air_pass::Pass;
-use miden_diagnostics::{DiagnosticsHandler, Span, Spanned};
+use miden_diagnostics::{DiagnosticsHandler, SourceSpan, Span, Spanned};
 
 use crate::{
     ast::{visit::VisitMut, *},
+    field::PrimeField,
     sema::{LexicalScope, SemanticAnalysisError},
     symbols,
 };
@@ -17,6 +18,9 @@ use crate::{
 /// value, replacing local variables bound to constants with the constant value, and folding
 /// constant expressions into constant values.
 ///
+/// Constant expressions are folded using the arithmetic of the configured [PrimeField], which
+/// defaults to the Goldilocks field, see [ConstantPropagation::with_field].
+///
 /// It is expected that the provided [Program] has already been run through semantic analysis,
 /// so it will panic if it encounters invalid constructions to help catch bugs in the semantic
 /// analysis pass, should they exist.
@@ -28,6 +32,8 @@ pub struct ConstantPropagation<'a> {
     /// The set of identifiers which are live (in use) in the current scope
     live: HashSet<Identifier>,
     in_constraint_comprehension: bool,
+    /// The field in which constant expressions are evaluated
+    field: PrimeField,
 }
 impl<'p> Pass for ConstantPropagation<'p> {
     type Input<'a> = Program;
@@ -35,6 +41,8 @@ impl<'p> Pass for ConstantPropagation<'p> {
     type Error = SemanticAnalysisError;
 
     fn run<'a>(&mut self, mut program: Self::Input<'a>) -> Result<Self::Output<'a>, Self::Error> {
+        // The pass may be run more than once, e.g. until a fixpoint is reached
+        self.global.clear();
         self.global.reserve(program.constants.len());
 
         match self.run_visitor(&mut program) {
@@ -51,12 +59,20 @@ impl<'a> ConstantPropagation<'a> {
             local: Default::default(),
             live: Default::default(),
             in_constraint_comprehension: false,
+            field: PrimeField::default(),
         }
     }
 
+    /// Sets the field in which constant expressions are evaluated
+    pub fn with_field(mut self, field: PrimeField) -> Self {
+        self.field = field;
+        self
+    }
+
     fn run_visitor(&mut self, program: &mut Program) -> ControlFlow<SemanticAnalysisError> {
         // Record all of the constant declarations
         for (name, constant) in program.constants.iter() {
+            self.check_constant(constant.span(), &constant.value)?;
             assert_eq!(
                 self.global
                     .insert(*name, Span::new(constant.span(), constant.value.clone())),
@@ -64,6 +80,33 @@ impl<'a> ConstantPropagation<'a> {
             );
         }
 
+        // Expand the comprehensions generating the values of periodic columns, which must be
+        // constant. The values of periodic columns must also be valid field elements
+        for column in program.periodic_columns.values_mut() {
+            if let Some(generator) = column.generator.take() {
+                let body_span = generator.body.span();
+                let mut values = Expr::ListComprehension(*generator);
+                self.visit_mut_expr(&mut values)?;
+                match values {
+                    Expr::Const(Span {
+                        item: ConstantExpr::Vector(values),
+                        ..
+                    }) => column.values = values,
+                    _ => {
+                        return ControlFlow::Break(SemanticAnalysisError::InvalidExpr(
+                            InvalidExprError::NonConstantPeriodicValue(body_span),
+                        ))
+                    }
+                }
+            }
+            self.check_constant(column.span(), &ConstantExpr::Vector(column.values.clone()))?;
+        }
+
+        // Visit all of the functions
+        for function in program.functions.values_mut() {
+            self.visit_mut_function(function)?;
+        }
+
         // Visit all of the evaluators
         for evaluator in program.evaluators.values_mut() {
             self.visit_mut_evaluator_function(evaluator)?;
@@ -74,6 +117,26 @@ impl<'a> ConstantPropagation<'a> {
         self.visit_mut_integrity_constraints(&mut program.integrity_constraints)
     }
 
+    /// Returns an error if any element of `value` is not a valid element of the configured field
+    fn check_constant(
+        &self,
+        span: SourceSpan,
+        value: &ConstantExpr,
+    ) -> ControlFlow<SemanticAnalysisError> {
+        let valid = match value {
+            ConstantExpr::Scalar(value) => self.field.contains(*value),
+            ConstantExpr::Vector(elems) => elems.iter().all(|v| self.field.contains(*v)),
+            ConstantExpr::Matrix(rows) => rows.iter().flatten().all(|v| self.field.contains(*v)),
+        };
+        if valid {
+            ControlFlow::Continue(())
+        } else {
+            ControlFlow::Break(SemanticAnalysisError::InvalidExpr(
+                InvalidExprError::InvalidFieldElement(span, self.field.modulus()),
+            ))
+        }
+    }
+
     fn try_fold_binary_expr(
         &mut self,
         expr: &mut BinaryExpr,
@@ -86,7 +149,74 @@ impl<'a> ConstantPropagation<'a> {
             return Err(err);
         }
         // If both operands are constant, fold
-        try_fold_binary_expr(expr).map_err(SemanticAnalysisError::InvalidExpr)
+        try_fold_binary_expr(expr, &self.field).map_err(SemanticAnalysisError::InvalidExpr)
+    }
+
+    /// Folds a call to one of the list folding builtins over a constant vector, or a range, to a
+    /// constant, returning `None` if the argument is not constant.
+    ///
+    /// `min` and `max` have no equivalent in field arithmetic, so they must be folded here, and
+    /// an error is returned if their argument is not constant. Elements are compared by their
+    /// canonical integer representation.
+    fn try_fold_list_folding(
+        &self,
+        call: &Call,
+    ) -> ControlFlow<SemanticAnalysisError, Option<Span<u64>>> {
+        assert_eq!(call.args.len(), 1);
+        let name = call.callee.as_ref().name();
+        let arg = &call.args[0];
+        let elems = match arg {
+            Expr::Const(value) => match &value.item {
+                ConstantExpr::Vector(ref elems) => elems.clone(),
+                invalid => panic!("bad argument to list folding builtin: {:#?}", invalid),
+            },
+            Expr::Range(range) => range.iter().map(|i| i as u64).collect(),
+            _ if matches!(name, symbols::Min | symbols::Max) => {
+                return ControlFlow::Break(SemanticAnalysisError::InvalidExpr(
+                    InvalidExprError::NonConstantFoldIterable(arg.span()),
+                ));
+            }
+            _ => return ControlFlow::Continue(None),
+        };
+        let folded = match name {
+            symbols::Sum => Some(elems.iter().fold(0, |acc, v| self.field.add(acc, *v))),
+            symbols::Prod => Some(elems.iter().fold(1, |acc, v| self.field.mul(acc, *v))),
+            symbols::Min => elems.iter().copied().min(),
+            symbols::Max => elems.iter().copied().max(),
+            invalid => unimplemented!("unknown list folding builtin: {}", invalid),
+        };
+        match folded {
+            Some(folded) => ControlFlow::Continue(Some(Span::new(arg.span(), folded))),
+            None => ControlFlow::Break(SemanticAnalysisError::InvalidExpr(
+                InvalidExprError::EmptyFoldIterable(arg.span()),
+            )),
+        }
+    }
+
+    /// Visits the constraint to which a set of attributes is attached
+    fn visit_mut_attributed_constraint(
+        &mut self,
+        statement: &mut Statement,
+    ) -> ControlFlow<SemanticAnalysisError> {
+        match statement {
+            Statement::Enforce(ref mut expr) => self.visit_mut_enforce(expr),
+            Statement::EnforceAll(ref mut expr) => {
+                self.in_constraint_comprehension = true;
+                self.visit_mut_list_comprehension(expr)?;
+                self.in_constraint_comprehension = false;
+                ControlFlow::Continue(())
+            }
+            Statement::EnforceMatch(ref mut expr) => {
+                self.in_constraint_comprehension = true;
+                self.visit_mut_enforce_match(expr)?;
+                self.in_constraint_comprehension = false;
+                ControlFlow::Continue(())
+            }
+            Statement::WithAttributes(_, ref mut stmt) => {
+                self.visit_mut_attributed_constraint(stmt)
+            }
+            _ => unreachable!("attributes are only attached to constraints"),
+        }
     }
 }
 impl<'a> VisitMut<SemanticAnalysisError> for ConstantPropagation<'a> {
@@ -96,8 +226,10 @@ impl<'a> VisitMut<SemanticAnalysisError> for ConstantPropagation<'a> {
         expr: &mut ScalarExpr,
     ) -> ControlFlow<SemanticAnalysisError> {
         match expr {
-            // Expression is already folded
-            ScalarExpr::Const(_) => ControlFlow::Continue(()),
+            // Expression is already folded, but literals must be valid field elements
+            ScalarExpr::Const(value) => {
+                self.check_constant(value.span(), &ConstantExpr::Scalar(value.item))
+            }
             // Need to check if this access is to a constant value, and transform to a constant if so
             ScalarExpr::SymbolAccess(sym) => {
                 let constant_value = match sym.name {
@@ -148,27 +280,60 @@ impl<'a> VisitMut<SemanticAnalysisError> for ConstantPropagation<'a> {
             // Fold constant expressions
             ScalarExpr::Binary(ref mut binary_expr) => {
                 match self.try_fold_binary_expr(binary_expr) {
-                    Ok(maybe_folded) => {
-                        if let Some(folded) = maybe_folded {
-                            *expr = ScalarExpr::Const(folded);
+                    Ok(Some(folded)) => {
+                        *expr = ScalarExpr::Const(folded);
+                        ControlFlow::Continue(())
+                    }
+                    Ok(None) => {
+                        if let Some(simplified) = try_fold_trivial_exp(binary_expr) {
+                            *expr = simplified;
                         }
                         ControlFlow::Continue(())
                     }
                     Err(err) => ControlFlow::Break(err),
                 }
             }
+            // Casts have no effect once type checking is done, so they are replaced by their
+            // (possibly folded) argument
+            ScalarExpr::Call(ref mut call)
+                if call.is_builtin() && call.callee.as_ref().name() == symbols::Felt =>
+            {
+                self.visit_mut_call(call)?;
+                match ScalarExpr::try_from(call.args.pop().unwrap()) {
+                    Ok(arg) => {
+                        *expr = arg;
+                        ControlFlow::Continue(())
+                    }
+                    Err(err) => ControlFlow::Break(SemanticAnalysisError::InvalidExpr(err)),
+                }
+            }
+            ScalarExpr::Call(ref mut call)
+                if call.is_builtin()
+                    && matches!(
+                        call.callee.as_ref().name(),
+                        symbols::Sum | symbols::Prod | symbols::Min | symbols::Max
+                    ) =>
+            {
+                self.visit_mut_call(call)?;
+                if let Some(folded) = self.try_fold_list_folding(call)? {
+                    *expr = ScalarExpr::Const(folded);
+                }
+                ControlFlow::Continue(())
+            }
             // While calls cannot be constant folded, arguments can be
             ScalarExpr::Call(ref mut call) => self.visit_mut_call(call),
-            // This cannot be constant folded
-            ScalarExpr::BoundedSymbolAccess(_) => ControlFlow::Continue(()),
+            // These cannot be constant folded
+            ScalarExpr::BoundedSymbolAccess(_) | ScalarExpr::TraceLen(_) => {
+                ControlFlow::Continue(())
+            }
         }
     }
 
     fn visit_mut_expr(&mut self, expr: &mut Expr) -> ControlFlow<SemanticAnalysisError> {
         let span = expr.span();
         match expr {
-            // Already constant
-            Expr::Const(_) => ControlFlow::Continue(()),
+            // Already constant, but literals must be valid field elements
+            Expr::Const(value) => self.check_constant(value.span(), &value.item),
             // Lift to `Expr::Const` if the scalar expression is constant
             //
             // We deal with symbol accesses directly, as they may evaluate to an aggregate constant
@@ -239,26 +404,18 @@ impl<'a> VisitMut<SemanticAnalysisError> for ConstantPropagation<'a> {
             Expr::Call(ref mut call) if call.is_builtin() => {
                 self.visit_mut_call(call)?;
                 match call.callee.as_ref().name() {
-                    name @ (symbols::Sum | symbols::Prod) => {
-                        assert_eq!(call.args.len(), 1);
-                        if let Expr::Const(ref value) = &call.args[0] {
-                            let span = value.span();
-                            match &value.item {
-                                ConstantExpr::Vector(ref elems) => {
-                                    let folded = if name == symbols::Sum {
-                                        elems.iter().sum::<u64>()
-                                    } else {
-                                        elems.iter().product::<u64>()
-                                    };
-                                    *expr =
-                                        Expr::Const(Span::new(span, ConstantExpr::Scalar(folded)));
-                                }
-                                invalid => {
-                                    panic!("bad argument to list folding builtin: {:#?}", invalid)
-                                }
-                            }
+                    symbols::Sum | symbols::Prod | symbols::Min | symbols::Max => {
+                        if let Some(folded) = self.try_fold_list_folding(call)? {
+                            *expr = Expr::Const(Span::new(
+                                folded.span(),
+                                ConstantExpr::Scalar(folded.item),
+                            ));
                         }
                     }
+                    // Casts have no effect once type checking is done
+                    symbols::Felt => {
+                        *expr = call.args.pop().unwrap();
+                    }
                     invalid => unimplemented!("unknown builtin function: {}", invalid),
                 }
                 ControlFlow::Continue(())
@@ -277,7 +434,7 @@ impl<'a> VisitMut<SemanticAnalysisError> for ConstantPropagation<'a> {
                 Err(err) => ControlFlow::Break(err),
             },
             // Ranges are constant
-            Expr::Range(_) => ControlFlow::Continue(()),
+            Expr::Range(_) | Expr::TraceLen(_) => ControlFlow::Continue(()),
             // Visit vector elements, and promote the vector to `Expr::Const` if possible
             Expr::Vector(ref mut vector) => {
                 if vector.is_empty() {
@@ -292,7 +449,7 @@ impl<'a> VisitMut<SemanticAnalysisError> for ConstantPropagation<'a> {
 
                 if is_constant {
                     let ty = match vector.first().and_then(|e| e.ty()).unwrap() {
-                        Type::Felt => Type::Vector(vector.len()),
+                        Type::Felt | Type::Binary | Type::U32 => Type::Vector(vector.len()),
                         Type::Vector(n) => Type::Matrix(vector.len(), n),
                         _ => unreachable!(),
                     };
@@ -386,7 +543,7 @@ impl<'a> VisitMut<SemanticAnalysisError> for ConstantPropagation<'a> {
                         ..
                     }) => rows.len(),
                     Expr::Const(_) => panic!("expected iterable constant, got scalar"),
-                    Expr::Range(range) => range.end - range.start,
+                    Expr::Range(range) => range.len(),
                     _ => unreachable!(),
                 };
 
@@ -412,8 +569,8 @@ impl<'a> VisitMut<SemanticAnalysisError> for ConstantPropagation<'a> {
                                 self.local.insert(binding, Span::new(span, value));
                             }
                             Expr::Range(range) => {
-                                assert!(range.end > range.start + step);
-                                let value = ConstantExpr::Scalar((range.start + step) as u64);
+                                assert!(step < range.len());
+                                let value = ConstantExpr::Scalar(range.get(step) as u64);
                                 self.local.insert(binding, Span::new(span, value));
                             }
                             _ => unreachable!(),
@@ -430,7 +587,10 @@ impl<'a> VisitMut<SemanticAnalysisError> for ConstantPropagation<'a> {
                                 }
                             }
                             // The selector cannot be evaluated, bail out early
-                            _ => return ControlFlow::Continue(()),
+                            _ => {
+                                self.local.exit();
+                                return ControlFlow::Continue(());
+                            }
                         }
                     }
 
@@ -442,6 +602,7 @@ impl<'a> VisitMut<SemanticAnalysisError> for ConstantPropagation<'a> {
                     if let ScalarExpr::Const(folded_body) = body {
                         folded.push(folded_body.item);
                     } else {
+                        self.local.exit();
                         return ControlFlow::Continue(());
                     }
                 }
@@ -487,7 +648,7 @@ impl<'a> VisitMut<SemanticAnalysisError> for ConstantPropagation<'a> {
                                 self.local.insert(expr.name, value.clone());
                             }
                             Expr::Range(ref range) => {
-                                let vector = range.item.clone().map(|i| i as u64).collect();
+                                let vector = range.item.iter().map(|i| i as u64).collect();
                                 self.local.insert(
                                     expr.name,
                                     Span::new(range.span(), ConstantExpr::Vector(vector)),
@@ -524,11 +685,26 @@ impl<'a> VisitMut<SemanticAnalysisError> for ConstantPropagation<'a> {
                     self.visit_mut_list_comprehension(expr)?;
                     self.in_constraint_comprehension = false;
                 }
+                Statement::EnforceMatch(ref mut expr) => {
+                    self.in_constraint_comprehension = true;
+                    self.visit_mut_enforce_match(expr)?;
+                    self.in_constraint_comprehension = false;
+                }
+                Statement::WithAttributes(_, ref mut stmt) => {
+                    self.visit_mut_attributed_constraint(stmt)?;
+                }
                 Statement::Expr(ref mut expr) => {
                     self.visit_mut_expr(expr)?;
                 }
-                // This statement type is only present in the AST after inlining
-                Statement::EnforceIf(_, _) => unreachable!(),
+                Statement::Debug(ref mut expr) => {
+                    self.visit_mut_scalar_expr(expr)?;
+                }
+                // This statement type is only present in the AST after inlining, which is the
+                // case when constant propagation is run again following inlining
+                Statement::EnforceIf(ref mut expr, ref mut selector) => {
+                    self.visit_mut_enforce(expr)?;
+                    self.visit_mut_scalar_expr(selector)?;
+                }
             }
 
             // If we have a non-empty buffer, then we are collapsing a let into the current block,
@@ -560,37 +736,113 @@ impl<'a> VisitMut<SemanticAnalysisError> for ConstantPropagation<'a> {
 
 /// This function attempts to folds a binary operator expression into a constant value.
 ///
-/// If the operands are both constant, the operator is applied, and if the result does not
-/// overflow/underflow, then `Ok(Some)` is returned with the result of the evaluation.
+/// If the operands are both constant, the operator is applied using the arithmetic of `field`,
+/// and `Ok(Some)` is returned with the result of the evaluation, reduced modulo the field prime.
 ///
-/// If the operands are not both constant, or the operation would overflow/underflow, then
-/// `Ok(None)` is returned.
+/// If the operands are not both constant, then `Ok(None)` is returned.
 ///
 /// If the operands are constant, or there is some validation error with the expression,
 /// `Err(InvalidExprError)` will be returned.
 pub(crate) fn try_fold_binary_expr(
     expr: &BinaryExpr,
+    field: &PrimeField,
 ) -> Result<Option<Span<u64>>, InvalidExprError> {
     // If both operands are constant, fold
     if let (ScalarExpr::Const(l), ScalarExpr::Const(r)) = (expr.lhs.as_ref(), expr.rhs.as_ref()) {
         let folded = match expr.op {
-            BinaryOp::Add => l.item.checked_add(r.item),
-            BinaryOp::Sub => l.item.checked_sub(r.item),
-            BinaryOp::Mul => l.item.checked_mul(r.item),
-            BinaryOp::Exp => match r.item.try_into() {
-                Ok(exp) => l.item.checked_pow(exp),
+            BinaryOp::Add => field.add(l.item, r.item),
+            BinaryOp::Sub => field.sub(l.item, r.item),
+            BinaryOp::Mul => field.mul(l.item, r.item),
+            BinaryOp::Exp => match u32::try_from(r.item) {
+                Ok(exp) => field.pow(l.item, exp as u64),
+                Err(_) if field.is_negative(r.item) => {
+                    return Err(InvalidExprError::NegativeExponent(r.span()))
+                }
                 Err(_) => return Err(InvalidExprError::InvalidExponent(expr.span())),
             },
             // This op cannot be folded
             BinaryOp::Eq => return Ok(None),
+            BinaryOp::And | BinaryOp::Or | BinaryOp::Not => {
+                unreachable!("logical operators are lowered during semantic analysis")
+            }
         };
-        Ok(folded.map(|v| Span::new(expr.span(), v)))
+        Ok(Some(Span::new(expr.span(), folded)))
+    } else if expr.op == BinaryOp::Exp {
+        validate_exponent(expr.rhs.as_ref(), field)
     } else {
-        // If we observe a non-constant power in an exponentiation operation, raise an error
-        if expr.op == BinaryOp::Exp && !expr.rhs.is_constant() {
-            Err(InvalidExprError::NonConstantExponent(expr.rhs.span()))
-        } else {
-            Ok(None)
+        Ok(None)
+    }
+}
+
+/// Simplifies an exponentiation of a non-constant base by a power of `0` or `1`, i.e. `x^0`
+/// becomes `1` and `x^1` becomes `x`.
+fn try_fold_trivial_exp(expr: &BinaryExpr) -> Option<ScalarExpr> {
+    if expr.op != BinaryOp::Exp {
+        return None;
+    }
+    match expr.rhs.as_ref() {
+        ScalarExpr::Const(power) if power.item == 0 => {
+            Some(ScalarExpr::Const(Span::new(expr.span(), 1)))
+        }
+        ScalarExpr::Const(power) if power.item == 1 => Some(expr.lhs.as_ref().clone()),
+        _ => None,
+    }
+}
+
+/// Validates the power of an exponentiation whose base is not constant.
+///
+/// The power must be a constant which fits in a `u32`, or an integer-typed expression, i.e. one
+/// referencing the bindings of a comprehension over a range, which will become constant once the
+/// comprehension is unrolled. A power consisting only of constants which could not be folded
+/// must be negative or too large. A constant power in the upper half of the field is the result
+/// of folding a negative value, e.g. `0 - 1`.
+fn validate_exponent(
+    power: &ScalarExpr,
+    field: &PrimeField,
+) -> Result<Option<Span<u64>>, InvalidExprError> {
+    match power {
+        ScalarExpr::Const(value) if u32::try_from(value.item).is_err() => {
+            if field.is_negative(value.item) {
+                Err(InvalidExprError::NegativeExponent(value.span()))
+            } else {
+                Err(InvalidExprError::InvalidExponent(value.span()))
+            }
+        }
+        ScalarExpr::Const(_) => Ok(None),
+        power => match eval_integer(power) {
+            Some(Some(value)) if value > u32::MAX as i128 => {
+                Err(InvalidExprError::InvalidExponent(power.span()))
+            }
+            // Folding only fails for a constant power which fits in a u32 if the power, or one
+            // of its intermediate results, is negative
+            Some(Some(_)) => Err(InvalidExprError::NegativeExponent(power.span())),
+            Some(None) => Err(InvalidExprError::InvalidExponent(power.span())),
+            None if power.ty() == Ok(Some(Type::U32)) => Ok(None),
+            None => Err(InvalidExprError::NonConstantExponent(power.span())),
+        },
+    }
+}
+
+/// Evaluates `expr` using signed integer arithmetic, if it consists only of constants.
+///
+/// Returns `None` if the expression is not constant, and `Some(None)` if the evaluation overflows.
+fn eval_integer(expr: &ScalarExpr) -> Option<Option<i128>> {
+    match expr {
+        ScalarExpr::Const(value) => Some(Some(value.item as i128)),
+        ScalarExpr::Binary(ref expr) => {
+            let lhs = eval_integer(expr.lhs.as_ref())?;
+            let rhs = eval_integer(expr.rhs.as_ref())?;
+            let (Some(lhs), Some(rhs)) = (lhs, rhs) else {
+                return Some(None);
+            };
+            Some(match expr.op {
+                BinaryOp::Add => lhs.checked_add(rhs),
+                BinaryOp::Sub => lhs.checked_sub(rhs),
+                BinaryOp::Mul => lhs.checked_mul(rhs),
+                BinaryOp::Exp => u32::try_from(rhs).ok().and_then(|rhs| lhs.checked_pow(rhs)),
+                _ => return None,
+            })
         }
+        _ => None,
     }
 }
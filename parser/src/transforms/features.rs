@@ -0,0 +1,315 @@
+use std::{
+    collections::{HashMap, HashSet},
+    ops::ControlFlow,
+};
+
+use air_pass::Pass;
+use miden_diagnostics::{DiagnosticsHandler, Severity, SourceSpan, Span, Spanned};
+
+use crate::{
+    ast::{visit::VisitMut, *},
+    sema::{LexicalScope, SemanticAnalysisError},
+    Symbol,
+};
+
+/// This pass removes the parts of a [Program] which are gated by a feature that is not enabled,
+/// i.e. constraints with a `feature` attribute, e.g. `@[feature="debug"] enf dbg = a`, and trace
+/// column bindings with one, e.g. `main: [a, @[feature="debug"] dbg]`.
+///
+/// The columns of a segment which follow a disabled binding are shifted down to take its place,
+/// so disabling a binding shrinks the segment by the number of columns it binds. Any remaining
+/// reference to a disabled column is an error, as is disabling every constraint of a section.
+///
+/// This must be run before any other transformation, so that no analysis or optimization is
+/// applied to the disabled parts of the program.
+pub struct FeatureGating<'a> {
+    diagnostics: &'a DiagnosticsHandler,
+    /// The set of enabled features
+    enabled: HashSet<Symbol>,
+    /// The disabled bindings, along with the feature which gates them
+    disabled: HashMap<Identifier, Span<Symbol>>,
+    /// For each segment with disabled columns, the new index of each of its columns, or `None`
+    /// if the column is disabled, keyed by the name of the segment
+    columns: HashMap<Identifier, Vec<Option<TraceColumnIndex>>>,
+    /// The local bindings in the current scope, which shadow any trace column of the same name
+    local: LexicalScope<Identifier, ()>,
+}
+impl<'p> Pass for FeatureGating<'p> {
+    type Input<'a> = Program;
+    type Output<'a> = Program;
+    type Error = SemanticAnalysisError;
+
+    fn run<'a>(&mut self, mut program: Self::Input<'a>) -> Result<Self::Output<'a>, Self::Error> {
+        self.disabled.clear();
+        self.columns.clear();
+
+        match self.run_visitor(&mut program) {
+            ControlFlow::Continue(()) => Ok(program),
+            ControlFlow::Break(err) => Err(err),
+        }
+    }
+}
+impl<'a> FeatureGating<'a> {
+    pub fn new(diagnostics: &'a DiagnosticsHandler) -> Self {
+        Self {
+            diagnostics,
+            enabled: Default::default(),
+            disabled: Default::default(),
+            columns: Default::default(),
+            local: Default::default(),
+        }
+    }
+
+    /// Enables the given features, in addition to any which are already enabled
+    pub fn with_features<I, S>(mut self, features: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.enabled
+            .extend(features.into_iter().map(|f| Symbol::intern(f.as_ref())));
+        self
+    }
+
+    fn run_visitor(&mut self, program: &mut Program) -> ControlFlow<SemanticAnalysisError> {
+        for segment in program.trace_columns.iter_mut() {
+            self.remove_disabled_columns(segment)?;
+        }
+
+        // Remove the disabled constraints, including those in the body of an evaluator
+        for evaluator in program.evaluators.values_mut() {
+            self.remove_disabled_constraints(&mut evaluator.body, &mut vec![]);
+        }
+        let mut removed = vec![];
+        self.remove_disabled_constraints(&mut program.boundary_constraints, &mut removed);
+        self.check_not_empty(&program.boundary_constraints, &removed, "boundary")?;
+        let mut removed = vec![];
+        self.remove_disabled_constraints(&mut program.integrity_constraints, &mut removed);
+        self.check_not_empty(&program.integrity_constraints, &removed, "integrity")?;
+
+        // Check, and if necessary update, the references to the columns of each segment. These
+        // may only appear in the root module's constraints, as evaluators refer to their params.
+        if self.columns.is_empty() {
+            return ControlFlow::Continue(());
+        }
+        self.visit_mut_boundary_constraints(&mut program.boundary_constraints)?;
+        self.visit_mut_integrity_constraints(&mut program.integrity_constraints)
+    }
+
+    /// Returns true if `feature` is enabled
+    fn is_enabled(&self, feature: Symbol) -> bool {
+        self.enabled.contains(&feature)
+    }
+
+    /// Removes the disabled bindings of `segment`, shifting the columns which follow them
+    fn remove_disabled_columns(
+        &mut self,
+        segment: &mut TraceSegment,
+    ) -> ControlFlow<SemanticAnalysisError> {
+        let features = core::mem::take(&mut segment.features);
+        let (enabled, disabled): (Vec<_>, Vec<_>) = features
+            .into_iter()
+            .partition(|(_, feature)| self.is_enabled(feature.item));
+        segment.features = enabled.into_iter().collect();
+        if disabled.is_empty() {
+            return ControlFlow::Continue(());
+        }
+
+        let mut is_disabled = vec![false; segment.size];
+        segment.bindings.retain(|binding| {
+            let name = binding
+                .name
+                .expect("trace column bindings are always named");
+            match disabled.iter().find(|(gated, _)| *gated == name) {
+                Some((_, feature)) => {
+                    is_disabled[binding.offset..(binding.offset + binding.size)].fill(true);
+                    self.disabled.insert(name, *feature);
+                    false
+                }
+                None => true,
+            }
+        });
+
+        let mut next = 0;
+        let columns = is_disabled
+            .into_iter()
+            .map(|disabled| {
+                (!disabled).then(|| {
+                    next += 1;
+                    next - 1
+                })
+            })
+            .collect::<Vec<_>>();
+        for binding in segment.bindings.iter_mut() {
+            binding.offset = columns[binding.offset].unwrap();
+        }
+        segment.size = next;
+
+        if segment.is_empty() {
+            self.diagnostics
+                .diagnostic(Severity::Error)
+                .with_message("trace segment has no enabled columns")
+                .with_primary_label(
+                    segment.span(),
+                    "every column of this segment is gated by a feature which is not enabled",
+                )
+                .emit();
+            return ControlFlow::Break(SemanticAnalysisError::Invalid);
+        }
+
+        self.columns.insert(segment.name, columns);
+        ControlFlow::Continue(())
+    }
+
+    /// Removes the constraints in `block` which are gated by a feature which is not enabled,
+    /// recording the span of the attributes responsible in `removed`.
+    ///
+    /// A `let` whose body no longer contains any constraints is removed along with them.
+    fn remove_disabled_constraints(
+        &self,
+        block: &mut Vec<Statement>,
+        removed: &mut Vec<SourceSpan>,
+    ) {
+        block.retain_mut(|statement| match statement {
+            Statement::WithAttributes(attrs, _) => match attrs.get("feature") {
+                Some(feature) if !self.is_enabled(feature) => {
+                    removed.push(attrs.span());
+                    false
+                }
+                _ => true,
+            },
+            Statement::Let(expr) => {
+                let num_removed = removed.len();
+                self.remove_disabled_constraints(&mut expr.body, removed);
+                removed.len() == num_removed || expr.body.iter().any(|s| s.has_constraints())
+            }
+            _ => true,
+        });
+    }
+
+    /// Returns an error if removing disabled constraints left `block` without any constraints
+    fn check_not_empty(
+        &self,
+        block: &[Statement],
+        removed: &[SourceSpan],
+        section: &str,
+    ) -> ControlFlow<SemanticAnalysisError> {
+        if removed.is_empty() || block.iter().any(|s| s.has_constraints()) {
+            return ControlFlow::Continue(());
+        }
+
+        let mut diagnostic = self
+            .diagnostics
+            .diagnostic(Severity::Error)
+            .with_message(format!("no {section} constraints are enabled"))
+            .with_primary_label(removed[0], "this constraint is gated by a disabled feature");
+        for span in removed[1..].iter().copied() {
+            diagnostic = diagnostic.with_secondary_label(span, "as is this one");
+        }
+        diagnostic
+            .with_note(format!(
+                "The {section}_constraints section must contain at least one constraint which is enabled."
+            ))
+            .emit();
+        ControlFlow::Break(SemanticAnalysisError::Invalid)
+    }
+
+    /// Emits an error for a reference to the disabled binding `name`
+    fn disabled_column(&self, span: SourceSpan, name: Identifier) -> SemanticAnalysisError {
+        let feature = self.disabled[&name];
+        self.diagnostics
+            .diagnostic(Severity::Error)
+            .with_message("reference to a disabled trace column")
+            .with_primary_label(span, format!("`{name}` is not declared"))
+            .with_secondary_label(
+                feature.span(),
+                format!("because it is gated by the feature `{}`", feature.item),
+            )
+            .with_note("Enable the feature, or gate this constraint by it as well.")
+            .emit();
+        SemanticAnalysisError::Invalid
+    }
+}
+impl<'a> VisitMut<SemanticAnalysisError> for FeatureGating<'a> {
+    fn visit_mut_let(&mut self, expr: &mut Let) -> ControlFlow<SemanticAnalysisError> {
+        self.visit_mut_expr(&mut expr.value)?;
+        self.local.enter();
+        self.local.insert(expr.name, ());
+        for statement in expr.body.iter_mut() {
+            self.visit_mut_statement(statement)?;
+        }
+        self.local.exit();
+        ControlFlow::Continue(())
+    }
+
+    fn visit_mut_list_comprehension(
+        &mut self,
+        expr: &mut ListComprehension,
+    ) -> ControlFlow<SemanticAnalysisError> {
+        for iterable in expr.iterables.iter_mut() {
+            self.visit_mut_expr(iterable)?;
+        }
+        self.local.enter();
+        for binding in expr.bindings.iter() {
+            self.local.insert(*binding, ());
+        }
+        if let Some(filter) = expr.filter.as_mut() {
+            self.visit_mut_scalar_expr(filter)?;
+        }
+        if let Some(selector) = expr.selector.as_mut() {
+            self.visit_mut_scalar_expr(selector)?;
+        }
+        self.visit_mut_scalar_expr(expr.body.as_mut())?;
+        self.local.exit();
+        ControlFlow::Continue(())
+    }
+
+    fn visit_mut_symbol_access(
+        &mut self,
+        expr: &mut SymbolAccess,
+    ) -> ControlFlow<SemanticAnalysisError> {
+        // Trace columns are declared in the root module, and so are resolved as locals
+        let name = match expr.name {
+            ResolvableIdentifier::Local(name) if self.local.get(&name).is_none() => name,
+            _ => return ControlFlow::Continue(()),
+        };
+        if self.disabled.contains_key(&name) {
+            return ControlFlow::Break(self.disabled_column(expr.span(), name));
+        }
+
+        // Accesses to the segment itself refer to its columns by index, which must be updated
+        let Some(columns) = self.columns.get(&name) else {
+            return ControlFlow::Continue(());
+        };
+        let column = |index: usize| columns.get(index).copied().flatten();
+        let access_type = match expr.access_type {
+            AccessType::Index(index) => column(index).map(AccessType::Index),
+            AccessType::Slice(ref range) if !range.is_empty() => {
+                match (column(range.start), column(range.end - 1)) {
+                    (Some(start), Some(end)) if end - start == range.len() - 1 => {
+                        Some(AccessType::Slice(start..(end + 1)))
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+        match access_type {
+            Some(access_type) => {
+                expr.access_type = access_type;
+                ControlFlow::Continue(())
+            }
+            None => {
+                self.diagnostics
+                    .diagnostic(Severity::Error)
+                    .with_message("reference to a disabled trace column")
+                    .with_primary_label(
+                        expr.span(),
+                        "this refers to columns which are gated by a feature which is not enabled",
+                    )
+                    .emit();
+                ControlFlow::Break(SemanticAnalysisError::Invalid)
+            }
+        }
+    }
+}
@@ -4,27 +4,32 @@ use std::{
 };
 
 use air_pass::Pass;
-use miden_diagnostics::{DiagnosticsHandler, SourceSpan, Span, Spanned};
+use miden_diagnostics::{DiagnosticsHandler, Severity, SourceSpan, Span, Spanned};
 
 use crate::{
     ast::{visit::VisitMut, *},
+    field::PrimeField,
     sema::{BindingType, LexicalScope, SemanticAnalysisError},
     symbols,
 };
 
 use super::constant_propagation;
 
+/// The number of iterations of a single comprehension above which [Inlining] warns that the
+/// comprehension is unusually large, see [Inlining::with_unroll_warning_threshold]
+pub const DEFAULT_UNROLL_WARNING_THRESHOLD: usize = 1024;
+
 /// This pass performs the following transformations on a [Program]:
 ///
 /// * Monomorphizing and inlining evaluators/functions at their call sites
 /// * Unrolling constraint comprehensions into a sequence of scalar constraints
 /// * Unrolling list comprehensions into a tree of `let` statements which end in
-/// a vector expression (the implicit result of the tree). Each iteration of the
-/// unrolled comprehension is reified as a value and bound to a variable so that
-/// other transformations may refer to it directly.
+///   a vector expression (the implicit result of the tree). Each iteration of the
+///   unrolled comprehension is reified as a value and bound to a variable so that
+///   other transformations may refer to it directly.
 /// * Rewriting aliases of top-level declarations to refer to those declarations directly
 /// * Removing let-bound variables which are unused, which is also used to clean up
-/// after the aliasing rewrite mentioned above.
+///   after the aliasing rewrite mentioned above.
 ///
 /// The trickiest transformation comes with inlining the body of evaluators at their
 /// call sites, as evaluator parameter lists can arbitrarily destructure/regroup columns
@@ -56,8 +61,6 @@ use super::constant_propagation;
 /// be observed at this stage of compilation (e.g. no references to constant declarations, no
 /// undefined variables, expressions are well-typed, etc.).
 pub struct Inlining<'a> {
-    // This may be unused for now, but it's helpful to assume its needed in case we want it in the future
-    #[allow(unused)]
     diagnostics: &'a DiagnosticsHandler,
     /// The name of the root module
     root: Identifier,
@@ -75,14 +78,22 @@ pub struct Inlining<'a> {
     imported: HashMap<QualifiedIdentifier, BindingType>,
     /// All evaluator functions in the program
     evaluators: HashMap<QualifiedIdentifier, EvaluatorFunction>,
+    /// All pure functions in the program
+    functions: HashMap<QualifiedIdentifier, Function>,
     /// A set of identifiers for which accesses should be rewritten.
     ///
-    /// When an identifier is in this set, it means it is a local alias for a trace column,
-    /// and should be rewritten based on the current `BindingType` associated with the alias
+    /// When an identifier is in this set, it means it is a local alias for a trace column, or
+    /// for random values, and should be rewritten based on the current `BindingType` associated with the alias
     /// identifier in `bindings`.
     rewrites: HashSet<Identifier>,
     in_comprehension_constraint: bool,
     next_ident: usize,
+    /// The field in which constant expressions are evaluated
+    field: PrimeField,
+    /// The number of iterations of a comprehension above which a warning is raised
+    unroll_warning_threshold: usize,
+    /// The comprehensions for which a warning about their size has already been raised
+    large_comprehensions: HashSet<SourceSpan>,
 }
 impl<'p> Pass for Inlining<'p> {
     type Input<'a> = Program;
@@ -96,6 +107,11 @@ impl<'p> Pass for Inlining<'p> {
             .iter()
             .map(|(k, v)| (*k, v.clone()))
             .collect();
+        self.functions = program
+            .functions
+            .iter()
+            .map(|(k, v)| (*k, v.clone()))
+            .collect();
 
         // We'll be referencing the trace configuration during inlining, so keep a copy of it
         self.trace = program.trace_columns.clone();
@@ -155,14 +171,12 @@ impl<'p> Pass for Inlining<'p> {
         }
         // Public inputs..
         for input in program.public_inputs.values() {
-            self.bindings.insert(
-                input.name,
-                BindingType::PublicInput(Type::Vector(input.size)),
-            );
+            self.bindings
+                .insert(input.name, BindingType::PublicInput(input.ty()));
         }
         // For periodic columns, we register the imported item, but do not add any to the local bindings.
         for (name, periodic) in program.periodic_columns.iter() {
-            let binding_ty = BindingType::PeriodicColumn(periodic.values.len());
+            let binding_ty = BindingType::PeriodicColumn(periodic.period());
             self.imported.insert(*name, binding_ty);
         }
 
@@ -187,12 +201,33 @@ impl<'a> Inlining<'a> {
             let_bound: Default::default(),
             imported: Default::default(),
             evaluators: Default::default(),
+            functions: Default::default(),
             rewrites: Default::default(),
             in_comprehension_constraint: false,
             next_ident: 0,
+            field: PrimeField::default(),
+            unroll_warning_threshold: DEFAULT_UNROLL_WARNING_THRESHOLD,
+            large_comprehensions: Default::default(),
         }
     }
 
+    /// Sets the field in which constant expressions are evaluated when they are folded after
+    /// inlining; this should be the same field given to [super::ConstantPropagation]
+    pub fn with_field(mut self, field: PrimeField) -> Self {
+        self.field = field;
+        self
+    }
+
+    /// Sets the number of iterations above which unrolling a single comprehension raises a
+    /// warning, which defaults to [DEFAULT_UNROLL_WARNING_THRESHOLD].
+    ///
+    /// Large comprehensions are valid, but are usually the result of a mistake in the bounds of
+    /// a range, which otherwise silently produces a large number of constraints.
+    pub fn with_unroll_warning_threshold(mut self, threshold: usize) -> Self {
+        self.unroll_warning_threshold = threshold;
+        self
+    }
+
     /// Generate a new variable
     ///
     /// This is only used when expanding list comprehensions, so we use a special prefix for
@@ -249,6 +284,12 @@ impl<'a> Inlining<'a> {
             statements.append(&mut expanded);
         }
 
+        // The expansion of a statement may end in a `let`, e.g. when an evaluator body binds a
+        // local, or a call is hoisted out of a constraint, so the statements which follow it
+        // must be nested in its body to keep the `let` in tail position. This is only valid as
+        // the locals of such an expansion are generated or renamed, so cannot capture anything.
+        nest_trailing_statements(statements);
+
         Ok(())
     }
 
@@ -257,10 +298,22 @@ impl<'a> Inlining<'a> {
         &mut self,
         statement: Statement,
     ) -> Result<Vec<Statement>, SemanticAnalysisError> {
+        // Calls nested in the expressions of a statement are bound to generated variables first,
+        // so that they are expanded like any other let-bound call
+        let statement = self.hoist_calls(statement);
         match statement {
             // Expanding a let requires special treatment, as let-bound values may be inlined as a block
             // of statements, which requires us to rewrite the `let` into a `let` tree
             Statement::Let(expr) => self.expand_let(expr),
+            // A call to an evaluator function applied at a boundary is inlined like any other call,
+            // but the resulting constraints are then converted to boundary constraints
+            Statement::Enforce(ScalarExpr::Call(call)) if call.boundary.is_some() => {
+                self.expand_boundary_evaluator_callsite(call)
+            }
+            // A call to the `is_binary` builtin is lowered directly to the constraint it represents
+            Statement::Enforce(ScalarExpr::Call(call)) if call.is_builtin() => {
+                self.expand_constraint(binary_constraint(call))
+            }
             // A call to an evaluator function is expanded by inlining the function itself at the call site
             Statement::Enforce(ScalarExpr::Call(call)) => self.expand_evaluator_callsite(call),
             // Constraints are inlined by expanding the constraint expression
@@ -272,10 +325,21 @@ impl<'a> Inlining<'a> {
                 self.in_comprehension_constraint = in_cc;
                 result
             }
+            // Each case of a match is expanded like the equivalent conditional constraint, i.e. `enf c when s`
+            Statement::EnforceMatch(expr) => {
+                let mut statements = vec![];
+                for arm in expr.arms.into_iter() {
+                    statements.append(&mut self.expand_statement(Statement::EnforceAll(arm))?);
+                }
+                Ok(statements)
+            }
             // Conditional constraints are expanded like regular constraints, except the selector is applied
             // to all constraints in the expansion.
             Statement::EnforceIf(expr, mut selector) => {
                 let mut statements = match expr {
+                    ScalarExpr::Call(call) if call.is_builtin() => {
+                        self.expand_constraint(binary_constraint(call))?
+                    }
                     ScalarExpr::Call(call) => self.expand_evaluator_callsite(call)?,
                     expr => self.expand_constraint(expr)?,
                 };
@@ -291,33 +355,52 @@ impl<'a> Inlining<'a> {
                 }
                 Ok(statements)
             }
+            // Attributes are pushed down to each of the constraints in the expansion of the statement
+            Statement::WithAttributes(attrs, stmt) => {
+                let mut statements = self.expand_statement(*stmt)?;
+                for statement in statements.iter_mut() {
+                    let mut visitor = ApplyConstraintAttributes { attrs: &attrs };
+                    if let ControlFlow::Break(err) = visitor.visit_mut_statement(statement) {
+                        return Err(err);
+                    }
+                }
+                Ok(statements)
+            }
+            // Calls in expression statements are produced by list comprehensions whose body is a call
+            // to a list folding builtin, e.g. `[sum(row) for row in m]`, and must be expanded
+            Statement::Expr(Expr::Call(call)) => self.expand_call(call),
             // Expression statements are introduced during inlining, and are always already expanded,
             // but they are recursively visited to apply rewrites
             Statement::Expr(mut expr) => {
                 self.rewrite_expr(&mut expr)?;
                 Ok(vec![Statement::Expr(expr)])
             }
+            // Debug statements enforce nothing, so only the expression itself needs rewriting
+            Statement::Debug(mut expr) => {
+                self.rewrite_scalar_expr(&mut expr)?;
+                Ok(vec![Statement::Debug(expr)])
+            }
         }
     }
 
     /// Let expressions are expanded using the following rules:
     ///
     /// * The let-bound expression is expanded first. If it expands to a statement block and
-    /// not an expression, the block is inlined in place of the let being expanded, and the
-    /// rest of the expansion takes place at the end of the block; replacing the last statement
-    /// in the block. If the last statement in the block was an expression, it is treated as
-    /// the let-bound value. If the last statement in the block was another `let` however, then
-    /// we recursively walk down the let tree until we reach the bottom, which must always be
-    /// an expression statement.
+    ///   not an expression, the block is inlined in place of the let being expanded, and the
+    ///   rest of the expansion takes place at the end of the block; replacing the last statement
+    ///   in the block. If the last statement in the block was an expression, it is treated as
+    ///   the let-bound value. If the last statement in the block was another `let` however, then
+    ///   we recursively walk down the let tree until we reach the bottom, which must always be
+    ///   an expression statement.
     ///
     /// * The body is expanded in-place after the previous step has been completed.
     ///
     /// * If a let-bound variable is an alias for a declaration, we replace all uses
-    /// of the variable with direct references to the declaration, making the let-bound variable
-    /// dead
+    ///   of the variable with direct references to the declaration, making the let-bound variable
+    ///   dead
     ///
     /// * If a let-bound variable is dead (i.e. has no references), then the let is elided,
-    /// by replacing it with the result of expanding its body
+    ///   by replacing it with the result of expanding its body
     fn expand_let(&mut self, expr: Let) -> Result<Vec<Statement>, SemanticAnalysisError> {
         let span = expr.span();
         let name = expr.name;
@@ -387,12 +470,19 @@ impl<'a> Inlining<'a> {
         let prev_rewrites = self.rewrites.clone();
 
         // Register the binding
-        let binding_ty = self.expr_binding_type(&expr.value).unwrap();
+        //
+        // Trace column bindings do not carry a row offset, so a let bound to an access like `a'`
+        // is kept as a local, rather than aliased to the column it refers to
+        let mut binding_ty = self.expr_binding_type(&expr.value).unwrap();
+        if binding_ty.is_trace_binding() && has_row_offset(&expr.value) {
+            binding_ty = BindingType::Local(expr.value.ty().unwrap());
+        }
 
-        // If this let is a vector of trace column bindings, then we can
-        // elide the let, and rewrite all uses of the let-bound variable
-        // to the respective elements of the vector
-        let inline_body = binding_ty.is_trace_binding();
+        // If this let is a vector of trace column bindings, or is bound to random values, then we
+        // can elide the let, and rewrite all uses of the let-bound variable to the respective
+        // elements of the vector, or of the random values array
+        let inline_body = binding_ty.is_trace_binding()
+            || matches!(binding_ty.clone().unalias(), BindingType::RandomValue(_));
         if inline_body {
             self.rewrites.insert(expr.name);
         }
@@ -431,7 +521,154 @@ impl<'a> Inlining<'a> {
                 other => unimplemented!("unhandled builtin: {}", other),
             }
         } else {
-            todo!("pure functions are not implemented yet")
+            self.expand_function_callsite(call)
+        }
+    }
+
+    /// This function handles inlining calls to pure functions.
+    ///
+    /// The body of a function is a tree of `let` statements ending in the value it returns, so we
+    /// bind each argument to its parameter with a `let` at the root of that tree, and expand the
+    /// result in the scope of the caller. The parameters and locals of the function are renamed
+    /// first, so that they cannot conflict with the bindings of the caller.
+    fn expand_function_callsite(
+        &mut self,
+        call: Call,
+    ) -> Result<Vec<Statement>, SemanticAnalysisError> {
+        // The callee is guaranteed to be resolved and exist at this point
+        let callee = call
+            .callee
+            .resolved()
+            .expect("callee should have been resolved by now");
+        // We clone the function here as we will be modifying the body during the
+        // inlining process, and we must not modify the original
+        let mut function = self.functions.get(&callee).unwrap().clone();
+
+        let span = call.span();
+        let mut visitor = RenameLocalsVisitor {
+            renames: Default::default(),
+            next_ident: &mut self.next_ident,
+        };
+        let mut params = Vec::with_capacity(function.params.len());
+        for (param, _) in function.params.iter() {
+            let name = visitor.rename(*param);
+            params.push(name);
+        }
+        if let ControlFlow::Break(err) = visitor.visit_mut_statement_block(&mut function.body) {
+            return Err(err);
+        }
+
+        let mut body = call
+            .args
+            .into_iter()
+            .zip(params)
+            .rfold(function.body, |body, (arg, param)| {
+                vec![Statement::Let(Let::new(span, param, arg, body))]
+            });
+
+        // The function body is never a constraint, even if called from a constraint comprehension
+        let in_cc = core::mem::replace(&mut self.in_comprehension_constraint, false);
+        let result = self.expand_statement_block(&mut body);
+        self.in_comprehension_constraint = in_cc;
+        result?;
+
+        Ok(body)
+    }
+
+    /// Binds every call nested in the expressions of `statement` to a generated variable, and
+    /// returns the statement wrapped in the `let` tree binding those variables.
+    ///
+    /// Calls which are the sole expression of a statement, e.g. `enf foo([a])` or a call bound
+    /// by a `let`, are expanded directly, and are left as is.
+    fn hoist_calls(&mut self, mut statement: Statement) -> Statement {
+        let mut calls = vec![];
+        match statement {
+            Statement::Enforce(ref mut expr) => self.hoist_nested_calls(expr, &mut calls),
+            Statement::EnforceIf(ref mut expr, ref mut selector) => {
+                self.hoist_nested_calls(expr, &mut calls);
+                self.hoist_scalar_calls(selector, &mut calls);
+            }
+            Statement::Debug(ref mut expr) => self.hoist_scalar_calls(expr, &mut calls),
+            Statement::Let(Let {
+                value: Expr::Call(_) | Expr::ListComprehension(_),
+                ..
+            })
+            | Statement::Expr(Expr::Call(_)) => (),
+            Statement::Let(Let { ref mut value, .. }) | Statement::Expr(ref mut value) => {
+                self.hoist_calls_in_expr(value, &mut calls)
+            }
+            _ => (),
+        }
+
+        calls.into_iter().rfold(statement, |body, (name, call)| {
+            Statement::Let(Let::new(call.span(), name, Expr::Call(call), vec![body]))
+        })
+    }
+
+    /// Hoists the calls nested in `expr`, but not `expr` itself
+    fn hoist_nested_calls(&mut self, expr: &mut ScalarExpr, calls: &mut Vec<(Identifier, Call)>) {
+        if let ScalarExpr::Binary(ref mut expr) = expr {
+            self.hoist_scalar_calls(expr.lhs.as_mut(), calls);
+            self.hoist_scalar_calls(expr.rhs.as_mut(), calls);
+        }
+    }
+
+    fn hoist_scalar_calls(&mut self, expr: &mut ScalarExpr, calls: &mut Vec<(Identifier, Call)>) {
+        match expr {
+            ScalarExpr::Call(ref call) => {
+                let span = call.span();
+                let name = self.next_ident(span);
+                let access = ScalarExpr::SymbolAccess(SymbolAccess {
+                    span,
+                    name: ResolvableIdentifier::Local(name),
+                    access_type: AccessType::Default,
+                    offset: 0,
+                    ty: call.ty,
+                });
+                let ScalarExpr::Call(call) = core::mem::replace(expr, access) else {
+                    unreachable!()
+                };
+                calls.push((name, call));
+            }
+            ScalarExpr::Binary(_) => self.hoist_nested_calls(expr, calls),
+            _ => (),
+        }
+    }
+
+    fn hoist_calls_in_expr(&mut self, expr: &mut Expr, calls: &mut Vec<(Identifier, Call)>) {
+        match expr {
+            Expr::Vector(ref mut elems) => {
+                for elem in elems.iter_mut() {
+                    self.hoist_calls_in_expr(elem, calls);
+                }
+            }
+            Expr::Matrix(ref mut rows) => {
+                for elem in rows.iter_mut().flat_map(|row| row.iter_mut()) {
+                    self.hoist_scalar_calls(elem, calls);
+                }
+            }
+            Expr::Binary(ref mut expr) => {
+                self.hoist_scalar_calls(expr.lhs.as_mut(), calls);
+                self.hoist_scalar_calls(expr.rhs.as_mut(), calls);
+            }
+            Expr::Call(_) => {
+                let span = expr.span();
+                let Expr::Call(call) =
+                    core::mem::replace(expr, Expr::Const(Span::new(span, ConstantExpr::Scalar(0))))
+                else {
+                    unreachable!()
+                };
+                let name = self.next_ident(span);
+                *expr = Expr::SymbolAccess(SymbolAccess {
+                    span,
+                    name: ResolvableIdentifier::Local(name),
+                    access_type: AccessType::Default,
+                    offset: 0,
+                    ty: call.ty,
+                });
+                calls.push((name, call));
+            }
+            _ => (),
         }
     }
 
@@ -465,8 +702,10 @@ impl<'a> Inlining<'a> {
             }
             Expr::SymbolAccess(ref access) => {
                 match self.let_bound.get(access.name.as_ref()).cloned() {
-                    Some(expr) => self.expand_fold(op, expr),
-                    None => match self.access_binding_type(access) {
+                    Some(expr) => self.expand_fold(op, access_aggregate(expr, &access.access_type)),
+                    // Aliases of global declarations are folded in the same way as the
+                    // declarations they refer to
+                    None => match self.access_binding_type(access).map(BindingType::unalias) {
                         Ok(BindingType::TraceColumn(tb)) => {
                             let mut vector = vec![];
                             for i in 0..tb.size {
@@ -477,13 +716,51 @@ impl<'a> Inlining<'a> {
                             let folded = self.expand_vector_fold(span, op, &mut vector)?;
                             Ok(vec![Statement::Expr(folded)])
                         }
+                        // Public inputs (or slices of them) are folded element-wise, with indices
+                        // relative to the start of the slice, if applicable
+                        Ok(BindingType::PublicInput(Type::Vector(len))) => {
+                            let mut vector = vec![];
+                            for i in 0..len {
+                                vector.push(Expr::SymbolAccess(
+                                    access.access(AccessType::Index(i)).unwrap(),
+                                ));
+                            }
+                            let folded = self.expand_vector_fold(span, op, &mut vector)?;
+                            Ok(vec![Statement::Expr(folded)])
+                        }
+                        // Vectors of random values are folded element-wise in the same way
+                        Ok(BindingType::RandomValue(rb)) if !rb.is_scalar() => {
+                            let mut vector = vec![];
+                            for i in 0..rb.size {
+                                vector.push(Expr::SymbolAccess(
+                                    access.access(AccessType::Index(i)).unwrap(),
+                                ));
+                            }
+                            let folded = self.expand_vector_fold(span, op, &mut vector)?;
+                            Ok(vec![Statement::Expr(folded)])
+                        }
                         Ok(_) | Err(_) => unimplemented!(),
                     },
                 }
             }
             // Constant propagation will have already folded calls to list-folding builtins
-            // with constant arguments, so we should panic if we ever see one here
-            Expr::Const(_) => panic!("expected constant to have been folded"),
+            // with constant arguments, but constant arguments may still be observed here when
+            // they are derived from an iterable binding, e.g. a row of a constant matrix
+            Expr::Const(Span {
+                item: ConstantExpr::Vector(elems),
+                ..
+            }) => {
+                // The elements are folded in the field, just as constant propagation would
+                let folded = match op {
+                    BinaryOp::Add => elems.iter().fold(0, |acc, elem| self.field.add(acc, *elem)),
+                    BinaryOp::Mul => elems.iter().fold(1, |acc, elem| self.field.mul(acc, *elem)),
+                    _ => unreachable!(),
+                };
+                Ok(vec![Statement::Expr(Expr::Const(Span::new(
+                    span,
+                    ConstantExpr::Scalar(folded),
+                )))])
+            }
             // All other invalid expressions should have been caught by now
             ref invalid => panic!("invalid argument to list folding builtin: {:#?}", invalid),
         }
@@ -539,7 +816,7 @@ impl<'a> Inlining<'a> {
     /// This function rewrites expressions which contain accesses for which rewrites have been registered.
     fn rewrite_expr(&mut self, expr: &mut Expr) -> Result<(), SemanticAnalysisError> {
         match expr {
-            Expr::Const(_) | Expr::Range(_) => return Ok(()),
+            Expr::Const(_) | Expr::Range(_) | Expr::TraceLen(_) => return Ok(()),
             Expr::Vector(ref mut elems) => {
                 for elem in elems.iter_mut() {
                     self.rewrite_expr(elem)?;
@@ -557,7 +834,7 @@ impl<'a> Inlining<'a> {
                 self.rewrite_scalar_expr(binary_expr.rhs.as_mut())?;
             }
             Expr::SymbolAccess(ref mut access) => {
-                if let Some(rewrite) = self.get_trace_access_rewrite(access) {
+                if let Some(rewrite) = self.get_access_rewrite(access) {
                     *access = rewrite;
                 }
             }
@@ -579,13 +856,13 @@ impl<'a> Inlining<'a> {
     /// This function rewrites scalar expressions which contain accesses for which rewrites have been registered.
     fn rewrite_scalar_expr(&mut self, expr: &mut ScalarExpr) -> Result<(), SemanticAnalysisError> {
         match expr {
-            ScalarExpr::Const(_) => Ok(()),
+            ScalarExpr::Const(_) | ScalarExpr::TraceLen(_) => Ok(()),
             ScalarExpr::SymbolAccess(ref mut access)
             | ScalarExpr::BoundedSymbolAccess(BoundedSymbolAccess {
                 column: ref mut access,
                 ..
             }) => {
-                if let Some(rewrite) = self.get_trace_access_rewrite(access) {
+                if let Some(rewrite) = self.get_access_rewrite(access) {
                     *access = rewrite;
                 }
                 Ok(())
@@ -626,9 +903,9 @@ impl<'a> Inlining<'a> {
     /// the expansion is, respectively:
     ///
     /// * A tree of let statements (using generated variables), where each let binds the value of a
-    /// single iteration of the comprehension. The body of the final let, and thus the effective value
-    /// of the entire tree, is a vector containing all of the bindings in the evaluation order of the
-    /// comprehension.
+    ///   single iteration of the comprehension. The body of the final let, and thus the effective value
+    ///   of the entire tree, is a vector containing all of the bindings in the evaluation order of the
+    ///   comprehension.
     /// * A flat list of constraint statements
     fn expand_comprehension(
         &mut self,
@@ -641,12 +918,15 @@ impl<'a> Inlining<'a> {
         let Type::Vector(num_iterations) = expr.ty.unwrap() else {
             panic!("invalid comprehension type");
         };
+        self.check_unroll_size(&expr, num_iterations);
 
         // Step the iterables for each iteration, giving each it's own lexical scope
         for i in 0..num_iterations {
             self.bindings.enter();
+            let prev_rewrites = self.rewrites.clone();
             let mut expansion = self.expand_comprehension_iteration(&expr, i)?;
             statements.append(&mut expansion);
+            self.rewrites = prev_rewrites;
             self.bindings.exit();
         }
 
@@ -722,6 +1002,30 @@ impl<'a> Inlining<'a> {
         Ok(vec![result])
     }
 
+    /// Warns if unrolling `expr` requires more iterations than the configured threshold.
+    ///
+    /// A comprehension may be unrolled several times, e.g. in an evaluator called from several
+    /// places, so the warning is only raised the first time.
+    fn check_unroll_size(&mut self, expr: &ListComprehension, num_iterations: usize) {
+        if num_iterations <= self.unroll_warning_threshold
+            || !self.large_comprehensions.insert(expr.span())
+        {
+            return;
+        }
+        self.diagnostics
+            .diagnostic(Severity::Warning)
+            .with_message("large comprehension")
+            .with_primary_label(
+                expr.span(),
+                format!("this comprehension unrolls to {num_iterations} iterations"),
+            )
+            .with_note(format!(
+                "This warning is raised for comprehensions of more than {} iterations, check that the bounds of the iterables are correct",
+                self.unroll_warning_threshold
+            ))
+            .emit();
+    }
+
     fn expand_comprehension_iteration(
         &mut self,
         lc: &ListComprehension,
@@ -755,7 +1059,7 @@ impl<'a> Inlining<'a> {
                     self.bindings.insert(binding, binding_ty);
                     Expr::Const(Span::new(
                         span,
-                        ConstantExpr::Scalar((range.start + index) as u64),
+                        ConstantExpr::Scalar(range.get(index) as u64),
                     ))
                 }
                 // If the iterable was a vector, the abstract value is whatever expression is at
@@ -789,11 +1093,15 @@ impl<'a> Inlining<'a> {
                     // The access here must be of aggregate type, so index into it for the current iteration
                     let mut current_access = access.access(AccessType::Index(index)).unwrap();
                     // Rewrite the resulting access if we have a rewrite for the underlying symbol
-                    if let Some(rewrite) = self.get_trace_access_rewrite(&current_access) {
+                    if let Some(rewrite) = self.get_access_rewrite(&current_access) {
                         current_access = rewrite;
                     }
                     let binding_ty = self.access_binding_type(&current_access).unwrap();
                     self.bindings.insert(binding, binding_ty);
+                    // The abstract value has already been rewritten, so if the binding shadows
+                    // a name with a registered rewrite, e.g. `for b in b`, it must not be
+                    // rewritten again
+                    self.rewrites.remove(&binding);
                     Expr::SymbolAccess(current_access)
                 }
                 // TODO: Currently, calls cannot be used as iterables, because we don't have pure functions
@@ -802,18 +1110,44 @@ impl<'a> Inlining<'a> {
                 Expr::Call(_) => unimplemented!("calls to functions as iterables"),
                 // Binary expressions are scalar, so cannot be used as iterables, and we don't (currently)
                 // support nested comprehensions, so it is never possible to observe these expression types here
-                Expr::Binary(_) | Expr::ListComprehension(_) => unreachable!(),
+                Expr::Binary(_) | Expr::ListComprehension(_) | Expr::TraceLen(_) => unreachable!(),
             };
             bound_values.insert(binding, abstract_value);
         }
 
-        // Clone the comprehension body for this iteration, so we don't modify the original
-        let mut body = lc.body.as_ref().clone();
-
-        // Rewrite all references to the iterable bindings in the comprehension body
+        // Rewrite all references to the iterable bindings in the comprehension filter, if present,
+        // which must then be constant. If it is zero, or false, this iteration is skipped entirely.
         let mut visitor = RewriteIterableBindingsVisitor {
             values: &bound_values,
+            field: &self.field,
+        };
+        if let Some(mut filter) = lc.filter.clone() {
+            if let ControlFlow::Break(err) = visitor.visit_mut_scalar_expr(&mut filter) {
+                return Err(err);
+            }
+            match *filter {
+                ScalarExpr::Const(value) if value.item == 0 => return Ok(vec![]),
+                ScalarExpr::Const(_) => (),
+                other => {
+                    return Err(SemanticAnalysisError::InvalidExpr(
+                        InvalidExprError::NonConstantComprehensionFilter(other.span()),
+                    ))
+                }
+            }
+        }
+
+        // Clone the comprehension body for this iteration, so we don't modify the original
+        //
+        // A call to the `is_binary` builtin is lowered before rewriting, so that its argument is
+        // rewritten like any other operand of a constraint
+        let mut body = match lc.body.as_ref() {
+            ScalarExpr::Call(call) if self.in_comprehension_constraint && call.is_builtin() => {
+                binary_constraint(call.clone())
+            }
+            body => body.clone(),
         };
+
+        // Rewrite all references to the iterable bindings in the comprehension body
         if let ControlFlow::Break(err) = visitor.visit_mut_scalar_expr(&mut body) {
             return Err(err);
         }
@@ -941,10 +1275,7 @@ impl<'a> Inlining<'a> {
             }
 
             for input in self.public_inputs.values() {
-                eval_bindings.insert(
-                    input.name,
-                    BindingType::PublicInput(Type::Vector(input.size)),
-                );
+                eval_bindings.insert(input.name, BindingType::PublicInput(input.ty()));
             }
         }
 
@@ -952,26 +1283,69 @@ impl<'a> Inlining<'a> {
         // which should be performed on the inlined function body.
         //
         // NOTE: We create a new nested scope for the parameters in order to avoid conflicting
-        // with the root declarations
+        // with the root declarations. The caller's rewrites are saved, as they must still be
+        // applied to the statements following the call when it is nested in another evaluator.
         eval_bindings.enter();
+        let prev_rewrites = core::mem::take(&mut self.rewrites);
         self.populate_rewrites(
             &mut eval_bindings,
             call.args.as_slice(),
             evaluator.params.as_slice(),
         );
 
+        // The locals of the evaluator are renamed, so that they cannot capture references in the
+        // statements following the call, which may be nested in their bodies after expansion
+        let mut visitor = RenameLocalsVisitor {
+            renames: Default::default(),
+            next_ident: &mut self.next_ident,
+        };
+        if let ControlFlow::Break(err) = visitor.visit_mut_statement_block(&mut evaluator.body) {
+            return Err(err);
+        }
+
         // While we're inlining the body, use the set of evaluator bindings we built above
         let prev_bindings = core::mem::replace(&mut self.bindings, eval_bindings);
 
         // Expand the evaluator body into a block of statements
         self.expand_statement_block(&mut evaluator.body)?;
 
-        // Restore the caller's bindings before we leave
+        // Restore the caller's bindings and rewrites before we leave
         self.bindings = prev_bindings;
+        self.rewrites = prev_rewrites;
 
         Ok(evaluator.body)
     }
 
+    /// This function handles inlining calls to evaluator functions which are applied at a trace
+    /// boundary, e.g. `enf foo([a, b]).first`.
+    ///
+    /// The evaluator is inlined as usual, after which each constraint in the expansion is converted
+    /// to a boundary constraint on the column referenced by the left-hand side of the constraint.
+    /// Since the evaluator body was validated as a set of integrity constraints, we must also verify
+    /// here that it only references values which are valid at a boundary.
+    fn expand_boundary_evaluator_callsite(
+        &mut self,
+        call: Call,
+    ) -> Result<Vec<Statement>, SemanticAnalysisError> {
+        let callsite = call.span();
+        let boundary = call.boundary.unwrap();
+        let mut statements = self.expand_evaluator_callsite(call)?;
+
+        let mut visitor = ApplyConstraintBoundary {
+            diagnostics: self.diagnostics,
+            boundary,
+            callsite,
+            imported: &self.imported,
+        };
+        for statement in statements.iter_mut() {
+            if let ControlFlow::Break(err) = visitor.visit_mut_statement(statement) {
+                return Err(err);
+            }
+        }
+
+        Ok(statements)
+    }
+
     /// Populate the set of access rewrites, as well as the initial set of bindings to use when inlining an evaluator function.
     ///
     /// This is done by resolving the arguments provided by the call to the evaluator, with the parameter list of the evaluator itself.
@@ -1162,14 +1536,34 @@ impl<'a> Inlining<'a> {
 
     /// Returns a new [SymbolAccess] which should be used in place of `access` in the current scope.
     ///
-    /// This function should only be called on accesses which have a trace column/param [BindingType],
-    /// but it will simply return `None` for other types, so it is safe to call on all accesses.
-    fn get_trace_access_rewrite(&self, access: &SymbolAccess) -> Option<SymbolAccess> {
+    /// This function should only be called on accesses which have a trace column/param or random
+    /// value [BindingType], but it will simply return `None` for other types, so it is safe to call
+    /// on all accesses. Random values are always rewritten to an access of the random values array
+    /// at their global offset, e.g. `$rand[2]`, regardless of the binding they were accessed by.
+    fn get_access_rewrite(&self, access: &SymbolAccess) -> Option<SymbolAccess> {
         if self.rewrites.contains(access.name.as_ref()) {
             // If we have a rewrite for this access, then the bindings map will
             // have an accurate trace binding for us; rewrite this access to be
             // relative to that trace binding
-            match self.access_binding_type(access).unwrap() {
+            match self.access_binding_type(access).unwrap().unalias() {
+                BindingType::RandomValue(rb) => {
+                    let rv = self.random_values.as_ref().unwrap();
+                    let (access_type, ty) = if rb.is_scalar() {
+                        (AccessType::Index(rb.offset), Type::Felt)
+                    } else {
+                        (
+                            AccessType::Slice(rb.offset..(rb.offset + rb.size)),
+                            Type::Vector(rb.size),
+                        )
+                    };
+                    Some(SymbolAccess {
+                        span: access.span(),
+                        name: ResolvableIdentifier::Global(rv.name),
+                        access_type,
+                        offset: access.offset,
+                        ty: Some(ty),
+                    })
+                }
                 BindingType::TraceColumn(tb) => {
                     let original_binding = self.trace[tb.segment]
                         .bindings
@@ -1198,9 +1592,9 @@ impl<'a> Inlining<'a> {
                         ty: Some(ty),
                     })
                 }
-                // We only have a rewrite when the binding type is TraceColumn
+                // We only have a rewrite when the binding type is TraceColumn or RandomValue
                 invalid => panic!(
-                    "unexpected trace access binding type, expected column(s), got: {:#?}",
+                    "unexpected access binding type, expected column(s) or random value(s), got: {:#?}",
                     &invalid
                 ),
             }
@@ -1213,9 +1607,10 @@ impl<'a> Inlining<'a> {
     fn expr_binding_type(&self, expr: &Expr) -> Result<BindingType, InvalidAccessError> {
         match expr {
             Expr::Const(constant) => Ok(BindingType::Local(constant.ty())),
-            Expr::Range(range) => Ok(BindingType::Local(Type::Vector(range.end - range.start))),
+            Expr::Range(range) => Ok(BindingType::Local(Type::Vector(range.len()))),
+            Expr::TraceLen(_) => Ok(BindingType::Local(Type::Felt)),
             Expr::Vector(ref elems) => match elems[0].ty() {
-                None | Some(Type::Felt) => {
+                None | Some(Type::Felt | Type::Binary | Type::U32) => {
                     let mut binding_tys = Vec::with_capacity(elems.len());
                     for elem in elems.iter() {
                         binding_tys.push(self.expr_binding_type(elem)?);
@@ -1261,12 +1656,85 @@ impl<'a> Inlining<'a> {
     }
 }
 
+/// Returns true if `expr` is an access, or a vector of accesses, to a row other than the current one
+fn has_row_offset(expr: &Expr) -> bool {
+    match expr {
+        Expr::SymbolAccess(access) => access.offset > 0,
+        Expr::Vector(elems) => elems.iter().any(has_row_offset),
+        _ => false,
+    }
+}
+
+/// This visitor is used to give the parameters and locals of a function fresh names when
+/// inlining it at a call site.
+struct RenameLocalsVisitor<'a> {
+    renames: LexicalScope<Identifier, Identifier>,
+    next_ident: &'a mut usize,
+}
+impl<'a> RenameLocalsVisitor<'a> {
+    /// Generates a fresh name for `name`, to be used in the current scope
+    fn rename(&mut self, name: Identifier) -> Identifier {
+        let id = *self.next_ident;
+        *self.next_ident += 1;
+        let renamed = Identifier::new(name.span(), crate::Symbol::intern(format!("%fn{}", id)));
+        self.renames.insert(name, renamed);
+        renamed
+    }
+}
+impl<'a> VisitMut<SemanticAnalysisError> for RenameLocalsVisitor<'a> {
+    fn visit_mut_let(&mut self, expr: &mut Let) -> ControlFlow<SemanticAnalysisError> {
+        self.visit_mut_expr(&mut expr.value)?;
+        self.renames.enter();
+        expr.name = self.rename(expr.name);
+        self.visit_mut_statement_block(&mut expr.body)?;
+        self.renames.exit();
+        ControlFlow::Continue(())
+    }
+
+    fn visit_mut_list_comprehension(
+        &mut self,
+        expr: &mut ListComprehension,
+    ) -> ControlFlow<SemanticAnalysisError> {
+        for iterable in expr.iterables.iter_mut() {
+            self.visit_mut_expr(iterable)?;
+        }
+        // The comprehension bindings shadow any renamed locals in the comprehension body
+        self.renames.enter();
+        for binding in expr.bindings.iter().copied() {
+            self.renames.insert(binding, binding);
+        }
+        self.visit_mut_scalar_expr(expr.body.as_mut())?;
+        if let Some(ref mut filter) = expr.filter {
+            self.visit_mut_scalar_expr(filter)?;
+        }
+        if let Some(ref mut selector) = expr.selector {
+            self.visit_mut_scalar_expr(selector)?;
+        }
+        self.renames.exit();
+        ControlFlow::Continue(())
+    }
+
+    fn visit_mut_symbol_access(
+        &mut self,
+        expr: &mut SymbolAccess,
+    ) -> ControlFlow<SemanticAnalysisError> {
+        if let ResolvableIdentifier::Local(id) = expr.name {
+            if let Some(renamed) = self.renames.get(&id) {
+                expr.name = ResolvableIdentifier::Local(*renamed);
+            }
+        }
+        ControlFlow::Continue(())
+    }
+}
+
 /// This visitor is used to rewrite uses of iterable bindings within a comprehension body,
 /// including expansion of constant accesses.
 struct RewriteIterableBindingsVisitor<'a> {
     /// This map contains the set of symbols to be rewritten, and the abstract values which
     /// should replace them in the comprehension body.
     values: &'a HashMap<Identifier, Expr>,
+    /// The field in which constant expressions are folded
+    field: &'a PrimeField,
 }
 impl<'a> RewriteIterableBindingsVisitor<'a> {
     fn rewrite_scalar_access(
@@ -1305,10 +1773,9 @@ impl<'a> RewriteIterableBindingsVisitor<'a> {
                 let span = range.span();
                 let range = range.item.clone();
                 match access.access_type {
-                    AccessType::Index(idx) => Some(ScalarExpr::Const(Span::new(
-                        span,
-                        (range.start + idx) as u64,
-                    ))),
+                    AccessType::Index(idx) => {
+                        Some(ScalarExpr::Const(Span::new(span, range.get(idx) as u64)))
+                    }
                     invalid => panic!(
                         "expected range to be reduced to scalar by access, got {:#?}",
                         invalid
@@ -1345,6 +1812,10 @@ impl<'a> RewriteIterableBindingsVisitor<'a> {
                     invalid
                 ),
             },
+            Some(Expr::TraceLen(span)) => {
+                assert_eq!(access.access_type, AccessType::Default);
+                Some(ScalarExpr::TraceLen(*span))
+            }
             Some(Expr::SymbolAccess(symbol_access)) => {
                 let mut new_access = symbol_access.access(access.access_type).unwrap();
                 new_access.offset = access.offset;
@@ -1357,6 +1828,39 @@ impl<'a> RewriteIterableBindingsVisitor<'a> {
         };
         ControlFlow::Continue(result)
     }
+
+    /// Rewrites references to iterable bindings in an argument to a list folding builtin.
+    ///
+    /// Unlike scalar expressions, the argument may refer to an iterable binding of aggregate type,
+    /// e.g. a row of a matrix, in which case it is replaced with the abstract value of the binding.
+    fn rewrite_builtin_arg(&mut self, expr: &mut Expr) -> ControlFlow<SemanticAnalysisError> {
+        match expr {
+            Expr::SymbolAccess(ref access) if access.access_type == AccessType::Default => {
+                if let Some(value) = self.values.get(access.name.as_ref()) {
+                    *expr = value.clone();
+                }
+                ControlFlow::Continue(())
+            }
+            Expr::SymbolAccess(ref access) => {
+                if let Some(replacement) = self.rewrite_scalar_access(access.clone())? {
+                    match replacement.try_into() {
+                        Ok(replacement) => *expr = replacement,
+                        Err(err) => {
+                            return ControlFlow::Break(SemanticAnalysisError::InvalidExpr(err))
+                        }
+                    }
+                }
+                ControlFlow::Continue(())
+            }
+            Expr::Vector(ref mut elems) => {
+                for elem in elems.iter_mut() {
+                    self.rewrite_builtin_arg(elem)?;
+                }
+                ControlFlow::Continue(())
+            }
+            _ => self.visit_mut_expr(expr),
+        }
+    }
 }
 impl<'a> VisitMut<SemanticAnalysisError> for RewriteIterableBindingsVisitor<'a> {
     fn visit_mut_scalar_expr(
@@ -1364,31 +1868,38 @@ impl<'a> VisitMut<SemanticAnalysisError> for RewriteIterableBindingsVisitor<'a>
         expr: &mut ScalarExpr,
     ) -> ControlFlow<SemanticAnalysisError> {
         match expr {
-            // Nothing to do with constants
-            ScalarExpr::Const(_) => ControlFlow::Continue(()),
+            // Nothing to do with constants or the trace length
+            ScalarExpr::Const(_) | ScalarExpr::TraceLen(_) => ControlFlow::Continue(()),
             // If we observe an access, try to rewrite it as an iterable binding, if it is
             // not a candidate for rewrite, leave it alone.
-            //
-            // NOTE: We handle BoundedSymbolAccess here even though comprehension constraints are not
-            // permitted in boundary_constraints currently. That is handled elsewhere, we just need to
-            // make sure the symbols themselves are rewritten properly here.
-            ScalarExpr::SymbolAccess(ref mut access)
-            | ScalarExpr::BoundedSymbolAccess(BoundedSymbolAccess {
-                column: ref mut access,
-                ..
-            }) => {
+            ScalarExpr::SymbolAccess(ref mut access) => {
                 if let Some(replacement) = self.rewrite_scalar_access(access.clone())? {
                     *expr = replacement;
                     return ControlFlow::Continue(());
                 }
                 ControlFlow::Continue(())
             }
+            // A boundary access of an iterable binding, e.g. `enf x.first = 0 for x in c`, applies
+            // to the column the binding refers to in the current iteration, so only the column is
+            // rewritten, and the boundary is preserved.
+            ScalarExpr::BoundedSymbolAccess(ref mut access) => {
+                match self.rewrite_scalar_access(access.column.clone())? {
+                    Some(ScalarExpr::SymbolAccess(column)) => {
+                        access.column = column;
+                        ControlFlow::Continue(())
+                    }
+                    Some(_) => ControlFlow::Break(SemanticAnalysisError::InvalidExpr(
+                        InvalidExprError::BoundedSymbolAccess(access.span()),
+                    )),
+                    None => ControlFlow::Continue(()),
+                }
+            }
             // We need to visit both operands of a binary expression - but while we're here,
             // check to see if resolving the operands reduces to a constant expression that
             // can be folded.
             ScalarExpr::Binary(ref mut binary_expr) => {
                 self.visit_mut_binary_expr(binary_expr)?;
-                match constant_propagation::try_fold_binary_expr(binary_expr) {
+                match constant_propagation::try_fold_binary_expr(binary_expr, self.field) {
                     Ok(Some(folded)) => {
                         *expr = ScalarExpr::Const(folded);
                         ControlFlow::Continue(())
@@ -1397,7 +1908,29 @@ impl<'a> VisitMut<SemanticAnalysisError> for RewriteIterableBindingsVisitor<'a>
                     Err(err) => ControlFlow::Break(SemanticAnalysisError::InvalidExpr(err)),
                 }
             }
-            // If we observe a call here, just rewrite the arguments, inlining happens elsewhere
+            // Casts have no effect once type checking is done, so they are replaced by their
+            // rewritten argument, as is done during constant propagation outside of comprehensions
+            ScalarExpr::Call(ref mut call)
+                if call.is_builtin() && call.callee.as_ref().name() == symbols::Felt =>
+            {
+                match ScalarExpr::try_from(call.args.pop().unwrap()) {
+                    Ok(mut arg) => {
+                        self.visit_mut_scalar_expr(&mut arg)?;
+                        *expr = arg;
+                        ControlFlow::Continue(())
+                    }
+                    Err(err) => ControlFlow::Break(SemanticAnalysisError::InvalidExpr(err)),
+                }
+            }
+            // If we observe a call here, just rewrite the arguments, inlining happens elsewhere.
+            //
+            // Arguments to list folding builtins may be aggregates, so are rewritten separately
+            ScalarExpr::Call(ref mut call) if call.is_builtin() => {
+                for arg in call.args.iter_mut() {
+                    self.rewrite_builtin_arg(arg)?;
+                }
+                ControlFlow::Continue(())
+            }
             ScalarExpr::Call(ref mut call) => {
                 for arg in call.args.iter_mut() {
                     self.visit_mut_expr(arg)?;
@@ -1408,6 +1941,68 @@ impl<'a> VisitMut<SemanticAnalysisError> for RewriteIterableBindingsVisitor<'a>
     }
 }
 
+/// Applies `access_type` to `value`, the value of a let-bound variable of aggregate type, producing
+/// the expression corresponding to the accessed element, row, or slice of that value.
+fn access_aggregate(value: Expr, access_type: &AccessType) -> Expr {
+    match (value, access_type) {
+        (value, AccessType::Default) => value,
+        (Expr::Vector(elems), AccessType::Index(idx)) => elems.item[*idx].clone(),
+        (Expr::Vector(elems), AccessType::Slice(range)) => Expr::Vector(Span::new(
+            elems.span(),
+            elems.item[range.start..range.end].to_vec(),
+        )),
+        (Expr::Matrix(rows), AccessType::Index(idx)) => {
+            let row = rows.item[*idx]
+                .iter()
+                .cloned()
+                .map(|elem| elem.try_into().unwrap())
+                .collect();
+            Expr::Vector(Span::new(rows.span(), row))
+        }
+        (Expr::Const(constant), AccessType::Index(idx)) => {
+            let span = constant.span();
+            match constant.item {
+                ConstantExpr::Matrix(rows) => {
+                    Expr::Const(Span::new(span, ConstantExpr::Vector(rows[*idx].clone())))
+                }
+                invalid => panic!("invalid access of constant aggregate: {:#?}", invalid),
+            }
+        }
+        (Expr::Const(constant), AccessType::Slice(range)) => {
+            let span = constant.span();
+            match constant.item {
+                ConstantExpr::Vector(elems) => Expr::Const(Span::new(
+                    span,
+                    ConstantExpr::Vector(elems[range.start..range.end].to_vec()),
+                )),
+                invalid => panic!("invalid access of constant aggregate: {:#?}", invalid),
+            }
+        }
+        (value, access_type) => panic!(
+            "invalid access of aggregate value {:#?}: {}",
+            value, access_type
+        ),
+    }
+}
+
+/// Lowers a call to the `is_binary` builtin, i.e. `is_binary(x)`, to the equivalent constraint `x^2 = x`
+fn binary_constraint(call: Call) -> ScalarExpr {
+    assert_eq!(call.callee.as_ref().name(), symbols::IsBinary);
+    let span = call.span();
+    let [arg]: [Expr; 1] = call
+        .args
+        .try_into()
+        .expect("is_binary expects a single argument");
+    let value: ScalarExpr = arg.try_into().expect("is_binary expects a scalar argument");
+    let square = ScalarExpr::Binary(BinaryExpr::new(
+        span,
+        BinaryOp::Exp,
+        value.clone(),
+        ScalarExpr::Const(Span::new(span, 2)),
+    ));
+    ScalarExpr::Binary(BinaryExpr::new(span, BinaryOp::Eq, square, value))
+}
+
 /// This visitor is used to apply a selector expression to all constraints in a block
 ///
 /// For constraints which already have a selector, this rewrites those selectors to be the
@@ -1443,9 +2038,155 @@ impl<'a> VisitMut<SemanticAnalysisError> for ApplyConstraintSelector<'a> {
                 ));
                 ControlFlow::Continue(())
             }
-            Statement::EnforceAll(_) => unreachable!(),
-            Statement::Expr(_) => ControlFlow::Continue(()),
+            Statement::WithAttributes(_, ref mut stmt) => self.visit_mut_statement(stmt),
+            Statement::EnforceAll(_) | Statement::EnforceMatch(_) => unreachable!(),
+            Statement::Expr(_) | Statement::Debug(_) => ControlFlow::Continue(()),
+        }
+    }
+}
+
+/// This visitor is used to attach attributes to all constraints in a block
+///
+/// For constraints which already have attributes, e.g. those given in the body of an inlined
+/// evaluator, the attributes being applied are merged with them, without overriding them.
+struct ApplyConstraintAttributes<'a> {
+    attrs: &'a Attributes,
+}
+impl<'a> VisitMut<SemanticAnalysisError> for ApplyConstraintAttributes<'a> {
+    fn visit_mut_statement(
+        &mut self,
+        statement: &mut Statement,
+    ) -> ControlFlow<SemanticAnalysisError> {
+        match statement {
+            Statement::Let(ref mut expr) => self.visit_mut_let(expr),
+            Statement::Enforce(_) | Statement::EnforceIf(_, _) => {
+                let stmt = core::mem::replace(
+                    statement,
+                    Statement::Expr(Expr::Const(Span::new(
+                        SourceSpan::UNKNOWN,
+                        ConstantExpr::Scalar(0),
+                    ))),
+                );
+                *statement = Statement::WithAttributes(self.attrs.clone(), Box::new(stmt));
+                ControlFlow::Continue(())
+            }
+            Statement::WithAttributes(ref mut attrs, _) => {
+                attrs.inherit(self.attrs);
+                ControlFlow::Continue(())
+            }
+            Statement::EnforceAll(_) | Statement::EnforceMatch(_) => unreachable!(),
+            Statement::Expr(_) | Statement::Debug(_) => ControlFlow::Continue(()),
+        }
+    }
+}
+
+/// This visitor is used to convert the constraints of an inlined evaluator into boundary
+/// constraints, when the evaluator is applied at a boundary.
+///
+/// Each constraint must have the form `column = expr`, and is rewritten to `column.first = expr`
+/// (or `.last`). Accesses with a row offset, or to periodic columns, are invalid at a boundary,
+/// and are rejected.
+struct ApplyConstraintBoundary<'a> {
+    diagnostics: &'a DiagnosticsHandler,
+    boundary: Boundary,
+    callsite: SourceSpan,
+    /// The fully-qualified bindings in the program, i.e. the periodic columns
+    imported: &'a HashMap<QualifiedIdentifier, BindingType>,
+}
+impl<'a> ApplyConstraintBoundary<'a> {
+    fn invalid_boundary_evaluator(
+        &self,
+        span: SourceSpan,
+        label: &str,
+        note: &str,
+    ) -> ControlFlow<SemanticAnalysisError> {
+        self.diagnostics
+            .diagnostic(Severity::Error)
+            .with_message("invalid evaluator in boundary constraint")
+            .with_primary_label(span, label)
+            .with_secondary_label(
+                self.callsite,
+                format!("evaluator is applied to the {} row here", self.boundary),
+            )
+            .with_note(note)
+            .emit();
+        ControlFlow::Break(SemanticAnalysisError::Invalid)
+    }
+}
+impl<'a> VisitMut<SemanticAnalysisError> for ApplyConstraintBoundary<'a> {
+    fn visit_mut_statement(
+        &mut self,
+        statement: &mut Statement,
+    ) -> ControlFlow<SemanticAnalysisError> {
+        match statement {
+            Statement::Let(ref mut expr) => self.visit_mut_let(expr),
+            Statement::Enforce(ScalarExpr::Binary(BinaryExpr {
+                op: BinaryOp::Eq,
+                ref mut lhs,
+                ref mut rhs,
+                ..
+            })) => {
+                self.visit_mut_scalar_expr(rhs.as_mut())?;
+                match lhs.as_mut() {
+                    ScalarExpr::SymbolAccess(ref mut access) => {
+                        self.visit_mut_symbol_access(access)?;
+                        let span = access.span();
+                        let column = core::mem::replace(
+                            lhs.as_mut(),
+                            ScalarExpr::Const(Span::new(SourceSpan::UNKNOWN, 0)),
+                        );
+                        let ScalarExpr::SymbolAccess(column) = column else {
+                            unreachable!()
+                        };
+                        **lhs = ScalarExpr::BoundedSymbolAccess(BoundedSymbolAccess::new(
+                            span,
+                            column,
+                            self.boundary,
+                        ));
+                        ControlFlow::Continue(())
+                    }
+                    other => self.invalid_boundary_evaluator(
+                        other.span(),
+                        "expected this to be a reference to a trace column",
+                        "Evaluators applied at a boundary must only contain constraints of the form `column = expr`.",
+                    ),
+                }
+            }
+            Statement::Enforce(expr) | Statement::EnforceIf(expr, _) => {
+                self.invalid_boundary_evaluator(
+                    expr.span(),
+                    "this constraint cannot be applied at a boundary",
+                    "Evaluators applied at a boundary must only contain unconditional constraints of the form `column = expr`.",
+                )
+            }
+            Statement::WithAttributes(_, ref mut stmt) => self.visit_mut_statement(stmt),
+            Statement::EnforceAll(_) | Statement::EnforceMatch(_) => unreachable!(),
+            Statement::Expr(ref mut expr) => self.visit_mut_expr(expr),
+            Statement::Debug(ref mut expr) => self.visit_mut_scalar_expr(expr),
+        }
+    }
+
+    fn visit_mut_symbol_access(
+        &mut self,
+        expr: &mut SymbolAccess,
+    ) -> ControlFlow<SemanticAnalysisError> {
+        if expr.offset > 0 {
+            return self.invalid_boundary_evaluator(
+                expr.span(),
+                "invalid access of a trace column with offset",
+                "It is not allowed to access trace columns with an offset in boundary constraints.",
+            );
+        }
+        if let ResolvableIdentifier::Resolved(qid) = expr.name {
+            if let Some(ty @ BindingType::PeriodicColumn(_)) = self.imported.get(&qid) {
+                return self.invalid_boundary_evaluator(
+                    expr.span(),
+                    &format!("cannot access {ty} here"),
+                    &format!("It is not allowed to access {ty} in boundary constraints."),
+                );
+            }
         }
+        ControlFlow::Continue(())
     }
 }
 
@@ -1,5 +1,7 @@
 mod constant_propagation;
+mod features;
 mod inlining;
 
 pub use self::constant_propagation::ConstantPropagation;
-pub use self::inlining::Inlining;
+pub use self::features::FeatureGating;
+pub use self::inlining::{Inlining, DEFAULT_UNROLL_WARNING_THRESHOLD};
@@ -28,6 +28,29 @@ pub trait Pass {
     {
         Chain::new(self, pass)
     }
+
+    /// Wraps this pass so that it only runs when `predicate` holds for its input
+    ///
+    /// When the predicate does not hold, the input is returned unchanged, so this is only
+    /// available for passes whose output is the same type as their input.
+    fn when<F, T>(self, predicate: F) -> When<Self, F>
+    where
+        Self: Sized + for<'a> Pass<Input<'a> = T, Output<'a> = T>,
+        F: Fn(&T) -> bool,
+    {
+        When::new(self, predicate)
+    }
+
+    /// Wraps this pass so that it is run repeatedly until its output stops changing
+    ///
+    /// See [Fixpoint] for details.
+    fn fixpoint<T>(self) -> Fixpoint<Self>
+    where
+        Self: Sized + for<'a> Pass<Input<'a> = T, Output<'a> = T>,
+        T: Clone + PartialEq,
+    {
+        Fixpoint::new(self)
+    }
 }
 impl<P, T, U, E> Pass for &mut P
 where
@@ -101,3 +124,211 @@ where
         self.b.run(u)
     }
 }
+
+/// This struct is not meant to be used directly, but is instead produced by [Pass::when].
+/// `When` runs the wrapped pass only if a predicate over the input holds, and otherwise
+/// returns the input unchanged.
+///
+/// ## Example
+///
+/// ```rust
+/// use std::sync::Arc;
+///
+/// use air_parser::{ast::Program, transforms::{ConstantPropagation, Inlining}};
+/// use air_pass::Pass;
+/// use miden_diagnostics::{
+///     term::termcolor::ColorChoice, CodeMap, DefaultEmitter, DiagnosticsHandler,
+/// };
+///
+/// let codemap = Arc::new(CodeMap::new());
+/// let emitter = Arc::new(DefaultEmitter::new(ColorChoice::Auto));
+/// let diagnostics = DiagnosticsHandler::new(Default::default(), codemap.clone(), emitter);
+///
+/// let source = "
+/// def test
+/// trace_columns:
+///     main: [clk]
+/// public_inputs:
+///     stack_inputs: [16]
+/// boundary_constraints:
+///     enf clk.first = 0
+/// integrity_constraints:
+///     enf clk' = clk + 1";
+/// let program = air_parser::parse(&diagnostics, codemap, source).unwrap();
+///
+/// // This program calls no evaluators, so there is nothing to inline
+/// let has_evaluators = |program: &Program| !program.evaluators.is_empty();
+/// let mut pipeline = ConstantPropagation::new(&diagnostics)
+///     .chain(Inlining::new(&diagnostics).when(has_evaluators));
+/// let program = pipeline.run(program).unwrap();
+/// assert_eq!(program.integrity_constraints.len(), 1);
+/// ```
+pub struct When<P, F> {
+    pass: P,
+    predicate: F,
+}
+impl<P, F> When<P, F> {
+    fn new(pass: P, predicate: F) -> Self {
+        Self { pass, predicate }
+    }
+}
+impl<P, F> Clone for When<P, F>
+where
+    P: Clone,
+    F: Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self::new(self.pass.clone(), self.predicate.clone())
+    }
+}
+impl<P, F, T, E> Pass for When<P, F>
+where
+    P: for<'a> Pass<Input<'a> = T, Output<'a> = T, Error = E>,
+    F: Fn(&T) -> bool,
+{
+    type Input<'a> = T;
+    type Output<'a> = T;
+    type Error = E;
+
+    fn run<'a>(&mut self, input: Self::Input<'a>) -> Result<Self::Output<'a>, Self::Error> {
+        if (self.predicate)(&input) {
+            self.pass.run(input)
+        } else {
+            Ok(input)
+        }
+    }
+}
+
+/// This struct is not meant to be used directly, but is instead produced by [Pass::fixpoint].
+/// `Fixpoint` runs the wrapped pass repeatedly, feeding each output back in as the next input,
+/// until two successive values compare equal.
+///
+/// To guard against passes which never converge, the number of iterations is capped (see
+/// [Fixpoint::with_max_iterations]), and exceeding the cap is reported as a [NotConverged]
+/// error, so the error type of the wrapped pass must be convertible from it.
+pub struct Fixpoint<P> {
+    pass: P,
+    max_iterations: usize,
+}
+impl<P> Fixpoint<P> {
+    /// The maximum number of times the wrapped pass is run by default
+    pub const DEFAULT_MAX_ITERATIONS: usize = 32;
+
+    fn new(pass: P) -> Self {
+        Self {
+            pass,
+            max_iterations: Self::DEFAULT_MAX_ITERATIONS,
+        }
+    }
+
+    /// Sets the maximum number of times the wrapped pass may be run before giving up
+    pub fn with_max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+}
+impl<P> Clone for Fixpoint<P>
+where
+    P: Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            pass: self.pass.clone(),
+            max_iterations: self.max_iterations,
+        }
+    }
+}
+impl<P, T, E> Pass for Fixpoint<P>
+where
+    P: for<'a> Pass<Input<'a> = T, Output<'a> = T, Error = E>,
+    T: Clone + PartialEq,
+    E: From<NotConverged>,
+{
+    type Input<'a> = T;
+    type Output<'a> = T;
+    type Error = E;
+
+    fn run<'a>(&mut self, input: Self::Input<'a>) -> Result<Self::Output<'a>, Self::Error> {
+        let mut input = input;
+        for _ in 0..self.max_iterations {
+            let output = self.pass.run(input.clone())?;
+            if output == input {
+                return Ok(output);
+            }
+            input = output;
+        }
+
+        Err(NotConverged {
+            iterations: self.max_iterations,
+        }
+        .into())
+    }
+}
+
+/// The error produced by [Fixpoint] when the wrapped pass is still changing its input after
+/// the maximum number of iterations
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct NotConverged {
+    /// The number of times the pass was run
+    pub iterations: usize,
+}
+impl core::fmt::Display for NotConverged {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "pass failed to reach a fixpoint after {} iterations",
+            self.iterations
+        )
+    }
+}
+impl std::error::Error for NotConverged {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Eq)]
+    enum TestError {
+        NotConverged(NotConverged),
+    }
+    impl From<NotConverged> for TestError {
+        fn from(err: NotConverged) -> Self {
+            Self::NotConverged(err)
+        }
+    }
+
+    /// Halves its input, counting the number of times it has been run
+    struct Halve {
+        runs: usize,
+    }
+    impl Pass for Halve {
+        type Input<'a> = u32;
+        type Output<'a> = u32;
+        type Error = TestError;
+
+        fn run<'a>(&mut self, input: Self::Input<'a>) -> Result<Self::Output<'a>, Self::Error> {
+            self.runs += 1;
+            Ok(input / 2)
+        }
+    }
+
+    #[test]
+    fn fixpoint_converges() {
+        let mut pass = Halve { runs: 0 }.fixpoint();
+        assert_eq!(pass.run(8), Ok(0));
+        // 8 -> 4 -> 2 -> 1 -> 0 -> 0
+        assert_eq!(pass.pass.runs, 5);
+    }
+
+    #[test]
+    fn fixpoint_exceeds_max_iterations() {
+        let mut pass = Halve { runs: 0 }.fixpoint().with_max_iterations(3);
+        assert_eq!(
+            pass.run(8),
+            Err(TestError::NotConverged(NotConverged { iterations: 3 }))
+        );
+        assert_eq!(pass.pass.runs, 3);
+    }
+}